@@ -0,0 +1,74 @@
+"""Adaptive sampling: poll slowly while nothing changes, quickly while power is flowing."""
+
+from .models import PowerDirection
+from .models import PowerReading
+
+STABLE_WATTS_DELTA = 0.5  # W; smaller changes between readings count as stable
+GROWTH_FACTOR = 2.0  # interval multiplier after each stable reading
+
+
+class AdaptiveInterval:
+    """Choose the delay before the next sample from how recent readings changed.
+
+    The interval starts at min_interval and doubles after every stable reading, up
+    to max_interval. A reading is stable when the Mac is on AC and neither charging
+    nor draining the battery (idle or fully charged), its direction and battery
+    percent match the previous reading, and power moved by less than watts_delta.
+    Any other reading - charging, discharging, or a jump in power - drops the
+    interval straight back to min_interval, so transitions are sampled at full
+    resolution.
+
+    Usage:
+        adaptive = AdaptiveInterval(min_interval=1.0, max_interval=60.0)
+        delay = adaptive.observe(reading)  # seconds until the next sample
+    """
+
+    def __init__(self, min_interval: float, max_interval: float, watts_delta: float = STABLE_WATTS_DELTA):
+        """Initialize the controller.
+
+        Args:
+            min_interval: Shortest delay, used while power is changing (seconds, > 0)
+            max_interval: Longest delay, reached after a run of stable readings (seconds, >= min_interval)
+            watts_delta: Largest change in watts_actual that still counts as stable (>= 0)
+
+        Raises:
+            ValueError: If the bounds are out of order or not positive
+        """
+        if min_interval <= 0:
+            raise ValueError(f"min_interval must be positive, got {min_interval}")
+        if max_interval < min_interval:
+            raise ValueError(f"max_interval ({max_interval}) must be at least min_interval ({min_interval})")
+        if watts_delta < 0:
+            raise ValueError(f"watts_delta must be non-negative, got {watts_delta}")
+        self.min_interval = min_interval
+        self.max_interval = max_interval
+        self.watts_delta = watts_delta
+        self.interval = min_interval
+        self._previous: PowerReading | None = None
+
+    def observe(self, reading: PowerReading) -> float:
+        """Feed the latest reading and get the delay before the next one.
+
+        Args:
+            reading: Latest PowerReading
+
+        Returns:
+            Seconds to wait, between min_interval and max_interval
+        """
+        if self._is_stable(reading):
+            self.interval = min(self.interval * GROWTH_FACTOR, self.max_interval)
+        else:
+            self.interval = self.min_interval
+        self._previous = reading
+        return self.interval
+
+    def _is_stable(self, reading: PowerReading) -> bool:
+        """Whether nothing worth sampling quickly happened since the previous reading."""
+        previous = self._previous
+        if previous is None or reading.direction is not PowerDirection.AC_IDLE:
+            return False
+        return (
+            previous.direction is reading.direction
+            and previous.battery_percent == reading.battery_percent
+            and abs(reading.watts_actual - previous.watts_actual) <= self.watts_delta
+        )
@@ -0,0 +1,187 @@
+"""Result types for aggregate commands (`stats`, `chargers`, `compare-db`).
+
+The table and `--json` output of each command are both rendered from these, so
+scripts see exactly the numbers the tables show. The `to_dict()` keys are a
+public schema: add fields, don't rename or remove them.
+"""
+
+from dataclasses import dataclass
+from pathlib import Path
+
+from .degradation import ChargerPeak
+
+
+def _round(value: float | None, digits: int) -> float | None:
+    """Round a value that may be missing."""
+    return round(value, digits) if value is not None else None
+
+
+@dataclass(frozen=True, slots=True)
+class StatsResult:
+    """Database-wide statistics shown by `powermonitor stats`.
+
+    Attributes:
+        database_path: Database file
+        database_size_bytes: Size of the database file (0 if it doesn't exist yet)
+        count: Number of stored readings
+        earliest: ISO timestamp of the oldest reading (None if empty)
+        latest: ISO timestamp of the newest reading (None if empty)
+        avg_watts: Average power over all readings (None if empty)
+        min_watts: Lowest power (None if empty)
+        max_watts: Highest power (None if empty)
+        avg_battery: Average battery percentage (None if empty)
+    """
+
+    database_path: str
+    database_size_bytes: int
+    count: int
+    earliest: str | None = None
+    latest: str | None = None
+    avg_watts: float | None = None
+    min_watts: float | None = None
+    max_watts: float | None = None
+    avg_battery: float | None = None
+
+    @classmethod
+    def from_statistics(cls, stat_data: dict, db_path: Path, size_bytes: int) -> "StatsResult":
+        """Build from Database.get_statistics() output.
+
+        Args:
+            stat_data: Statistics dictionary
+            db_path: Database file
+            size_bytes: Size of the database file
+
+        Returns:
+            StatsResult (power and battery fields are None when there are no readings)
+        """
+        empty = stat_data["count"] == 0
+        return cls(
+            database_path=str(db_path),
+            database_size_bytes=size_bytes,
+            count=stat_data["count"],
+            earliest=stat_data["earliest"],
+            latest=stat_data["latest"],
+            avg_watts=None if empty else stat_data["avg_watts"],
+            min_watts=None if empty else stat_data["min_watts"],
+            max_watts=None if empty else stat_data["max_watts"],
+            avg_battery=None if empty else stat_data["avg_battery"],
+        )
+
+    def to_dict(self) -> dict:
+        """Convert to a JSON-serializable dictionary (stable field names).
+
+        Returns:
+            Dictionary with power rounded to 0.01W and battery to 0.1%
+        """
+        return {
+            "database_path": self.database_path,
+            "database_size_bytes": self.database_size_bytes,
+            "count": self.count,
+            "earliest": self.earliest,
+            "latest": self.latest,
+            "avg_watts": _round(self.avg_watts, 2),
+            "min_watts": _round(self.min_watts, 2),
+            "max_watts": _round(self.max_watts, 2),
+            "avg_battery": _round(self.avg_battery, 1),
+        }
+
+
+@dataclass(frozen=True, slots=True)
+class ChargerStats:
+    """Recorded history of one charger, shown by `powermonitor chargers`.
+
+    Chargers are identified by the name and manufacturer they report, so two
+    identical adapters are counted together.
+
+    Attributes:
+        name: Charger name
+        manufacturer: Charger manufacturer (None if not reported)
+        readings: Number of readings taken with this charger connected
+        max_watts_negotiated: Highest negotiated power seen (W)
+        avg_watts_actual: Average power while connected (W)
+        first_seen: ISO timestamp of the first reading
+        last_seen: ISO timestamp of the latest reading
+        peak: Stored peak negotiated power, kept across pruning (None if never tracked)
+    """
+
+    name: str
+    manufacturer: str | None
+    readings: int
+    max_watts_negotiated: int
+    avg_watts_actual: float
+    first_seen: str
+    last_seen: str
+    peak: ChargerPeak | None = None
+
+    @property
+    def degraded(self) -> bool:
+        """Whether the charger now negotiates well below its peak (see degradation.ChargerPeak)."""
+        return self.peak is not None and self.peak.degraded
+
+    def to_dict(self) -> dict:
+        """Convert to a JSON-serializable dictionary (stable field names).
+
+        Returns:
+            Dictionary with average power rounded to 0.01W
+        """
+        return {
+            "name": self.name,
+            "manufacturer": self.manufacturer,
+            "readings": self.readings,
+            "max_watts_negotiated": self.max_watts_negotiated,
+            "avg_watts_actual": round(self.avg_watts_actual, 2),
+            "first_seen": self.first_seen,
+            "last_seen": self.last_seen,
+            "peak_watts_negotiated": self.peak.peak_watts if self.peak else None,
+            "last_watts_negotiated": self.peak.last_watts if self.peak else None,
+            "degraded": self.degraded,
+        }
+
+
+@dataclass(frozen=True, slots=True)
+class DatabaseSummary:
+    """Aggregates of one database, compared side by side by `powermonitor compare-db`.
+
+    Attributes:
+        database_path: Database file
+        count: Number of stored readings
+        earliest: ISO timestamp of the oldest reading (None if empty)
+        latest: ISO timestamp of the newest reading (None if empty)
+        avg_watts: Average power flow over all readings (None if empty)
+        avg_draw_watts: Average draw while on battery, as a positive number (None without battery readings)
+        peak_draw_watts: Highest draw while on battery (None without battery readings)
+        peak_charge_watts: Highest charging power (None if never charging)
+        max_capacity: Battery max capacity in the latest reading (mAh, None if empty)
+        battery_health_percent: Latest max capacity as a percentage of design capacity
+            (None when design capacity was never recorded, e.g. databases from older versions)
+    """
+
+    database_path: str
+    count: int
+    earliest: str | None = None
+    latest: str | None = None
+    avg_watts: float | None = None
+    avg_draw_watts: float | None = None
+    peak_draw_watts: float | None = None
+    peak_charge_watts: float | None = None
+    max_capacity: int | None = None
+    battery_health_percent: float | None = None
+
+    def to_dict(self) -> dict:
+        """Convert to a JSON-serializable dictionary (stable field names).
+
+        Returns:
+            Dictionary with power rounded to 0.01W and health to 0.1%
+        """
+        return {
+            "database_path": self.database_path,
+            "count": self.count,
+            "earliest": self.earliest,
+            "latest": self.latest,
+            "avg_watts": _round(self.avg_watts, 2),
+            "avg_draw_watts": _round(self.avg_draw_watts, 2),
+            "peak_draw_watts": _round(self.peak_draw_watts, 2),
+            "peak_charge_watts": _round(self.peak_charge_watts, 2),
+            "max_capacity": self.max_capacity,
+            "battery_health_percent": _round(self.battery_health_percent, 1),
+        }
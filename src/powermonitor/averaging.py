@@ -0,0 +1,86 @@
+"""Store coarser readings than are sampled: average each store interval's samples into one reading."""
+
+from collections.abc import Sequence
+from dataclasses import replace
+from statistics import fmean
+
+from .models import PowerReading
+
+# Measurements averaged over a window; every other field (capacity, flags, charger) comes from the
+# window's latest sample, since it describes state rather than a quantity that fluctuates between samples
+AVERAGED_FIELDS = ("watts_actual", "voltage", "amperage", "battery_temp", "system_power", "gpu_power")
+
+
+def average_readings(readings: Sequence[PowerReading]) -> PowerReading:
+    """Combine samples into one reading.
+
+    Args:
+        readings: Samples, oldest first (at least one)
+
+    Returns:
+        The latest sample with AVERAGED_FIELDS replaced by their mean over all samples
+        (optional fields average the samples that have a value)
+
+    Raises:
+        ValueError: If readings is empty
+    """
+    if not readings:
+        raise ValueError("Cannot average an empty window")
+    averages = {}
+    for name in AVERAGED_FIELDS:
+        values = [value for r in readings if (value := getattr(r, name)) is not None]
+        averages[name] = fmean(values) if values else None
+    return replace(readings[-1], **averages)
+
+
+class ReadingAverager:
+    """Collect samples and release one averaged reading per store interval.
+
+    The first sample opens a window; the first sample at least `interval` seconds
+    later closes it, and the window's samples (including that one) are averaged.
+
+    Usage:
+        averager = ReadingAverager(interval=60.0)
+        stored = averager.add(reading)  # None until a window is complete
+        stored = averager.flush()  # Partial window on shutdown
+    """
+
+    def __init__(self, interval: float):
+        """Initialize the averager.
+
+        Args:
+            interval: Seconds covered by each stored reading (> 0)
+
+        Raises:
+            ValueError: If interval is not positive
+        """
+        if interval <= 0:
+            raise ValueError(f"interval must be positive, got {interval}")
+        self.interval = interval
+        self._window: list[PowerReading] = []
+
+    def add(self, reading: PowerReading) -> PowerReading | None:
+        """Add a sample.
+
+        Args:
+            reading: Latest sample
+
+        Returns:
+            The averaged reading when this sample completes a window, else None
+        """
+        self._window.append(reading)
+        if (reading.timestamp - self._window[0].timestamp).total_seconds() < self.interval:
+            return None
+        return self.flush()
+
+    def flush(self) -> PowerReading | None:
+        """Average and clear the open window.
+
+        Returns:
+            The averaged reading, or None if no samples are pending
+        """
+        if not self._window:
+            return None
+        averaged = average_readings(self._window)
+        self._window = []
+        return averaged
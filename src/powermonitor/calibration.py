@@ -0,0 +1,85 @@
+"""Battery calibration heuristics: does the reported percentage match the pack voltage?"""
+
+import math
+
+# Resting (open-circuit) voltage per cell vs. state of charge for the lithium-cobalt-oxide
+# cells in MacBook packs. Apple's high-voltage cells charge to ~4.3V, so that is 100%.
+CELL_OCV_CURVE: tuple[tuple[float, float], ...] = (
+    (3.30, 0.0),
+    (3.60, 5.0),
+    (3.69, 10.0),
+    (3.74, 20.0),
+    (3.77, 30.0),
+    (3.80, 40.0),
+    (3.84, 50.0),
+    (3.89, 60.0),
+    (3.96, 70.0),
+    (4.04, 80.0),
+    (4.13, 90.0),
+    (4.30, 100.0),
+)
+
+MAX_CELL_VOLTAGE = 4.35  # Fully charged high-voltage cell; bounds the series cell count from below
+REST_CURRENT = 1.0  # A; above this, load sag or charge overpotential skews the voltage
+SUSPECT_TOLERANCE = 30.0  # Percentage points between reported and voltage-estimated charge
+
+
+def infer_cell_count(voltage: float) -> int:
+    """Guess how many cells are in series from the pack voltage.
+
+    MacBook packs are 2S-4S. Taking the fewest cells that keep every cell at or below
+    MAX_CELL_VOLTAGE is exact except for a nearly empty 4S pack, which reads as 3S.
+
+    Args:
+        voltage: Pack voltage (V)
+
+    Returns:
+        Series cell count (at least 1)
+    """
+    return max(1, math.ceil(voltage / MAX_CELL_VOLTAGE))
+
+
+def estimate_percent(voltage: float, cells: int | None = None) -> float:
+    """Estimate state of charge from a resting pack voltage.
+
+    Args:
+        voltage: Pack voltage (V)
+        cells: Series cell count (inferred from voltage if None)
+
+    Returns:
+        Estimated charge percentage (0-100)
+    """
+    cell_voltage = voltage / (cells or infer_cell_count(voltage))
+    if cell_voltage <= CELL_OCV_CURVE[0][0]:
+        return CELL_OCV_CURVE[0][1]
+    for (low_v, low_pct), (high_v, high_pct) in zip(CELL_OCV_CURVE, CELL_OCV_CURVE[1:], strict=False):
+        if cell_voltage <= high_v:
+            return low_pct + (high_pct - low_pct) * (cell_voltage - low_v) / (high_v - low_v)
+    return CELL_OCV_CURVE[-1][1]
+
+
+def is_calibration_suspect(
+    voltage: float,
+    amperage: float,
+    battery_percent: int,
+    cells: int | None = None,
+    tolerance: float = SUSPECT_TOLERANCE,
+) -> bool:
+    """Check whether the reported percentage is implausible for the battery voltage.
+
+    Only readings near rest (|amperage| <= REST_CURRENT) are judged, because voltage under
+    load or while charging doesn't reflect state of charge.
+
+    Args:
+        voltage: Pack voltage (V)
+        amperage: Battery current (A)
+        battery_percent: Percentage the battery reports
+        cells: Series cell count (inferred from voltage if None)
+        tolerance: Allowed gap in percentage points before flagging
+
+    Returns:
+        True if the reported and voltage-estimated charge differ by more than tolerance
+    """
+    if voltage <= 0 or abs(amperage) > REST_CURRENT:
+        return False
+    return abs(estimate_percent(voltage, cells) - battery_percent) > tolerance
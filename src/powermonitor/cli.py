@@ -1,28 +1,123 @@
 """powermonitor CLI entry point - launches TUI by default."""
 
-import csv
+import itertools
 import json
+import math
+import os
 import sys
+import tempfile
+import time
+from collections.abc import Callable
+from collections.abc import Iterable
+from dataclasses import dataclass
+from dataclasses import fields
+from dataclasses import replace
+from datetime import UTC
+from datetime import date
+from datetime import datetime
+from datetime import timedelta
 from pathlib import Path
 from typing import Annotated
 
 import typer
 from loguru import logger
+from rich.cells import cell_len
 from rich.console import Console
+from rich.console import Group
+from rich.live import Live
+from rich.markup import escape
 from rich.table import Table
 
+from .aggregates import DatabaseSummary
+from .aggregates import StatsResult
+from .calibration import REST_CURRENT
+from .calibration import estimate_percent
+from .collector import BACKENDS
+from .collector import CollectLevel
+from .collector import create_collector
+from .collector.ioreg import IORegCollector
+from .collector.ioreg import export_battery_plist
+from .collector.tasks import TaskEnergy
+from .collector.tasks import TaskEnergyReader
+from .conditions import parse_condition
+from .config import MqttConfig
 from .config import PowerMonitorConfig
+from .config import Thresholds
+from .config_loader import format_config
+from .config_loader import get_config_path
 from .config_loader import load_config
+from .csv_import import read_csv
 from .database import Database
+from .export import EXPORT_FORMATS
+from .export import EXTENSION_FORMATS
+from .export import export_readings
+from .glyphs import ASCII
+from .glyphs import EMOJI
+from .glyphs import Glyphs
+from .health import DAYS_PER_MONTH
+from .health import MAX_PROJECTION_DAYS
+from .health import PERIODS
+from .health import REPLACEMENT_HEALTH_PERCENT
+from .health import bucket_health
+from .health import capacity_trend
+from .health import daily_health
+from .hooks import EventHooks
+from .hooks import parse_budgets
+from .labels import sanitize_label
 from .logger import setup_logger
+from .models import AdapterSource
+from .models import CollectorUnavailableError
+from .models import DerivedReading
+from .models import PowerDirection
+from .models import PowerReading
+from .mqtt import MqttPublisher
+from .numformat import CANONICAL
+from .numformat import NumberFormat
+from .numformat import number_format_for_locale
+from .probe import probe_all
+from .replay import DEFAULT_MAX_DELAY
+from .replay import ReplayCollector
+from .replay import read_ndjson
+from .replay import replay_schedule
+from .report import Report
+from .report import parse_time_bound
+from .report import render_report
+from .schedule import parse_interval
+from .socket_server import ReadingBroadcaster
+from .socket_server import SocketInUseError
+from .socket_server import default_socket_path
+from .system_info import describe_architecture
+from .system_info import get_system_info
+from .system_info import get_version
+from .system_info import native_architecture
+from .system_info import running_under_rosetta
 from .tui.app import PowerMonitorApp
+from .udp import ReadingSender
+from .udp import parse_collector_address
 
 app = typer.Typer(
     help="macOS power monitoring tool with TUI and data export",
     invoke_without_command=True,
     no_args_is_help=False,
 )
-console = Console()
+config_app = typer.Typer(help="Show the configuration file location and effective settings")
+app.add_typer(config_app, name="config")
+smc_app = typer.Typer(help="Read SMC keys directly, e.g. to probe sensors powermonitor doesn't model (macOS only)")
+app.add_typer(smc_app, name="smc")
+console = Console()  # Command output (redirected by --output)
+err_console = Console(stderr=True)  # Errors always go to stderr
+_quiet = False  # Set by the global --quiet option: suppress warnings and notices on stderr
+_numbers: NumberFormat = CANONICAL  # Set by the global --locale option: separators for displayed numbers
+_reference_voltage: float | None = None  # Set by the global --reference-voltage option: also show amps at V
+_deadzone = 0.0  # Set by the global --deadzone option: display power below this many W as 0
+_glyphs: Glyphs = EMOJI  # Set by the global --ascii option: ASCII stand-ins for emoji and symbols
+
+_DIRECTION_LABELS = {
+    PowerDirection.CHARGING: "Charging",
+    PowerDirection.AC_IDLE: "AC Power",
+    PowerDirection.AC_DISCHARGING: "AC, discharging",
+    PowerDirection.DISCHARGING: "Battery",
+}
 
 
 @app.callback()
@@ -33,6 +128,7 @@ def main(
         typer.Option(
             "-i",
             "--interval",
+            "--sample-interval",
             help="Data collection interval in seconds (overrides config file)",
         ),
     ] = None,
@@ -50,6 +146,45 @@ def main(
             help="Number of readings to display in chart (overrides config file)",
         ),
     ] = None,
+    schedule: Annotated[
+        str | None,
+        typer.Option(
+            "--schedule",
+            help="Sample by time of day, e.g. '09:00-18:00=1m, *=10m' (overrides --interval outside windows)",
+        ),
+    ] = None,
+    max_interval: Annotated[
+        float | None,
+        typer.Option(
+            "--max-interval",
+            help="Adaptive sampling: stretch --interval up to this many seconds while readings are stable (0 = off)",
+        ),
+    ] = None,
+    store_interval: Annotated[
+        float | None,
+        typer.Option(
+            "--store-interval",
+            help="Store one reading averaged over this many seconds of samples (0 = store every sample)",
+        ),
+    ] = None,
+    plot_series: Annotated[
+        str | None,
+        typer.Option(
+            "--plot-series",
+            help="Metrics to chart, e.g. 'watts,battery,voltage,temp' (overrides config file)",
+        ),
+    ] = None,
+    live_chart: Annotated[
+        bool,
+        typer.Option(
+            "--live-chart",
+            help="Scroll the chart over this session's last --chart-limit readings (overrides config file)",
+        ),
+    ] = False,
+    gpu_power: Annotated[
+        bool,
+        typer.Option("--gpu-power", help="Also show GPU power from powermetrics (Apple Silicon, needs sudo)"),
+    ] = False,
     debug: Annotated[
         bool,
         typer.Option(
@@ -58,6 +193,111 @@ def main(
             show_default=True,
         ),
     ] = False,
+    backend: Annotated[
+        str,
+        typer.Option(
+            "--backend",
+            "-b",
+            help=f"Data collection backend: {', '.join(BACKENDS)} (mock works without a Mac battery)",
+        ),
+    ] = "auto",
+    label: Annotated[
+        str | None,
+        typer.Option(
+            "--label",
+            "-l",
+            help="Tag every reading collected in this session (e.g. 'video render')",
+        ),
+    ] = None,
+    verbose: Annotated[
+        bool,
+        typer.Option(
+            "--verbose",
+            "-v",
+            help="Show extra details such as charger serial number and firmware",
+        ),
+    ] = False,
+    output: Annotated[
+        Path | None,
+        typer.Option(
+            "--output",
+            "-o",
+            help="Write command output to this file instead of stdout (errors still go to stderr)",
+        ),
+    ] = None,
+    low_battery: Annotated[
+        int | None,
+        typer.Option("--low-battery", help="Warn at or below this battery percent (overrides config file)"),
+    ] = None,
+    high_power: Annotated[
+        float | None,
+        typer.Option("--high-power", help="Warn above this many watts (overrides config file)"),
+    ] = None,
+    overheat_temp: Annotated[
+        float | None,
+        typer.Option("--overheat-temp", help="Warn at or above this battery temperature in °C (overrides config file)"),
+    ] = None,
+    quiet: Annotated[
+        bool,
+        typer.Option("--quiet", "-q", help="Only print requested output and errors (no warnings or notices)"),
+    ] = False,
+    on_plug: Annotated[
+        str | None,
+        typer.Option("--on-plug", help="Shell command to run when a charger is connected"),
+    ] = None,
+    on_unplug: Annotated[
+        str | None,
+        typer.Option("--on-unplug", help="Shell command to run when the charger is disconnected"),
+    ] = None,
+    on_low: Annotated[
+        str | None,
+        typer.Option("--on-low", help="Shell command to run when the battery drops to the low-battery threshold"),
+    ] = None,
+    on_full: Annotated[
+        str | None,
+        typer.Option("--on-full", help="Shell command to run when the battery is fully charged"),
+    ] = None,
+    on_hot: Annotated[
+        str | None,
+        typer.Option(
+            "--on-hot",
+            help="Shell command to run while the battery is at or above the overheat threshold (every 10m by default)",
+        ),
+    ] = None,
+    hook_budget: Annotated[
+        str | None,
+        typer.Option("--hook-budget", help="Minimum time between runs of each hook, e.g. 'hot=30m,low=1h'"),
+    ] = None,
+    locale_name: Annotated[
+        str | None,
+        typer.Option(
+            "--locale",
+            help="Format displayed numbers for a locale, e.g. de_DE.UTF-8, or 'system' for LC_NUMERIC/LANG "
+            "(JSON and CSV are unaffected)",
+        ),
+    ] = None,
+    reference_voltage: Annotated[
+        float | None,
+        typer.Option(
+            "--reference-voltage",
+            help="Also show power as current at this voltage, e.g. 5 for '15W (≈3.0A @ 5V)' like USB chargers",
+        ),
+    ] = None,
+    deadzone: Annotated[
+        float,
+        typer.Option(
+            "--deadzone",
+            help="Show power smaller than this many watts as 0.0W to hide jitter at rest (display only, not stored)",
+        ),
+    ] = 0.0,
+    ascii_output: Annotated[
+        bool,
+        typer.Option(
+            "--ascii",
+            "--plain",
+            help="Use ASCII such as [CHG], [BAT], [AC], and [OK] instead of emoji and symbols in human output",
+        ),
+    ] = False,
 ) -> None:
     """Main entry point for powermonitor CLI.
 
@@ -65,10 +305,43 @@ def main(
 
     Configuration priority: CLI arguments > Config file (~/.powermonitor/config.toml) > Defaults
     """
+    global _quiet, _numbers, _reference_voltage, _deadzone, _glyphs
+    _quiet = quiet
+    _glyphs = ASCII if ascii_output else EMOJI
+    _numbers = CANONICAL
+    if locale_name is not None:
+        try:
+            _numbers = number_format_for_locale("" if locale_name == "system" else locale_name)
+        except ValueError as e:
+            err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+            sys.exit(1)
+    if reference_voltage is not None and not (math.isfinite(reference_voltage) and reference_voltage > 0):
+        err_console.print(f"[red]Error: --reference-voltage must be a positive voltage, got {reference_voltage}[/red]")
+        sys.exit(1)
+    _reference_voltage = reference_voltage
+    if not (math.isfinite(deadzone) and deadzone >= 0):
+        err_console.print(f"[red]Error: --deadzone must be zero or a positive wattage, got {deadzone}[/red]")
+        sys.exit(1)
+    _deadzone = deadzone
+
+    # Threshold options are global so subcommands (status, config show) honor them too
+    ctx.obj = {"low_battery": low_battery, "high_power": high_power, "overheat_temp": overheat_temp}
+    if quiet:
+        # Configure early so warnings while loading the config file are suppressed too
+        setup_logger(log_to_file=False, quiet=True)
+
     # If a subcommand was invoked, do not run the TUI here.
     if ctx.invoked_subcommand is not None:
+        if output is not None:
+            _redirect_output(ctx, output)
         return
 
+    if output is not None:
+        err_console.print(
+            "[red]Error: --output only applies to subcommands (e.g. powermonitor -o out.txt history)[/red]"
+        )
+        sys.exit(1)
+
     # Load configuration from file (or use defaults)
     base_config = load_config()
 
@@ -80,26 +353,65 @@ def main(
             stats_history_limit=stats_limit if stats_limit is not None else base_config.stats_history_limit,
             chart_history_limit=chart_limit if chart_limit is not None else base_config.chart_history_limit,
             database_path=base_config.database_path,
+            max_db_rows=base_config.max_db_rows,
             default_history_limit=base_config.default_history_limit,
             default_export_limit=base_config.default_export_limit,
             log_level="DEBUG" if debug else base_config.log_level,
+            title_format=base_config.title_format,
+            live_chart=live_chart or base_config.live_chart,
+            schedule=schedule if schedule is not None else base_config.schedule,
+            max_interval=max_interval if max_interval is not None else base_config.max_interval,
+            chart_series=plot_series if plot_series is not None else base_config.chart_series,
+            store_interval=store_interval if store_interval is not None else base_config.store_interval,
+            thresholds=base_config.thresholds.with_overrides(**ctx.obj),
+            collector=replace(base_config.collector, gpu_power=gpu_power or base_config.collector.gpu_power),
         )
     except ValueError as e:
         logger.error(f"Invalid configuration: {e}")
         sys.exit(1)
 
     # Setup logging with config level
-    setup_logger(level=config.log_level)
+    _setup_logging(config)
 
-    # Check platform
-    if sys.platform != "darwin":
+    if backend not in BACKENDS:
+        logger.error(f"Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}")
+        sys.exit(1)
+
+    # Check platform (the mock backend needs no hardware)
+    if sys.platform != "darwin" and backend != "mock":
         logger.error("powermonitor only supports macOS")
         sys.exit(1)
 
+    # Event hooks run while the TUI is collecting
+    commands = {"plug": on_plug, "unplug": on_unplug, "low": on_low, "full": on_full, "hot": on_hot}
+    commands = {event: command for event, command in commands.items() if command}
+    try:
+        budgets = parse_budgets(hook_budget) if hook_budget is not None else None
+    except ValueError as e:
+        logger.error(f"Invalid --hook-budget: {e}")
+        sys.exit(1)
+    hooks = None
+    if commands:
+        hooks = EventHooks(
+            commands,
+            low_battery=config.thresholds.low_battery,
+            overheat_temp=config.thresholds.overheat_temp,
+            budgets=budgets,
+        )
+
     # Launch TUI
     try:
         logger.info("Starting powermonitor TUI...")
-        PowerMonitorApp(config=config).run()
+        PowerMonitorApp(
+            config=config,
+            collector=create_collector(backend, config=config.collector),
+            label=label,
+            verbose=verbose,
+            hooks=hooks,
+            reference_voltage=reference_voltage,
+            deadzone=deadzone,
+            glyphs=_glyphs,
+        ).run()
     except KeyboardInterrupt:
         logger.info("Exiting powermonitor...")
         sys.exit(0)
@@ -108,408 +420,2434 @@ def main(
         sys.exit(1)
 
 
+def _redirect_output(ctx: typer.Context, path: Path) -> None:
+    """Send command output to a file for the rest of this invocation.
+
+    Commands print through the module-level console, so it is swapped for a
+    file-backed one and restored (and the file closed) when the command finishes.
+
+    Args:
+        ctx: Typer context of the main callback
+        path: File to write output to (truncated)
+    """
+    global console
+
+    try:
+        handle = open(path, "w")
+    except OSError as e:
+        err_console.print(f"[red]Error: Cannot open output file {path}: {e}[/red]")
+        sys.exit(1)
+
+    original = console
+    console = Console(file=handle, width=original.width)
+
+    def restore() -> None:
+        global console
+        console = original
+        handle.close()
+
+    ctx.call_on_close(restore)
+
+
+def _num(value: float, decimals: int = 1, sign: bool = False, grouping: bool = False) -> str:
+    """Format a number for display with the --locale separators."""
+    return _numbers.format_number(value, decimals, sign=sign, grouping=grouping)
+
+
+def _watts(watts: float, sign: bool = False) -> str:
+    """Format power for display, with the --reference-voltage current when requested and --deadzone applied."""
+    return _numbers.format_watts(watts, _reference_voltage, sign=sign, deadzone=_deadzone)
+
+
+def _setup_logging(config: PowerMonitorConfig) -> None:
+    """Configure logging for the running command, honoring the global --quiet option."""
+    setup_logger(level=config.log_level, quiet=_quiet)
+
+
+def _effective_thresholds(ctx: typer.Context, config: PowerMonitorConfig) -> Thresholds:
+    """Apply the global threshold options to the configured thresholds, exiting on invalid values."""
+    try:
+        return config.thresholds.with_overrides(**(ctx.obj or {}))
+    except ValueError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+
+
 @app.command()
-def export(
-    output: Annotated[
-        Path,
-        typer.Argument(help="Output file path (CSV or JSON based on extension)"),
-    ],
-    limit: Annotated[
-        int | None,
-        typer.Option("--limit", "-n", help="Maximum number of readings to export (None = all)"),
+def status(
+    ctx: typer.Context,
+    as_json: Annotated[
+        bool,
+        typer.Option("--json", help="Print the reading as a JSON object"),
+    ] = False,
+    json_derived: Annotated[
+        bool,
+        typer.Option(
+            "--json-derived",
+            help="Print the reading wrapped with derived values (direction, health, time to full, energy)",
+        ),
+    ] = False,
+    smc_json: Annotated[
+        bool,
+        typer.Option("--smc-json", help="Print raw SMC sensor values keyed by SMC key (IOKit backend only)"),
+    ] = False,
+    smc_record: Annotated[
+        Path | None,
+        typer.Option(
+            "--smc-record",
+            help="Save the raw bytes of every SMC sensor to a JSON file, for bug reports (IOKit backend only)",
+        ),
     ] = None,
-    format_type: Annotated[
+    export_plist: Annotated[
+        bool,
+        typer.Option(
+            "--export-plist",
+            help="Print the battery plist reduced to the fields powermonitor parses (no serials), for bug reports",
+        ),
+    ] = False,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+    save: Annotated[
+        bool,
+        typer.Option("--save", help="Also record the reading in the database (e.g. from cron)"),
+    ] = False,
+    label: Annotated[
+        str | None,
+        typer.Option("--label", "-l", help="Tag the reading with a session label (e.g. 'video render')"),
+    ] = None,
+    minimal: Annotated[
+        bool,
+        typer.Option("--minimal", help="Only read battery percent and charging state (fast, via pmset)"),
+    ] = False,
+    gpu_power: Annotated[
+        bool,
+        typer.Option("--gpu-power", help="Also read GPU power from powermetrics (Apple Silicon, needs sudo)"),
+    ] = False,
+    field: Annotated[
         str | None,
         typer.Option(
-            "--format",
-            "-f",
-            help="Output format: csv or json (auto-detected from extension if not specified)",
+            "--field",
+            help="Print only these reading fields' raw values, comma-separated (tab-separated output)",
+        ),
+    ] = None,
+    avg: Annotated[
+        str | None,
+        typer.Option(
+            "--avg",
+            help="Also show the average power recorded over this recent window, e.g. 5m (from the database)",
         ),
     ] = None,
 ) -> None:
-    """Export power readings to CSV or JSON file.
+    """Take a single power reading and print it.
 
-    Uses config file for database path and default export limit.
+    With --smc-json, prints every SMC sensor the IOKit backend reads (PPBR, PDTR,
+    PSTR, ...) as a JSON object instead; unavailable sensors are null. Useful for
+    your own analysis or for attaching to bug reports.
+
+    With --smc-record FILE, the raw type and bytes of every SMC sensor are saved
+    instead of decoded values. Maintainers replay the file with MockSmcReader to
+    reproduce how your Mac's sensors are decoded.
+
+    Readings outside the configured [thresholds] (or the global --low-battery,
+    --high-power, and --overheat-temp options) are followed by a warning. With
+    --save, so is a charger that has negotiated well below its recorded peak for
+    several readings (see `powermonitor chargers`). --label tags the reading, so
+    readings saved from cron can be filtered with `history --label`.
+
+    With --minimal, only battery percent and charging state are read, skipping
+    ioreg and the SMC - suited to status bars that poll often.
+
+    With --json-derived, the --json object is wrapped as {"reading": ..., "derived": ...}
+    where "derived" holds direction, battery_health_percent, time_to_full_minutes,
+    and energy_wh_estimate. Plain --json output is unchanged.
+
+    With --gpu-power, GPU power is read with powermetrics, which only works as
+    root on Apple Silicon; otherwise a warning is logged and the row is omitted.
+
+    With --export-plist, prints the AppleSmartBattery entry from ioreg as a plist
+    holding only the fields powermonitor reads; serial numbers, device names, and
+    other identifying keys are removed, so it is safe to attach to a bug report.
+
+    With --field, prints just the named fields of the reading (any PowerReading
+    field, e.g. battery_percent or watts_actual) with no label or formatting:
+    booleans as true/false, missing values as an empty string, and times in
+    ISO 8601. Several comma-separated fields are printed on one line, separated
+    by tabs. Threshold warnings are not printed.
+
+    With --avg WINDOW (e.g. 30s, 5m, 1h), the mean watts_actual of the readings
+    recorded in the database over that window is shown too: as an "Average" row,
+    as avg_watts in --json (or in "derived" with --json-derived), and as the
+    avg_watts field for --field. If nothing was recorded in the window, the
+    current reading's watts are used.
 
     Examples:
-        powermonitor export data.csv
-        powermonitor export data.json --limit 1000
-        powermonitor export backup.csv --format csv
+        powermonitor status
+        powermonitor status --minimal --json
+        pct=$(powermonitor status --field battery_percent)
+        powermonitor status --field watts_actual,battery_percent
+        powermonitor status --avg 5m --field avg_watts
+        powermonitor status --json
+        powermonitor status --json-derived
+        powermonitor status --smc-json > smc.json
+        powermonitor status --smc-record smc-recording.json
+        powermonitor status --export-plist > battery.plist
+        powermonitor status --save
+        powermonitor status --save --label overnight
+        sudo powermonitor status --gpu-power
+        powermonitor --low-battery 30 status
     """
-    # Load config for database path and defaults
     config = load_config()
-    setup_logger(level=config.log_level)
+    _setup_logging(config)
+    thresholds = _effective_thresholds(ctx, config)
 
-    # Detect format from extension if not specified
-    if format_type is None:
-        ext = output.suffix.lower()
-        if ext == ".csv":
-            format_type = "csv"
-        elif ext == ".json":
-            format_type = "json"
-        else:
-            console.print(
-                f"[red]Error: Cannot detect format from extension '{ext}'. Use --format csv or --format json[/red]"
-            )
-            sys.exit(1)
-
-    # Validate format
-    if format_type not in ["csv", "json"]:
-        console.print(f"[red]Error: Invalid format '{format_type}'. Must be 'csv' or 'json'[/red]")
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
         sys.exit(1)
 
-    try:
-        # Get database using config path
-        with Database(config.database_path) as db:
-            # Determine effective limit: CLI overrides config default
-            if limit is None:
-                limit = config.default_export_limit
-            # Query readings
-            console.print("[cyan]Querying database...[/cyan]")
-            readings = db.query_history(limit=limit)
+    if minimal and (smc_json or smc_record or export_plist or save or json_derived):
+        err_console.print(
+            "[red]Error: --minimal can't be combined with --smc-json, --smc-record, --export-plist, "
+            "--json-derived, or --save[/red]"
+        )
+        sys.exit(1)
 
-            if not readings:
-                console.print("[yellow]No readings found in database[/yellow]")
-                sys.exit(0)
+    avg_window = None
+    if avg is not None:
+        if minimal or smc_json or smc_record or export_plist:
+            err_console.print(
+                "[red]Error: --avg can't be combined with --minimal, --smc-json, --smc-record, or --export-plist[/red]"
+            )
+            sys.exit(1)
+        try:
+            avg_window = timedelta(seconds=parse_interval(avg))
+        except ValueError as e:
+            err_console.print(f"[red]Error: --avg: {escape(str(e))}[/red]")
+            sys.exit(1)
 
-            # Export based on format
-            if format_type == "csv":
-                _export_csv(output, readings)
-            else:
-                _export_json(output, readings)
+    selected_fields = None
+    if field is not None:
+        if as_json or json_derived or smc_json or smc_record or export_plist:
+            err_console.print(
+                "[red]Error: --field can't be combined with --json, --json-derived, --smc-json, "
+                "--smc-record, or --export-plist[/red]"
+            )
+            sys.exit(1)
+        try:
+            selected_fields = _parse_fields(field, extra=("avg_watts",) if avg_window else ())
+        except ValueError as e:
+            err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+            sys.exit(1)
 
-            console.print(f"[green]✓ Exported {len(readings)} readings to {output}[/green]")
+    if smc_json:
+        _print_smc_json(backend)
+        return
+    if smc_record:
+        _record_smc(backend, smc_record)
+        return
+    if export_plist:
+        _print_battery_plist(backend)
+        return
 
+    level = CollectLevel.MINIMAL if minimal else CollectLevel.FULL
+    try:
+        collector_config = replace(config.collector, level=level, gpu_power=gpu_power or config.collector.gpu_power)
+        reading = create_collector(backend, config=collector_config).collect()
     except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        logger.exception("Export failed")
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Status command failed")
         sys.exit(1)
+    if label is not None:
+        reading.label = label
 
+    if minimal and selected_fields is None:
+        _print_minimal_status(reading, as_json)
+        return
 
-def _export_csv(output_path: Path, readings: list) -> None:
-    """Export readings to CSV file."""
-    with open(output_path, "w", newline="") as f:
-        writer = csv.writer(f)
-
-        # Header
-        writer.writerow(
-            [
-                "timestamp",
-                "watts_actual",
-                "watts_negotiated",
-                "voltage",
-                "amperage",
-                "current_capacity",
-                "max_capacity",
-                "battery_percent",
-                "is_charging",
-                "external_connected",
-                "charger_name",
-                "charger_manufacturer",
-            ]
-        )
+    charger_peak = None
+    if save:
+        try:
+            with Database(config.database_path, max_rows=config.max_db_rows) as db:
+                db.insert_reading(reading)
+                charger_peak = db.update_charger_peak(reading)
+        except Exception as e:
+            err_console.print(f"[red]Error: Failed to save reading: {e}[/red]")
+            logger.exception("Saving reading failed")
+            sys.exit(1)
 
-        # Data
-        for r in readings:
-            writer.writerow(
-                [
-                    r.timestamp.isoformat(),
-                    r.watts_actual,
-                    r.watts_negotiated,
-                    r.voltage,
-                    r.amperage,
-                    r.current_capacity,
-                    r.max_capacity,
-                    r.battery_percent,
-                    r.is_charging,
-                    r.external_connected,
-                    r.charger_name or "",
-                    r.charger_manufacturer or "",
-                ]
-            )
+    avg_watts = None
+    if avg_window is not None:
+        try:
+            with Database(config.database_path, max_rows=config.max_db_rows) as db:
+                avg_watts = db.average_watts_over(avg_window, reading)
+        except Exception as e:
+            err_console.print(f"[red]Error: Failed to read recorded readings: {e}[/red]")
+            logger.exception("Averaging readings failed")
+            sys.exit(1)
 
+    if selected_fields is not None:
+        values = {"avg_watts": avg_watts}
+        row = [_format_field(values[name] if name in values else getattr(reading, name)) for name in selected_fields]
+        # Written to the underlying file: Rich would expand the tabs into spaces
+        console.file.write("\t".join(row) + "\n")
+        return
+    if json_derived:
+        data = DerivedReading(reading).to_dict()
+        if avg_watts is not None:
+            data["derived"]["avg_watts"] = avg_watts
+        _print_json(data)
+        return
+    if as_json:
+        data = reading.to_dict()
+        if avg_watts is not None:
+            data["avg_watts"] = avg_watts
+        _print_json(data)
+        return
 
-def _export_json(output_path: Path, readings: list) -> None:
-    """Export readings to JSON file."""
-    data = [
-        {
-            "timestamp": r.timestamp.isoformat(),
-            "watts_actual": r.watts_actual,
-            "watts_negotiated": r.watts_negotiated,
-            "voltage": r.voltage,
-            "amperage": r.amperage,
-            "current_capacity": r.current_capacity,
-            "max_capacity": r.max_capacity,
-            "battery_percent": r.battery_percent,
-            "is_charging": r.is_charging,
-            "external_connected": r.external_connected,
-            "charger_name": r.charger_name,
-            "charger_manufacturer": r.charger_manufacturer,
-        }
-        for r in readings
-    ]
-
-    with open(output_path, "w") as f:
-        json.dump(data, f, indent=2)
+    table = Table(show_header=False, box=None)
+    table.add_column("Metric", style="cyan")
+    table.add_column("Value", style="white")
+
+    status_text = f"{_glyphs.icon(reading.direction)} {_DIRECTION_LABELS[reading.direction]}"
+    if reading.direction is PowerDirection.AC_IDLE and reading.charge_inhibit_reason:
+        status_text += f" (not charging: {escape(sanitize_label(reading.charge_inhibit_reason))})"
+    table.add_row("Status", status_text)
+    if reading.adapter_source is AdapterSource.SMC:
+        table.add_row("Power", f"{_watts(reading.watts_actual, sign=True)} (adapter wattage unknown)")
+        table.add_row("Adapter", "Connected (detected from SMC input power; ioreg reports no adapter details)")
+    else:
+        table.add_row("Power", f"{_watts(reading.watts_actual, sign=True)} (negotiated {reading.watts_negotiated}W)")
+    if avg_watts is not None:
+        table.add_row("Average", f"{_watts(avg_watts, sign=True)} over the last {escape(avg)}")
+    if reading.system_power is not None:
+        table.add_row("System", f"{_num(reading.system_power)}W")
+    if reading.gpu_power is not None:
+        table.add_row("GPU", f"{_num(reading.gpu_power, 2)}W")
+    table.add_row("Battery", f"{reading.battery_percent}% ({reading.current_capacity}/{reading.max_capacity} mAh)")
+    electrical = f"{_num(reading.voltage, 2)}V, {_num(reading.amperage, 2, sign=True)}A"
+    if reading.amperage_ioreg is not None:
+        electrical += f" (ioreg {_num(reading.amperage_ioreg, 2, sign=True)}A)"
+    table.add_row("Electrical", electrical)
+    if reading.max_charge_current is not None and reading.direction is PowerDirection.CHARGING:
+        charge_rate = f"{_num(reading.amperage, 2)}A of {_num(reading.max_charge_current, 2)}A max"
+        if reading.charging_throttled:
+            charge_rate += f" [yellow]{_glyphs.warning} Charging throttled (thermal/optimized)[/yellow]"
+        table.add_row("Charge rate", charge_rate)
+    if reading.charge_efficiency is not None:
+        table.add_row("Efficiency", f"~{_num(reading.charge_efficiency, 0)}% of adapter power reaches the battery")
+    if reading.charger_name or reading.charger_certified is False:
+        charger_text = escape(sanitize_label(reading.charger_name) or "Unknown")
+        if reading.charger_certified is False:
+            charger_text += f" [yellow]{_glyphs.warning} Possibly uncertified charger[/yellow]"
+        table.add_row("Charger", charger_text)
+    if reading.charger_port is not None:
+        table.add_row("Port", f"Charger on port {escape(sanitize_label(reading.charger_port))}")
+    if reading.battery_temp is not None:
+        table.add_row("Temperature", f"{_num(reading.battery_temp)}°C")
+    if reading.max_temp is not None:
+        table.add_row("Hottest", f"{_num(reading.max_temp)}°C ({escape(reading.max_temp_sensor or '?')})")
+    if reading.thermal_state is not None:
+        table.add_row("Thermal", reading.thermal_state.capitalize())
+    if reading.low_power_mode is not None:
+        table.add_row("Low Power Mode", "On" if reading.low_power_mode else "Off")
+
+    console.print(table)
+    for warning in thresholds.check(reading):
+        console.print(f"[yellow]{_glyphs.warning} {warning}[/yellow]")
+    if charger_peak is not None and charger_peak.degraded:
+        console.print(f"[yellow]{_glyphs.warning} {escape(charger_peak.warning())}[/yellow]")
+
+
+@config_app.command("path")
+def config_path() -> None:
+    """Print the configuration file path.
 
+    Examples:
+        powermonitor config path
+        $EDITOR "$(powermonitor config path)"
+    """
+    path = get_config_path()
+    console.print(str(path), markup=False, highlight=False, soft_wrap=True)
+    if not path.exists() and not _quiet:
+        err_console.print("[dim]File does not exist yet; defaults are in use[/dim]")
 
-@app.command()
-def stats() -> None:
-    """Show database statistics.
 
-    Uses config file for database path.
+@config_app.command("show")
+def config_show(ctx: typer.Context) -> None:
+    """Print the effective configuration in config.toml format.
 
-    Displays information about stored readings including:
-    - Total number of readings
-    - Date range (earliest to latest)
-    - Database file size
+    Shows the config file merged with defaults and any global overrides
+    (e.g. --low-battery), so the output can be saved as a starting config file.
 
     Examples:
-        powermonitor stats
+        powermonitor config show
+        powermonitor config show > config.toml
     """
-    # Load config for database path
     config = load_config()
-    setup_logger(level=config.log_level)
+    config.thresholds = _effective_thresholds(ctx, config)
+    console.print(format_config(config), markup=False, highlight=False, soft_wrap=True, end="")
 
-    try:
-        db_path = Path(config.database_path)
 
-        # Get database file size
-        if db_path.exists():
-            size_bytes = db_path.stat().st_size
-            size_mb = size_bytes / (1024 * 1024)
-        else:
-            console.print("[yellow]Database file does not exist yet[/yellow]")
-            sys.exit(0)
+def _check_smc_backend(option: str, backend: str) -> None:
+    """Exit with an error unless SMC sensors can be read directly for the given option."""
+    if backend not in ("auto", "iokit"):
+        err_console.print(f"[red]Error: {option} reads SMC sensors directly and cannot use --backend {backend}[/red]")
+        sys.exit(1)
+    if sys.platform != "darwin":
+        err_console.print(f"[red]Error: {option} requires macOS; SMC sensors are read through IOKit[/red]")
+        sys.exit(1)
 
-        with Database(db_path) as db:
-            # Get statistics
-            stat_data = db.get_statistics(limit=None)  # Get all readings for stats
 
-            if stat_data["count"] == 0:
-                console.print("[yellow]No readings in database[/yellow]")
-                sys.exit(0)
+def _print_smc_json(backend: str) -> None:
+    """Print the raw SMC sensor map as JSON, exiting with an error if IOKit is unavailable."""
+    _check_smc_backend("--smc-json", backend)
+
+    try:
+        # Imported lazily: loading the IOKit bindings fails on systems without the framework
+        from .collector.iokit.collector import IOKitCollector
+        from .collector.iokit.connection import SMCError
+    except (ImportError, OSError) as e:
+        err_console.print(f"[red]Error: IOKit backend unavailable, cannot read SMC sensors: {e}[/red]")
+        sys.exit(1)
+
+    try:
+        data = IOKitCollector().read_smc_sensors()
+    except SMCError as e:
+        err_console.print(f"[red]Error: SMC access failed: {e}[/red]")
+        sys.exit(1)
 
-            # Display statistics
-            table = Table(title="Database Statistics", show_header=False)
-            table.add_column("Metric", style="cyan")
-            table.add_column("Value", style="white")
+    _print_json(data.to_key_map())
 
-            table.add_row("Total readings", f"{stat_data['count']:,}")
-            table.add_row("Earliest reading", stat_data["earliest"] or "N/A")
-            table.add_row("Latest reading", stat_data["latest"] or "N/A")
-            table.add_row("Database size", f"{size_mb:.2f} MB")
-            table.add_row("Database path", str(db_path))
 
-            console.print(table)
+def _record_smc(backend: str, path: Path) -> None:
+    """Save the raw bytes of every SMC sensor to a file, exiting with an error if IOKit is unavailable."""
+    _check_smc_backend("--smc-record", backend)
 
-    except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        logger.exception("Stats command failed")
+    try:
+        # Imported lazily: loading the IOKit bindings fails on systems without the framework
+        from .collector.iokit.collector import SMCPowerData
+        from .collector.iokit.collector import read_temperatures
+        from .collector.iokit.connection import SMCConnection
+        from .collector.iokit.connection import SMCError
+        from .collector.iokit.reader import RecordingSmcReader
+    except (ImportError, OSError) as e:
+        err_console.print(f"[red]Error: IOKit backend unavailable, cannot read SMC sensors: {e}[/red]")
         sys.exit(1)
 
+    try:
+        with RecordingSmcReader(SMCConnection()) as smc:
+            SMCPowerData.read(smc)
+            read_temperatures(smc)
+        smc.save(path)
+    except SMCError as e:
+        err_console.print(f"[red]Error: SMC access failed: {e}[/red]")
+        sys.exit(1)
+    except OSError as e:
+        err_console.print(f"[red]Error: Could not write {path}: {e}[/red]")
+        sys.exit(1)
 
-@app.command()
-def cleanup(
-    days: Annotated[
-        int | None,
-        typer.Option("--days", "-d", help="Delete readings older than N days"),
-    ] = None,
-    all_data: Annotated[
+    available = sum(value is not None for value in smc.recorded.values())
+    console.print(f"Recorded {available}/{len(smc.recorded)} SMC sensors to {escape(str(path))}")
+
+
+@smc_app.command("read")
+def smc_read(
+    key: Annotated[
+        str,
+        typer.Argument(help="4-character SMC key, e.g. PDTR or TB0T (case-sensitive)"),
+    ],
+    as_json: Annotated[
         bool,
-        typer.Option("--all", help="Delete ALL readings (requires confirmation)"),
+        typer.Option("--json", help="Print the key as a JSON object"),
     ] = False,
 ) -> None:
-    """Clean up old power readings from database.
+    """Read one SMC key and print its raw bytes, data type, and decoded value.
 
-    Uses config file for database path.
+    For experimenting with sensors powermonitor doesn't read yet. Keys differ
+    between Mac models; a key this Mac doesn't have is reported as an error.
 
     Examples:
-        powermonitor cleanup --days 30
-        powermonitor cleanup --all
+        powermonitor smc read PDTR
+        powermonitor smc read TB0T --json
     """
-    # Load config for database path
-    config = load_config()
-    setup_logger(level=config.log_level)
+    if sys.platform != "darwin":
+        err_console.print("[red]Error: smc read requires macOS; SMC keys are read through IOKit[/red]")
+        sys.exit(1)
 
-    if not days and not all_data:
-        console.print("[red]Error: Must specify either --days N or --all[/red]")
-        console.print("Use --help for usage information")
+    try:
+        # Imported lazily: loading the IOKit bindings fails on systems without the framework
+        from .collector.iokit.connection import SMCConnection
+        from .collector.iokit.parser import describe_type
+        from .collector.iokit.structures import InvalidKeyError
+        from .collector.iokit.structures import SMCError
+        from .collector.iokit.structures import SMCKeyNotFoundError
+    except (ImportError, OSError) as e:
+        err_console.print(f"[red]Error: IOKit unavailable, cannot read SMC keys: {e}[/red]")
         sys.exit(1)
 
     try:
-        with Database(config.database_path) as db:
-            if all_data:
-                # Confirm deletion of all data
-                console.print("[yellow]⚠️  WARNING: This will delete ALL readings![/yellow]")
-                confirm = typer.confirm("Are you sure you want to continue?")
-                if not confirm:
-                    console.print("[cyan]Operation cancelled[/cyan]")
-                    sys.exit(0)
+        with SMCConnection() as smc:
+            value = smc.read_raw(key)
+    except InvalidKeyError as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+    except SMCKeyNotFoundError as e:
+        err_console.print(f"[red]Error: {escape(str(e))} (keys differ between Mac models)[/red]")
+        sys.exit(1)
+    except SMCError as e:
+        err_console.print(f"[red]Error: SMC access failed: {escape(str(e))}[/red]")
+        sys.exit(1)
 
-                deleted = db.clear_history()
-                console.print(f"[green]✓ Deleted all {deleted} readings[/green]")
+    description = describe_type(value.data_type, len(value.data))
+    decoded = value.decode()
+    if as_json:
+        _print_json(
+            {
+                "key": key,
+                "type": value.data_type,
+                "type_description": description,
+                "size": len(value.data),
+                "bytes": value.data.hex(),
+                "value": decoded,
+            }
+        )
+        return
 
-            else:
-                # Delete old data
-                assert days is not None, "days must be specified"  # Type checker hint
-                console.print(f"[cyan]Deleting readings older than {days} days...[/cyan]")
+    console.print(f"Key:   {escape(key)}")
+    console.print(f"Type:  {escape(repr(value.data_type))} ({description})")
+    console.print(f"Bytes: {value.data.hex(' ') or '(none)'} ({len(value.data)} bytes)")
+    console.print(f"Value: {decoded:g}")
 
-                deleted = db.cleanup_old_data(days=days)
-                console.print(f"[green]✓ Deleted {deleted} old readings[/green]")
 
+def _print_battery_plist(backend: str) -> None:
+    """Print the sanitized ioreg battery plist, exiting with an error if ioreg can't be read."""
+    if backend == "mock":
+        err_console.print("[red]Error: --export-plist reads ioreg and cannot use --backend mock[/red]")
+        sys.exit(1)
+
+    try:
+        battery = IORegCollector().read_battery_dict()
     except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        logger.exception("Cleanup failed")
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Reading ioreg for --export-plist failed")
         sys.exit(1)
 
+    console.print(export_battery_plist(battery).decode(), markup=False, highlight=False, soft_wrap=True, end="")
+
+
+def _print_minimal_status(reading: PowerReading, as_json: bool) -> None:
+    """Print the fields populated at CollectLevel.MINIMAL."""
+    if as_json:
+        _print_json(
+            {
+                "timestamp": reading.timestamp.isoformat(),
+                "battery_percent": reading.battery_percent,
+                "is_charging": reading.is_charging,
+                "external_connected": reading.external_connected,
+                "fully_charged": reading.fully_charged,
+            }
+        )
+        return
+    direction = reading.direction
+    console.print(f"{_glyphs.icon(direction)} {reading.battery_percent}% {_DIRECTION_LABELS[direction]}")
+
+
+def _parse_fields(spec: str, extra: tuple[str, ...] = ()) -> list[str]:
+    """Parse a --field list into PowerReading field names (plus extra names the command computes).
+
+    Raises:
+        ValueError: If the list is empty or names an unknown field
+    """
+    known = [f.name for f in fields(PowerReading)] + list(extra)
+    names = [name.strip() for name in spec.split(",") if name.strip()]
+    if not names:
+        raise ValueError("--field needs at least one field name")
+    unknown = [name for name in names if name not in known]
+    if unknown:
+        raise ValueError(f"Unknown field {', '.join(repr(n) for n in unknown)}. Known fields: {', '.join(known)}")
+    return names
+
+
+def _format_field(value: object) -> str:
+    """Format a reading field for --field: raw, unlabeled, and easy to parse in a shell."""
+    if value is None:
+        return ""
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    if isinstance(value, datetime | date):
+        return value.isoformat()
+    if isinstance(value, str):
+        # A tab or newline in a charger name would split the value across fields or lines
+        return sanitize_label(value)
+    return str(value)
+
+
+def _print_json(data: dict) -> None:
+    """Print a JSON document without Rich markup, highlighting, or line wrapping."""
+    console.print(json.dumps(data, indent=2), markup=False, highlight=False, soft_wrap=True)
+
 
 @app.command()
-def history(
+def doctor(
+    ctx: typer.Context,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+) -> None:
+    """Check the battery and data collection for problems and suggest fixes.
+
+    Takes one reading and reports whether collection works, whether powermonitor
+    runs under Rosetta, which SMC sensors are unreadable on this Mac (IOKit
+    backend), whether the reported battery percentage is plausible for the battery
+    voltage (a large mismatch means the gauge needs a calibration cycle), and any
+    [thresholds] the reading exceeds.
+
+    Examples:
+        powermonitor doctor
+        powermonitor doctor --backend ioreg
+    """
+    config = load_config()
+    _setup_logging(config)
+    thresholds = _effective_thresholds(ctx, config)
+
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
+        sys.exit(1)
+
+    try:
+        collector = create_collector(backend, config=config.collector)
+        reading = collector.collect()
+    except CollectorUnavailableError as e:
+        err_console.print(f"[red]{_glyphs.error} Collection ({backend}): required tool missing - {e}[/red]")
+        sys.exit(1)
+    except Exception as e:
+        err_console.print(f"[red]{_glyphs.error} Collection ({backend}): {e}[/red]")
+        logger.exception("Doctor collection failed")
+        sys.exit(1)
+    console.print(f"[green]{_glyphs.ok}[/green] Collection: {type(collector).__name__} returned a reading")
+    if sys.platform == "darwin":
+        _doctor_architecture()
+    if backend in ("auto", "iokit"):
+        _doctor_smc_sensors()
+
+    if reading.calibration_suspect:
+        estimate = estimate_percent(reading.voltage)
+        console.print(
+            f"[yellow]{_glyphs.warning} Calibration: battery reports {reading.battery_percent}% but "
+            f"{reading.voltage:.2f}V suggests about {estimate:.0f}%[/yellow]"
+        )
+        console.print(
+            "  Run a calibration cycle: charge to 100% and stay plugged in for 2 hours, use the Mac on battery "
+            "until it sleeps, then charge to 100% without interruption."
+        )
+    elif abs(reading.amperage) > REST_CURRENT:
+        console.print(
+            f"[dim]- Calibration: not checked while {abs(reading.amperage):.2f}A is flowing "
+            "(run again with the Mac idle on battery or fully charged)[/dim]"
+        )
+    else:
+        console.print(
+            f"[green]{_glyphs.ok}[/green] Calibration: {reading.battery_percent}% is consistent with "
+            f"{reading.voltage:.2f}V"
+        )
+
+    warnings = thresholds.check(reading)
+    for warning in warnings:
+        console.print(f"[yellow]{_glyphs.warning} {warning}[/yellow]")
+    if not warnings:
+        console.print(f"[green]{_glyphs.ok}[/green] Thresholds: reading is within the configured limits")
+
+
+def _doctor_architecture() -> None:
+    """Report whether powermonitor runs natively or as a translated x86_64 build (for doctor)."""
+    if running_under_rosetta():
+        console.print(
+            f"[yellow]{_glyphs.warning} Architecture: {describe_architecture()}; SMC keys are chosen for "
+            "the native hardware, but translated reads are less reliable[/yellow]"
+        )
+        console.print("  Reinstall with an arm64 Python (e.g. from Homebrew in /opt/homebrew rather than /usr/local).")
+        return
+    console.print(f"[green]{_glyphs.ok}[/green] Architecture: {describe_architecture()} (native)")
+
+
+def _doctor_smc_sensors() -> None:
+    """Report which SMC power sensors this Mac can read, and list its temperature sensors (for doctor)."""
+    if sys.platform != "darwin":
+        console.print("[dim]- SMC sensors: not checked (IOKit requires macOS)[/dim]")
+        return
+
+    try:
+        # Imported lazily: loading the IOKit bindings fails on systems without the framework
+        from .collector.iokit.collector import SMC_SENSORS
+        from .collector.iokit.collector import SMC_TEMPERATURE_SENSORS
+        from .collector.iokit.collector import SMCPowerData
+        from .collector.iokit.collector import read_temperatures
+        from .collector.iokit.collector import smc_field_keys
+        from .collector.iokit.connection import SMCConnection
+        from .collector.iokit.structures import SMCError
+        from .collector.iokit.structures import SMCMultipleError
+    except (ImportError, OSError) as e:
+        console.print(
+            f"[yellow]{_glyphs.warning} SMC sensors: IOKit unavailable, readings use ioreg only: "
+            f"{escape(str(e))}[/yellow]"
+        )
+        return
+
+    keys = smc_field_keys(native_architecture())
+    unreadable: dict[str, Exception] = {}
+    try:
+        with SMCConnection() as smc:
+            try:
+                SMCPowerData.read(smc, strict=True, keys=keys)
+            except SMCMultipleError as e:
+                unreadable = dict(e.errors)
+            temperatures = read_temperatures(smc)
+    except SMCError as e:
+        console.print(
+            f"[yellow]{_glyphs.warning} SMC sensors: SMC access failed, readings use ioreg only: "
+            f"{escape(str(e))}[/yellow]"
+        )
+        return
+
+    if unreadable:
+        console.print(
+            f"[yellow]{_glyphs.warning} SMC sensors: {len(unreadable)} of {len(keys)} keys unreadable[/yellow]"
+        )
+        for key, error in unreadable.items():
+            console.print(f"  {key} ({SMC_SENSORS[key]}): {escape(str(error))}")
+        console.print("  Readings leave these out (or use ioreg instead); keys differ between Mac models.")
+    else:
+        console.print(f"[green]{_glyphs.ok}[/green] SMC sensors: all {len(keys)} keys readable")
+
+    if not temperatures:
+        console.print("[dim]- Temperatures: none of the known SMC temperature sensors exist on this Mac[/dim]")
+        return
+    console.print(f"[green]{_glyphs.ok}[/green] Temperatures: {len(temperatures)} sensors")
+    for key, temp in temperatures.items():
+        console.print(f"  {key} ({SMC_TEMPERATURE_SENSORS[key]}): {_num(temp)}°C")
+
+
+@app.command()
+def probe() -> None:
+    """Report every data source as JSON, for attaching to bug reports.
+
+    Covers ioreg (does it list a battery, does the entry parse), each SMC key
+    (readable, and its value), pmset, and powermetrics (installed, permitted),
+    with a sample value from each. Unlike doctor, nothing is judged: a source
+    that fails is reported with its error, and the command still exits 0.
+
+    Examples:
+        powermonitor probe > probe.json
+        sudo powermonitor probe  # Includes a powermetrics sample
+    """
+    config = load_config()
+    _setup_logging(config)
+    _print_json(probe_all())
+
+
+@app.command()
+def export(
+    output: Annotated[
+        Path,
+        typer.Argument(help="Output file path (CSV, JSON, or NDJSON based on extension)"),
+    ],
     limit: Annotated[
         int | None,
-        typer.Option("--limit", "-n", help="Number of recent readings to show (uses config default if not specified)"),
+        typer.Option("--limit", "-n", help="Maximum number of readings to export (None = all)"),
+    ] = None,
+    format_type: Annotated[
+        str | None,
+        typer.Option(
+            "--format",
+            "-f",
+            help="Output format: csv, json, or ndjson (auto-detected from extension if not specified)",
+        ),
+    ] = None,
+    no_header: Annotated[
+        bool,
+        typer.Option("--no-header", help="CSV only: omit the column name row"),
+    ] = False,
+    delimiter: Annotated[
+        str | None,
+        typer.Option("--delimiter", "-d", help="CSV only: single field separator character; '\\t' for tab"),
     ] = None,
 ) -> None:
-    """Show recent power readings from database.
+    """Export power readings to CSV, JSON, or NDJSON file.
 
-    Uses config file for database path and default limit.
+    Uses config file for database path and default export limit.
+    A .tsv extension exports CSV with tab-separated fields.
 
     Examples:
-        powermonitor history
-        powermonitor history --limit 50
+        powermonitor export data.csv
+        powermonitor export data.json --limit 1000
+        powermonitor export backup.csv --format csv
+        powermonitor export data.tsv --no-header
+        powermonitor export data.txt --format csv --delimiter ';'
+        powermonitor export session.ndjson  # replay later with `powermonitor replay`
     """
     # Load config for database path and defaults
     config = load_config()
-    setup_logger(level=config.log_level)
+    _setup_logging(config)
 
-    # Use config default if limit not specified
-    if limit is None:
-        limit = config.default_history_limit
+    # Detect format from extension if not specified
+    if format_type is None:
+        ext = output.suffix.lower()
+        format_type = EXTENSION_FORMATS.get(ext)
+        if ext == ".tsv" and delimiter is None:
+            delimiter = "\t"
+        if format_type is None:
+            err_console.print(
+                f"[red]Error: Cannot detect format from extension '{ext}'. "
+                "Use --format csv, --format json, or --format ndjson[/red]"
+            )
+            sys.exit(1)
+
+    # Validate format
+    if format_type not in EXPORT_FORMATS:
+        err_console.print(f"[red]Error: Invalid format '{format_type}'. Must be 'csv', 'json', or 'ndjson'[/red]")
+        sys.exit(1)
+
+    if format_type != "csv" and (no_header or delimiter is not None):
+        err_console.print("[red]Error: --no-header and --delimiter only apply to CSV export[/red]")
+        sys.exit(1)
+
+    # Shells pass "\t" through literally, so accept it as the way to ask for tabs
+    if delimiter == "\\t":
+        delimiter = "\t"
+    if delimiter is not None and len(delimiter) != 1:
+        err_console.print(f"[red]Error: --delimiter must be a single character, got {escape(repr(delimiter))}[/red]")
+        sys.exit(1)
 
     try:
+        # Get database using config path
         with Database(config.database_path) as db:
-            readings = db.query_history(limit=limit)
+            # Determine effective limit: CLI overrides config default
+            if limit is None:
+                limit = config.default_export_limit
+            # Query readings
+            console.print("[cyan]Querying database...[/cyan]")
+            readings = db.iter_history(limit=limit)
 
-            if not readings:
-                console.print("[yellow]No readings in database[/yellow]")
+            # Peek at the first row so an empty database doesn't create an output file
+            first = next(readings, None)
+            if first is None:
+                console.print("[yellow]No readings found in database[/yellow]")
                 sys.exit(0)
+            readings = itertools.chain([first], readings)
 
-            # Create table
-            table = Table(title=f"Recent Power Readings (Last {len(readings)})")
-            table.add_column("Time", style="cyan")
-            table.add_column("Power", style="green", justify="right")
-            table.add_column("Battery", style="yellow", justify="right")
-            table.add_column("Voltage", style="blue", justify="right")
-            table.add_column("Current", style="magenta", justify="right")
-            table.add_column("Status", style="white")
+            count = export_readings(output, readings, format_type, delimiter=delimiter or ",", header=not no_header)
 
-            # Reverse to show oldest first
-            for r in reversed(readings):
-                # Format status
-                if r.is_charging:
-                    status = "⚡ Charging"
-                elif r.external_connected:
-                    status = "🔌 AC Power"
-                else:
-                    status = "🔋 Battery"
+            console.print(f"[green]{_glyphs.ok} Exported {count} readings to {output}[/green]")
 
-                # Format time (show only time if today, otherwise date + time)
-                time_str = r.timestamp.strftime("%H:%M:%S")
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Export failed")
+        sys.exit(1)
 
-                table.add_row(
-                    time_str,
-                    f"{r.watts_actual:+.1f}W",
-                    f"{r.battery_percent}%",
-                    f"{r.voltage:.1f}V",
-                    f"{r.amperage:+.2f}A",
-                    status,
-                )
 
-            console.print(table)
+# Skipped rows listed individually by `import`; the rest are only counted
+MAX_REPORTED_SKIPS = 10
+
+
+@app.command("import")
+def import_csv(
+    input_path: Annotated[
+        Path,
+        typer.Argument(help="CSV file written by `powermonitor export` (.tsv for tab-separated)"),
+    ],
+    delimiter: Annotated[
+        str | None,
+        typer.Option("--delimiter", "-d", help="Single field separator character; '\\t' for tab"),
+    ] = None,
+    dry_run: Annotated[
+        bool,
+        typer.Option("--dry-run", help="Check the file and show how many readings would be imported"),
+    ] = False,
+) -> None:
+    """Import power readings from a CSV export into the database.
+
+    For moving history to another Mac or restoring a backup. The header row must
+    use the export's column names (older exports without the newer columns work).
+    Malformed rows are skipped and listed; the rest are inserted in one transaction.
+    Readings already in the database are left out, so importing the same file twice
+    is harmless (identical rows within the file are all kept).
+
+    Examples:
+        powermonitor import backup.csv
+        powermonitor import data.tsv --dry-run
+        powermonitor import data.txt --delimiter ';'
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if delimiter is None:
+        delimiter = "\t" if input_path.suffix.lower() == ".tsv" else ","
+    # Shells pass "\t" through literally, so accept it as the way to ask for tabs
+    if delimiter == "\\t":
+        delimiter = "\t"
+    if len(delimiter) != 1:
+        err_console.print(f"[red]Error: --delimiter must be a single character, got {escape(repr(delimiter))}[/red]")
+        sys.exit(1)
+
+    try:
+        result = read_csv(input_path, delimiter=delimiter)
+    except (OSError, ValueError) as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    for line_number, reason in result.skipped[:MAX_REPORTED_SKIPS]:
+        console.print(f"[yellow]{_glyphs.warning} Skipped line {line_number}: {escape(reason)}[/yellow]")
+    if len(result.skipped) > MAX_REPORTED_SKIPS:
+        console.print(f"[yellow]... and {len(result.skipped) - MAX_REPORTED_SKIPS} more malformed rows[/yellow]")
+
+    if not result.readings:
+        console.print("[yellow]No valid readings found in file[/yellow]")
+        sys.exit(0)
+
+    if dry_run:
+        console.print(
+            f"[cyan]Would import {len(result.readings)} readings "
+            f"(skipping {len(result.skipped)} malformed rows)[/cyan]"
+        )
+        return
 
+    try:
+        with Database(config.database_path, max_rows=config.max_db_rows) as db:
+            count = db.insert_readings(result.readings, skip_existing=True)
     except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        logger.exception("History command failed")
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Import failed")
         sys.exit(1)
 
+    notes = []
+    if count < len(result.readings):
+        notes.append(f"{len(result.readings) - count} already in the database")
+    if result.skipped:
+        notes.append(f"skipped {len(result.skipped)} malformed rows")
+    details = f" ({', '.join(notes)})" if notes else ""
+    console.print(f"[green]{_glyphs.ok} Imported {count} readings from {input_path}{details}[/green]")
+
 
 @app.command()
-def health(
-    days: Annotated[
-        int,
-        typer.Option("--days", "-d", help="Number of days to analyze"),
-    ] = 30,
+def stats(
+    as_json: Annotated[
+        bool,
+        typer.Option("--json", help="Print statistics as a JSON object"),
+    ] = False,
 ) -> None:
-    """Show battery health trend over time.
+    """Show database statistics.
 
     Uses config file for database path.
 
-    Analyzes max_capacity changes to detect battery degradation.
+    Displays information about stored readings including:
+    - Total number of readings
+    - Date range (earliest to latest)
+    - Database file size
+
+    With --json, prints an object with database_path, database_size_bytes, count,
+    earliest, latest, avg_watts, min_watts, max_watts, and avg_battery. An empty or
+    missing database gives count 0 and nulls rather than a message.
 
     Examples:
-        powermonitor health
-        powermonitor health --days 60
+        powermonitor stats
+        powermonitor stats --json
     """
     # Load config for database path
     config = load_config()
-    setup_logger(level=config.log_level)
+    _setup_logging(config)
 
     try:
-        with Database(config.database_path) as db:
-            results = db.get_battery_health_trend(days=days)
+        db_path = Path(config.database_path)
 
-            if not results:
-                console.print(f"[yellow]No readings found in the last {days} days[/yellow]")
-                sys.exit(0)
+        if not db_path.exists():
+            if as_json:
+                _print_json(StatsResult(database_path=str(db_path), database_size_bytes=0, count=0).to_dict())
+                return
+            console.print("[yellow]Database file does not exist yet[/yellow]")
+            sys.exit(0)
 
-            # Calculate trend
-            first_capacity = results[0][1]
-            last_capacity = results[-1][1]
-            change_mah = last_capacity - first_capacity
-            change_percent = (change_mah / first_capacity) * 100
+        with Database(db_path) as db:
+            # Get all readings for stats
+            result = StatsResult.from_statistics(db.get_statistics(limit=None), db_path, db_path.stat().st_size)
 
-            # Determine status
-            if change_percent < -2:
-                status = "[red]⚠️  Degrading (significant)[/red]"
-            elif change_percent < -0.5:
-                status = "[yellow]⚠️  Degrading (normal wear)[/yellow]"
-            else:
-                status = "[green]✓ Stable[/green]"
+        if as_json:
+            _print_json(result.to_dict())
+            return
 
-            # Display summary
-            console.print(f"\n[bold]Battery Health Analysis ({days} days)[/bold]\n")
+        if result.count == 0:
+            console.print("[yellow]No readings in database[/yellow]")
+            sys.exit(0)
 
-            summary_table = Table(show_header=False, box=None)
-            summary_table.add_column("Metric", style="cyan")
-            summary_table.add_column("Value", style="white")
+        # Display statistics
+        table = Table(title="Database Statistics", show_header=False)
+        table.add_column("Metric", style="cyan")
+        table.add_column("Value", style="white")
 
-            summary_table.add_row("First reading", results[0][0])
-            summary_table.add_row("First avg capacity", f"{first_capacity:.0f} mAh")
-            summary_table.add_row("Last reading", results[-1][0])
-            summary_table.add_row("Last avg capacity", f"{last_capacity:.0f} mAh")
-            summary_table.add_row("Change", f"{change_mah:+.0f} mAh ({change_percent:+.2f}%)")
-            summary_table.add_row("Status", status)
-            summary_table.add_row("Days analyzed", str(len(results)))
+        table.add_row("Total readings", _num(result.count, 0, grouping=True))
+        table.add_row("Earliest reading", result.earliest or "N/A")
+        table.add_row("Latest reading", result.latest or "N/A")
+        table.add_row("Database size", f"{_num(result.database_size_bytes / (1024 * 1024), 2)} MB")
+        table.add_row("Database path", result.database_path)
 
-            console.print(summary_table)
+        console.print(table)
 
-            # Show daily trend if more than 3 data points
-            if len(results) > 3:
-                console.print(f"\n[bold]Daily Trend (Last {min(7, len(results))} days)[/bold]\n")
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Stats command failed")
+        sys.exit(1)
 
-                trend_table = Table()
-                trend_table.add_column("Date", style="cyan")
-                trend_table.add_column("Avg Capacity", style="green", justify="right")
-                trend_table.add_column("Readings", style="yellow", justify="right")
 
-                # Show last 7 days
-                for row in results[-7:]:
-                    trend_table.add_row(row[0], f"{row[1]:.0f} mAh", str(row[2]))
+def _compare_row(
+    label: str, current: float | None, other: float | None, unit: str, decimals: int = 1
+) -> tuple[str, str, str, str]:
+    """Format one `compare-db` row: both values and the other database's difference from the current one."""
 
-                console.print(trend_table)
+    def value(number: float | None) -> str:
+        return "N/A" if number is None else f"{_num(number, decimals, grouping=True)}{unit}"
 
-    except Exception as e:
-        console.print(f"[red]Error: {e}[/red]")
-        logger.exception("Health command failed")
-        sys.exit(1)
+    change = ""
+    if current is not None and other is not None:
+        change = f"{_num(other - current, decimals, sign=True)}{unit}"
+        if current:
+            change += f" ({_num((other - current) / abs(current) * 100, 0, sign=True)}%)"
+    return label, value(current), value(other), change
+
+
+@app.command("compare-db")
+def compare_db(
+    other_path: Annotated[
+        Path,
+        typer.Argument(help="Database file to compare with, e.g. copied from another Mac"),
+    ],
+    as_json: Annotated[
+        bool,
+        typer.Option("--json", help="Print both summaries as a JSON object"),
+    ] = False,
+) -> None:
+    """Compare the recorded history with another database side by side.
+
+    Answers questions like "is my new MacBook more efficient than my old one?" by
+    showing average and peak power, battery draw, and battery health of both. The
+    other database is opened read-only, and files from older powermonitor versions
+    work (values they never recorded, such as battery health, show as N/A).
+
+    With --json, prints {"current": {...}, "other": {...}}, each with database_path,
+    count, earliest, latest, avg_watts, avg_draw_watts, peak_draw_watts,
+    peak_charge_watts, max_capacity, and battery_health_percent.
+
+    Examples:
+        powermonitor compare-db ~/old-mac/powermonitor.db
+        powermonitor compare-db backup.db --json
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    summaries: list[DatabaseSummary] = []
+    for path in (Path(config.database_path), other_path.expanduser()):
+        try:
+            with Database(path, read_only=True) as db:
+                summaries.append(db.get_summary())
+        except (FileNotFoundError, ValueError) as e:
+            err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+            sys.exit(1)
+        except Exception as e:
+            err_console.print(f"[red]Error: Cannot read {escape(str(path))}: {escape(str(e))}[/red]")
+            logger.exception("compare-db failed")
+            sys.exit(1)
+    current, other = summaries
+
+    if as_json:
+        _print_json({"current": current.to_dict(), "other": other.to_dict()})
+        return
+
+    def date_range(summary: DatabaseSummary) -> str:
+        if summary.earliest is None or summary.latest is None:
+            return "N/A"
+        return f"{summary.earliest[:10]} to {summary.latest[:10]}"
+
+    table = Table(title="Database Comparison")
+    table.add_column("Metric", style="cyan")
+    table.add_column(f"Current ({escape(Path(current.database_path).name)})", justify="right")
+    table.add_column(f"Other ({escape(Path(other.database_path).name)})", justify="right")
+    table.add_column("Change", justify="right")
+    table.add_row(*_compare_row("Readings", current.count, other.count, "", decimals=0))
+    table.add_row("Date range", date_range(current), date_range(other), "")
+    table.add_row(*_compare_row("Average power", current.avg_watts, other.avg_watts, "W", decimals=2))
+    table.add_row(*_compare_row("Average draw on battery", current.avg_draw_watts, other.avg_draw_watts, "W", 2))
+    table.add_row(*_compare_row("Peak draw on battery", current.peak_draw_watts, other.peak_draw_watts, "W", 2))
+    table.add_row(*_compare_row("Peak charging", current.peak_charge_watts, other.peak_charge_watts, "W", 2))
+    table.add_row(*_compare_row("Max capacity", current.max_capacity, other.max_capacity, " mAh", decimals=0))
+    table.add_row(*_compare_row("Battery health", current.battery_health_percent, other.battery_health_percent, "%"))
+    console.print(table)
+    console.print("[dim]Change is the other database relative to the current one[/dim]")
+
+
+@app.command()
+def chargers(
+    as_json: Annotated[
+        bool,
+        typer.Option("--json", help="Print chargers as a JSON object"),
+    ] = False,
+) -> None:
+    """Summarize every charger seen in the recorded history.
+
+    Uses config file for database path. Chargers are grouped by the name and
+    manufacturer they report; readings without charger info are ignored.
+
+    The peak negotiated power of each charger is remembered even after its readings
+    are pruned. A charger that has negotiated well below its peak for several
+    readings in a row is marked degraded (often a failing cable or port).
+
+    With --json, prints {"chargers": [...]}, each entry with name, manufacturer,
+    readings, max_watts_negotiated, avg_watts_actual, first_seen, last_seen,
+    peak_watts_negotiated, last_watts_negotiated, and degraded.
+
+    Examples:
+        powermonitor chargers
+        powermonitor chargers --json
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    try:
+        db_path = Path(config.database_path)
+        charger_stats = []
+        if db_path.exists():
+            with Database(db_path) as db:
+                charger_stats = db.get_charger_stats()
+
+        if as_json:
+            _print_json({"chargers": [c.to_dict() for c in charger_stats]})
+            return
+
+        if not charger_stats:
+            console.print("[yellow]No chargers recorded yet[/yellow]")
+            return
+
+        table = Table(title="Chargers")
+        table.add_column("Charger", style="cyan")
+        table.add_column("Readings", justify="right")
+        table.add_column("Max negotiated", justify="right", style="green")
+        table.add_column("Avg power", justify="right")
+        table.add_column("First seen")
+        table.add_column("Last seen")
+        table.add_column("Condition")
+        for c in charger_stats:
+            name = escape(sanitize_label(c.name))
+            if manufacturer := sanitize_label(c.manufacturer):
+                name += f" ({escape(manufacturer)})"
+            if c.peak is None:
+                condition = "[dim]-[/dim]"
+            elif c.degraded:
+                condition = f"[red]{_glyphs.warning} Degraded ({c.peak.last_watts}W, peak {c.peak.peak_watts}W)[/red]"
+            else:
+                condition = f"[green]OK[/green] (peak {c.peak.peak_watts}W)"
+            table.add_row(
+                name,
+                _num(c.readings, 0, grouping=True),
+                f"{c.max_watts_negotiated}W",
+                f"{_num(c.avg_watts_actual)}W",
+                c.first_seen,
+                c.last_seen,
+                condition,
+            )
+        console.print(table)
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Chargers command failed")
+        sys.exit(1)
+
+
+@app.command()
+def sessions(
+    limit: Annotated[
+        int,
+        typer.Option("--limit", "-n", help="Number of recent charge sessions to show"),
+    ] = 10,
+    as_json: Annotated[
+        bool,
+        typer.Option("--json", help="Print sessions as a JSON object"),
+    ] = False,
+) -> None:
+    """List recent charge sessions with their duration and energy.
+
+    Uses config file for database path. A session is recorded by the TUI from
+    when charging starts until it stops or the battery is full (or the TUI
+    exits); charges shorter than a minute are ignored.
+
+    With --json, prints {"sessions": [...]}, each entry with started_at,
+    ended_at, duration_seconds, start_percent, end_percent, and total_wh.
+
+    Examples:
+        powermonitor sessions
+        powermonitor sessions --limit 30
+        powermonitor sessions --json
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if limit <= 0:
+        err_console.print("[red]Error: --limit must be positive[/red]")
+        sys.exit(1)
+
+    try:
+        db_path = Path(config.database_path)
+        charge_sessions = []
+        if db_path.exists():
+            with Database(db_path) as db:
+                charge_sessions = db.query_charge_sessions(limit=limit)
+
+        if as_json:
+            _print_json({"sessions": [s.to_dict() for s in charge_sessions]})
+            return
+
+        if not charge_sessions:
+            console.print("[yellow]No charge sessions recorded yet (they are detected while the TUI runs)[/yellow]")
+            return
+
+        table = Table(title=f"Recent Charge Sessions (Last {len(charge_sessions)})")
+        table.add_column("Started", style="cyan")
+        table.add_column("Duration", justify="right")
+        table.add_column("Battery", justify="right")
+        table.add_column("Energy", justify="right", style="green")
+        for s in charge_sessions:
+            table.add_row(
+                s.started_at.strftime("%Y-%m-%d %H:%M"),
+                _format_duration(s.duration.total_seconds()),
+                f"{s.start_percent}% {_glyphs.arrow} {s.end_percent}%",
+                f"{_num(s.total_wh, 2)} Wh",
+            )
+        console.print(table)
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Sessions command failed")
+        sys.exit(1)
+
+
+def _format_duration(seconds: float) -> str:
+    """Format a duration as e.g. "1h 05m" or "12m"."""
+    hours, minutes = divmod(round(seconds / 60), 60)
+    return f"{hours}h {minutes:02d}m" if hours else f"{minutes}m"
+
+
+@app.command()
+def report(
+    output: Annotated[
+        Path,
+        typer.Argument(help="HTML file to write"),
+    ],
+    since: Annotated[
+        str | None,
+        typer.Option("--since", help="Start of the range: a date/time (2025-12-28T09:00) or an age (24h, 7d)"),
+    ] = None,
+    until: Annotated[
+        str | None,
+        typer.Option("--until", help="End of the range: a date/time or an age (default: now)"),
+    ] = None,
+) -> None:
+    """Write a self-contained HTML report for a time range.
+
+    Uses config file for database path. The report has a power and battery
+    chart, statistics, a per-charger breakdown, and battery health, with inline
+    CSS and SVG so the single file can be shared or archived. Dates without a
+    time zone are local time.
+
+    Examples:
+        powermonitor report report.html
+        powermonitor report last-week.html --since 7d
+        powermonitor report trip.html --since 2025-12-20 --until 2025-12-27
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    now = datetime.now(tz=UTC)
+    try:
+        start = parse_time_bound(since, now) if since is not None else None
+        end = parse_time_bound(until, now) if until is not None else None
+    except ValueError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+    if start is not None and end is not None and start > end:
+        err_console.print("[red]Error: --since must be before --until[/red]")
+        sys.exit(1)
+
+    try:
+        db_path = Path(config.database_path)
+        readings = []
+        if db_path.exists():
+            with Database(db_path) as db:
+                readings = db.query_history(limit=None, since=start, until=end)
+
+        if not readings:
+            console.print("[yellow]No readings in the selected range[/yellow]")
+            return
+
+        result = Report.build(readings, db_path, generated_at=now, since=start, until=end)
+        output.write_text(render_report(result), encoding="utf-8")
+        count = _num(result.stats.count, 0, grouping=True)
+        console.print(f"[green]{_glyphs.ok} Wrote report of {count} readings to {output}[/green]")
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Report command failed")
+        sys.exit(1)
+
+
+@app.command()
+def cleanup(
+    days: Annotated[
+        int | None,
+        typer.Option("--days", "-d", help="Delete readings older than N days"),
+    ] = None,
+    all_data: Annotated[
+        bool,
+        typer.Option("--all", help="Delete ALL readings (requires confirmation)"),
+    ] = False,
+    dry_run: Annotated[
+        bool,
+        typer.Option("--dry-run", help="Show how many readings would be deleted without deleting them"),
+    ] = False,
+) -> None:
+    """Clean up old power readings from database.
+
+    Uses config file for database path.
+
+    Examples:
+        powermonitor cleanup --days 30
+        powermonitor cleanup --days 30 --dry-run
+        powermonitor cleanup --all
+    """
+    # Load config for database path
+    config = load_config()
+    _setup_logging(config)
+
+    if not days and not all_data:
+        err_console.print("[red]Error: Must specify either --days N or --all[/red]")
+        console.print("Use --help for usage information")
+        sys.exit(1)
+
+    try:
+        with Database(config.database_path) as db:
+            if dry_run:
+                # Count with the same conditions the delete would use
+                if all_data:
+                    count = db.clear_history(dry_run=True)
+                    console.print(f"[cyan]Dry run: would delete all {count} readings[/cyan]")
+                else:
+                    assert days is not None, "days must be specified"  # Type checker hint
+                    count = db.cleanup_old_data(days=days, dry_run=True)
+                    console.print(f"[cyan]Dry run: would delete {count} readings older than {days} days[/cyan]")
+
+            elif all_data:
+                # Confirm deletion of all data
+                console.print(f"[yellow]{_glyphs.warning} WARNING: This will delete ALL readings![/yellow]")
+                confirm = typer.confirm("Are you sure you want to continue?")
+                if not confirm:
+                    console.print("[cyan]Operation cancelled[/cyan]")
+                    sys.exit(0)
+
+                deleted = db.clear_history()
+                console.print(f"[green]{_glyphs.ok} Deleted all {deleted} readings[/green]")
+
+            else:
+                # Delete old data
+                assert days is not None, "days must be specified"  # Type checker hint
+                console.print(f"[cyan]Deleting readings older than {days} days...[/cyan]")
+
+                deleted = db.cleanup_old_data(days=days)
+                console.print(f"[green]{_glyphs.ok} Deleted {deleted} old readings[/green]")
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Cleanup failed")
+        sys.exit(1)
+
+
+@dataclass(frozen=True, slots=True)
+class _HistoryColumn:
+    """One selectable `history` column: how it is titled, styled, and rendered from a reading."""
+
+    header: str
+    render: Callable[[PowerReading], str]
+    style: str = "white"
+    justify: str = "right"
+
+
+def _history_columns(time_format: Callable[[datetime], str]) -> dict[str, _HistoryColumn]:
+    """All `history` columns by --columns name, in their default left-to-right order.
+
+    Args:
+        time_format: Formats the time column (depends on --bucket and --ms)
+    """
+    return {
+        "time": _HistoryColumn("Time", lambda r: time_format(r.timestamp), style="cyan", justify="left"),
+        "watts": _HistoryColumn("Power", lambda r: _watts(r.watts_actual, sign=True), style="green"),
+        "battery": _HistoryColumn("Battery", lambda r: f"{r.battery_percent}%", style="yellow"),
+        "voltage": _HistoryColumn("Voltage", lambda r: f"{_num(r.voltage)}V", style="blue"),
+        "current": _HistoryColumn("Current", lambda r: f"{_num(r.amperage, 2, sign=True)}A", style="magenta"),
+        "status": _HistoryColumn(
+            "Status", lambda r: f"{_glyphs.icon(r.direction)} {_DIRECTION_LABELS[r.direction]}", justify="left"
+        ),
+        "negotiated": _HistoryColumn("Negotiated", lambda r: f"{r.display_watts_negotiated}W"),
+        "temp": _HistoryColumn(
+            "Temp", lambda r: "-" if r.battery_temp is None else f"{_num(r.battery_temp)}°C", style="red"
+        ),
+        "system": _HistoryColumn("System", lambda r: "-" if r.system_power is None else f"{_num(r.system_power)}W"),
+        "charger": _HistoryColumn("Charger", lambda r: sanitize_label(r.charger_name) or "-", justify="left"),
+        "label": _HistoryColumn("Label", lambda r: sanitize_label(r.label) or "-", style="dim", justify="left"),
+    }
+
+
+# Shown when --columns isn't given
+DEFAULT_HISTORY_COLUMNS = ("time", "watts", "battery", "voltage", "current", "status")
+
+
+def _parse_columns(spec: str, known: Iterable[str]) -> list[str]:
+    """Parse a --columns list, keeping the order given.
+
+    Raises:
+        ValueError: If the list is empty, repeats a column, or names an unknown column
+    """
+    known = list(known)
+    names = [name.strip().lower() for name in spec.split(",") if name.strip()]
+    if not names:
+        raise ValueError("--columns needs at least one column name")
+    unknown = [name for name in names if name not in known]
+    if unknown:
+        raise ValueError(f"Unknown column {', '.join(repr(n) for n in unknown)}. Known columns: {', '.join(known)}")
+    if len(set(names)) != len(names):
+        raise ValueError("--columns lists a column more than once")
+    return names
+
+
+def _print_text_table(columns: list[_HistoryColumn], rows: list[list[str]]) -> None:
+    """Print rows as space-aligned plain text: a header line, no borders, colors, or title.
+
+    Columns are padded to their display width, so emoji and other wide glyphs stay aligned.
+    """
+    widths = [max([cell_len(column.header), *(cell_len(row[i]) for row in rows)]) for i, column in enumerate(columns)]
+
+    def line(cells: list[str]) -> str:
+        padded = []
+        for column, cell, width in zip(columns, cells, widths, strict=True):
+            padding = " " * (width - cell_len(cell))
+            padded.append(padding + cell if column.justify == "right" else cell + padding)
+        return "  ".join(padded).rstrip()
+
+    lines = [line([column.header for column in columns]), *(line(row) for row in rows)]
+    console.print("\n".join(lines), markup=False, highlight=False, soft_wrap=True)
+
+
+@app.command()
+def history(
+    limit: Annotated[
+        int | None,
+        typer.Option("--limit", "-n", help="Number of recent readings to show (uses config default if not specified)"),
+    ] = None,
+    label: Annotated[
+        str | None,
+        typer.Option("--label", "-l", help="Only show readings tagged with this session label"),
+    ] = None,
+    bucket: Annotated[
+        str | None,
+        typer.Option("--bucket", help="Average readings into time buckets, e.g. 5m or 1h (--limit counts buckets)"),
+    ] = None,
+    millis: Annotated[
+        bool,
+        typer.Option("--ms", help="Show milliseconds in the time column (for sub-second sampling intervals)"),
+    ] = False,
+    columns: Annotated[
+        str | None,
+        typer.Option(
+            "--columns",
+            "-c",
+            help=f"Comma-separated columns to show, in order (default: {','.join(DEFAULT_HISTORY_COLUMNS)})",
+        ),
+    ] = None,
+    text: Annotated[
+        bool,
+        typer.Option("--text", help="Print a plain space-aligned table (no borders, colors, or title)"),
+    ] = False,
+) -> None:
+    """Show recent power readings from database.
+
+    Uses config file for database path and default limit.
+
+    --columns picks the columns and their order from: time, watts, battery,
+    voltage, current, status, negotiated, temp, system, charger, label.
+    Missing values are shown as "-".
+
+    With --text, the table is printed as plain text columns separated by
+    spaces, with a header line - easy to read in logs or to feed to awk.
+
+    Examples:
+        powermonitor history
+        powermonitor history --limit 50
+        powermonitor history --label "video render"
+        powermonitor history --bucket 1h --limit 168
+        powermonitor history --ms --limit 50
+        powermonitor history --columns time,watts,temp
+        powermonitor history --text --columns time,battery | awk 'NR > 1 {print $2}'
+    """
+    # Load config for database path and defaults
+    config = load_config()
+    _setup_logging(config)
+
+    # Use config default if limit not specified
+    if limit is None:
+        limit = config.default_history_limit
+
+    if millis and bucket is not None:
+        err_console.print("[red]Error: --ms can't be combined with --bucket[/red]")
+        sys.exit(1)
+
+    bucket_seconds = None
+    if bucket is not None:
+        try:
+            bucket_seconds = int(parse_interval(bucket))
+        except ValueError as e:
+            err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+            sys.exit(1)
+        if bucket_seconds < 1:
+            err_console.print(f"[red]Error: --bucket must be at least 1s, got {escape(bucket)}[/red]")
+            sys.exit(1)
+
+    def time_format(timestamp: datetime) -> str:
+        # Bucketed views span days, so include the date
+        time_str = timestamp.strftime("%m-%d %H:%M" if bucket is not None else "%H:%M:%S")
+        if millis:
+            time_str += f".{timestamp.microsecond // 1000:03d}"
+        return time_str
+
+    available = _history_columns(time_format)
+    try:
+        selected = _parse_columns(columns, available) if columns is not None else list(DEFAULT_HISTORY_COLUMNS)
+    except ValueError as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+    table_columns = [available[name] for name in selected]
+
+    try:
+        with Database(config.database_path) as db:
+            if bucket_seconds is not None:
+                readings = db.query_bucketed(bucket_seconds, limit=limit, label=label)
+            else:
+                readings = db.query_history(limit=limit, label=label)
+
+            if not readings:
+                if label is not None:
+                    console.print(f"[yellow]No readings with label '{escape(label)}'[/yellow]")
+                else:
+                    console.print("[yellow]No readings in database[/yellow]")
+                sys.exit(0)
+
+            # Reverse to show oldest first
+            rows = [[column.render(r) for column in table_columns] for r in reversed(readings)]
+            if text:
+                _print_text_table(table_columns, rows)
+                return
+
+            if bucket is not None:
+                title = f"Power Readings ({escape(bucket)} averages, last {len(readings)})"
+            else:
+                title = f"Recent Power Readings (Last {len(readings)})"
+            if label is not None:
+                title += f" - {escape(label)}"
+            table = Table(title=title)
+            for column in table_columns:
+                table.add_column(column.header, style=column.style, justify=column.justify)
+            for row in rows:
+                table.add_row(*(escape(cell) for cell in row))
+
+            console.print(table)
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("History command failed")
+        sys.exit(1)
+
+
+@app.command()
+def health(
+    days: Annotated[
+        int,
+        typer.Option("--days", "-d", help="Number of days to analyze"),
+    ] = 30,
+) -> None:
+    """Show battery health trend over time.
+
+    Uses config file for database path.
+
+    Analyzes max_capacity changes to detect battery degradation.
+
+    Examples:
+        powermonitor health
+        powermonitor health --days 60
+    """
+    # Load config for database path
+    config = load_config()
+    _setup_logging(config)
+
+    try:
+        with Database(config.database_path) as db:
+            results = db.get_battery_health_trend(days=days)
+
+            if not results:
+                console.print(f"[yellow]No readings found in the last {days} days[/yellow]")
+                sys.exit(0)
+
+            # Calculate trend
+            first_capacity = results[0][1]
+            last_capacity = results[-1][1]
+            change_mah = last_capacity - first_capacity
+            change_percent = (change_mah / first_capacity) * 100
+
+            # Determine status
+            if change_percent < -2:
+                status = f"[red]{_glyphs.warning} Degrading (significant)[/red]"
+            elif change_percent < -0.5:
+                status = f"[yellow]{_glyphs.warning} Degrading (normal wear)[/yellow]"
+            else:
+                status = f"[green]{_glyphs.ok} Stable[/green]"
+
+            # Display summary
+            console.print(f"\n[bold]Battery Health Analysis ({days} days)[/bold]\n")
+
+            summary_table = Table(show_header=False, box=None)
+            summary_table.add_column("Metric", style="cyan")
+            summary_table.add_column("Value", style="white")
+
+            summary_table.add_row("First reading", results[0][0])
+            summary_table.add_row("First avg capacity", f"{first_capacity:.0f} mAh")
+            summary_table.add_row("Last reading", results[-1][0])
+            summary_table.add_row("Last avg capacity", f"{last_capacity:.0f} mAh")
+            summary_table.add_row("Change", f"{change_mah:+.0f} mAh ({change_percent:+.2f}%)")
+            summary_table.add_row("Status", status)
+            summary_table.add_row("Days analyzed", str(len(results)))
+
+            console.print(summary_table)
+
+            # Show daily trend if more than 3 data points
+            if len(results) > 3:
+                console.print(f"\n[bold]Daily Trend (Last {min(7, len(results))} days)[/bold]\n")
+
+                trend_table = Table()
+                trend_table.add_column("Date", style="cyan")
+                trend_table.add_column("Avg Capacity", style="green", justify="right")
+                trend_table.add_column("Readings", style="yellow", justify="right")
+
+                # Show last 7 days
+                for row in results[-7:]:
+                    trend_table.add_row(row[0], f"{row[1]:.0f} mAh", str(row[2]))
+
+                console.print(trend_table)
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Health command failed")
+        sys.exit(1)
+
+
+@app.command("health-trend")
+def health_trend(
+    period: Annotated[
+        str,
+        typer.Option("--period", "-p", help="Group by day, week, or month"),
+    ] = "week",
+    days: Annotated[
+        int | None,
+        typer.Option("--days", "-d", help="Only analyze the last N days (default: entire history)"),
+    ] = None,
+) -> None:
+    """Show how battery health has changed across the recorded history.
+
+    Health is max capacity as a percentage of design capacity, averaged per day.
+    Days without readings are interpolated from their neighbors.
+
+    Examples:
+        powermonitor health-trend
+        powermonitor health-trend --period month
+        powermonitor health-trend --period day --days 14
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if period not in PERIODS:
+        err_console.print(f"[red]Error: Invalid period '{period}'. Must be one of: {', '.join(PERIODS)}[/red]")
+        sys.exit(1)
+
+    try:
+        with Database(config.database_path) as db:
+            points = daily_health(db.get_daily_capacity(days=days))
+
+        if not points:
+            console.print("[yellow]No readings found in database[/yellow]")
+            sys.exit(0)
+
+        buckets = bucket_health(points, period)
+        first = buckets[0].health_percent
+
+        table = Table(title=f"Battery Health by {period.capitalize()}")
+        table.add_column(period.capitalize(), style="cyan")
+        table.add_column("Health", style="green", justify="right")
+        table.add_column("Change", style="yellow", justify="right")
+        table.add_column("Days measured", style="white", justify="right")
+
+        for bucket in buckets:
+            table.add_row(
+                bucket.label,
+                f"{bucket.health_percent:.1f}%",
+                f"{bucket.health_percent - first:+.1f}",
+                f"{bucket.measured_days}/{bucket.total_days}",
+            )
+
+        console.print(table)
+        interpolated = sum(1 for p in points if p.interpolated)
+        if interpolated:
+            console.print(f"[dim]{interpolated} day(s) without readings were interpolated[/dim]")
+
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Health trend command failed")
+        sys.exit(1)
+
+
+# Fits over a shorter span than this are flagged as rough; wear only shows over months
+CAPACITY_TREND_MIN_SPAN_DAYS = 30
+
+
+@app.command("capacity-trend")
+def capacity_trend_command(
+    days: Annotated[
+        int | None,
+        typer.Option("--days", "-d", help="Only analyze the last N days (default: entire history)"),
+    ] = None,
+) -> None:
+    """Project when the battery will reach Apple's replacement threshold.
+
+    Fits a straight line through the daily average full-charge (max) capacity and
+    reports the current and design capacity, the decline per month, and when the
+    line crosses 80% of design capacity. The longer the recorded history, the more
+    meaningful the projection.
+
+    Examples:
+        powermonitor capacity-trend
+        powermonitor capacity-trend --days 365
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    try:
+        with Database(config.database_path) as db:
+            trend = capacity_trend(db.get_daily_capacity(days=days))
+    except Exception as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        logger.exception("Capacity trend command failed")
+        sys.exit(1)
+
+    if trend is None:
+        console.print("[yellow]No readings found in database[/yellow]")
+        sys.exit(0)
+
+    table = Table(title="Battery Capacity Trend", show_header=False)
+    table.add_column("Metric", style="cyan")
+    table.add_column("Value", style="white")
+
+    span_days = (trend.last_day - trend.first_day).days
+    table.add_row("Period", f"{trend.first_day} to {trend.last_day} ({trend.measured_days} days with readings)")
+    current = f"{_num(trend.current_capacity, 0, grouping=True)} mAh"
+    if trend.health_percent is not None:
+        current += f" ({_num(trend.health_percent)}% of design)"
+    table.add_row("Current capacity", current)
+    if trend.design_capacity is not None:
+        table.add_row("Design capacity", f"{_num(trend.design_capacity, 0, grouping=True)} mAh")
+    else:
+        table.add_row("Design capacity", "[dim]Not recorded[/dim]")
+
+    if trend.decline_per_month is None:
+        table.add_row("Decline", "[dim]Needs readings on at least two days[/dim]")
+    else:
+        decline = f"{_num(trend.decline_per_month, 1, sign=True)} mAh/month"
+        if trend.design_capacity:
+            decline += f" ({_num(trend.decline_per_month / trend.design_capacity * 100, 2, sign=True)}% of design)"
+        table.add_row("Decline", decline)
+
+    if trend.threshold_capacity is not None:
+        threshold = f"{_num(REPLACEMENT_HEALTH_PERCENT, 0)}% of design"
+        if trend.below_threshold:
+            projection = f"[red]{_glyphs.warning} Already below {threshold} (service recommended)[/red]"
+        elif trend.projected_date is not None:
+            months = (trend.projected_date - trend.last_day).days / DAYS_PER_MONTH
+            projection = f"{trend.projected_date:%Y-%m} (in about {_num(months, 0)} months)"
+        elif trend.decline_per_month is not None and trend.decline_per_month < 0:
+            projection = f"Declining, but not within {MAX_PROJECTION_DAYS // 365} years"
+        elif trend.decline_per_month is not None:
+            projection = f"[green]{_glyphs.ok} Not declining toward it[/green]"
+        else:
+            projection = "[dim]-[/dim]"
+        table.add_row(f"Reaches {threshold}", projection)
+
+    console.print(table)
+    if trend.decline_per_month is not None and span_days < CAPACITY_TREND_MIN_SPAN_DAYS:
+        console.print(f"[dim]Only {span_days} days of history: the projection will settle as more is recorded[/dim]")
+
+
+@app.command("wait-for")
+def wait_for(
+    condition: Annotated[
+        str,
+        typer.Argument(help="Condition to wait for, e.g. 'battery>=80' or 'charging==false'"),
+    ],
+    interval: Annotated[
+        float,
+        typer.Option("--interval", "-i", help="Seconds between checks"),
+    ] = 5.0,
+    timeout: Annotated[
+        float | None,
+        typer.Option("--timeout", "-t", help="Give up after this many seconds (exit code 124)"),
+    ] = None,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+) -> None:
+    """Poll power readings until a condition is met, then exit.
+
+    Fields: battery, watts, negotiated, voltage, amperage, capacity (numbers) and
+    charging, plugged (true/false). Operators: >=, <=, ==, !=, >, <.
+
+    Exits 0 when the condition is met, 124 on timeout, and 1 on errors.
+
+    Examples:
+        powermonitor wait-for 'battery>=80' && sudo pmset -b sleep 1
+        powermonitor wait-for 'charging==false' --timeout 3600
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    try:
+        parsed = parse_condition(condition)
+    except ValueError as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    if interval <= 0:
+        err_console.print(f"[red]Error: --interval must be positive, got {interval}[/red]")
+        sys.exit(1)
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
+        sys.exit(1)
+
+    try:
+        collector = create_collector(backend, config=config.collector)
+    except RuntimeError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+
+    deadline = time.monotonic() + timeout if timeout is not None else None
+    console.print(f"[cyan]Waiting for {parsed}...[/cyan]")
+
+    try:
+        while True:
+            try:
+                reading = collector.collect()
+            except Exception as e:
+                # Transient collection failures shouldn't abort a long wait
+                logger.warning(f"Collection failed, retrying: {e}")
+            else:
+                if parsed.matches(reading):
+                    console.print(f"[green]{_glyphs.ok} Condition met: {parsed}[/green]")
+                    sys.exit(0)
+
+            delay = interval
+            if deadline is not None:
+                remaining = deadline - time.monotonic()
+                if remaining <= 0:
+                    console.print(f"[yellow]Timed out after {timeout:g}s waiting for {parsed}[/yellow]")
+                    sys.exit(124)
+                # Check one last time at the deadline rather than oversleeping it
+                delay = min(interval, remaining)
+            time.sleep(delay)
+    except KeyboardInterrupt:
+        console.print("\n[yellow]Stopped waiting[/yellow]")
+        sys.exit(130)
+
+
+@app.command(name="socket")
+def socket_server(
+    path: Annotated[
+        Path | None,
+        typer.Option("--path", "-p", help="Socket file path (default: ~/.powermonitor/powermonitor.sock)"),
+    ] = None,
+    interval: Annotated[
+        float,
+        typer.Option("--interval", "-i", help="Seconds between readings"),
+    ] = 1.0,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+) -> None:
+    """Stream readings as NDJSON to clients of a Unix domain socket.
+
+    Every connected client receives each reading as one JSON object per line (the
+    same fields as NDJSON export). Any number of clients may connect and disconnect;
+    the socket file is removed when the server stops.
+
+    Examples:
+        powermonitor socket --path /tmp/power.sock
+        nc -U /tmp/power.sock | jq .watts_actual
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if interval <= 0:
+        err_console.print(f"[red]Error: --interval must be positive, got {interval}[/red]")
+        sys.exit(1)
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
+        sys.exit(1)
+
+    try:
+        collector = create_collector(backend, config=config.collector)
+    except RuntimeError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+
+    socket_path = (path or default_socket_path()).expanduser()
+    broadcaster = ReadingBroadcaster(socket_path)
+    try:
+        broadcaster.open()
+    except (SocketInUseError, OSError) as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    console.print(f"[cyan]Streaming readings to {escape(str(socket_path))} every {interval:g}s...[/cyan]")
+    try:
+        while True:
+            broadcaster.accept_pending()
+            try:
+                reading = collector.collect()
+            except Exception as e:
+                # Transient collection failures shouldn't stop the stream
+                logger.warning(f"Collection failed, retrying: {e}")
+            else:
+                broadcaster.broadcast(reading)
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        console.print("\n[yellow]Stopped streaming[/yellow]")
+    finally:
+        broadcaster.close()
+
+
+@app.command()
+def mqtt(
+    broker: Annotated[
+        str | None,
+        typer.Option("--broker", help="Broker URL, mqtt://host[:port] or mqtts://host[:port] (default: [mqtt] broker)"),
+    ] = None,
+    username: Annotated[
+        str | None,
+        typer.Option("--username", "-u", help="Broker user name (default: [mqtt] username)"),
+    ] = None,
+    password: Annotated[
+        str | None,
+        typer.Option(
+            "--password",
+            envvar="POWERMONITOR_MQTT_PASSWORD",
+            help="Broker password (default: [mqtt] password)",
+            show_envvar=True,
+        ),
+    ] = None,
+    topic_prefix: Annotated[
+        str | None,
+        typer.Option("--topic-prefix", help="Prefix of the state topics (default: [mqtt] topic_prefix)"),
+    ] = None,
+    interval: Annotated[
+        float,
+        typer.Option("--interval", "-i", help="Seconds between readings"),
+    ] = 5.0,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+) -> None:
+    """Publish readings to an MQTT broker, with Home Assistant auto-discovery.
+
+    On connect, a discovery config for each sensor is published (retained) under the
+    Home Assistant discovery prefix, then every reading updates <topic-prefix>/<sensor>
+    (e.g. powermonitor/watts). <topic-prefix>/status is "online" while publishing and
+    "offline" otherwise. Needs the optional paho-mqtt dependency (pip install 'powermonitor[mqtt]').
+
+    Examples:
+        powermonitor mqtt --broker mqtt://homeassistant.local
+        POWERMONITOR_MQTT_PASSWORD=secret powermonitor mqtt --broker mqtts://broker:8883 -u mac
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if interval <= 0:
+        err_console.print(f"[red]Error: --interval must be positive, got {interval}[/red]")
+        sys.exit(1)
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
+        sys.exit(1)
+
+    try:
+        settings: MqttConfig = config.mqtt.with_overrides(
+            broker=broker, username=username, password=password, topic_prefix=topic_prefix
+        )
+        publisher = MqttPublisher(settings)
+    except (ValueError, RuntimeError) as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    try:
+        collector = create_collector(backend, config=config.collector)
+    except RuntimeError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+
+    try:
+        publisher.connect()
+    except OSError as e:
+        err_console.print(f"[red]Error: Could not connect to {escape(settings.broker)}: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    console.print(f"[cyan]Publishing readings to {escape(settings.broker)} every {interval:g}s...[/cyan]")
+    try:
+        while True:
+            try:
+                reading = collector.collect()
+            except Exception as e:
+                # Transient collection failures shouldn't stop publishing
+                logger.warning(f"Collection failed, retrying: {e}")
+            else:
+                publisher.publish(reading)
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        console.print("\n[yellow]Stopped publishing[/yellow]")
+    finally:
+        publisher.close()
+
+
+@app.command()
+def udp(
+    address: Annotated[
+        str,
+        typer.Argument(help="Collector address, host:port (IPv6 as [address]:port)"),
+    ],
+    interval: Annotated[
+        float,
+        typer.Option("--interval", "-i", help="Seconds between readings"),
+    ] = 5.0,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+) -> None:
+    """Send readings as JSON datagrams to a remote collector over UDP.
+
+    Each reading is one compact JSON object (the NDJSON export fields, plus
+    "hostname" and "model" so the receiver can tell Macs apart). A failed send is
+    logged and the loop carries on. Datagrams are unencrypted and unauthenticated:
+    only use this on trusted networks.
+
+    Examples:
+        powermonitor udp collector.local:9999
+        nc -ul 9999  # On the collector
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if interval <= 0:
+        err_console.print(f"[red]Error: --interval must be positive, got {interval}[/red]")
+        sys.exit(1)
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
+        sys.exit(1)
+    try:
+        host, port = parse_collector_address(address)
+    except ValueError as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    try:
+        collector = create_collector(backend, config=config.collector)
+    except RuntimeError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+
+    sender = ReadingSender(host, port)
+    try:
+        sender.open()
+    except OSError as e:
+        err_console.print(f"[red]Error: Could not resolve {escape(address)}: {escape(str(e))}[/red]")
+        sys.exit(1)
+
+    console.print(f"[cyan]Sending readings to {escape(address)} every {interval:g}s (unencrypted)...[/cyan]")
+    try:
+        while True:
+            try:
+                reading = collector.collect()
+            except Exception as e:
+                # Transient collection failures shouldn't stop sending
+                logger.warning(f"Collection failed, retrying: {e}")
+            else:
+                sender.send(reading)
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        console.print("\n[yellow]Stopped sending[/yellow]")
+    finally:
+        sender.close()
+
+
+@app.command()
+def top(
+    limit: Annotated[
+        int,
+        typer.Option("--limit", "-n", help="Number of processes to show"),
+    ] = 10,
+    interval: Annotated[
+        float,
+        typer.Option("--interval", "-i", help="Seconds between refreshes"),
+    ] = 2.0,
+    count: Annotated[
+        int,
+        typer.Option("--count", "-c", help="Stop after this many refreshes (0 = until Ctrl+C)"),
+    ] = 0,
+    backend: Annotated[
+        str,
+        typer.Option("--backend", "-b", help=f"Data collection backend: {', '.join(BACKENDS)}"),
+    ] = "auto",
+) -> None:
+    """Show the overall power reading with the processes using the most energy.
+
+    Per-process energy comes from `powermetrics --samplers tasks`, which needs root;
+    without it only the overall reading is shown. Each refresh samples processes for
+    about a second.
+
+    Examples:
+        sudo powermonitor top
+        sudo powermonitor top --limit 20 --interval 5
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if limit <= 0:
+        err_console.print(f"[red]Error: --limit must be positive, got {limit}[/red]")
+        sys.exit(1)
+    if interval <= 0:
+        err_console.print(f"[red]Error: --interval must be positive, got {interval}[/red]")
+        sys.exit(1)
+    if backend not in BACKENDS:
+        err_console.print(f"[red]Error: Invalid backend '{backend}'. Must be one of: {', '.join(BACKENDS)}[/red]")
+        sys.exit(1)
+
+    try:
+        collector = create_collector(backend, config=config.collector)
+    except RuntimeError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+
+    task_reader = TaskEnergyReader()
+    refreshes = 0
+    try:
+        with Live(console=console, auto_refresh=False) as live:
+            while True:
+                tasks = task_reader.read()
+                try:
+                    reading = collector.collect()
+                except Exception as e:
+                    # Transient collection failures shouldn't stop the display
+                    logger.warning(f"Collection failed, retrying: {e}")
+                else:
+                    live.update(_render_top(reading, tasks, limit), refresh=True)
+                refreshes += 1
+                if count and refreshes >= count:
+                    break
+                time.sleep(interval)
+    except KeyboardInterrupt:
+        pass
+
+
+def _render_top(reading: PowerReading, tasks: list[TaskEnergy] | None, limit: int) -> Group:
+    """Build one `top` frame: the overall reading above the top energy consumers."""
+    header = (
+        f"[bold]{_glyphs.icon(reading.direction)} {_DIRECTION_LABELS[reading.direction]}[/bold]  "
+        f"{_num(reading.watts_actual, sign=True)}W  {reading.battery_percent}%"
+    )
+    if reading.system_power is not None:
+        header += f"  (system {_num(reading.system_power)}W)"
+    if tasks is None:
+        return Group(header, "[dim]Per-process energy needs root: run `sudo powermonitor top`[/dim]")
+
+    table = Table(title=f"Top {min(limit, len(tasks))} processes by energy impact")
+    table.add_column("PID", style="cyan", justify="right")
+    table.add_column("Process", style="white")
+    table.add_column("CPU ms/s", style="magenta", justify="right")
+    table.add_column("Energy Impact", style="yellow", justify="right")
+    for task in tasks[:limit]:
+        energy = _num(task.energy_impact) if task.energy_impact is not None else "N/A"
+        table.add_row(str(task.pid), escape(task.name), _num(task.cpu_ms_per_s), energy)
+    return Group(header, table)
+
+
+@app.command()
+def burst(
+    duration: Annotated[
+        float,
+        typer.Option("--duration", "-d", help="Length of the burst in seconds"),
+    ] = 5.0,
+    rate: Annotated[
+        float,
+        typer.Option("--rate", "-r", help="Samples per second (max 10)"),
+    ] = 10.0,
+) -> None:
+    """Capture power transients by sampling SMC sensors at up to 10Hz.
+
+    Reads only PDTR (power input) and PSTR (system power), skipping ioreg, and
+    stores the samples in the burst_samples table. Requires the IOKit backend.
+
+    Examples:
+        powermonitor burst
+        powermonitor burst --duration 10 --rate 5
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if sys.platform != "darwin":
+        err_console.print("[red]Error: burst sampling requires macOS (IOKit/SMC)[/red]")
+        sys.exit(1)
+
+    try:
+        # Imported lazily: loading the IOKit bindings fails on systems without the framework
+        from .collector.iokit.burst import sample_burst
+        from .collector.iokit.connection import SMCError
+    except (ImportError, OSError) as e:
+        err_console.print(f"[red]Error: IOKit backend unavailable: {e}[/red]")
+        sys.exit(1)
+
+    try:
+        console.print(f"[cyan]Sampling SMC at {rate:g}Hz for {duration:g}s...[/cyan]")
+        result = sample_burst(duration, rate_hz=rate)
+    except ValueError as e:
+        err_console.print(f"[red]Error: {e}[/red]")
+        sys.exit(1)
+    except SMCError as e:
+        err_console.print(f"[red]Error: SMC access failed: {e}[/red]")
+        sys.exit(1)
+
+    try:
+        with Database(config.database_path) as db:
+            burst_id = db.insert_burst(result)
+    except Exception as e:
+        err_console.print(f"[red]Error: Failed to save burst: {e}[/red]")
+        logger.exception("Saving burst failed")
+        sys.exit(1)
+
+    table = Table(title=f"Burst #{burst_id} ({len(result.samples)} samples)")
+    table.add_column("Sensor", style="cyan")
+    table.add_column("Min", style="green", justify="right")
+    table.add_column("Max", style="red", justify="right")
+    table.add_column("Mean", style="yellow", justify="right")
+
+    for name, field in (("PDTR (power input)", "power_input"), ("PSTR (system power)", "system_power")):
+        stats = result.stats(field)
+        if stats is None:
+            table.add_row(name, "N/A", "N/A", "N/A")
+        else:
+            table.add_row(name, *(f"{value:.2f}W" for value in stats))
+
+    console.print(table)
+
+
+@app.command()
+def replay(
+    path: Annotated[
+        Path,
+        typer.Argument(help="NDJSON file of readings (from `powermonitor export <file>.ndjson`)"),
+    ],
+    speed: Annotated[
+        float,
+        typer.Option("--speed", "-s", help="Playback speed multiplier (2.0 = twice as fast)"),
+    ] = 1.0,
+    max_delay: Annotated[
+        float,
+        typer.Option("--max-delay", help="Longest pause between readings in seconds (skips gaps between sessions)"),
+    ] = DEFAULT_MAX_DELAY,
+    tui: Annotated[
+        bool,
+        typer.Option("--tui", help="Play the readings through the TUI instead of printing them"),
+    ] = False,
+    as_ndjson: Annotated[
+        bool,
+        typer.Option("--ndjson", help="Stream readings as NDJSON (one JSON object per line) for other programs"),
+    ] = False,
+    line_buffered: Annotated[
+        bool,
+        typer.Option(
+            "--line-buffered/--no-line-buffered",
+            help="Flush after every reading, so a pipe sees each one as it is replayed",
+        ),
+    ] = True,
+) -> None:
+    """Replay recorded readings with their original timing.
+
+    Useful for reproducing a reported session or for demos without a Mac battery.
+    Replayed readings are never written to your database.
+
+    With --tui, the readings are fed to the TUI as if they were being collected,
+    so the live panel, statistics, and chart show the recorded session. The TUI
+    keeps them in a temporary database that is deleted on exit.
+
+    With --ndjson, only the readings are written, so the stream can be piped into
+    another program (e.g. jq). Output is flushed after every reading unless
+    --no-line-buffered is given, and the replay stops quietly (exit code 0) when
+    the reading end of the pipe is closed.
+
+    Examples:
+        powermonitor replay session.ndjson
+        powermonitor replay session.ndjson --speed 10
+        powermonitor replay session.ndjson --tui --speed 10
+        powermonitor replay session.ndjson --ndjson | jq .watts_actual
+    """
+    config = load_config()
+    _setup_logging(config)
+
+    if speed <= 0:
+        err_console.print(f"[red]Error: --speed must be positive, got {speed}[/red]")
+        sys.exit(1)
+    if max_delay < 0:
+        err_console.print(f"[red]Error: --max-delay must be non-negative, got {max_delay}[/red]")
+        sys.exit(1)
+    if tui and as_ndjson:
+        err_console.print("[red]Error: --tui can't be combined with --ndjson[/red]")
+        sys.exit(1)
+    if not path.exists():
+        err_console.print(f"[red]Error: File not found: {path}[/red]")
+        sys.exit(1)
+
+    if tui:
+        try:
+            schedule = list(replay_schedule(read_ndjson(path), speed=speed, max_delay=max_delay))
+        except ValueError as e:
+            err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+            sys.exit(1)
+        if not schedule:
+            console.print("[yellow]No readings found in file[/yellow]")
+            return
+        _replay_in_tui(config, ReplayCollector(schedule))
+        return
+
+    try:
+        count = 0
+        for delay, reading in replay_schedule(read_ndjson(path), speed=speed, max_delay=max_delay):
+            if delay > 0:
+                time.sleep(delay)
+            if as_ndjson:
+                _stream_line(json.dumps(reading.to_dict()), flush=line_buffered)
+            else:
+                console.print(_format_reading_line(reading), highlight=False)
+            count += 1
+        if as_ndjson:
+            console.file.flush()
+    except ValueError as e:
+        err_console.print(f"[red]Error: {escape(str(e))}[/red]")
+        sys.exit(1)
+    except BrokenPipeError:
+        _exit_on_broken_pipe()
+    except KeyboardInterrupt:
+        if not as_ndjson:
+            console.print("\n[yellow]Replay stopped[/yellow]")
+        sys.exit(0)
+
+    # Keep NDJSON output pure: notices go to stderr
+    if count == 0:
+        (err_console if as_ndjson else console).print("[yellow]No readings found in file[/yellow]")
+    elif not as_ndjson:
+        console.print(f"[green]{_glyphs.ok} Replayed {count} readings[/green]")
+
+
+def _replay_in_tui(config: PowerMonitorConfig, collector: ReplayCollector) -> None:
+    """Run the TUI on recorded readings, storing them in a throwaway database instead of the user's."""
+    with tempfile.TemporaryDirectory(prefix="powermonitor-replay-") as tmp:
+        PowerMonitorApp(
+            config=replace(config, database_path=Path(tmp) / "replay.db"),
+            collector=collector,
+            reference_voltage=_reference_voltage,
+            deadzone=_deadzone,
+            glyphs=_glyphs,
+        ).run()
+
+
+def _stream_line(line: str, flush: bool) -> None:
+    """Write one line of streaming output as-is, without Rich markup or wrapping.
+
+    Args:
+        line: Text without the trailing newline
+        flush: Flush right away, so a reader at the other end of a pipe gets the line
+            now instead of when the block buffer fills
+    """
+    console.file.write(line + "\n")
+    if flush:
+        console.file.flush()
+
+
+def _exit_on_broken_pipe() -> None:
+    """Exit with code 0 after the reader of stdout has gone away (e.g. `| head -n 1`)."""
+    # Python flushes stdout once more at exit, which would fail again on the closed pipe,
+    # so point the descriptor at /dev/null first
+    try:
+        devnull = os.open(os.devnull, os.O_WRONLY)
+        os.dup2(devnull, sys.stdout.fileno())
+    except (OSError, ValueError):
+        pass  # Not a real file descriptor (e.g. captured output in tests)
+    sys.exit(0)
+
+
+def _format_reading_line(r: PowerReading) -> str:
+    """Format a reading as a single status line for streaming output."""
+    line = (
+        f"[cyan]{r.timestamp.strftime('%Y-%m-%d %H:%M:%S')}[/cyan]  {_glyphs.icon(r.direction)} "
+        f"{_watts(r.watts_actual, sign=True)}  {r.battery_percent}%  {_num(r.voltage, 2)}V  "
+        f"{_num(r.amperage, 2, sign=True)}A"
+    )
+    if r.label:
+        line += f"  [dim]{escape(r.label)}[/dim]"
+    return line
+
+
+@app.command()
+def version(
+    verbose: Annotated[
+        bool,
+        typer.Option("--verbose", "-v", help="Show platform and backend details for bug reports"),
+    ] = False,
+) -> None:
+    """Show powermonitor version.
+
+    With --verbose, also reports the Python version, platform, macOS version,
+    architecture (including whether an x86_64 build runs under Rosetta), and
+    whether the IOKit/SMC backend can be loaded.
+
+    Examples:
+        powermonitor version
+        powermonitor version --verbose
+    """
+    if not verbose:
+        console.print(f"powermonitor {get_version()}")
+        return
+
+    table = Table(title="powermonitor Version Info", show_header=False)
+    table.add_column("Item", style="cyan")
+    table.add_column("Value", style="white")
+
+    for item, value in get_system_info().items():
+        table.add_row(item, value)
+
+    console.print(table)
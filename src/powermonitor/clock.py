@@ -0,0 +1,67 @@
+"""Wall clocks for timestamping readings, injectable so time-dependent code can be tested."""
+
+from datetime import UTC
+from datetime import datetime
+from datetime import timedelta
+from typing import Protocol
+
+
+class Clock(Protocol):
+    """Protocol for anything that tells the current time."""
+
+    def now(self) -> datetime:
+        """Get the current time (timezone-aware)."""
+        ...
+
+
+class SystemClock:
+    """The real clock: datetime.now() in UTC."""
+
+    def now(self) -> datetime:
+        """Get the current UTC time."""
+        return datetime.now(tz=UTC)
+
+
+# Default for CollectorConfig.clock and collectors created without one
+SYSTEM_CLOCK = SystemClock()
+
+
+class MockClock:
+    """Clock that only moves when told to, for deterministic tests.
+
+    Usage:
+        clock = MockClock(datetime(2025, 12, 28, 12, 0, tzinfo=UTC))
+        collector = MockCollector(clock=clock)
+        collector.collect()  # Stamped 12:00:00
+        clock.advance(30)
+        collector.collect()  # Stamped 12:00:30
+    """
+
+    def __init__(self, start: datetime | None = None):
+        """Initialize the clock.
+
+        Args:
+            start: Initial time (defaults to 2025-01-01 00:00 UTC)
+
+        Raises:
+            ValueError: If start is naive
+        """
+        self._now = start if start is not None else datetime(2025, 1, 1, tzinfo=UTC)
+        if self._now.tzinfo is None:
+            raise ValueError("MockClock start time must be timezone-aware")
+
+    def now(self) -> datetime:
+        """Get the mocked current time."""
+        return self._now
+
+    def advance(self, seconds: float) -> datetime:
+        """Move the clock forward.
+
+        Args:
+            seconds: How far to move (negative values move it backwards)
+
+        Returns:
+            The new current time
+        """
+        self._now += timedelta(seconds=seconds)
+        return self._now
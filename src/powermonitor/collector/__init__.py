@@ -1,6 +1,21 @@
 """Power data collectors for macOS."""
 
+from .base import CollectLevel
+from .base import CollectorConfig
 from .base import PowerCollector
+from .base import WattsBlend
+from .factory import BACKENDS
+from .factory import create_collector
 from .factory import default_collector
+from .mock import MockCollector
 
-__all__ = ["PowerCollector", "default_collector"]
+__all__ = [
+    "BACKENDS",
+    "CollectLevel",
+    "CollectorConfig",
+    "MockCollector",
+    "PowerCollector",
+    "WattsBlend",
+    "create_collector",
+    "default_collector",
+]
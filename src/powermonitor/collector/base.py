@@ -1,10 +1,65 @@
-"""Base protocol for power collectors."""
+"""Base protocol and settings for power collectors."""
 
+from dataclasses import dataclass
+from dataclasses import field
+from enum import StrEnum
 from typing import Protocol
 
+from ..clock import SYSTEM_CLOCK
+from ..clock import Clock
 from ..models import PowerReading
 
 
+class CollectLevel(StrEnum):
+    """How much data a collector gathers per reading."""
+
+    MINIMAL = "minimal"  # Battery percent and charging state only, via `pmset -g batt`
+    FULL = "full"  # Every field the backend supports (ioreg tree, SMC sensors)
+
+
+class WattsBlend(StrEnum):
+    """Where the IOKit backend takes watts_actual from when both sources are available."""
+
+    SMC = "smc"  # SMC PDTR when present, otherwise voltage × current
+    IOREG = "ioreg"  # Always voltage × current from the battery readings
+    WEIGHTED = "weighted"  # Weighted average of both when both are plausible
+
+
+@dataclass(frozen=True, slots=True)
+class CollectorConfig:
+    """Settings passed to create_collector() ([collector] in config.toml).
+
+    Attributes:
+        level: How much data to gather (MINIMAL skips ioreg and SMC for lower latency)
+        watts_blend: How SMC and computed watts are combined (IOKit backend only)
+        smc_weight: Share of the SMC value in the WEIGHTED average (0-1)
+        instant_amperage: Compute ioreg watts from InstantAmperage instead of the averaged Amperage
+        gpu_power: Also read GPU power with powermetrics (Apple Silicon, requires root)
+        smc_timeout: Seconds to wait for an SMC read before falling back to ioreg (IOKit backend
+            only; 0 waits forever)
+        clock: Clock that timestamps readings (a MockClock in tests; not compared)
+    """
+
+    level: CollectLevel = CollectLevel.FULL
+    watts_blend: WattsBlend = WattsBlend.SMC
+    smc_weight: float = 0.5
+    instant_amperage: bool = False
+    gpu_power: bool = False
+    smc_timeout: float = 2.0
+    clock: Clock = field(default=SYSTEM_CLOCK, compare=False, repr=False)
+
+    def __post_init__(self) -> None:
+        """Validate settings.
+
+        Raises:
+            ValueError: If smc_weight is outside 0-1 or smc_timeout is negative
+        """
+        if not 0 <= self.smc_weight <= 1:
+            raise ValueError(f"smc_weight must be between 0 and 1, got {self.smc_weight}")
+        if self.smc_timeout < 0:
+            raise ValueError(f"smc_timeout must be 0 (no timeout) or positive, got {self.smc_timeout}")
+
+
 class PowerCollector(Protocol):
     """Protocol for power data collectors.
 
@@ -4,11 +4,18 @@ import sys
 
 from loguru import logger
 
+from .base import CollectLevel
+from .base import CollectorConfig
 from .base import PowerCollector
 from .ioreg import IORegCollector
+from .mock import MockCollector
+from .pmset import PmsetCollector
 
+# Collector backends selectable from the CLI (--backend)
+BACKENDS = ("auto", "iokit", "ioreg", "mock")
 
-def default_collector(verbose: bool = False) -> PowerCollector:
+
+def default_collector(verbose: bool = False, config: CollectorConfig | None = None) -> PowerCollector:
     """Get the default power collector for this platform.
 
     Returns IOKitCollector if available (direct SMC access),
@@ -16,6 +23,7 @@ def default_collector(verbose: bool = False) -> PowerCollector:
 
     Args:
         verbose: If True, print debug info about collector selection and SMC sensors
+        config: Collector settings passed to the collector (defaults to CollectorConfig())
 
     Returns:
         PowerCollector instance
@@ -33,8 +41,52 @@ def default_collector(verbose: bool = False) -> PowerCollector:
 
         if verbose:
             logger.info("Using IOKitCollector (SMC sensors)")
-        return IOKitCollector(verbose=verbose)
+        return IOKitCollector(verbose=verbose, config=config)
     except (ImportError, PermissionError, OSError) as e:
         if verbose:
             logger.warning(f"IOKitCollector unavailable ({e}), using IORegCollector")
-        return IORegCollector()
+        return IORegCollector(config=config)
+
+
+def create_collector(
+    backend: str = "auto", verbose: bool = False, config: CollectorConfig | None = None
+) -> PowerCollector:
+    """Create a collector for an explicitly selected backend.
+
+    At CollectLevel.MINIMAL every hardware backend is served by PmsetCollector, the
+    cheapest source of battery percent and charging state.
+
+    Args:
+        backend: One of BACKENDS - "auto" (default_collector), "iokit", "ioreg", or "mock"
+        verbose: If True, print debug info about collector selection and SMC sensors
+        config: Collector settings (defaults to CollectorConfig())
+
+    Returns:
+        PowerCollector instance
+
+    Raises:
+        ValueError: If backend is unknown
+        RuntimeError: If a hardware backend is requested on a platform other than macOS
+    """
+    if backend not in BACKENDS:
+        raise ValueError(f"Unknown backend '{backend}'. Must be one of: {', '.join(BACKENDS)}")
+
+    # The mock backend needs no hardware, so it works on any platform
+    if backend == "mock":
+        return MockCollector(clock=config.clock if config is not None else None)
+
+    if sys.platform != "darwin":
+        raise RuntimeError("powermonitor only supports macOS")
+
+    if config is not None and config.level is CollectLevel.MINIMAL:
+        return PmsetCollector(config=config)
+
+    if backend == "auto":
+        return default_collector(verbose=verbose, config=config)
+
+    if backend == "iokit":
+        from .iokit import IOKitCollector
+
+        return IOKitCollector(verbose=verbose, config=config)
+
+    return IORegCollector(config=config)
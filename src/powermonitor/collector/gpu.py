@@ -0,0 +1,56 @@
+"""GPU power on Apple Silicon via `powermetrics --samplers gpu_power` (requires root)."""
+
+import re
+from collections.abc import Sequence
+
+from .powermetrics import PowermetricsSampler
+
+# One 200ms sample; powermetrics otherwise samples forever
+POWERMETRICS_COMMAND = ("powermetrics", "--samplers", "gpu_power", "-n", "1", "-i", "200")
+
+_GPU_POWER_RE = re.compile(r"GPU Power:\s*(\d+(?:\.\d+)?)\s*mW")
+
+
+def parse_gpu_power(output: str) -> float | None:
+    """Parse the GPU power line from powermetrics output.
+
+    Args:
+        output: Text printed by `powermetrics --samplers gpu_power`
+
+    Returns:
+        GPU power in watts, or None if the output has no "GPU Power: N mW" line
+    """
+    match = _GPU_POWER_RE.search(output)
+    if match is None:
+        return None
+    return float(match.group(1)) / 1000.0
+
+
+class GpuPowerReader(PowermetricsSampler):
+    """Read GPU power with powermetrics, giving up for the session once it can't work.
+
+    Without root, or on Intel Macs (no GPU power line), a warning is logged once and
+    every read returns None.
+    """
+
+    def __init__(self, command: Sequence[str] = POWERMETRICS_COMMAND):
+        """Initialize the reader.
+
+        Args:
+            command: powermetrics command line (overridable for tests)
+        """
+        super().__init__(command, what="GPU power")
+
+    def read(self) -> float | None:
+        """Read the current GPU power.
+
+        Returns:
+            GPU power in watts, or None if unavailable
+        """
+        output = self.run()
+        if output is None:
+            return None
+        power = parse_gpu_power(output)
+        if power is None:
+            self.disable("powermetrics reported no GPU power (Intel Mac?)")
+        return power
@@ -2,5 +2,8 @@
 
 from .collector import IOKitCollector
 from .connection import SMCConnection
+from .reader import MockSmcReader
+from .reader import RecordingSmcReader
+from .reader import SmcReader
 
-__all__ = ["SMCConnection", "IOKitCollector"]
+__all__ = ["SMCConnection", "IOKitCollector", "SmcReader", "RecordingSmcReader", "MockSmcReader"]
@@ -0,0 +1,79 @@
+"""High-frequency SMC burst sampling for capturing power transients."""
+
+import time
+from collections.abc import Callable
+from datetime import UTC
+from datetime import datetime
+
+from ...models import BurstResult
+from ...models import BurstSample
+from .connection import SMCConnection
+from .connection import SMCError
+from .reader import SmcReader
+
+MAX_BURST_RATE_HZ = 10.0  # SMC reads take a few ms; faster rates just repeat values
+MAX_BURST_DURATION = 60.0  # seconds
+
+
+def _read_optional(smc: SmcReader, key: str) -> float | None:
+    """Read a sensor, returning None if this Mac doesn't expose it."""
+    try:
+        return smc.read_key(key)
+    except SMCError:
+        return None
+
+
+def sample_burst(
+    duration: float,
+    rate_hz: float = MAX_BURST_RATE_HZ,
+    connection_factory: Callable[[], SmcReader] = SMCConnection,
+    clock: Callable[[], float] = time.monotonic,
+    sleep: Callable[[float], None] = time.sleep,
+) -> BurstResult:
+    """Sample only PDTR/PSTR at a high rate over a short window.
+
+    Unlike IOKitCollector.collect(), this skips the ioreg subprocess entirely and
+    keeps one SMC connection open for the whole burst, so brief spikes that the
+    regular polling interval misses are captured.
+
+    Args:
+        duration: Length of the burst in seconds (0 < duration <= MAX_BURST_DURATION)
+        rate_hz: Samples per second (0 < rate_hz <= MAX_BURST_RATE_HZ)
+        connection_factory: Creates the SMC connection (injectable for tests)
+        clock: Monotonic clock (injectable for tests)
+        sleep: Sleep function (injectable for tests)
+
+    Returns:
+        BurstResult with one BurstSample per tick
+
+    Raises:
+        ValueError: If duration or rate_hz is out of range
+        SMCError: If the SMC connection cannot be opened
+    """
+    if not 0 < duration <= MAX_BURST_DURATION:
+        raise ValueError(f"duration must be between 0 and {MAX_BURST_DURATION:g} seconds, got {duration}")
+    if not 0 < rate_hz <= MAX_BURST_RATE_HZ:
+        raise ValueError(f"rate_hz must be between 0 and {MAX_BURST_RATE_HZ:g}, got {rate_hz}")
+
+    period = 1.0 / rate_hz
+    samples: list[BurstSample] = []
+    started_at = datetime.now(UTC)
+
+    with connection_factory() as smc:
+        start = clock()
+        next_tick = start
+        while next_tick - start < duration:
+            samples.append(
+                BurstSample(
+                    timestamp=datetime.now(UTC),
+                    power_input=_read_optional(smc, "PDTR"),
+                    system_power=_read_optional(smc, "PSTR"),
+                )
+            )
+            # Schedule against absolute ticks so read time doesn't accumulate as drift
+            next_tick += period
+            delay = next_tick - clock()
+            if delay > 0:
+                sleep(delay)
+
+    return BurstResult(started_at=started_at, rate_hz=rate_hz, samples=samples)
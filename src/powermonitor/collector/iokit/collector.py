@@ -1,14 +1,29 @@
 """IOKit-based power collector using SMC sensors."""
 
+import math
+import threading
+from collections.abc import Callable
+from collections.abc import Iterable
+from collections.abc import Mapping
 from dataclasses import dataclass
+from dataclasses import field
 
 from loguru import logger
 
+from ...models import AdapterSource
+from ...models import PowerDirection
 from ...models import PowerReading
+from ...system_info import native_architecture
+from ...system_info import running_under_rosetta
+from ..base import CollectorConfig
 from ..base import PowerCollector
+from ..base import WattsBlend
 from ..ioreg import IORegCollector
 from .connection import SMCConnection
 from .connection import SMCError
+from .reader import SmcReader
+from .structures import SMCMultipleError
+from .structures import SMCTimeoutError
 
 # SMC sensor keys for power monitoring
 SMC_SENSORS = {
@@ -19,8 +34,64 @@ SMC_SENSORS = {
     "PDBR": "Display brightness power (W)",
     "TB0T": "Battery temperature (°C)",
     "CHCC": "Charging status (0 = not charging)",
+    "B0AC": "Battery current (mA)",
 }
 
+# SMC key -> SMCPowerData field
+SMC_FIELD_KEYS = {
+    "PPBR": "battery_power",
+    "PDTR": "power_input",
+    "PSTR": "system_power",
+    "PHPC": "heatpipe_power",
+    "PDBR": "display_power",
+    "TB0T": "battery_temp",
+    "CHCC": "charging_status",
+    "B0AC": "battery_current",
+}
+
+# SMC temperature keys read for the hottest-sensor summary; each model has only some of them
+SMC_TEMPERATURE_SENSORS = {
+    "TB0T": "Battery",
+    "TB1T": "Battery cell 1",
+    "TB2T": "Battery cell 2",
+    "TC0P": "CPU proximity",
+    "TC0D": "CPU die",
+    "TG0P": "GPU proximity",
+    "TG0D": "GPU die",
+    "Tp01": "CPU performance core",
+    "Tp05": "CPU efficiency core",
+    "Tg05": "GPU cluster",
+    "Ts0P": "Palm rest",
+    "TW0P": "Wireless module",
+}
+
+# Sensors a model lacks often read 0 or garbage instead of failing
+MAX_PLAUSIBLE_TEMP = 150.0
+
+# Keys only the Apple Silicon SMC provides. The key set follows the hardware, not the process:
+# an x86_64 build running under Rosetta still talks to the Apple Silicon SMC
+APPLE_SILICON_KEYS = frozenset({"PDBR"})
+
+
+def smc_field_keys(architecture: str) -> dict[str, str]:
+    """Select the SMC keys to read on a Mac.
+
+    Args:
+        architecture: Native hardware architecture ("arm64" or "x86_64"; see native_architecture)
+
+    Returns:
+        Subset of SMC_FIELD_KEYS in the same order
+    """
+    if architecture == "arm64":
+        return dict(SMC_FIELD_KEYS)
+    return {key: field for key, field in SMC_FIELD_KEYS.items() if key not in APPLE_SILICON_KEYS}
+
+
+MAX_PLAUSIBLE_WATTS = 300.0  # Above any MacBook adapter or battery rating; larger values are sensor glitches
+
+# PDTR below this is sensor noise rather than an adapter delivering power
+MIN_ADAPTER_INPUT_WATTS = 1.0
+
 
 @dataclass
 class SMCPowerData:
@@ -33,6 +104,168 @@ class SMCPowerData:
     display_power: float | None = None  # PDBR
     battery_temp: float | None = None  # TB0T
     charging_status: float | None = None  # CHCC
+    battery_current: float | None = None  # B0AC (mA)
+    # SMC temperature key -> °C for the SMC_TEMPERATURE_SENSORS this Mac has (see read_temperatures)
+    temperatures: dict[str, float] = field(default_factory=dict)
+
+    @property
+    def hottest(self) -> tuple[str, float] | None:
+        """The hottest temperature sensor as (SMC key, °C), or None if none were read."""
+        if not self.temperatures:
+            return None
+        return max(self.temperatures.items(), key=lambda item: item[1])
+
+    def to_key_map(self) -> dict[str, float | None]:
+        """Map each SMC key name to its decoded value (None if the sensor is unavailable).
+
+        Returns:
+            Dictionary ordered like SMC_SENSORS, e.g. {"PPBR": 4.2, "PDTR": 20.1, ...}
+        """
+        return {key: getattr(self, field) for key, field in SMC_FIELD_KEYS.items()}
+
+    @classmethod
+    def read(cls, smc: SmcReader, strict: bool = False, keys: Mapping[str, str] | None = None) -> "SMCPowerData":
+        """Read every sensor in keys.
+
+        Args:
+            smc: Open SMC reader (SMCConnection, or a recording for tests)
+            strict: Raise instead of leaving unreadable sensors at None (for diagnostics)
+            keys: SMC key -> field to read (default: all of SMC_FIELD_KEYS; see smc_field_keys)
+
+        Returns:
+            SMCPowerData with sensor readings (None for sensors this Mac doesn't have)
+
+        Raises:
+            SMCMultipleError: With strict, if any sensor failed; errors maps each failed key to its error
+        """
+        data = cls()
+        errors: dict[str, SMCError] = {}
+        for key, field in (SMC_FIELD_KEYS if keys is None else keys).items():
+            try:
+                setattr(data, field, smc.read_key(key))
+            except SMCError as e:
+                # Usually a sensor this Mac model doesn't have
+                errors[key] = e
+        if strict and errors:
+            raise SMCMultipleError(errors)
+        return data
+
+
+def read_temperatures(smc: SmcReader, keys: Iterable[str] = SMC_TEMPERATURE_SENSORS) -> dict[str, float]:
+    """Read the temperature sensors this Mac has.
+
+    Keys the model doesn't have, and implausible values (at or below 0°C or above
+    MAX_PLAUSIBLE_TEMP), are skipped rather than treated as errors.
+
+    Args:
+        smc: Open SMC reader
+        keys: SMC temperature keys to try
+
+    Returns:
+        SMC key -> °C, in the order of keys
+    """
+    temperatures = {}
+    for key in keys:
+        try:
+            value = smc.read_key(key)
+        except SMCError:
+            continue
+        if 0 < value <= MAX_PLAUSIBLE_TEMP:
+            temperatures[key] = value
+    return temperatures
+
+
+def signed_battery_current(current_ma: float, reading: PowerReading) -> float:
+    """Convert SMC battery current to amps using the ioreg sign convention.
+
+    Some Macs report B0AC as a magnitude, so the sign is taken from ioreg's
+    amperage when it is non-zero, otherwise from the charging state.
+
+    Args:
+        current_ma: B0AC value in mA (signed or unsigned depending on the model)
+        reading: ioreg-based reading for the same moment
+
+    Returns:
+        Current in A: positive = charging, negative = discharging
+    """
+    amps = abs(current_ma) / 1000.0
+    if reading.amperage < 0:
+        return -amps
+    if reading.amperage > 0:
+        return amps
+    # ioreg reported 0 A (common at rest): trust the SMC sign, then the charging state
+    if current_ma < 0 or reading.direction is PowerDirection.DISCHARGING:
+        return -amps
+    return amps
+
+
+def _plausible_watts(watts: float) -> bool:
+    """Check that a power value is finite and within MAX_PLAUSIBLE_WATTS."""
+    return math.isfinite(watts) and abs(watts) <= MAX_PLAUSIBLE_WATTS
+
+
+def blend_watts(smc_watts: float | None, computed_watts: float, config: CollectorConfig) -> float:
+    """Combine SMC PDTR power with voltage × current according to config.watts_blend.
+
+    WEIGHTED averages the two only when both are plausible (finite, within
+    MAX_PLAUSIBLE_WATTS, and flowing the same way); otherwise it behaves like SMC.
+
+    Args:
+        smc_watts: SMC PDTR reading (W), or None if unavailable
+        computed_watts: Voltage × current (W)
+        config: Collector settings
+
+    Returns:
+        Power in W
+    """
+    if config.watts_blend is WattsBlend.IOREG or smc_watts is None:
+        return computed_watts
+    if (
+        config.watts_blend is WattsBlend.WEIGHTED
+        and _plausible_watts(smc_watts)
+        and _plausible_watts(computed_watts)
+        and smc_watts * computed_watts > 0
+    ):
+        return config.smc_weight * smc_watts + (1 - config.smc_weight) * computed_watts
+    return smc_watts
+
+
+def charge_efficiency(battery_power: float | None, power_input: float | None, is_charging: bool) -> float | None:
+    """Estimate the share of adapter power that reaches the battery while charging.
+
+    Args:
+        battery_power: SMC PPBR reading (W, negative while charging), or None if unavailable
+        power_input: SMC PDTR reading (W), or None if unavailable
+        is_charging: Whether the battery is charging
+
+    Returns:
+        Efficiency in percent, or None when not charging, a sensor is missing,
+        or the ratio falls outside 0-100% (e.g. sensors sampled mid-transition)
+    """
+    if not is_charging or battery_power is None or power_input is None:
+        return None
+    if not (_plausible_watts(battery_power) and _plausible_watts(power_input)) or power_input <= 0:
+        return None
+    efficiency = -battery_power / power_input * 100
+    return efficiency if 0 < efficiency <= 100 else None
+
+
+def adapter_detected_by_smc(power_input: float | None, watts_negotiated: int) -> bool:
+    """Check whether SMC input power shows an adapter that ioreg didn't describe.
+
+    On some configurations ioreg's adapter details are empty even while plugged in,
+    but PDTR still reports the power coming in.
+
+    Args:
+        power_input: SMC PDTR reading (W), or None if unavailable
+        watts_negotiated: Adapter wattage from ioreg (0 when it has no adapter details)
+
+    Returns:
+        True when ioreg has no wattage but PDTR shows plausible input power
+    """
+    if watts_negotiated > 0 or power_input is None:
+        return False
+    return _plausible_watts(power_input) and power_input >= MIN_ADAPTER_INPUT_WATTS
 
 
 class IOKitCollector(PowerCollector):
@@ -42,23 +275,52 @@ class IOKitCollector(PowerCollector):
     macOS SMC sensors via IOKit. Falls back to IORegCollector if SMC access fails.
 
     SMC sensors used:
-    - PDTR: Power delivery/input rate (W) - Most accurate for watts_actual
-    - PPBR: Battery power rate (W)
+    - PDTR: Power delivery/input rate (W) - Most accurate for watts_actual; also detects
+      an adapter when ioreg has no adapter details (adapter_source is then SMC)
+    - PPBR: Battery power rate (W) - With PDTR, estimates charge_efficiency while charging
     - PSTR: System total power consumption (W)
     - PHPC: Heatpipe/cooling power (W)
     - PDBR: Display brightness power (W)
     - TB0T: Battery temperature (°C)
     - CHCC: Charging status
+    - B0AC: Battery current (mA) - Preferred over ioreg Amperage, which is coarse
+    - SMC_TEMPERATURE_SENSORS: whichever exist on the model; the hottest sets max_temp
+
+    SMC reads run on a watchdog thread bounded by config.smc_timeout, because the
+    kernel call can block indefinitely when the SMC is in a bad state. A blocked
+    thread can't be killed, so it is abandoned (a daemon thread, so it doesn't keep
+    the process alive); while it stays blocked, collections skip the SMC and use
+    ioreg rather than leaking another thread each time.
     """
 
-    def __init__(self, verbose: bool = False):
+    def __init__(
+        self,
+        verbose: bool = False,
+        config: CollectorConfig | None = None,
+        smc_factory: Callable[[], SmcReader] | None = None,
+    ):
         """Initialize IOKit collector.
 
         Args:
             verbose: If True, print debug info about SMC sensors
+            config: Collector settings (watts_blend, smc_weight); defaults to CollectorConfig()
+            smc_factory: Opens the SMC reader for each collection; defaults to SMCConnection
+                (pass a MockSmcReader factory to replay recorded sensors)
         """
         self.verbose = verbose
-        self.fallback_collector = IORegCollector()
+        self.config = config or CollectorConfig()
+        self.smc_factory = smc_factory
+        if smc_factory is None:
+            if running_under_rosetta():
+                logger.warning(
+                    "powermonitor is running under Rosetta; install an arm64 Python for reliable SMC readings"
+                )
+            self.smc_keys = smc_field_keys(native_architecture())
+        else:
+            # A recording may come from any Mac, so replay every key it has
+            self.smc_keys = dict(SMC_FIELD_KEYS)
+        self.fallback_collector = IORegCollector(config=self.config)
+        self._blocked_reader: threading.Thread | None = None
 
     def collect(self) -> PowerReading:
         """Collect power data from SMC sensors and IORegistry.
@@ -84,16 +346,32 @@ class IOKitCollector(PowerCollector):
             PowerReading enhanced with SMC sensor data
         """
         # Read SMC sensors
-        smc_data = self._read_smc_sensors()
+        smc_data = self.read_smc_sensors()
 
         # Get base battery info from IORegistry (via ioreg subprocess)
         # In production, this could use direct IORegistry API calls
         reading = self.fallback_collector.collect()
 
         # Enhance reading with SMC data
-        # PDTR (Power Delivery/Input Rate) is most accurate for watts_actual
-        if smc_data.power_input is not None:
-            reading.watts_actual = smc_data.power_input
+        # PSTR is total machine consumption, as opposed to the battery charge rate
+        reading.system_power = smc_data.system_power
+        # TB0T is read directly from the battery sensor, so it is fresher than ioreg's value
+        if smc_data.battery_temp is not None:
+            reading.battery_temp = smc_data.battery_temp
+        # B0AC is finer-grained than ioreg Amperage, which often reads 0 at rest
+        if smc_data.battery_current is not None:
+            reading.amperage_ioreg = reading.amperage
+            reading.amperage = signed_battery_current(smc_data.battery_current, reading)
+            reading.watts_actual = PowerReading.calculate_watts(reading.voltage, reading.amperage)
+        # Input power means an adapter is delivering power, even when ioreg doesn't describe one
+        if adapter_detected_by_smc(smc_data.power_input, reading.watts_negotiated):
+            reading.external_connected = True
+            reading.adapter_source = AdapterSource.SMC
+        # PDTR (Power Delivery/Input Rate) is most accurate for watts_actual; combine per watts_blend
+        reading.watts_actual = blend_watts(smc_data.power_input, reading.watts_actual, self.config)
+        if smc_data.hottest is not None:
+            reading.max_temp_sensor, reading.max_temp = smc_data.hottest
+        reading.charge_efficiency = charge_efficiency(smc_data.battery_power, smc_data.power_input, reading.is_charging)
 
         # Debug output if verbose
         if self.verbose:
@@ -105,11 +383,16 @@ class IOKitCollector(PowerCollector):
             logger.debug(f"  PDBR (Display): {smc_data.display_power}W")
             logger.debug(f"  TB0T (Battery Temp): {smc_data.battery_temp}°C")
             logger.debug(f"  CHCC (Charging): {smc_data.charging_status}")
-            logger.debug(f"Using PDTR for watts_actual: {reading.watts_actual}W")
+            logger.debug(f"  B0AC (Battery Current): {smc_data.battery_current}mA")
+            for key, temp in smc_data.temperatures.items():
+                logger.debug(f"  {key} ({SMC_TEMPERATURE_SENSORS[key]}): {temp}°C")
+            logger.debug(f"watts_actual ({self.config.watts_blend} blend): {reading.watts_actual}W")
+            logger.debug(f"Charge efficiency: {reading.charge_efficiency}%")
+            logger.debug(f"Adapter source: {reading.adapter_source}")
 
         return reading
 
-    def _read_smc_sensors(self) -> SMCPowerData:
+    def read_smc_sensors(self) -> SMCPowerData:
         """Read all SMC power sensors.
 
         Returns:
@@ -117,32 +400,41 @@ class IOKitCollector(PowerCollector):
 
         Raises:
             SMCError: If SMC connection fails
+            SMCTimeoutError: If the read takes longer than config.smc_timeout, or an
+                earlier read that timed out is still blocked
         """
-        data = SMCPowerData()
-
-        with SMCConnection() as smc:
-            # Try to read each sensor, but don't fail if some are missing
-            for key in ["PPBR", "PDTR", "PSTR", "PHPC", "PDBR", "TB0T", "CHCC"]:
-                try:
-                    value = smc.read_key(key)
-
-                    if key == "PPBR":
-                        data.battery_power = value
-                    elif key == "PDTR":
-                        data.power_input = value
-                    elif key == "PSTR":
-                        data.system_power = value
-                    elif key == "PHPC":
-                        data.heatpipe_power = value
-                    elif key == "PDBR":
-                        data.display_power = value
-                    elif key == "TB0T":
-                        data.battery_temp = value
-                    elif key == "CHCC":
-                        data.charging_status = value
-
-                except SMCError:
-                    # Sensor not available on this Mac model
-                    pass
+        timeout = self.config.smc_timeout
+        if not timeout:
+            return self._read_smc()
 
-        return data
+        if self._blocked_reader is not None:
+            if self._blocked_reader.is_alive():
+                raise SMCTimeoutError("An earlier SMC read is still blocked")
+            self._blocked_reader = None
+
+        result: list[SMCPowerData] = []
+        error: list[BaseException] = []
+
+        def read() -> None:
+            try:
+                result.append(self._read_smc())
+            except BaseException as e:  # Re-raised on the caller's thread
+                error.append(e)
+
+        reader = threading.Thread(target=read, name="smc-read", daemon=True)
+        reader.start()
+        reader.join(timeout)
+        if reader.is_alive():
+            self._blocked_reader = reader
+            logger.warning(f"SMC read did not finish within {timeout:g}s; using ioreg until it returns")
+            raise SMCTimeoutError(f"SMC read did not finish within {timeout:g}s")
+        if error:
+            raise error[0]
+        return result[0]
+
+    def _read_smc(self) -> SMCPowerData:
+        """Open the SMC reader and read every sensor (blocking)."""
+        with (self.smc_factory or SMCConnection)() as smc:
+            data = SMCPowerData.read(smc, keys=self.smc_keys)
+            data.temperatures = read_temperatures(smc)
+            return data
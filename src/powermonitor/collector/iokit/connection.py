@@ -14,19 +14,20 @@ from .bindings import IOServiceGetMatchingServices
 from .bindings import IOServiceMatching
 from .bindings import IOServiceOpen
 from .bindings import mach_task_self
-from .parser import bytes_to_float
+from .reader import SmcValue
 from .structures import KERNEL_INDEX_SMC
 from .structures import SMC_CMD_READ_BYTES
 from .structures import SMC_CMD_READ_KEYINFO
+from .structures import SMC_KEY_DATA_SIZE
+from .structures import SMC_RESULT_KEY_NOT_FOUND
 from .structures import KeyInfo
+from .structures import SMCError
 from .structures import SMCKeyData
+from .structures import SMCKeyNotFoundError
 from .structures import key_to_str
 from .structures import str_to_key
 from .structures import type_to_str
-
-
-class SMCError(Exception):
-    """SMC operation error."""
+from .structures import validate_key
 
 
 def _get_kern_return_name(kr: int) -> str:
@@ -68,6 +69,10 @@ class SMCConnection:
             temp = smc.read_key("TB0T")   # Read battery temperature
     """
 
+    # Set on the class once a call returned a different struct size than expected, so the warning
+    # is logged once per process rather than once per connection
+    _warned_size_mismatch: bool = False
+
     def __init__(self):
         """Open connection to AppleSMC.
 
@@ -136,21 +141,21 @@ class SMCConnection:
         self.connection = connection.value
         logger.debug(f"IOKit connection established: {self.connection}")
 
-    def read_key(self, key: str) -> float:
-        """Read SMC sensor key and return value as float.
+    def read_raw(self, key: str) -> SmcValue:
+        """Read the data type and raw bytes of an SMC key.
 
         Args:
             key: 4-character SMC key (e.g., "PDTR", "TB0T")
 
         Returns:
-            Sensor value as float
+            SmcValue with the key's type and bytes
 
         Raises:
             SMCError: If read fails
-            ValueError: If key is invalid format
+            InvalidKeyError: If key is not exactly 4 ASCII characters
         """
-        if len(key) != 4:
-            raise ValueError(f"SMC key must be exactly 4 characters, got: '{key}' ({len(key)} chars)")
+        # Validate up front: str_to_key() maps malformed keys to 0, which would silently read key 0x00000000
+        validate_key(key)
 
         logger.debug(f"Reading SMC key: {key}")
         key_code = str_to_key(key)
@@ -161,10 +166,23 @@ class SMCConnection:
         logger.debug(f"Key {key} info: type={data_type}, size={key_info.data_size}")
 
         # Then read the actual value bytes
-        value_bytes = self._read_key_bytes(key_code, key_info)
+        return SmcValue(data_type=data_type, data=self._read_key_bytes(key_code, key_info))
 
+    def read_key(self, key: str) -> float:
+        """Read SMC sensor key and return value as float.
+
+        Args:
+            key: 4-character SMC key (e.g., "PDTR", "TB0T")
+
+        Returns:
+            Sensor value as float
+
+        Raises:
+            SMCError: If read fails
+            InvalidKeyError: If key is not exactly 4 ASCII characters
+        """
         # Convert bytes to float based on data type
-        value = bytes_to_float(value_bytes, data_type, key_info.data_size)
+        value = self.read_raw(key).decode()
         logger.debug(f"Key {key} value: {value}")
 
         return value
@@ -179,30 +197,19 @@ class SMCConnection:
             KeyInfo structure
 
         Raises:
+            SMCKeyNotFoundError: If the key doesn't exist on this Mac
             SMCError: If read fails
         """
         input_data = SMCKeyData()
-        output_data = SMCKeyData()
-
         input_data.key = key
         input_data.data8 = SMC_CMD_READ_KEYINFO
 
-        input_size = ctypes.sizeof(SMCKeyData)
-        output_size = ctypes.c_uint(ctypes.sizeof(SMCKeyData))
+        # key_info and the result byte are all that's needed from the output
+        output_data = self._call(input_data, "Read key info", SMCKeyData.result.offset + 1)
 
-        kr = IOConnectCallStructMethod(
-            self.connection,
-            KERNEL_INDEX_SMC,
-            ctypes.byref(input_data),
-            input_size,
-            ctypes.byref(output_data),
-            ctypes.byref(output_size),
-        )
-
-        if kr != KERN_SUCCESS:
-            key_name = key_to_str(key)
-            error_name = _get_kern_return_name(kr)
-            raise SMCError(f"Read key info failed for '{key_name}': {error_name} ({kr})")
+        # The call itself succeeds for unknown keys; the SMC reports them in the result byte
+        if output_data.result == SMC_RESULT_KEY_NOT_FOUND:
+            raise SMCKeyNotFoundError(key_to_str(key))
 
         return output_data.key_info
 
@@ -220,32 +227,63 @@ class SMCConnection:
             SMCError: If read fails
         """
         input_data = SMCKeyData()
-        output_data = SMCKeyData()
-
         input_data.key = key
         input_data.data8 = SMC_CMD_READ_BYTES
         input_data.key_info = key_info
 
-        input_size = ctypes.sizeof(SMCKeyData)
+        size = min(key_info.data_size, len(input_data.bytes))
+        output_data = self._call(input_data, "Read key bytes", SMCKeyData.bytes.offset + size)
+
+        # Extract bytes up to data_size
+        return bytes(output_data.bytes[:size])
+
+    def _call(self, input_data: SMCKeyData, operation: str, needed: int) -> SMCKeyData:
+        """Send one SMCKeyData request to AppleSMC and check the size of the reply.
+
+        The kernel reports how many bytes it wrote back. A different size than
+        SMC_KEY_DATA_SIZE means the struct layout doesn't match this macOS version: that
+        is logged once, and the reply is still used if it covers the fields being read.
+
+        Args:
+            input_data: Request (key and command set)
+            operation: Description for error messages, e.g. "Read key info"
+            needed: Bytes of the reply that must have been written for the fields being read
+
+        Returns:
+            Reply structure
+
+        Raises:
+            SMCError: If the call fails or the reply is too short to read
+        """
+        output_data = SMCKeyData()
         output_size = ctypes.c_uint(ctypes.sizeof(SMCKeyData))
 
         kr = IOConnectCallStructMethod(
             self.connection,
             KERNEL_INDEX_SMC,
             ctypes.byref(input_data),
-            input_size,
+            ctypes.sizeof(SMCKeyData),
             ctypes.byref(output_data),
             ctypes.byref(output_size),
         )
 
+        key_name = key_to_str(input_data.key)
         if kr != KERN_SUCCESS:
-            key_name = key_to_str(key)
             error_name = _get_kern_return_name(kr)
-            raise SMCError(f"Read key bytes failed for '{key_name}': {error_name} ({kr})")
-
-        # Extract bytes up to data_size
-        size = key_info.data_size
-        return bytes(output_data.bytes[:size])
+            raise SMCError(f"{operation} failed for '{key_name}': {error_name} ({kr})")
+
+        if output_size.value != SMC_KEY_DATA_SIZE and not self._warned_size_mismatch:
+            SMCConnection._warned_size_mismatch = True
+            logger.warning(
+                f"AppleSMC returned a {output_size.value}-byte SMCKeyData, expected {SMC_KEY_DATA_SIZE}; "
+                "SMC readings may be unreliable on this macOS version"
+            )
+        if output_size.value < needed:
+            raise SMCError(
+                f"{operation} failed for '{key_name}': reply is {output_size.value} bytes, "
+                f"needed {needed} (SMCKeyData layout mismatch)"
+            )
+        return output_data
 
     def close(self) -> None:
         """Close SMC connection and release resources.
@@ -2,19 +2,24 @@
 
 import struct
 
+# Fixed-point types name their integer and fraction bit counts in hex: sp78 is signed with
+# 7 integer and 8 fraction bits, fp4c unsigned with 4 and 12. The value is raw / 2**fraction.
+SIGNED_FIXED_FRACTION_BITS = {"sp78": 8, "sp87": 7, "sp96": 6, "spa5": 5, "spb4": 4, "spf0": 0}
+UNSIGNED_FIXED_FRACTION_BITS = {"fp88": 8, "fp79": 9, "fp6a": 10, "fp4c": 12}
 
-def _parse_signed_fixed(data: bytes) -> float:
+
+def _parse_signed_fixed(data: bytes, fraction_bits: int) -> float:
     if len(data) < 2:
         return 0.0
     raw = struct.unpack(">h", data[:2])[0]
-    return raw / 256.0
+    return raw / (1 << fraction_bits)
 
 
-def _parse_unsigned_fixed(data: bytes) -> float:
+def _parse_unsigned_fixed(data: bytes, fraction_bits: int) -> float:
     if len(data) < 2:
         return 0.0
     raw = struct.unpack(">H", data[:2])[0]
-    return raw / 256.0
+    return raw / (1 << fraction_bits)
 
 
 def _parse_float(data: bytes) -> float:
@@ -51,10 +56,35 @@ def _parse_unknown(data: bytes, data_size: int) -> float:
     return 0.0
 
 
+def describe_type(data_type: str, data_size: int) -> str:
+    """Describe how bytes_to_float decodes an SMC data type.
+
+    Args:
+        data_type: 4-character type string (e.g., "sp78", "flt ")
+        data_size: Number of bytes in the value
+
+    Returns:
+        Short description, e.g. "signed fixed-point, 8 fraction bits"
+    """
+    data_type = data_type.ljust(4)
+    if data_type in SIGNED_FIXED_FRACTION_BITS:
+        return f"signed fixed-point, {SIGNED_FIXED_FRACTION_BITS[data_type]} fraction bits"
+    if data_type in UNSIGNED_FIXED_FRACTION_BITS:
+        return f"unsigned fixed-point, {UNSIGNED_FIXED_FRACTION_BITS[data_type]} fraction bits"
+    if data_type == "flt ":
+        return "32-bit float"
+    if data_type in ("ui8 ", "ui16", "ui32"):
+        return "unsigned integer"
+    if data_size in (1, 2, 4):
+        return "unknown type, decoded as an unsigned integer"
+    return "unknown type, not decoded"
+
+
 def bytes_to_float(data: bytes, data_type: str, data_size: int) -> float:
     """Convert raw SMC bytes to float based on data type.
 
-    Supports 13 SMC data types with proper big-endian conversion.
+    Supports the signed (sp*) and unsigned (fp*) fixed-point types, IEEE 754 floats,
+    and unsigned integers, all big-endian. Data shorter than the type needs gives 0.0.
 
     Args:
         data: Raw bytes from SMC
@@ -63,13 +93,8 @@ def bytes_to_float(data: bytes, data_type: str, data_size: int) -> float:
 
     Returns:
         Parsed float value
-
-    Raises:
-        ValueError: If data is insufficient for the type
     """
     data_type = data_type.ljust(4)
-    signed_fixed_types = {"sp78", "sp87", "sp96", "spa5", "spb4", "spf0"}
-    unsigned_fixed_types = {"fp88", "fp79", "fp6a", "fp4c"}
     type_parsers = {
         "flt ": _parse_float,
         "ui8 ": _parse_ui8,
@@ -77,10 +102,10 @@ def bytes_to_float(data: bytes, data_type: str, data_size: int) -> float:
         "ui32": _parse_ui32,
     }
 
-    if data_type in signed_fixed_types:
-        return _parse_signed_fixed(data)
-    if data_type in unsigned_fixed_types:
-        return _parse_unsigned_fixed(data)
+    if data_type in SIGNED_FIXED_FRACTION_BITS:
+        return _parse_signed_fixed(data, SIGNED_FIXED_FRACTION_BITS[data_type])
+    if data_type in UNSIGNED_FIXED_FRACTION_BITS:
+        return _parse_unsigned_fixed(data, UNSIGNED_FIXED_FRACTION_BITS[data_type])
     if data_type in type_parsers:
         return type_parsers[data_type](data)
     return _parse_unknown(data, data_size)
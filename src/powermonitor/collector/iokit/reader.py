@@ -0,0 +1,196 @@
+"""SMC reader interface with record and replay implementations.
+
+SMCConnection reads the real SMC. RecordingSmcReader wraps any reader and keeps
+the raw bytes of every key it reads, and MockSmcReader replays such a recording,
+so the decoding and merge logic can be tested deterministically and a user's
+sensors can be reproduced by a maintainer (`powermonitor status --smc-record`).
+"""
+
+import json
+from collections.abc import Mapping
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Protocol
+
+from .parser import bytes_to_float
+from .structures import SMCError
+
+
+@dataclass(frozen=True, slots=True)
+class SmcValue:
+    """Raw value of one SMC key.
+
+    Attributes:
+        data_type: 4-character SMC type (e.g., "sp78", "flt ")
+        data: Value bytes, as many as the key's data size
+    """
+
+    data_type: str
+    data: bytes
+
+    def decode(self) -> float:
+        """Convert the bytes to a float according to the data type."""
+        return bytes_to_float(self.data, self.data_type, len(self.data))
+
+
+class SmcReader(Protocol):
+    """Protocol for anything SMC keys can be read from."""
+
+    def read_raw(self, key: str) -> SmcValue:
+        """Read the type and bytes of a key.
+
+        Raises:
+            SMCError: If the key can't be read (e.g., not present on this Mac)
+        """
+        ...
+
+    def read_key(self, key: str) -> float:
+        """Read a key and decode it to a float.
+
+        Raises:
+            SMCError: If the key can't be read
+        """
+        ...
+
+    def close(self) -> None:
+        """Release the reader's resources."""
+        ...
+
+    def __enter__(self) -> "SmcReader": ...
+
+    def __exit__(self, exc_type, exc_val, exc_tb) -> bool: ...
+
+
+class RecordingSmcReader:
+    """Pass reads through to another reader, keeping the raw value of every key.
+
+    Keys that fail to read are recorded as missing, so a replay reproduces
+    sensors that don't exist on the recorded Mac.
+
+    Usage:
+        with RecordingSmcReader(SMCConnection()) as smc:
+            SMCPowerData.read(smc)
+        smc.save("smc.json")
+    """
+
+    def __init__(self, inner: SmcReader):
+        """Initialize the recorder.
+
+        Args:
+            inner: Reader to record (closed along with the recorder)
+        """
+        self.inner = inner
+        self.recorded: dict[str, SmcValue | None] = {}
+
+    def read_raw(self, key: str) -> SmcValue:
+        """Read a key from the inner reader and record the result."""
+        try:
+            value = self.inner.read_raw(key)
+        except SMCError:
+            self.recorded[key] = None
+            raise
+        self.recorded[key] = value
+        return value
+
+    def read_key(self, key: str) -> float:
+        """Read and decode a key, recording its raw value."""
+        return self.read_raw(key).decode()
+
+    def to_dict(self) -> dict[str, dict | None]:
+        """Convert the recording to a JSON-serializable dictionary.
+
+        Returns:
+            {key: {"type": data_type, "bytes": hex string}, or None for missing keys}
+        """
+        return {
+            key: None if value is None else {"type": value.data_type, "bytes": value.data.hex()}
+            for key, value in self.recorded.items()
+        }
+
+    def save(self, path: Path | str) -> None:
+        """Write the recording as JSON (readable by MockSmcReader.load)."""
+        Path(path).write_text(json.dumps(self.to_dict(), indent=2) + "\n")
+
+    def close(self) -> None:
+        """Close the inner reader."""
+        self.inner.close()
+
+    def __enter__(self):
+        """Context manager entry."""
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        """Context manager exit - close the inner reader."""
+        self.close()
+        return False
+
+
+class MockSmcReader:
+    """Replay recorded SMC values without touching IOKit.
+
+    Keys that are missing from the recording (or recorded as missing) raise
+    SMCError, just like sensors a Mac doesn't have.
+    """
+
+    def __init__(self, values: Mapping[str, SmcValue | None]):
+        """Initialize the mock.
+
+        Args:
+            values: Raw value per key; None marks a key that failed to read
+        """
+        self.values = dict(values)
+
+    @classmethod
+    def from_dict(cls, data: Mapping[str, dict | None]) -> "MockSmcReader":
+        """Create from RecordingSmcReader.to_dict() output.
+
+        Raises:
+            ValueError: If an entry lacks "type" or "bytes" or the bytes aren't hex
+        """
+        values: dict[str, SmcValue | None] = {}
+        for key, entry in data.items():
+            if entry is None:
+                values[key] = None
+                continue
+            try:
+                values[key] = SmcValue(data_type=str(entry["type"]), data=bytes.fromhex(entry["bytes"]))
+            except (KeyError, TypeError, ValueError) as e:
+                raise ValueError(f"Invalid SMC recording entry for {key!r}: {entry!r}") from e
+        return cls(values)
+
+    @classmethod
+    def load(cls, path: Path | str) -> "MockSmcReader":
+        """Load a recording saved by RecordingSmcReader.save().
+
+        Raises:
+            ValueError: If the file isn't a valid recording
+        """
+        try:
+            data = json.loads(Path(path).read_text())
+        except json.JSONDecodeError as e:
+            raise ValueError(f"Invalid SMC recording {path}: {e}") from e
+        if not isinstance(data, dict):
+            raise ValueError(f"Invalid SMC recording {path}: expected a JSON object")
+        return cls.from_dict(data)
+
+    def read_raw(self, key: str) -> SmcValue:
+        """Return the recorded value of a key."""
+        value = self.values.get(key)
+        if value is None:
+            raise SMCError(f"SMC key '{key}' not available in recording")
+        return value
+
+    def read_key(self, key: str) -> float:
+        """Return the recorded value of a key, decoded."""
+        return self.read_raw(key).decode()
+
+    def close(self) -> None:
+        """Nothing to release."""
+
+    def __enter__(self):
+        """Context manager entry."""
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb):
+        """Context manager exit."""
+        return False
@@ -1,4 +1,9 @@
-"""SMC data structures matching the C layout."""
+"""SMC data structures matching the C layout.
+
+The structures use the C compiler's natural alignment (no _pack_), like AppleSMC's own
+SMCKeyData_t: the padding after vers, inside key_info, and before data32 is part of the
+80 bytes the kernel expects.
+"""
 
 from ctypes import Structure
 from ctypes import c_uint8
@@ -12,12 +17,13 @@ SMC_CMD_READ_KEYINFO = 9
 SMC_KEY_SIZE = 4
 SMC_DATA_SIZE = 32
 SMC_BYTES_SIZE = 32
+SMC_RESULT_KEY_NOT_FOUND = 0x84  # SMCKeyData.result when the Mac has no such key
+SMC_KEY_DATA_SIZE = 80  # sizeof(SMCKeyData_t); AppleSMC rejects or misreads other sizes
 
 
 class SMCVersion(Structure):
-    """SMC version structure."""
+    """SMC version structure (6 bytes)."""
 
-    _pack_ = 1  # Packed C struct
     _fields_ = [
         ("major", c_uint8),
         ("minor", c_uint8),
@@ -28,9 +34,8 @@ class SMCVersion(Structure):
 
 
 class SMCPLimitData(Structure):
-    """SMC power limit data."""
+    """SMC power limit data (16 bytes)."""
 
-    _pack_ = 1
     _fields_ = [
         ("version", c_uint16),
         ("length", c_uint16),
@@ -41,9 +46,8 @@ class SMCPLimitData(Structure):
 
 
 class KeyInfo(Structure):
-    """SMC key information."""
+    """SMC key information (12 bytes, including 3 bytes of trailing padding)."""
 
-    _pack_ = 1
     _fields_ = [
         ("data_size", c_uint32),
         ("data_type", c_uint32),
@@ -52,9 +56,8 @@ class KeyInfo(Structure):
 
 
 class SMCKeyData(Structure):
-    """SMC key data structure (80 bytes total)."""
+    """SMC key data structure (SMC_KEY_DATA_SIZE bytes total)."""
 
-    _pack_ = 1
     _fields_ = [
         ("key", c_uint32),
         ("vers", SMCVersion),
@@ -68,6 +71,54 @@ class SMCKeyData(Structure):
     ]
 
 
+class SMCError(Exception):
+    """SMC operation error."""
+
+
+class SMCTimeoutError(SMCError):
+    """An SMC read didn't finish within the collector's smc_timeout."""
+
+
+class SMCKeyNotFoundError(SMCError):
+    """The SMC on this Mac has no such key."""
+
+    def __init__(self, key: str):
+        super().__init__(f"SMC key {key!r} does not exist on this Mac")
+        self.key = key
+
+
+class SMCMultipleError(SMCError):
+    """Some SMC keys couldn't be read; raised only when asked for, e.g. by `doctor`."""
+
+    def __init__(self, errors: dict[str, SMCError]):
+        summary = "; ".join(f"{key}: {error}" for key, error in errors.items())
+        super().__init__(f"{len(errors)} SMC key(s) unreadable: {summary}")
+        self.errors = errors
+
+
+class InvalidKeyError(ValueError):
+    """SMC key name is not exactly 4 ASCII characters."""
+
+    def __init__(self, key: str, reason: str):
+        super().__init__(f"Invalid SMC key {key!r}: {reason}")
+        self.key = key
+
+
+def validate_key(key: str) -> None:
+    """Check that an SMC key name is well-formed.
+
+    Args:
+        key: Key name to check (e.g., "PDTR")
+
+    Raises:
+        InvalidKeyError: If key is not exactly 4 ASCII characters
+    """
+    if len(key) != SMC_KEY_SIZE:
+        raise InvalidKeyError(key, f"must be exactly 4 characters, got {len(key)}")
+    if not key.isascii():
+        raise InvalidKeyError(key, "must contain only ASCII characters")
+
+
 def str_to_key(s: str) -> int:
     """Convert 4-character SMC key string to u32 (big-endian).
 
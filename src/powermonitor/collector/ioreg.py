@@ -1,14 +1,356 @@
 """IORegCollector - subprocess-based power collector using ioreg command."""
 
 import plistlib
+import re
 import subprocess
-from datetime import UTC
-from datetime import datetime
+import time
+from collections.abc import Sequence
+from datetime import date
 
+from loguru import logger
+
+from ..models import AdapterDetail
+from ..models import AdapterSource
+from ..models import CollectorUnavailableError
 from ..models import CommandFailedError
 from ..models import MissingFieldError
+from ..models import NoBatteryError
 from ..models import ParseError
 from ..models import PowerReading
+from .base import CollectorConfig
+from .gpu import GpuPowerReader
+from .lowpower import read_low_power_mode
+from .plist_scan import scan_first_dict
+from .thermal import read_thermal_state
+
+IOREG_COMMAND = ("ioreg", "-rw0", "-c", "AppleSmartBattery", "-a")
+SPAWN_ATTEMPTS = 3  # Spawn failures (e.g., process table full) are often transient
+SPAWN_RETRY_DELAY = 0.1  # seconds; doubled after each failed attempt
+
+# NotChargingReason / ChargerInhibitReason bits seen on real Macs. Apple doesn't document
+# these, so unknown bits are reported by value rather than guessed.
+NOT_CHARGING_REASONS = {
+    0x400000: "Optimized Battery Charging",
+}
+
+# Keys the parser reads. `status --export-plist` keeps only these, so the dump reproduces
+# parsing without the serial numbers and device names in a full ioreg tree.
+BATTERY_KEYS = (
+    "BatteryInstalled",
+    "Voltage",
+    "Amperage",
+    "InstantAmperage",
+    "AppleRawCurrentCapacity",
+    "CurrentCapacity",
+    "AppleRawMaxCapacity",
+    "MaxCapacity",
+    "DesignCapacity",
+    "IsCharging",
+    "ExternalConnected",
+    "AppleRawExternalConnected",
+    "FullyCharged",
+    "Temperature",
+    "ManufactureDate",
+    "ChargerInhibitReason",
+    "ChargingInhibitReason",
+    "NotChargingReason",
+    "MaxChargeCurrent",
+    "ChargingCurrent",
+)
+CHARGER_DATA_KEYS = ("ChargerInhibitReason", "ChargingInhibitReason", "NotChargingReason", "ChargingCurrent")
+FED_DETAILS_KEYS = ("FedExternalConnected",)
+# Identity keys the parser reads but sanitize_battery drops (see _known_fields)
+IDENTITY_KEYS = ("BatterySerialNumber", "Serial")
+ADAPTER_KEYS = (
+    "Port",
+    "Watts",
+    "Name",
+    "Description",
+    "Manufacturer",
+    "AdapterVoltage",
+    "Voltage",
+    "Current",
+    "FirmwareVersion",
+    "FwVersion",
+    "HwVersion",
+    "HardwareVersion",
+)
+
+_INT64_SIGN = 1 << 63
+_UINT64_RANGE = 1 << 64
+
+_PLIST_END = b"</plist>"
+_DICT_TAG_RE = re.compile(rb"<dict>|</dict>")
+_PLIST_HEADER = (
+    b'<?xml version="1.0" encoding="UTF-8"?>\n'
+    b'<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">\n'
+    b'<plist version="1.0">\n'
+)
+
+
+def _sanitize_plist(raw: bytes) -> bytes:
+    """Strip sequences known to break plist parsing.
+
+    Removes embedded NUL bytes and anything but whitespace after the closing </plist> tag.
+
+    Args:
+        raw: Raw ioreg output
+
+    Returns:
+        Cleaned plist bytes
+    """
+    cleaned = raw.replace(b"\x00", b"")
+    end = cleaned.find(_PLIST_END)
+    # The trailing newline ioreg prints is harmless; only cut (and warn about) real garbage
+    if end != -1 and cleaned[end + len(_PLIST_END) :].strip():
+        cleaned = cleaned[: end + len(_PLIST_END)]
+    return cleaned
+
+
+def _extract_first_dict(raw: bytes) -> bytes | None:
+    """Extract the first complete top-level <dict> element (the AppleSmartBattery entry).
+
+    Args:
+        raw: Plist bytes that failed to parse as a whole
+
+    Returns:
+        The <dict>...</dict> bytes, or None if no complete dictionary is present
+    """
+    depth = 0
+    start = None
+    for match in _DICT_TAG_RE.finditer(raw):
+        if match.group() == b"<dict>":
+            if depth == 0:
+                start = match.start()
+            depth += 1
+        elif depth > 0:
+            depth -= 1
+            if depth == 0:
+                return raw[start : match.end()]
+    return None
+
+
+def parse_ioreg_output(raw: bytes) -> object:
+    """Parse ioreg plist output, recovering from common corruption.
+
+    Output is sanitized first (see _sanitize_plist). If the full document still fails
+    to parse (e.g., truncated by a buffer limit), the first complete battery dictionary
+    is parsed on its own instead. If that isn't well-formed XML either (e.g., an
+    unescaped "&" in a device name), the known battery fields are pulled out with the
+    forgiving plist_scan reader, as long as Voltage and Amperage survive.
+
+    Args:
+        raw: Raw `ioreg -a` output
+
+    Returns:
+        Parsed plist (normally a list with one battery dictionary)
+
+    Raises:
+        ParseError: If neither the full document nor the battery dictionary can be parsed
+    """
+    cleaned = _sanitize_plist(raw)
+    if cleaned != raw:
+        logger.warning("ioreg output contained NUL bytes or trailing data; stripped before parsing")
+
+    try:
+        return plistlib.loads(cleaned)
+    except Exception as e:
+        battery_dict = _extract_first_dict(cleaned)
+        try:
+            if battery_dict is None:
+                raise ParseError("no complete battery dictionary")
+            battery = plistlib.loads(_PLIST_HEADER + battery_dict + b"\n" + _PLIST_END)
+        except Exception as recovery_error:
+            battery = _scan_known_fields(battery_dict or cleaned)
+            if battery is None:
+                raise ParseError(f"Failed to parse plist data: {e} (recovery failed: {recovery_error})") from e
+            logger.warning(f"Recovered AppleSmartBattery fields with the tolerant plist reader ({e})")
+            return [battery]
+
+        logger.warning(f"Recovered AppleSmartBattery data from malformed ioreg output ({e})")
+        return [battery]
+
+
+def _scan_known_fields(raw: bytes) -> dict | None:
+    """Pull the fields the parser reads out of a battery dictionary plistlib rejected.
+
+    Args:
+        raw: XML holding the battery <dict> (possibly malformed or unterminated)
+
+    Returns:
+        The known fields (see sanitize_battery, plus identity keys), or None if the
+        required Voltage and Amperage couldn't be read
+    """
+    scanned = scan_first_dict(raw)
+    if scanned is None or not all(isinstance(scanned.get(key), int) for key in ("Voltage", "Amperage")):
+        return None
+    known = sanitize_battery(scanned) | _pick(scanned, IDENTITY_KEYS)
+    battery_data = scanned.get("BatteryData")
+    if isinstance(battery_data, dict) and "ManufactureDate" in battery_data:
+        known["BatteryData"] = {"ManufactureDate": battery_data["ManufactureDate"]}
+    return known
+
+
+def signed_milliamps(value: int) -> int:
+    """Reinterpret a current that ioreg printed as an unsigned 64-bit integer.
+
+    Some models report negative (discharging) InstantAmperage as its two's complement,
+    e.g. 18446744073709550616 for -1000 mA.
+
+    Args:
+        value: Current in mA as found in the plist
+
+    Returns:
+        Signed current in mA
+    """
+    if _INT64_SIGN <= value < _UINT64_RANGE:
+        return value - _UINT64_RANGE
+    return value
+
+
+def decode_not_charging_reason(value: object) -> str | None:
+    """Turn an ioreg charge-inhibit value into a human-readable reason.
+
+    Args:
+        value: ChargerInhibitReason / NotChargingReason value - a bitmask on most Macs,
+            a descriptive string on some
+
+    Returns:
+        Reason text (multiple reasons joined by ", "), or None if nothing inhibits charging
+    """
+    if isinstance(value, str):
+        return value.strip() or None
+    if not isinstance(value, int) or isinstance(value, bool) or value <= 0:
+        return None
+
+    reasons = [name for bit, name in NOT_CHARGING_REASONS.items() if value & bit]
+    unknown = value & ~sum(NOT_CHARGING_REASONS)
+    if unknown:
+        reasons.append(f"reason {unknown:#x}")
+    return ", ".join(reasons)
+
+
+def decode_manufacture_date(value: object) -> date | None:
+    """Decode a battery manufacture date.
+
+    Intel Macs report the Smart Battery packed form: ((year - 1980) << 9) | (month << 5) | day.
+    A few report an ISO date string instead.
+
+    Args:
+        value: ManufactureDate value from ioreg
+
+    Returns:
+        Manufacture date, or None if absent or in an encoding this doesn't know
+    """
+    try:
+        if isinstance(value, str):
+            return date.fromisoformat(value.strip())
+        if isinstance(value, int) and not isinstance(value, bool) and 0 < value <= 0xFFFF:
+            return date(1980 + (value >> 9), (value >> 5) & 0x0F, value & 0x1F)
+    except ValueError:
+        pass
+    return None
+
+
+def first_adapter_details(battery: dict) -> dict | None:
+    """Find the connected adapter's details in a battery dictionary.
+
+    AppleRawAdapterDetails (an array) is preferred. Some macOS versions only populate
+    AdapterDetails (a single dictionary), so that is used when the raw key is absent or empty.
+
+    Args:
+        battery: Battery dictionary from ioreg
+
+    Returns:
+        Adapter dictionary, or None if neither key has one
+    """
+    for key in ("AppleRawAdapterDetails", "AdapterDetails"):
+        details = battery.get(key)
+        if isinstance(details, list):
+            details = details[0] if details else None
+        if isinstance(details, dict) and details:
+            return details
+    return None
+
+
+def decode_charger_port(battery: dict, adapter: dict | None) -> str | None:
+    """Find which USB-C port the charger is connected to.
+
+    An adapter-reported Port wins. Otherwise FedDetails (one entry per USB-C port
+    controller on Apple Silicon) is searched for the port with external power, and its
+    1-based position is used.
+
+    Args:
+        battery: Battery dictionary from ioreg
+        adapter: Adapter dictionary (see first_adapter_details), if any
+
+    Returns:
+        Port label such as "2", or None if the Mac doesn't report it
+    """
+    port = adapter.get("Port") if adapter else None
+    if isinstance(port, int | str) and not isinstance(port, bool) and str(port).strip():
+        return str(port).strip()
+
+    fed_details = battery.get("FedDetails")
+    if isinstance(fed_details, list):
+        for index, fed in enumerate(fed_details, start=1):
+            if isinstance(fed, dict) and fed.get("FedExternalConnected"):
+                return str(index)
+    return None
+
+
+def _pick(data: dict, keys: Sequence[str]) -> dict:
+    """Copy only the given keys that are present."""
+    return {key: data[key] for key in keys if key in data}
+
+
+def sanitize_battery(battery: dict) -> dict:
+    """Reduce an AppleSmartBattery dictionary to the fields powermonitor parses.
+
+    Serial numbers, device names, and every other key the parser ignores are dropped
+    (including the charger serial), so the result is safe to attach to bug reports.
+
+    The copy parses to the same reading except for these fields:
+    - charger_serial and battery_serial become None
+    - charger_certified becomes False for a charger that reported a serial number
+      but no manufacturer
+    - battery_manufacture_date is lost when the date is only in BatteryData
+      (Apple Silicon)
+
+    Args:
+        battery: Battery dictionary from ioreg
+
+    Returns:
+        Filtered copy of the battery dictionary
+    """
+    sanitized = _pick(battery, BATTERY_KEYS)
+    charger_data = battery.get("ChargerData")
+    if isinstance(charger_data, dict):
+        sanitized["ChargerData"] = _pick(charger_data, CHARGER_DATA_KEYS)
+    adapter_details = battery.get("AppleRawAdapterDetails")
+    if isinstance(adapter_details, list):
+        sanitized["AppleRawAdapterDetails"] = [
+            _pick(adapter, ADAPTER_KEYS) if isinstance(adapter, dict) else adapter for adapter in adapter_details
+        ]
+    if isinstance(battery.get("AdapterDetails"), dict):
+        sanitized["AdapterDetails"] = _pick(battery["AdapterDetails"], ADAPTER_KEYS)
+    fed_details = battery.get("FedDetails")
+    if isinstance(fed_details, list):
+        # Only which port has power, so exported plists still reproduce charger_port
+        sanitized["FedDetails"] = [_pick(fed, FED_DETAILS_KEYS) for fed in fed_details if isinstance(fed, dict)]
+    return sanitized
+
+
+def export_battery_plist(battery: dict) -> bytes:
+    """Serialize a sanitized battery dictionary in `ioreg -a` layout.
+
+    Args:
+        battery: Battery dictionary from ioreg
+
+    Returns:
+        XML plist holding a one-element array, readable by parse_ioreg_output
+    """
+    return plistlib.dumps([sanitize_battery(battery)])
 
 
 class IORegCollector:
@@ -20,6 +362,72 @@ class IORegCollector:
     # IORegCollector implementation details
     """
 
+    def __init__(
+        self,
+        command: Sequence[str] = IOREG_COMMAND,
+        spawn_attempts: int = SPAWN_ATTEMPTS,
+        retry_delay: float = SPAWN_RETRY_DELAY,
+        config: CollectorConfig | None = None,
+    ):
+        """Initialize ioreg collector.
+
+        Args:
+            command: ioreg command line (overridable for tests)
+            spawn_attempts: Times to try starting the process before giving up (must be >= 1)
+            retry_delay: Initial delay between spawn attempts in seconds (doubles each retry)
+            config: Collector settings; instant_amperage selects the current used for watts
+        """
+        if spawn_attempts < 1:
+            raise ValueError(f"spawn_attempts must be at least 1, got {spawn_attempts}")
+        self.command = list(command)
+        self.spawn_attempts = spawn_attempts
+        self.retry_delay = retry_delay
+        self.config = config or CollectorConfig()
+        self.gpu_reader = GpuPowerReader() if self.config.gpu_power else None
+
+    def _run_ioreg(self) -> subprocess.CompletedProcess:
+        """Run ioreg, retrying only if the process could not be started.
+
+        A non-zero exit or timeout means ioreg ran and failed, and a missing executable
+        won't appear on retry, so those are not retried.
+
+        Returns:
+            Completed process with plist bytes on stdout
+
+        Raises:
+            CollectorUnavailableError: If ioreg is not installed
+            CommandFailedError: If ioreg fails, times out, or cannot be started after all attempts
+        """
+        delay = self.retry_delay
+        attempt = 1
+        while True:
+            try:
+                return subprocess.run(
+                    self.command,
+                    capture_output=True,
+                    text=False,  # Get bytes for plist parsing
+                    check=True,
+                    timeout=10,
+                )
+            except subprocess.CalledProcessError as e:
+                raise CommandFailedError(f"ioreg command failed: {e}") from e
+            except subprocess.TimeoutExpired as e:
+                raise CommandFailedError(f"ioreg command timed out: {e}") from e
+            except FileNotFoundError as e:
+                raise CollectorUnavailableError(
+                    self.command[0],
+                    "it ships with macOS in /usr/sbin - add that to PATH, or use `status --minimal` (needs only pmset)",
+                ) from e
+            except OSError as e:
+                if attempt == self.spawn_attempts:
+                    raise CommandFailedError(
+                        f"Failed to start ioreg after {attempt} attempt(s): {e}", attempts=attempt
+                    ) from e
+                logger.debug(f"ioreg spawn failed (attempt {attempt}/{self.spawn_attempts}): {e}; retrying")
+                time.sleep(delay)
+                delay *= 2
+                attempt += 1
+
     def collect(self) -> PowerReading:
         """Collect current power reading from ioreg.
 
@@ -27,40 +435,54 @@ class IORegCollector:
             PowerReading with current power data
 
         Raises:
+            CollectorUnavailableError: If ioreg is not installed
             CommandFailedError: If ioreg execution fails
             ParseError: If plist parsing fails
             MissingFieldError: If required fields are missing
+            NoBatteryError: If the Mac has no battery
+        """
+        battery = self.read_battery_dict()
+
+        # Parse battery data into PowerReading
+        reading = self._parse_battery_data(battery)
+        reading.thermal_state = read_thermal_state()
+        reading.low_power_mode = read_low_power_mode(reading.external_connected)
+        if self.gpu_reader is not None:
+            reading.gpu_power = self.gpu_reader.read()
+        return reading
+
+    def read_battery_dict(self) -> dict:
+        """Run ioreg and return the raw AppleSmartBattery dictionary.
+
+        Returns:
+            Battery dictionary as reported by ioreg
+
+        Raises:
+            CollectorUnavailableError: If ioreg is not installed
+            CommandFailedError: If ioreg execution fails
+            ParseError: If plist parsing fails
+            NoBatteryError: If the Mac has no battery
         """
         # Execute ioreg command
-        try:
-            result = subprocess.run(
-                ["ioreg", "-rw0", "-c", "AppleSmartBattery", "-a"],
-                capture_output=True,
-                text=False,  # Get bytes for plist parsing
-                check=True,
-                timeout=10,
-            )
-        except subprocess.CalledProcessError as e:
-            raise CommandFailedError(f"ioreg command failed: {e}") from e
-        except subprocess.TimeoutExpired as e:
-            raise CommandFailedError(f"ioreg command timed out: {e}") from e
-        except FileNotFoundError as e:
-            raise CommandFailedError("ioreg command not found (not macOS?)") from e
+        result = self._run_ioreg()
+
+        # Macs without a battery have no AppleSmartBattery entry, so ioreg prints nothing
+        if not result.stdout.strip():
+            raise NoBatteryError("No battery found (ioreg returned no AppleSmartBattery entry)")
 
         # Parse plist output
-        try:
-            plist_data = plistlib.loads(result.stdout)
-        except Exception as e:
-            raise ParseError(f"Failed to parse plist data: {e}") from e
+        plist_data = parse_ioreg_output(result.stdout)
 
         # ioreg returns an array with one battery dictionary
-        if not isinstance(plist_data, list) or len(plist_data) == 0:
+        if not isinstance(plist_data, list):
             raise ParseError("Unexpected plist structure: expected array with battery data")
+        if len(plist_data) == 0:
+            raise NoBatteryError("No battery found (ioreg returned no AppleSmartBattery entry)")
 
         battery = plist_data[0]
-
-        # Parse battery data into PowerReading
-        return self._parse_battery_data(battery)
+        if not isinstance(battery, dict):
+            raise ParseError("Unexpected plist structure: battery entry is not a dictionary")
+        return battery
 
     def _parse_battery_data(self, battery: dict) -> PowerReading:
         """Parse battery dictionary into PowerReading.
@@ -73,7 +495,13 @@ class IORegCollector:
 
         Raises:
             MissingFieldError: If required fields are missing
+            NoBatteryError: If the battery reports it is not installed
         """
+        # BatteryInstalled is authoritative when present
+        battery_installed = battery.get("BatteryInstalled")
+        if battery_installed is False:
+            raise NoBatteryError("No battery installed (BatteryInstalled = false)")
+
         # Extract required fields with error handling
         try:
             voltage_mv = battery["Voltage"]  # mV
@@ -85,8 +513,15 @@ class IORegCollector:
         voltage = voltage_mv / 1000.0
         amperage = amperage_ma / 1000.0
 
-        # Calculate actual watts: V × A
-        watts_actual = PowerReading.calculate_watts(voltage, amperage)
+        # InstantAmperage tracks load changes faster than the averaged Amperage
+        instant_ma = battery.get("InstantAmperage")
+        instant_amperage = signed_milliamps(instant_ma) / 1000.0 if isinstance(instant_ma, int) else None
+
+        # Calculate actual watts: V × A (instantaneous current when configured and reported)
+        if self.config.instant_amperage and instant_amperage is not None:
+            watts_actual = PowerReading.calculate_watts(voltage, instant_amperage)
+        else:
+            watts_actual = PowerReading.calculate_watts(voltage, amperage)
 
         # Battery capacity (prefer raw values over percentage-based)
         current_capacity = battery.get("AppleRawCurrentCapacity", battery.get("CurrentCapacity", 0))
@@ -95,33 +530,73 @@ class IORegCollector:
         # Battery percentage
         battery_percent = round((current_capacity / max_capacity) * 100) if max_capacity > 0 else 0
 
-        # Charging status
+        # Charging status (AppleRawExternalConnected is the only flag on some models)
         is_charging = battery.get("IsCharging", False)
-        external_connected = battery.get("ExternalConnected", False)
+        external_connected = battery.get("ExternalConnected", battery.get("AppleRawExternalConnected", False))
+
+        # Why a connected charger isn't charging (charger-level reasons take precedence)
+        charger_data = battery.get("ChargerData")
+        if not isinstance(charger_data, dict):
+            charger_data = {}
+        charge_inhibit_reason = None
+        for source in (charger_data, battery):
+            for key in ("ChargerInhibitReason", "ChargingInhibitReason", "NotChargingReason"):
+                charge_inhibit_reason = charge_inhibit_reason or decode_not_charging_reason(source.get(key))
+
+        # Most current the battery accepts (mA); many models report neither key
+        max_charge_ma = battery.get("MaxChargeCurrent")
+        if max_charge_ma is None:
+            max_charge_ma = charger_data.get("ChargingCurrent", battery.get("ChargingCurrent"))
+        max_charge_current = max_charge_ma / 1000.0 if isinstance(max_charge_ma, int) and max_charge_ma > 0 else None
+
+        # Battery temperature is reported in hundredths of a degree Celsius
+        temperature = battery.get("Temperature")
+        battery_temp = temperature / 100.0 if isinstance(temperature, int | float) else None
 
         # Adapter details (negotiated power and charger info)
-        watts_negotiated = 0
-        charger_name = None
-        charger_manufacturer = None
+        adapter = AdapterDetail(None, None, None, None, None, None)
+        charger_certified = None
+
+        adapter_details = first_adapter_details(battery)
+        if adapter_details is not None:
+            adapter = AdapterDetail.from_ioreg(adapter_details)
+            # Only judge a charger that is actually connected and identified itself at all
+            if external_connected:
+                charger_certified = adapter.likely_certified()
+        charger_port = decode_charger_port(battery, adapter_details) if external_connected else None
 
-        adapter_details = battery.get("AppleRawAdapterDetails")
-        if adapter_details and isinstance(adapter_details, list) and len(adapter_details) > 0:
-            adapter = adapter_details[0]
-            watts_negotiated = adapter.get("Watts", 0)
-            charger_name = adapter.get("Name")
-            charger_manufacturer = adapter.get("Manufacturer")
+        # Battery identity (Apple Silicon keeps the date in BatteryData, in an encoding we don't decode)
+        battery_serial = battery.get("BatterySerialNumber", battery.get("Serial"))
+        battery_data = battery.get("BatteryData")
+        manufacture_date = battery.get("ManufactureDate")
+        if manufacture_date is None and isinstance(battery_data, dict):
+            manufacture_date = battery_data.get("ManufactureDate")
 
         return PowerReading(
-            timestamp=datetime.now(tz=UTC),
+            timestamp=self.config.clock.now(),
             watts_actual=watts_actual,
-            watts_negotiated=watts_negotiated,
+            watts_negotiated=adapter.watts or 0,
             voltage=voltage,
             amperage=amperage,
+            instant_amperage=instant_amperage,
+            max_charge_current=max_charge_current,
             current_capacity=current_capacity,
             max_capacity=max_capacity,
             battery_percent=battery_percent,
             is_charging=is_charging,
             external_connected=external_connected,
-            charger_name=charger_name,
-            charger_manufacturer=charger_manufacturer,
+            charger_name=adapter.name,
+            adapter_source=AdapterSource.IOREG if external_connected and adapter.watts else None,
+            charger_manufacturer=adapter.manufacturer,
+            battery_installed=battery_installed,
+            design_capacity=battery.get("DesignCapacity"),
+            fully_charged=battery.get("FullyCharged"),
+            battery_temp=battery_temp,
+            charge_inhibit_reason=charge_inhibit_reason,
+            charger_certified=charger_certified,
+            charger_serial=adapter.serial_number,
+            charger_firmware=adapter.firmware_version,
+            charger_port=charger_port,
+            battery_serial=battery_serial.strip() or None if isinstance(battery_serial, str) else None,
+            battery_manufacture_date=decode_manufacture_date(manufacture_date),
         )
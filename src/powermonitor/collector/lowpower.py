@@ -0,0 +1,76 @@
+"""Low Power Mode detection via `pmset -g`."""
+
+import re
+import subprocess
+
+from loguru import logger
+
+# "lowpowermode 1" on most Macs; Macs with a High Power Mode use "powermode" (0 automatic, 1 low, 2 high)
+_SETTING_RE = re.compile(r"^\s*(lowpowermode|powermode)\s+(\d+)", re.M)
+# `pmset -g custom` lists the settings of each power source under its own heading
+_SECTION_RE = re.compile(r"^(AC Power|Battery Power|UPS Power):\s*$", re.M)
+
+
+def _low_power(text: str) -> bool | None:
+    """Low Power Mode from the first lowpowermode/powermode setting in text."""
+    match = _SETTING_RE.search(text)
+    if match is None:
+        return None
+    return int(match.group(2)) == 1
+
+
+def parse_pmset_low_power_mode(output: str, external_connected: bool | None = None) -> bool | None:
+    """Parse `pmset -g` or `pmset -g custom` output into the Low Power Mode state.
+
+    `pmset -g` prints the settings in use. `pmset -g custom` prints one section per
+    power source; the section for the current source is used when external_connected
+    is known (Low Power Mode is often enabled only on battery).
+
+    Args:
+        output: Text printed by pmset
+        external_connected: Whether external power is connected, to pick a `custom` section
+
+    Returns:
+        True if Low Power Mode is on, False if off, None if the output doesn't mention it
+    """
+    headings = list(_SECTION_RE.finditer(output))
+    if not headings:
+        return _low_power(output)
+
+    sections = {
+        heading.group(1): output[heading.end() : following.start() if following else len(output)]
+        for heading, following in zip(headings, [*headings[1:], None], strict=True)
+    }
+    if external_connected is not None:
+        current = sections.get("AC Power" if external_connected else "Battery Power")
+        if current is not None:
+            return _low_power(current)
+    # Source unknown (or no section for it): report the mode only if every source agrees
+    states = {_low_power(section) for section in sections.values()} - {None}
+    return states.pop() if len(states) == 1 else None
+
+
+def read_low_power_mode(external_connected: bool | None = None) -> bool | None:
+    """Read whether Low Power Mode is on from pmset.
+
+    Low Power Mode is supplementary, so failures are logged and swallowed.
+
+    Args:
+        external_connected: Whether external power is connected
+
+    Returns:
+        Low Power Mode state, or None if pmset is unavailable or doesn't report it (older macOS)
+    """
+    try:
+        result = subprocess.run(
+            ["pmset", "-g"],
+            capture_output=True,
+            text=True,
+            check=True,
+            timeout=5,
+        )
+    except (subprocess.SubprocessError, OSError) as e:
+        logger.debug(f"Could not read Low Power Mode: {e}")
+        return None
+
+    return parse_pmset_low_power_mode(result.stdout, external_connected)
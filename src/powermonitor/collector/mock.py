@@ -0,0 +1,86 @@
+"""MockCollector - canned power readings for tests, demos, and non-macOS development."""
+
+from collections.abc import Sequence
+from dataclasses import replace
+from datetime import UTC
+from datetime import datetime
+
+from ..clock import SYSTEM_CLOCK
+from ..clock import Clock
+from ..models import PowerReading
+
+
+def default_mock_reading() -> PowerReading:
+    """Build the canned reading returned by MockCollector when none is supplied.
+
+    Returns:
+        PowerReading resembling a MacBook charging from a 67W USB-C adapter
+    """
+    return PowerReading(
+        timestamp=datetime.now(tz=UTC),
+        watts_actual=45.5,
+        watts_negotiated=67,
+        voltage=20.0,
+        amperage=2.275,
+        current_capacity=3500,
+        max_capacity=4709,
+        battery_percent=74,
+        is_charging=True,
+        external_connected=True,
+        charger_name="USB-C Power Adapter",
+        charger_manufacturer="Apple Inc.",
+    )
+
+
+class MockCollector:
+    """Collector that replays canned readings instead of querying the hardware.
+
+    Each call to collect() returns a copy of the next scripted reading stamped with the
+    clock's current time. When the script is exhausted it either starts over (cycle=True) or keeps
+    returning the last reading (cycle=False).
+
+    Usage:
+        collector = MockCollector()  # Always returns default_mock_reading()
+        collector = MockCollector([charging, unplugged], cycle=True)
+    """
+
+    def __init__(
+        self,
+        readings: PowerReading | Sequence[PowerReading] | None = None,
+        cycle: bool = True,
+        clock: Clock | None = None,
+    ):
+        """Initialize mock collector.
+
+        Args:
+            readings: A single reading or a scripted sequence (defaults to default_mock_reading())
+            cycle: Restart the sequence after the last reading instead of repeating it
+            clock: Clock that timestamps readings (defaults to the system clock)
+        """
+        if readings is None:
+            readings = [default_mock_reading()]
+        elif isinstance(readings, PowerReading):
+            readings = [readings]
+
+        if len(readings) == 0:
+            raise ValueError("MockCollector requires at least one reading")
+
+        self.readings = list(readings)
+        self.cycle = cycle
+        self.clock = clock or SYSTEM_CLOCK
+        self._index = 0
+
+    def collect(self) -> PowerReading:
+        """Return the next scripted reading with a fresh timestamp.
+
+        Returns:
+            Copy of the scripted PowerReading
+        """
+        reading = self.readings[self._index]
+
+        if self._index < len(self.readings) - 1:
+            self._index += 1
+        elif self.cycle:
+            self._index = 0
+
+        return replace(reading, timestamp=self.clock.now())
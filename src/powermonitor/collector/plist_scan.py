@@ -0,0 +1,181 @@
+"""Forgiving reader for XML plists that plistlib rejects.
+
+ioreg occasionally emits plists that aren't well-formed XML: an unescaped "&" in a
+device name, a raw control character, an empty <integer/>, or a tag left open.
+plistlib (expat) rejects the whole document for any of these. This scanner walks the
+tags with a regular expression instead of an XML parser and keeps every value it can
+read, dropping only the broken ones, so the battery fields around a quirk survive.
+
+It is only a fallback (see ioreg.parse_ioreg_output): it doesn't validate structure,
+and a value it can't make sense of is skipped rather than reported.
+"""
+
+import base64
+import binascii
+import html
+import re
+from datetime import datetime
+
+# <name ...>, </name>, and <name/>; the XML declaration, DOCTYPE, and comments don't match
+_TAG_RE = re.compile(rb"<(/?)([A-Za-z]+)\b[^<>]*?(/?)>")
+
+_SCALAR_TAGS = {"key", "string", "integer", "real", "data", "date"}
+
+
+class _Invalid:
+    """Marker for a value that couldn't be read (distinct from a legitimate None)."""
+
+
+_INVALID = _Invalid()
+
+
+def _tokenize(raw: bytes) -> list[tuple[str, str | bytes]]:
+    """Split XML into ("open" | "close" | "empty", tag name) and ("text", bytes) tokens."""
+    tokens: list[tuple[str, str | bytes]] = []
+    position = 0
+    for match in _TAG_RE.finditer(raw):
+        if match.start() > position:
+            tokens.append(("text", raw[position : match.start()]))
+        closing, name, self_closing = match.groups()
+        kind = "close" if closing else "empty" if self_closing else "open"
+        tokens.append((kind, name.decode("ascii").lower()))
+        position = match.end()
+    if position < len(raw):
+        tokens.append(("text", raw[position:]))
+    return tokens
+
+
+def _convert(tag: str, text: bytes) -> object:
+    """Convert the text of a scalar element, or return _INVALID."""
+    value = text.decode("utf-8", errors="replace")
+    try:
+        if tag in ("key", "string"):
+            return html.unescape(value)
+        if tag == "integer":
+            return int(value.strip())
+        if tag == "real":
+            return float(value.strip())
+        if tag == "data":
+            return base64.b64decode("".join(value.split()))
+        if tag == "date":
+            return datetime.fromisoformat(value.strip().replace("Z", "+00:00"))
+    except (ValueError, binascii.Error):
+        return _INVALID
+    return _INVALID
+
+
+class _Scanner:
+    """Recursive-descent walk over the tokens that never raises on bad input."""
+
+    def __init__(self, tokens: list[tuple[str, str | bytes]]):
+        self.tokens = tokens
+        self.position = 0
+
+    def _next_tag(self) -> tuple[str, str] | None:
+        """Skip text between elements and return the next tag token without consuming it."""
+        while self.position < len(self.tokens):
+            kind, value = self.tokens[self.position]
+            if kind != "text":
+                return kind, str(value)
+            self.position += 1
+        return None
+
+    def value(self) -> object:
+        """Read the next value, or return _INVALID (consuming what was unreadable)."""
+        token = self._next_tag()
+        if token is None:
+            return _INVALID
+        kind, name = token
+        if kind == "close":
+            # Stray closing tag: leave it for the enclosing dict/array to deal with
+            return _INVALID
+        self.position += 1
+        if kind == "empty":
+            return {"true": True, "false": False, "string": "", "dict": {}, "array": []}.get(name, _INVALID)
+        if name == "dict":
+            return self.dict()
+        if name == "array":
+            return self.array()
+        if name in _SCALAR_TAGS:
+            return self._scalar(name)
+        self._skip_element(name)
+        return _INVALID
+
+    def dict(self) -> dict:
+        """Read key/value pairs up to </dict> (or the end), dropping pairs that can't be read."""
+        result = {}
+        while (token := self._next_tag()) is not None:
+            kind, name = token
+            if kind == "close":
+                self.position += 1
+                if name == "dict":
+                    return result
+                continue  # Mismatched closing tag: ignore it
+            if kind == "open" and name == "key":
+                self.position += 1
+                key = self._scalar("key")
+                value = self.value()
+                if isinstance(key, str) and value is not _INVALID:
+                    result[key] = value
+            else:
+                self.value()  # Value without a key
+        return result
+
+    def array(self) -> list:
+        """Read values up to </array> (or the end), dropping those that can't be read."""
+        result = []
+        while (token := self._next_tag()) is not None:
+            kind, name = token
+            if kind == "close":
+                self.position += 1
+                if name == "array":
+                    return result
+                continue
+            value = self.value()
+            if value is not _INVALID:
+                result.append(value)
+        return result
+
+    def _scalar(self, tag: str) -> object:
+        """Read text up to the matching closing tag; another tag first means the element is broken."""
+        text = b""
+        while self.position < len(self.tokens):
+            kind, value = self.tokens[self.position]
+            if isinstance(value, bytes):
+                text += value
+                self.position += 1
+                continue
+            if kind == "close" and value == tag:
+                self.position += 1
+                return _convert(tag, text)
+            return _INVALID  # Leave the unexpected tag for the caller
+        return _INVALID
+
+    def _skip_element(self, tag: str) -> None:
+        """Skip an unknown element and everything inside it."""
+        depth = 1
+        while self.position < len(self.tokens) and depth:
+            kind, value = self.tokens[self.position]
+            if value == tag and kind == "open":
+                depth += 1
+            elif value == tag and kind == "close":
+                depth -= 1
+            self.position += 1
+
+
+def scan_first_dict(raw: bytes) -> dict | None:
+    """Read the first <dict> in a possibly malformed XML plist.
+
+    Args:
+        raw: XML plist bytes (e.g. `ioreg -a` output)
+
+    Returns:
+        The dictionary with every readable entry, or None if there is no <dict> at all
+    """
+    tokens = _tokenize(raw)
+    for index, (kind, value) in enumerate(tokens):
+        if kind == "open" and value == "dict":
+            scanner = _Scanner(tokens)
+            scanner.position = index + 1
+            return scanner.dict()
+    return None
@@ -0,0 +1,109 @@
+"""PmsetCollector - minimal collector using `pmset -g batt` (battery percent and charging state only)."""
+
+import re
+import subprocess
+from datetime import UTC
+from datetime import datetime
+
+from ..models import CollectorUnavailableError
+from ..models import CommandFailedError
+from ..models import NoBatteryError
+from ..models import ParseError
+from ..models import PowerReading
+from .base import CollectorConfig
+
+PMSET_COMMAND = ("pmset", "-g", "batt")
+
+_SOURCE_RE = re.compile(r"Now drawing from '([^']+)'")
+_BATTERY_RE = re.compile(r"-InternalBattery-\d+.*?\s(\d+)%;\s*([^;]+)")
+
+# Battery states pmset reports that mean charge is going in
+_CHARGING_STATES = {"charging", "finishing charge"}
+
+
+def parse_pmset_batt(output: str, timestamp: datetime | None = None) -> PowerReading:
+    """Parse `pmset -g batt` output into a minimal PowerReading.
+
+    Only battery_percent, is_charging, external_connected, and fully_charged are
+    populated; electrical and capacity fields are zero and charger fields None.
+
+    Args:
+        output: Text printed by `pmset -g batt`
+        timestamp: Reading time (defaults to now)
+
+    Returns:
+        Minimal PowerReading
+
+    Raises:
+        NoBatteryError: If pmset lists no internal battery
+        ParseError: If the battery line can't be parsed
+    """
+    battery = _BATTERY_RE.search(output)
+    if battery is None:
+        if "InternalBattery" in output:
+            raise ParseError(f"Unrecognized pmset battery line: {output.strip()!r}")
+        raise NoBatteryError("No battery found (pmset lists no internal battery)")
+
+    percent = int(battery.group(1))
+    state = battery.group(2).strip().lower()
+    source = _SOURCE_RE.search(output)
+
+    return PowerReading(
+        timestamp=timestamp if timestamp is not None else datetime.now(tz=UTC),
+        watts_actual=0.0,
+        watts_negotiated=0,
+        voltage=0.0,
+        amperage=0.0,
+        current_capacity=0,
+        max_capacity=0,
+        battery_percent=percent,
+        is_charging=state in _CHARGING_STATES,
+        external_connected=(source is not None and source.group(1) == "AC Power") or state.startswith("ac attached"),
+        charger_name=None,
+        charger_manufacturer=None,
+        battery_installed=True,
+        fully_charged=state == "charged",
+    )
+
+
+class PmsetCollector:
+    """Collect battery percent and charging state only (CollectLevel.MINIMAL).
+
+    `pmset -g batt` answers from the power management daemon's cached state, so it is
+    much cheaper than walking the ioreg tree or opening an SMC connection.
+    """
+
+    def __init__(self, config: CollectorConfig | None = None):
+        """Initialize pmset collector.
+
+        Args:
+            config: Collector settings; only the clock is used
+        """
+        self.config = config or CollectorConfig()
+
+    def collect(self) -> PowerReading:
+        """Collect a minimal reading from pmset.
+
+        Returns:
+            PowerReading with only battery_percent, is_charging, external_connected, and fully_charged set
+
+        Raises:
+            CollectorUnavailableError: If pmset is not installed
+            CommandFailedError: If pmset fails or cannot be started
+            NoBatteryError: If the Mac has no battery
+            ParseError: If the output is not recognized
+        """
+        try:
+            result = subprocess.run(PMSET_COMMAND, capture_output=True, text=True, check=True, timeout=5)
+        except subprocess.CalledProcessError as e:
+            raise CommandFailedError(f"pmset command failed: {e}") from e
+        except subprocess.TimeoutExpired as e:
+            raise CommandFailedError(f"pmset command timed out: {e}") from e
+        except FileNotFoundError as e:
+            raise CollectorUnavailableError(
+                PMSET_COMMAND[0], "it ships with macOS in /usr/bin - add that to PATH, or drop --minimal"
+            ) from e
+        except OSError as e:
+            raise CommandFailedError(f"Failed to start pmset: {e}") from e
+
+        return parse_pmset_batt(result.stdout, timestamp=self.config.clock.now())
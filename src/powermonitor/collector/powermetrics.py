@@ -0,0 +1,60 @@
+"""Run `powermetrics` samplers (root only), shared by GPU power and `powermonitor top`."""
+
+import os
+import subprocess
+from collections.abc import Sequence
+
+from loguru import logger
+
+
+class PowermetricsSampler:
+    """Run a powermetrics command, giving up for the session once it can't work.
+
+    powermetrics only runs as root, and some samplers only exist on Apple Silicon. The
+    first failure of that kind is logged as a warning and later runs return None
+    without spawning anything, so an unprivileged session degrades to "no data".
+    """
+
+    def __init__(self, command: Sequence[str], what: str):
+        """Initialize the sampler.
+
+        Args:
+            command: powermetrics command line
+            what: What the sampler provides, for warnings (e.g. "GPU power")
+        """
+        self.command = list(command)
+        self.what = what
+        self.available = True
+        self.disabled_reason: str | None = None  # Why the sampler gave up, once it has
+
+    def disable(self, reason: str) -> None:
+        """Stop running powermetrics for the rest of the session."""
+        logger.warning(f"{self.what} unavailable: {reason}")
+        self.available = False
+        self.disabled_reason = reason
+
+    def run(self) -> str | None:
+        """Run powermetrics once.
+
+        Returns:
+            powermetrics output, or None if unavailable
+        """
+        if not self.available:
+            return None
+        if os.geteuid() != 0:
+            self.disable("powermetrics needs root - run powermonitor with sudo")
+            return None
+
+        try:
+            result = subprocess.run(self.command, capture_output=True, text=True, check=True, timeout=10)
+        except FileNotFoundError:
+            self.disable(f"{self.command[0]} not found on PATH")
+            return None
+        except subprocess.CalledProcessError as e:
+            self.disable(f"powermetrics failed: {(e.stderr or '').strip() or e}")
+            return None
+        except (subprocess.TimeoutExpired, OSError) as e:
+            # Possibly transient (busy system); try again next time
+            logger.debug(f"Could not read {self.what}: {e}")
+            return None
+        return result.stdout
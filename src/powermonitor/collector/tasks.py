@@ -0,0 +1,101 @@
+"""Per-process energy via `powermetrics --samplers tasks` (requires root), for `powermonitor top`."""
+
+import re
+from collections.abc import Sequence
+from dataclasses import dataclass
+
+from .powermetrics import PowermetricsSampler
+
+# One 1s sample with the per-process "Energy Impact" column
+TASKS_COMMAND = ("powermetrics", "--samplers", "tasks", "--show-process-energy", "-n", "1", "-i", "1000")
+
+# Task rows: name (may contain spaces), pid, then numeric columns
+_TASK_ROW_RE = re.compile(r"^(?P<name>\S.*?)\s+(?P<pid>-?\d+)\s+(?P<values>\d[\d.\s]*)$")
+
+
+@dataclass(frozen=True, slots=True)
+class TaskEnergy:
+    """One process from the powermetrics tasks sampler.
+
+    Attributes:
+        name: Process name
+        pid: Process ID
+        cpu_ms_per_s: CPU time used, in ms per second of wall time
+        energy_impact: powermetrics' unitless energy impact (None if the column is missing)
+    """
+
+    name: str
+    pid: int
+    cpu_ms_per_s: float
+    energy_impact: float | None
+
+
+def parse_tasks(output: str) -> list[TaskEnergy]:
+    """Parse the running-tasks table printed by `powermetrics --samplers tasks`.
+
+    Aggregate rows (ALL_TASKS, DEAD_TASKS) have negative IDs and are skipped.
+
+    Args:
+        output: powermetrics output
+
+    Returns:
+        Tasks sorted by energy impact (or CPU time when energy isn't reported), highest first
+    """
+    tasks = []
+    has_energy = False
+    in_table = False
+    for line in output.splitlines():
+        stripped = line.strip()
+        if stripped.startswith("Name") and " ID " in f" {stripped} ":
+            in_table = True
+            has_energy = "Energy Impact" in stripped
+            continue
+        if not in_table:
+            continue
+        if not stripped:
+            if tasks:
+                break  # End of the table
+            continue
+        match = _TASK_ROW_RE.match(stripped)
+        if match is None:
+            continue
+        pid = int(match.group("pid"))
+        if pid < 0:
+            continue
+        values = [float(value) for value in match.group("values").split()]
+        tasks.append(
+            TaskEnergy(
+                name=match.group("name"),
+                pid=pid,
+                cpu_ms_per_s=values[0],
+                energy_impact=values[-1] if has_energy else None,
+            )
+        )
+    return sorted(tasks, key=lambda t: t.energy_impact if t.energy_impact is not None else t.cpu_ms_per_s, reverse=True)
+
+
+class TaskEnergyReader(PowermetricsSampler):
+    """Read per-process energy with powermetrics, giving up for the session once it can't work."""
+
+    def __init__(self, command: Sequence[str] = TASKS_COMMAND):
+        """Initialize the reader.
+
+        Args:
+            command: powermetrics command line (overridable for tests)
+        """
+        super().__init__(command, what="Per-process energy")
+
+    def read(self) -> list[TaskEnergy] | None:
+        """Sample running processes (takes about one second).
+
+        Returns:
+            Tasks, highest energy impact first, or None if unavailable
+        """
+        output = self.run()
+        if output is None:
+            return None
+        tasks = parse_tasks(output)
+        if not tasks:
+            self.disable("powermetrics printed no task table")
+            return None
+        return tasks
@@ -0,0 +1,77 @@
+"""Thermal pressure detection via `pmset -g therm`."""
+
+import re
+import subprocess
+
+from loguru import logger
+
+from ..models import ThermalState
+
+_PRESSURE_RE = re.compile(r"thermal pressure level\W+(nominal|fair|serious|critical|heavy|moderate|light)", re.I)
+_SPEED_LIMIT_RE = re.compile(r"CPU_Speed_Limit\s*=\s*(\d+)")
+
+# pmset and IOPMrootDomain use these names for the same levels as NSProcessInfo.thermalState
+_PRESSURE_ALIASES = {
+    "light": ThermalState.FAIR,
+    "moderate": ThermalState.FAIR,
+    "heavy": ThermalState.SERIOUS,
+}
+
+
+def parse_pmset_therm(output: str) -> ThermalState | None:
+    """Parse `pmset -g therm` output into a thermal pressure level.
+
+    Checks, in order:
+    1. An explicit "thermal pressure level: <name>" line
+    2. CPU_Speed_Limit (percentage of full speed the CPU is allowed to run at)
+    3. "No thermal warning level has been recorded" (nothing has throttled: nominal)
+
+    Args:
+        output: Text printed by `pmset -g therm`
+
+    Returns:
+        ThermalState, or None if the output contains no thermal information
+    """
+    match = _PRESSURE_RE.search(output)
+    if match:
+        name = match.group(1).lower()
+        return _PRESSURE_ALIASES.get(name) or ThermalState(name)
+
+    match = _SPEED_LIMIT_RE.search(output)
+    if match:
+        speed_limit = int(match.group(1))
+        if speed_limit >= 100:
+            return ThermalState.NOMINAL
+        if speed_limit >= 70:
+            return ThermalState.FAIR
+        if speed_limit >= 40:
+            return ThermalState.SERIOUS
+        return ThermalState.CRITICAL
+
+    if "no thermal warning level has been recorded" in output.lower():
+        return ThermalState.NOMINAL
+
+    return None
+
+
+def read_thermal_state() -> ThermalState | None:
+    """Read the current thermal pressure level from pmset.
+
+    Thermal state is supplementary, so failures are logged and swallowed.
+
+    Returns:
+        ThermalState, or None if pmset is unavailable or its output is not recognized
+    """
+    try:
+        result = subprocess.run(
+            ["pmset", "-g", "therm"],
+            capture_output=True,
+            text=True,
+            check=True,
+            timeout=5,
+        )
+    except (subprocess.SubprocessError, OSError) as e:
+        logger.debug(f"Could not read thermal state: {e}")
+        return None
+
+    return parse_pmset_therm(result.stdout)
@@ -0,0 +1,104 @@
+"""Condition expressions over power readings (used by `powermonitor wait-for`)."""
+
+import operator
+import re
+from collections.abc import Callable
+from dataclasses import dataclass
+
+from .models import PowerReading
+
+# Short names accepted in conditions, mapped to PowerReading attributes
+FIELD_ALIASES: dict[str, str] = {
+    "battery": "battery_percent",
+    "percent": "battery_percent",
+    "watts": "watts_actual",
+    "negotiated": "watts_negotiated",
+    "voltage": "voltage",
+    "amperage": "amperage",
+    "capacity": "current_capacity",
+    "charging": "is_charging",
+    "plugged": "external_connected",
+    "external": "external_connected",
+}
+
+BOOL_FIELDS = {"is_charging", "external_connected"}
+
+OPERATORS: dict[str, Callable[[object, object], bool]] = {
+    ">=": operator.ge,
+    "<=": operator.le,
+    "==": operator.eq,
+    "!=": operator.ne,
+    ">": operator.gt,
+    "<": operator.lt,
+}
+
+# Longer operators first so ">=" is not read as ">"
+_CONDITION_RE = re.compile(r"^\s*([A-Za-z_]+)\s*(>=|<=|==|!=|>|<)\s*(\S+)\s*$")
+
+
+@dataclass(frozen=True, slots=True)
+class Condition:
+    """A single `field operator value` comparison.
+
+    Attributes:
+        field: PowerReading attribute name
+        op: Comparison operator (one of OPERATORS)
+        value: Value to compare against (bool for flag fields, float otherwise)
+    """
+
+    field: str
+    op: str
+    value: bool | float
+
+    def matches(self, reading: PowerReading) -> bool:
+        """Check whether a reading satisfies this condition.
+
+        Args:
+            reading: Reading to test
+
+        Returns:
+            True if the comparison holds
+        """
+        return OPERATORS[self.op](getattr(reading, self.field), self.value)
+
+    def __str__(self) -> str:
+        value = str(self.value).lower() if isinstance(self.value, bool) else f"{self.value:g}"
+        return f"{self.field} {self.op} {value}"
+
+
+def parse_condition(text: str) -> Condition:
+    """Parse a condition such as "battery>=80" or "charging==false".
+
+    Args:
+        text: Condition string: field, operator, value
+
+    Returns:
+        Parsed Condition
+
+    Raises:
+        ValueError: If the syntax, field, or value is invalid
+    """
+    match = _CONDITION_RE.match(text)
+    if match is None:
+        raise ValueError(f"Invalid condition {text!r}; expected <field><op><value>, e.g. 'battery>=80'")
+
+    name, op, raw_value = match.groups()
+    field = FIELD_ALIASES.get(name.lower())
+    if field is None:
+        raise ValueError(f"Unknown field {name!r}; valid fields: {', '.join(sorted(FIELD_ALIASES))}")
+
+    value: bool | float
+    if field in BOOL_FIELDS:
+        if op not in ("==", "!="):
+            raise ValueError(f"Field {name!r} is true/false and only supports == and !=")
+        lowered = raw_value.lower()
+        if lowered not in ("true", "false"):
+            raise ValueError(f"Field {name!r} expects true or false, got {raw_value!r}")
+        value = lowered == "true"
+    else:
+        try:
+            value = float(raw_value.rstrip("%"))
+        except ValueError:
+            raise ValueError(f"Field {name!r} expects a number, got {raw_value!r}") from None
+
+    return Condition(field=field, op=op, value=value)
@@ -2,8 +2,14 @@
 
 import warnings
 from dataclasses import dataclass
+from dataclasses import field
+from dataclasses import replace
 from pathlib import Path
 
+from .collector.base import CollectorConfig
+from .models import PowerReading
+from .schedule import parse_schedule
+
 
 def _get_default_db_path() -> Path:
     """Get default database path.
@@ -16,6 +22,144 @@ def _get_default_db_path() -> Path:
     return Path.home() / ".powermonitor" / "powermonitor.db"
 
 
+# Presets for the TUI header summary (see tui.app.format_title)
+TITLE_FORMATS = ("watts", "percent", "watts+percent")
+
+# Metrics the TUI chart can draw as line series (see tui.widgets.ChartWidget)
+CHART_SERIES = ("watts", "negotiated", "battery", "voltage", "amperage", "temp")
+
+
+def parse_chart_series(spec: str) -> tuple[str, ...]:
+    """Parse a comma-separated chart series list such as "watts,battery,temp".
+
+    Args:
+        spec: Series names from CHART_SERIES (case-insensitive; duplicates ignored)
+
+    Returns:
+        Series names in the given order
+
+    Raises:
+        ValueError: If a name is unknown or the list is empty
+    """
+    series: list[str] = []
+    for name in filter(None, (part.strip().lower() for part in spec.split(","))):
+        if name not in CHART_SERIES:
+            raise ValueError(f"Unknown chart series {name!r}; expected some of {', '.join(CHART_SERIES)}")
+        if name not in series:
+            series.append(name)
+    if not series:
+        raise ValueError(f"Chart series list is empty; expected some of {', '.join(CHART_SERIES)}")
+    return tuple(series)
+
+
+@dataclass(frozen=True, slots=True)
+class Thresholds:
+    """Warning thresholds shared by the TUI, `status`, and `doctor` ([thresholds] in config.toml).
+
+    Attributes:
+        low_battery: Warn at or below this battery percentage while on battery power (0-100)
+        high_power: Warn when power flow in either direction exceeds this many watts (must be > 0)
+        overheat_temp: Warn at or above this battery temperature in °C (must be > 0)
+    """
+
+    low_battery: int = 20
+    high_power: float = 60.0
+    overheat_temp: float = 45.0
+
+    def __post_init__(self) -> None:
+        """Validate threshold ranges.
+
+        Raises:
+            ValueError: If any threshold is out of range
+        """
+        if not 0 <= self.low_battery <= 100:
+            raise ValueError(f"low_battery must be between 0 and 100, got {self.low_battery}")
+        if self.high_power <= 0:
+            raise ValueError(f"high_power must be positive, got {self.high_power}")
+        if self.overheat_temp <= 0:
+            raise ValueError(f"overheat_temp must be positive, got {self.overheat_temp}")
+
+    def with_overrides(self, **overrides: int | float | None) -> "Thresholds":
+        """Apply CLI overrides on top of these thresholds.
+
+        Args:
+            **overrides: Threshold values by field name; None means "not given, keep current"
+
+        Returns:
+            New Thresholds (validated again)
+
+        Raises:
+            ValueError: If an override is out of range
+        """
+        return replace(self, **{name: value for name, value in overrides.items() if value is not None})
+
+    def check(self, reading: PowerReading) -> list[str]:
+        """List the thresholds a reading exceeds.
+
+        Args:
+            reading: Reading to check
+
+        Returns:
+            Human-readable warning per exceeded threshold (empty if none)
+        """
+        warnings_found = []
+        if not reading.external_connected and reading.battery_percent <= self.low_battery:
+            warnings_found.append(f"Low battery: {reading.battery_percent}% (threshold {self.low_battery}%)")
+        if abs(reading.watts_actual) > self.high_power:
+            warnings_found.append(f"High power: {abs(reading.watts_actual):.1f}W (threshold {self.high_power:g}W)")
+        if reading.battery_temp is not None and reading.battery_temp >= self.overheat_temp:
+            warnings_found.append(
+                f"Battery overheating: {reading.battery_temp:.1f}°C (threshold {self.overheat_temp:g}°C)"
+            )
+        return warnings_found
+
+
+@dataclass(frozen=True, slots=True)
+class MqttConfig:
+    """MQTT broker settings for `powermonitor mqtt` ([mqtt] in config.toml).
+
+    Attributes:
+        broker: Broker URL, mqtt://host[:port] or mqtts://host[:port] for TLS; empty means not configured
+        username: Broker user name (empty = anonymous)
+        password: Broker password
+        topic_prefix: State topics are <topic_prefix>/<sensor>, e.g. powermonitor/watts
+        discovery_prefix: Home Assistant MQTT discovery prefix
+    """
+
+    broker: str = ""
+    username: str = ""
+    password: str = field(default="", repr=False)
+    topic_prefix: str = "powermonitor"
+    discovery_prefix: str = "homeassistant"
+
+    def __post_init__(self) -> None:
+        """Validate the broker URL and topics.
+
+        Raises:
+            ValueError: If the broker URL has another scheme or a topic prefix is empty or has wildcards
+        """
+        if self.broker and not self.broker.startswith(("mqtt://", "mqtts://")):
+            raise ValueError(f"broker must be an mqtt:// or mqtts:// URL, got {self.broker!r}")
+        for name in ("topic_prefix", "discovery_prefix"):
+            value = getattr(self, name)
+            if not value.strip("/") or any(char in value for char in "#+"):
+                raise ValueError(f"{name} must be a non-empty topic without wildcards (# or +), got {value!r}")
+
+    def with_overrides(self, **overrides: str | None) -> "MqttConfig":
+        """Apply CLI overrides on top of these settings.
+
+        Args:
+            **overrides: Values by field name; None means "not given, keep current"
+
+        Returns:
+            New MqttConfig (validated again)
+
+        Raises:
+            ValueError: If an override is invalid
+        """
+        return replace(self, **{name: value for name, value in overrides.items() if value is not None})
+
+
 @dataclass(slots=True)
 class PowerMonitorConfig:
     """Configuration for powermonitor application.
@@ -25,9 +169,23 @@ class PowerMonitorConfig:
         stats_history_limit: Number of readings to include in statistics (must be > 0)
         chart_history_limit: Number of readings to display in chart (must be > 0)
         database_path: Path to SQLite database file
+        max_db_rows: Keep at most this many readings, pruning the oldest automatically (0 = unlimited)
         default_history_limit: Default number of readings for history command (must be > 0)
         default_export_limit: Default number of readings for export command (must be > 0)
         log_level: Logging level (DEBUG, INFO, WARNING, ERROR) - stored in uppercase
+        title_format: TUI header summary preset (one of TITLE_FORMATS)
+        live_chart: Chart this session's readings from an in-memory window of
+            chart_history_limit samples instead of re-querying the database
+        schedule: Time-of-day sampling schedule (see schedule.parse_schedule); empty means
+            a fixed collection_interval
+        max_interval: Longest interval for adaptive sampling (see adaptive.AdaptiveInterval),
+            which stretches collection_interval up to this while readings are stable; 0 disables it
+        chart_series: Comma-separated metrics drawn by the TUI chart (see parse_chart_series)
+        store_interval: Seconds of samples averaged into each stored reading (see
+            averaging.ReadingAverager); 0 stores every sample
+        thresholds: Warning thresholds (see Thresholds)
+        collector: Collector settings such as the SMC/computed watts blend (see CollectorConfig)
+        mqtt: MQTT broker and topics for `powermonitor mqtt` (see MqttConfig)
 
     Notes:
         - log_level is automatically normalized to uppercase in __post_init__
@@ -39,9 +197,19 @@ class PowerMonitorConfig:
     stats_history_limit: int = 100  # number of readings for statistics
     chart_history_limit: int = 60  # number of readings to display in chart
     database_path: Path | str = _get_default_db_path()  # Will use factory default if None
+    max_db_rows: int = 0  # automatic retention limit (0 = unlimited)
     default_history_limit: int = 20  # default for history command
     default_export_limit: int = 1000  # default for export command
     log_level: str = "INFO"  # logging level (normalized to uppercase)
+    title_format: str = "watts"  # TUI header summary preset
+    live_chart: bool = False  # scroll the chart over a bounded in-memory window
+    schedule: str = ""  # time-of-day sampling intervals, e.g. "09:00-18:00=1m, *=10m"
+    max_interval: float = 0.0  # adaptive sampling upper bound in seconds (0 = fixed interval)
+    chart_series: str = "watts,negotiated"  # metrics drawn by the TUI chart
+    store_interval: float = 0.0  # seconds averaged into each stored reading (0 = store every sample)
+    thresholds: Thresholds = field(default_factory=Thresholds)  # warning thresholds
+    collector: CollectorConfig = field(default_factory=CollectorConfig)  # collector settings
+    mqtt: MqttConfig = field(default_factory=MqttConfig)  # MQTT publishing settings
 
     def __post_init__(self) -> None:
         """Validate and normalize configuration values after initialization.
@@ -78,6 +246,9 @@ class PowerMonitorConfig:
         if self.chart_history_limit <= 0:
             raise ValueError(f"chart_history_limit must be positive, got {self.chart_history_limit}")
 
+        if self.max_db_rows < 0:
+            raise ValueError(f"max_db_rows must be 0 (unlimited) or positive, got {self.max_db_rows}")
+
         if self.default_history_limit <= 0:
             raise ValueError(f"default_history_limit must be positive, got {self.default_history_limit}")
 
@@ -90,6 +261,28 @@ class PowerMonitorConfig:
         if self.log_level not in valid_levels:
             raise ValueError(f"log_level must be one of {valid_levels_str}, got {self.log_level}.")
 
+        if self.title_format not in TITLE_FORMATS:
+            raise ValueError(f"title_format must be one of {', '.join(TITLE_FORMATS)}, got {self.title_format!r}")
+
+        if self.schedule:
+            parse_schedule(self.schedule, self.collection_interval)  # Raises ValueError if malformed
+
+        if self.max_interval != 0 and self.max_interval < self.collection_interval:
+            raise ValueError(
+                f"max_interval must be 0 (off) or at least collection_interval ({self.collection_interval}), "
+                f"got {self.max_interval}"
+            )
+        if self.max_interval and self.schedule:
+            raise ValueError("max_interval (adaptive sampling) can't be combined with a sampling schedule")
+
+        parse_chart_series(self.chart_series)  # Raises ValueError if malformed
+
+        if self.store_interval != 0 and self.store_interval < self.collection_interval:
+            raise ValueError(
+                f"store_interval must be 0 (store every sample) or at least collection_interval "
+                f"({self.collection_interval}), got {self.store_interval}"
+            )
+
         # Warn about very short intervals (performance concerns)
         if self.collection_interval < 0.1:
             warnings.warn(
@@ -1,12 +1,21 @@
 """Configuration file loader for powermonitor."""
 
+import json
+import re
 import tomllib
 from pathlib import Path
 from typing import Any
 
 from loguru import logger
 
+from .collector.base import CollectorConfig
+from .collector.base import WattsBlend
+from .config import TITLE_FORMATS
+from .config import MqttConfig
 from .config import PowerMonitorConfig
+from .config import Thresholds
+from .config import parse_chart_series
+from .schedule import parse_schedule
 
 
 def get_config_path() -> Path:
@@ -18,6 +27,110 @@ def get_config_path() -> Path:
     return Path.home() / ".powermonitor" / "config.toml"
 
 
+def format_config(config: PowerMonitorConfig) -> str:
+    """Render a configuration in config.toml format.
+
+    The output can be saved as the config file and loads back to the same settings,
+    except the MQTT password, which is never printed.
+
+    Args:
+        config: Configuration to render (typically the effective, merged configuration)
+
+    Returns:
+        TOML document with one table per section
+    """
+    sections = {
+        "tui": {
+            "interval": config.collection_interval,
+            "stats_limit": config.stats_history_limit,
+            "chart_limit": config.chart_history_limit,
+            "title_format": config.title_format,
+            "live_chart": config.live_chart,
+            "schedule": config.schedule,
+            "max_interval": config.max_interval,
+            "chart_series": config.chart_series,
+            "store_interval": config.store_interval,
+        },
+        "database": {"path": str(config.database_path), "max_rows": config.max_db_rows},
+        "cli": {
+            "default_history_limit": config.default_history_limit,
+            "default_export_limit": config.default_export_limit,
+        },
+        "logging": {"level": config.log_level},
+        "thresholds": {
+            "low_battery": config.thresholds.low_battery,
+            "high_power": config.thresholds.high_power,
+            "overheat_temp": config.thresholds.overheat_temp,
+        },
+        "collector": {
+            "watts_blend": str(config.collector.watts_blend),
+            "smc_weight": config.collector.smc_weight,
+            "instant_amperage": config.collector.instant_amperage,
+            "gpu_power": config.collector.gpu_power,
+            "smc_timeout": config.collector.smc_timeout,
+        },
+        "mqtt": {
+            "broker": config.mqtt.broker,
+            "username": config.mqtt.username,
+            "topic_prefix": config.mqtt.topic_prefix,
+            "discovery_prefix": config.mqtt.discovery_prefix,
+        },
+    }
+
+    lines: list[str] = []
+    for section, values in sections.items():
+        if lines:
+            lines.append("")
+        lines.append(f"[{section}]")
+        # JSON scalars are valid TOML for the strings and numbers used here
+        lines.extend(f"{key} = {json.dumps(value)}" for key, value in values.items())
+    return "\n".join(lines) + "\n"
+
+
+def save_title_format(title_format: str) -> Path:
+    """Persist the TUI header preset as tui.title_format in the config file.
+
+    Only that setting is rewritten (or added under [tui]), so comments and other
+    settings in the file are kept. The file is created if it doesn't exist.
+
+    Args:
+        title_format: One of TITLE_FORMATS
+
+    Returns:
+        Path of the config file written
+
+    Raises:
+        ValueError: If title_format is not a known preset
+        OSError: If the file can't be written
+    """
+    if title_format not in TITLE_FORMATS:
+        raise ValueError(f"title_format must be one of {', '.join(TITLE_FORMATS)}, got {title_format!r}")
+    config_path = get_config_path()
+    lines = config_path.read_text().splitlines() if config_path.exists() else []
+    setting = f"title_format = {json.dumps(title_format)}"
+
+    section = None
+    tui_header = None
+    for i, line in enumerate(lines):
+        stripped = line.strip()
+        if stripped.startswith("["):
+            section = "tui" if re.match(r"\[\s*tui\s*\]", stripped) else stripped
+            if section == "tui":
+                tui_header = i
+        elif section == "tui" and re.match(r"title_format\s*=", stripped):
+            lines[i] = setting
+            break
+    else:
+        if tui_header is not None:
+            lines.insert(tui_header + 1, setting)
+        else:
+            lines.extend(["", "[tui]", setting] if lines else ["[tui]", setting])
+
+    config_path.parent.mkdir(parents=True, exist_ok=True)
+    config_path.write_text("\n".join(lines) + "\n")
+    return config_path
+
+
 def _convert_to_type(value: Any, target_type: type, field_name: str) -> Any:
     """Convert a value to the target type with descriptive error messages.
 
@@ -117,13 +230,38 @@ def _validate_config_structure(user_config: dict[str, Any], config_path: Path) -
         config_path: Path to config file for error messages
     """
     # Validate sections and warn about unknown keys
-    _warn_unknown_keys(user_config, "tui", {"interval", "stats_limit", "chart_limit"}, config_path)
-    _warn_unknown_keys(user_config, "database", {"path"}, config_path)
+    _warn_unknown_keys(
+        user_config,
+        "tui",
+        {
+            "interval",
+            "stats_limit",
+            "chart_limit",
+            "title_format",
+            "live_chart",
+            "schedule",
+            "max_interval",
+            "chart_series",
+            "store_interval",
+        },
+        config_path,
+    )
+    _warn_unknown_keys(user_config, "database", {"path", "max_rows"}, config_path)
     _warn_unknown_keys(user_config, "cli", {"default_history_limit", "default_export_limit"}, config_path)
     _warn_unknown_keys(user_config, "logging", {"level"}, config_path)
+    _warn_unknown_keys(user_config, "thresholds", {"low_battery", "high_power", "overheat_temp"}, config_path)
+    _warn_unknown_keys(
+        user_config,
+        "collector",
+        {"watts_blend", "smc_weight", "instant_amperage", "gpu_power", "smc_timeout"},
+        config_path,
+    )
+    _warn_unknown_keys(
+        user_config, "mqtt", {"broker", "username", "password", "topic_prefix", "discovery_prefix"}, config_path
+    )
 
     # Warn about unknown sections
-    valid_sections = {"tui", "database", "cli", "logging"}
+    valid_sections = {"tui", "database", "cli", "logging", "thresholds", "collector", "mqtt"}
     for section in user_config:
         if section not in valid_sections:
             logger.warning(f"Unknown config section [{section}] in {config_path} - ignoring")
@@ -204,6 +342,7 @@ def load_config() -> PowerMonitorConfig:
         database_path = default_config.database_path
     else:
         database_path = database_path_raw
+    max_db_rows = safe_convert("database.max_rows", int, default_config.max_db_rows)
 
     # Log level (ensure it's a string, validation happens in __post_init__)
     log_level_raw = _get_nested_value(user_config, "logging.level", default_config.log_level)
@@ -216,6 +355,135 @@ def load_config() -> PowerMonitorConfig:
     else:
         log_level = log_level_raw
 
+    # Title format (must be one of the known presets)
+    title_format_raw = _get_nested_value(user_config, "tui.title_format", default_config.title_format)
+    if title_format_raw not in TITLE_FORMATS:
+        logger.warning(
+            f"Invalid 'tui.title_format' value {title_format_raw!r}; expected one of "
+            f"{', '.join(TITLE_FORMATS)} - using default value {default_config.title_format!r}"
+        )
+        title_format = default_config.title_format
+    else:
+        title_format = title_format_raw
+
+    # Live chart flag (TOML booleans only; "true" strings are rejected rather than guessed)
+    live_chart_raw = _get_nested_value(user_config, "tui.live_chart", default_config.live_chart)
+    if not isinstance(live_chart_raw, bool):
+        logger.warning(
+            f"Invalid 'tui.live_chart' value {live_chart_raw!r}; expected true or false - "
+            f"using default value {default_config.live_chart!r}"
+        )
+        live_chart = default_config.live_chart
+    else:
+        live_chart = live_chart_raw
+
+    # Sampling schedule (a string that must parse; see schedule.parse_schedule)
+    schedule_raw = _get_nested_value(user_config, "tui.schedule", default_config.schedule)
+    try:
+        if not isinstance(schedule_raw, str):
+            raise ValueError("expected a string")
+        if schedule_raw:
+            parse_schedule(schedule_raw, collection_interval)
+        schedule = schedule_raw
+    except ValueError as e:
+        logger.warning(f"Invalid 'tui.schedule' value {schedule_raw!r} ({e}) - using a fixed interval")
+        schedule = default_config.schedule
+
+    # Adaptive sampling bound (0 = off; at least interval, and not together with a schedule)
+    max_interval = safe_convert("tui.max_interval", float, default_config.max_interval)
+    if max_interval != 0 and (max_interval < collection_interval or schedule):
+        if schedule:
+            reason = "it can't be combined with tui.schedule"
+        else:
+            reason = f"it must be 0 or at least tui.interval ({collection_interval})"
+        logger.warning(f"Invalid 'tui.max_interval' value {max_interval!r} ({reason}) - using a fixed interval")
+        max_interval = default_config.max_interval
+
+    # Averaged storage (0 = store every sample; otherwise at least interval)
+    store_interval = safe_convert("tui.store_interval", float, default_config.store_interval)
+    if store_interval != 0 and store_interval < collection_interval:
+        logger.warning(
+            f"Invalid 'tui.store_interval' value {store_interval!r} "
+            f"(it must be 0 or at least tui.interval ({collection_interval})) - storing every sample"
+        )
+        store_interval = default_config.store_interval
+
+    # Chart series (a comma-separated list of CHART_SERIES names)
+    chart_series_raw = _get_nested_value(user_config, "tui.chart_series", default_config.chart_series)
+    try:
+        if not isinstance(chart_series_raw, str):
+            raise ValueError("expected a comma-separated string")
+        parse_chart_series(chart_series_raw)
+        chart_series = chart_series_raw
+    except ValueError as e:
+        logger.warning(
+            f"Invalid 'tui.chart_series' value {chart_series_raw!r} ({e}) - "
+            f"using default value {default_config.chart_series!r}"
+        )
+        chart_series = default_config.chart_series
+
+    # Thresholds are validated together; out-of-range values fall back to the default set
+    default_thresholds = default_config.thresholds
+    try:
+        thresholds = Thresholds(
+            low_battery=safe_convert("thresholds.low_battery", int, default_thresholds.low_battery),
+            high_power=safe_convert("thresholds.high_power", float, default_thresholds.high_power),
+            overheat_temp=safe_convert("thresholds.overheat_temp", float, default_thresholds.overheat_temp),
+        )
+    except ValueError as e:
+        logger.warning(f"Invalid [thresholds] in {config_path}: {e} - using default thresholds")
+        thresholds = default_thresholds
+
+    # Collector settings: watts_blend must name a WattsBlend; smc_weight and smc_timeout are range-checked
+    # by CollectorConfig
+    default_collector = default_config.collector
+    watts_blend_raw = _get_nested_value(user_config, "collector.watts_blend", default_collector.watts_blend)
+    if watts_blend_raw not in list(WattsBlend):
+        logger.warning(
+            f"Invalid 'collector.watts_blend' value {watts_blend_raw!r}; expected one of "
+            f"{', '.join(WattsBlend)} - using default value {str(default_collector.watts_blend)!r}"
+        )
+        watts_blend_raw = default_collector.watts_blend
+    instant_amperage = _get_nested_value(user_config, "collector.instant_amperage", default_collector.instant_amperage)
+    if not isinstance(instant_amperage, bool):
+        logger.warning(
+            f"Invalid 'collector.instant_amperage' value {instant_amperage!r}; expected true or false - "
+            f"using default value {default_collector.instant_amperage!r}"
+        )
+        instant_amperage = default_collector.instant_amperage
+    gpu_power = _get_nested_value(user_config, "collector.gpu_power", default_collector.gpu_power)
+    if not isinstance(gpu_power, bool):
+        logger.warning(
+            f"Invalid 'collector.gpu_power' value {gpu_power!r}; expected true or false - "
+            f"using default value {default_collector.gpu_power!r}"
+        )
+        gpu_power = default_collector.gpu_power
+    try:
+        collector = CollectorConfig(
+            watts_blend=WattsBlend(watts_blend_raw),
+            smc_weight=safe_convert("collector.smc_weight", float, default_collector.smc_weight),
+            smc_timeout=safe_convert("collector.smc_timeout", float, default_collector.smc_timeout),
+            instant_amperage=instant_amperage,
+            gpu_power=gpu_power,
+        )
+    except ValueError as e:
+        logger.warning(f"Invalid [collector] in {config_path}: {e} - using default collector settings")
+        collector = default_collector
+
+    # [mqtt] settings are validated together by MqttConfig
+    default_mqtt = default_config.mqtt
+    try:
+        mqtt = MqttConfig(
+            broker=safe_convert("mqtt.broker", str, default_mqtt.broker),
+            username=safe_convert("mqtt.username", str, default_mqtt.username),
+            password=safe_convert("mqtt.password", str, default_mqtt.password),
+            topic_prefix=safe_convert("mqtt.topic_prefix", str, default_mqtt.topic_prefix),
+            discovery_prefix=safe_convert("mqtt.discovery_prefix", str, default_mqtt.discovery_prefix),
+        )
+    except ValueError as e:
+        logger.warning(f"Invalid [mqtt] in {config_path}: {e} - using default MQTT settings")
+        mqtt = default_mqtt
+
     # Create PowerMonitorConfig instance (validation happens in __post_init__)
     try:
         return PowerMonitorConfig(
@@ -223,9 +491,19 @@ def load_config() -> PowerMonitorConfig:
             stats_history_limit=stats_history_limit,
             chart_history_limit=chart_history_limit,
             database_path=database_path,
+            max_db_rows=max_db_rows,
             default_history_limit=default_history_limit,
             default_export_limit=default_export_limit,
             log_level=log_level,
+            title_format=title_format,
+            live_chart=live_chart,
+            schedule=schedule,
+            max_interval=max_interval,
+            chart_series=chart_series,
+            store_interval=store_interval,
+            thresholds=thresholds,
+            collector=collector,
+            mqtt=mqtt,
         )
     except ValueError as e:
         # This should rarely happen now (only if __post_init__ validation fails)
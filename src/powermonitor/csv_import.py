@@ -0,0 +1,165 @@
+"""Read readings back from CSV exports (`powermonitor import`)."""
+
+import csv
+import math
+from collections.abc import Callable
+from dataclasses import dataclass
+from dataclasses import field
+from datetime import UTC
+from datetime import datetime
+from pathlib import Path
+from typing import Any
+
+from .export import CSV_COLUMNS
+from .models import PowerReading
+from .models import ThermalState
+
+# Columns every export has had; the others were added over time and may be absent from older files
+REQUIRED_COLUMNS = CSV_COLUMNS[:10]
+
+
+def _parse_bool(value: str) -> bool:
+    lowered = value.lower()
+    if lowered not in ("true", "false"):
+        raise ValueError(f"expected True or False, got {value!r}")
+    return lowered == "true"
+
+
+def _parse_float(value: str) -> float:
+    number = float(value)
+    if not math.isfinite(number):
+        raise ValueError(f"expected a finite number, got {value!r}")
+    return number
+
+
+def _parse_timestamp(value: str) -> datetime:
+    # Stored timestamps are UTC and compared as text, so other offsets must be converted first;
+    # a naive value can't be placed on that timeline (exports always carry an offset)
+    timestamp = datetime.fromisoformat(value)
+    if timestamp.tzinfo is None:
+        raise ValueError(f"expected a timestamp with a UTC offset, got {value!r}")
+    return timestamp.astimezone(UTC)
+
+
+_PARSERS: dict[str, Callable[[str], Any]] = {
+    "timestamp": _parse_timestamp,
+    "watts_actual": _parse_float,
+    "watts_negotiated": int,
+    "voltage": _parse_float,
+    "amperage": _parse_float,
+    "current_capacity": int,
+    "max_capacity": int,
+    "battery_percent": int,
+    "is_charging": _parse_bool,
+    "external_connected": _parse_bool,
+    "charger_name": str,
+    "charger_manufacturer": str,
+    "watts_negotiated_stable": int,
+    "label": str,
+    "thermal_state": ThermalState,
+    "design_capacity": int,
+    "battery_temp": _parse_float,
+    "system_power": _parse_float,
+    "low_power_mode": _parse_bool,
+}
+
+
+@dataclass
+class CsvImport:
+    """Result of reading a CSV export.
+
+    Attributes:
+        readings: Readings from every valid row, in file order
+        skipped: (line number, reason) for each malformed row
+    """
+
+    readings: list[PowerReading] = field(default_factory=list)
+    skipped: list[tuple[int, str]] = field(default_factory=list)
+
+
+def check_header(header: list[str]) -> None:
+    """Check a CSV header row against the export columns.
+
+    Columns may come in any order, and optional columns added in later versions may be missing.
+
+    Args:
+        header: Column names from the first row
+
+    Raises:
+        ValueError: If a column is unknown, repeated, or a required column is missing
+    """
+    unknown = [name for name in header if name not in CSV_COLUMNS]
+    if unknown:
+        raise ValueError(f"Unknown column(s): {', '.join(unknown)} (expected {', '.join(CSV_COLUMNS)})")
+    repeated = sorted({name for name in header if header.count(name) > 1})
+    if repeated:
+        raise ValueError(f"Repeated column(s): {', '.join(repeated)}")
+    missing = [name for name in REQUIRED_COLUMNS if name not in header]
+    if missing:
+        raise ValueError(f"Missing column(s): {', '.join(missing)}")
+
+
+def parse_row(header: list[str], row: list[str]) -> PowerReading:
+    """Build a reading from one CSV row.
+
+    Empty cells become None for optional columns.
+
+    Args:
+        header: Column names (already validated with check_header)
+        row: Cell values
+
+    Returns:
+        PowerReading
+
+    Raises:
+        ValueError: If the row has the wrong number of cells or a value can't be parsed
+    """
+    if len(row) != len(header):
+        raise ValueError(f"expected {len(header)} fields, got {len(row)}")
+    values: dict[str, Any] = {}
+    for name, cell in zip(header, row, strict=True):
+        if cell == "":
+            if name in REQUIRED_COLUMNS:
+                raise ValueError(f"{name} is empty")
+            values[name] = None
+            continue
+        try:
+            values[name] = _PARSERS[name](cell)
+        except ValueError:
+            raise ValueError(f"invalid {name}: {cell!r}") from None
+    return PowerReading(**values)
+
+
+def read_csv(path: Path, delimiter: str = ",") -> CsvImport:
+    """Read readings from a CSV file written by `powermonitor export`.
+
+    Malformed rows are skipped and reported rather than aborting the whole file.
+
+    Args:
+        path: CSV file with a header row
+        delimiter: Single-character field separator
+
+    Returns:
+        Parsed readings and the skipped rows
+
+    Raises:
+        ValueError: If the file is empty or the header doesn't match the export columns
+        OSError: If the file can't be read
+    """
+    result = CsvImport()
+    with open(path, newline="") as f:
+        reader = csv.reader(f, delimiter=delimiter)
+        header = next(reader, None)
+        if header is None:
+            raise ValueError(f"{path} is empty")
+        check_header(header)
+        line_number = reader.line_num + 1
+        for row in reader:
+            if row:
+                try:
+                    result.readings.append(parse_row(header, row))
+                except ValueError as e:
+                    result.skipped.append((line_number, str(e)))
+            # Quoted fields may span lines, so the next row starts after the reader's position
+            line_number = reader.line_num + 1
+    return result
@@ -1,10 +1,14 @@
 """SQLite database operations for powermonitor."""
 
+from collections import Counter
+from collections.abc import Iterable
+from collections.abc import Iterator
 from datetime import UTC
 from datetime import datetime
 from datetime import timedelta
 from pathlib import Path
 
+from loguru import logger
 from peewee import BooleanField
 from peewee import DateTimeField
 from peewee import FloatField
@@ -12,9 +16,18 @@ from peewee import IntegerField
 from peewee import Model
 from peewee import SqliteDatabase
 from peewee import TextField
+from peewee import chunked
 from peewee import fn
 
+from .aggregates import ChargerStats
+from .aggregates import DatabaseSummary
+from .degradation import ChargerPeak
+from .degradation import tracked_charger
+from .models import BurstResult
+from .models import BurstSample
+from .models import ChargeSession
 from .models import PowerReading
+from .models import ThermalState
 
 
 def get_default_db_path() -> Path:
@@ -36,6 +49,33 @@ def get_default_db_path() -> Path:
 # CLI and TUI should use config.database_path instead
 DB_PATH = get_default_db_path()
 
+# Columns added after the initial schema, mapped to their SQLite column definitions.
+# Existing databases are upgraded in place with ALTER TABLE when these are missing.
+COLUMN_MIGRATIONS: dict[str, str] = {
+    "watts_negotiated_stable": "INTEGER",
+    "label": "TEXT",
+    "thermal_state": "TEXT",
+    "design_capacity": "INTEGER",
+    "battery_temp": "REAL",
+    "system_power": "REAL",
+    "low_power_mode": "INTEGER",
+}
+
+# With max_rows set, the row limit is enforced on the first insert and then every this many inserts,
+# so long-running sessions don't pay for a COUNT(*) on every reading
+PRUNE_CHECK_EVERY = 100
+
+# Rows per INSERT in insert_readings; 19 columns x 50 rows stays under older SQLite builds' 999-variable limit
+INSERT_BATCH_SIZE = 50
+
+# Applied on every connection, in order. WAL lets the TUI write while other commands read or write,
+# and busy_timeout waits for a competing writer instead of failing with "database is locked".
+# busy_timeout comes first so switching a new file to WAL also waits out a concurrent opener.
+SQLITE_PRAGMAS: dict[str, str | int] = {
+    "busy_timeout": 5000,  # ms
+    "journal_mode": "wal",
+}
+
 
 class Database:
     """SQLite database manager for power readings using Peewee ORM.
@@ -64,30 +104,72 @@ class Database:
         db.close()  # Recommended when not using context manager
     """
 
-    def __init__(self, db_path: Path | str = DB_PATH):
+    def __init__(self, db_path: Path | str = DB_PATH, max_rows: int = 0, read_only: bool = False):
         """Initialize database connection.
 
         Args:
             db_path: Path to SQLite database file
+            max_rows: Keep at most this many readings, pruning the oldest from the insert
+                path (checked every PRUNE_CHECK_EVERY inserts); 0 = unlimited
+            read_only: Open an existing database without creating or migrating its schema,
+                e.g. one copied from another Mac (only aggregate queries that check for
+                newer columns, like get_summary, are safe on older schemas)
+
+        Raises:
+            FileNotFoundError: If read_only and the file doesn't exist
+            ValueError: If read_only and the file has no power_readings table
         """
         self.db_path = Path(db_path)
+        self.max_rows = max_rows
+        self._inserts_since_prune_check: int | None = None  # None = not checked yet this session
+
+        if read_only:
+            if not self.db_path.is_file():
+                raise FileNotFoundError(f"Database file not found: {self.db_path}")
+            # Only busy_timeout: switching journal_mode would need write access
+            pragmas = {"busy_timeout": SQLITE_PRAGMAS["busy_timeout"]}
+            self.db = SqliteDatabase(f"{self.db_path.resolve().as_uri()}?mode=ro", uri=True, pragmas=pragmas)
+            self._create_model()
+            with self.db.connection_context():
+                if not self.db.table_exists("power_readings"):
+                    raise ValueError(f"Not a powermonitor database (no power_readings table): {self.db_path}")
+            return
+
         # Ensure parent directory exists for custom database paths
         self.db_path.parent.mkdir(parents=True, exist_ok=True)
 
         # Create per-instance Peewee database
-        self.db = SqliteDatabase(str(self.db_path))
+        self.db = SqliteDatabase(str(self.db_path), pragmas=SQLITE_PRAGMAS)
 
         # Create per-instance model bound to this database
         self._create_model()
 
-        # Create tables and index with connection context
-        with self.db.connection_context():
-            self.db.create_tables([self.PowerReadingModel])
+        # Create tables and index with connection context. BEGIN IMMEDIATE takes the write lock
+        # before the schema is inspected, so concurrent openers (e.g. two `status --record` runs)
+        # set it up one at a time and the second finds the first's tables and columns.
+        with self.db.connection_context(), self.db.atomic("IMMEDIATE"):
+            self.db.create_tables(
+                [self.PowerReadingModel, self.BurstSampleModel, self.ChargeSessionModel, self.ChargerPeakModel]
+            )
 
             # Create index with specific name for backward compatibility
             # Peewee's index=True would create an auto-named index, but tests expect idx_timestamp
             self.db.execute_sql("CREATE INDEX IF NOT EXISTS idx_timestamp ON power_readings(timestamp DESC)")
 
+            self._migrate_schema()
+
+    def _migrate_schema(self) -> None:
+        """Add columns introduced after the initial schema to an existing table.
+
+        create_tables() never alters an existing table, so databases created by older
+        versions are missing newer nullable columns. Must be called inside a connection context,
+        in the write transaction that set up the schema, so the columns read are current.
+        """
+        existing = {column.name for column in self.db.get_columns("power_readings")}
+        for column, definition in COLUMN_MIGRATIONS.items():
+            if column not in existing:
+                self.db.execute_sql(f"ALTER TABLE power_readings ADD COLUMN {column} {definition}")
+
     def _create_model(self):
         """Create a PowerReadingModel bound to this instance's database."""
 
@@ -106,12 +188,60 @@ class Database:
             external_connected = BooleanField()
             charger_name = TextField(null=True)
             charger_manufacturer = TextField(null=True)
+            watts_negotiated_stable = IntegerField(null=True)
+            label = TextField(null=True)
+            thermal_state = TextField(null=True)
+            design_capacity = IntegerField(null=True)
+            battery_temp = FloatField(null=True)
+            system_power = FloatField(null=True)
+            low_power_mode = BooleanField(null=True)
 
             class Meta:
                 database = self.db
                 table_name = "power_readings"
 
+        class BurstSampleModel(Model):
+            """Peewee ORM model for burst_samples table (high-frequency SMC captures)."""
+
+            burst_id = IntegerField(index=True)
+            timestamp = DateTimeField()
+            rate_hz = FloatField()
+            power_input = FloatField(null=True)
+            system_power = FloatField(null=True)
+
+            class Meta:
+                database = self.db
+                table_name = "burst_samples"
+
+        class ChargeSessionModel(Model):
+            """Peewee ORM model for charge_sessions table (see sessions.ChargeSessionTracker)."""
+
+            started_at = DateTimeField(index=True)
+            ended_at = DateTimeField()
+            start_percent = IntegerField()
+            end_percent = IntegerField()
+            total_wh = FloatField()
+
+            class Meta:
+                database = self.db
+                table_name = "charge_sessions"
+
+        class ChargerPeakModel(Model):
+            """Peewee ORM model for charger_peaks table (see degradation.ChargerPeak)."""
+
+            name = TextField(unique=True)
+            peak_watts = IntegerField()
+            last_watts = IntegerField()
+            low_readings = IntegerField(default=0)
+
+            class Meta:
+                database = self.db
+                table_name = "charger_peaks"
+
         self.PowerReadingModel = PowerReadingModel
+        self.BurstSampleModel = BurstSampleModel
+        self.ChargeSessionModel = ChargeSessionModel
+        self.ChargerPeakModel = ChargerPeakModel
 
     def __enter__(self):
         """Enter context manager (no-op, provided for API consistency)."""
@@ -140,54 +270,471 @@ class Database:
             Row ID of inserted reading
         """
         with self.db.connection_context():
-            model = self.PowerReadingModel.create(
-                timestamp=reading.timestamp,
-                watts_actual=reading.watts_actual,
-                watts_negotiated=reading.watts_negotiated,
-                voltage=reading.voltage,
-                amperage=reading.amperage,
-                current_capacity=reading.current_capacity,
-                max_capacity=reading.max_capacity,
-                battery_percent=reading.battery_percent,
-                is_charging=reading.is_charging,
-                external_connected=reading.external_connected,
-                charger_name=reading.charger_name,
-                charger_manufacturer=reading.charger_manufacturer,
-            )
-            return model.id
+            model = self.PowerReadingModel.create(**_reading_row(reading))
+        self._maybe_prune()
+        return model.id
+
+    def insert_readings(self, readings: Iterable[PowerReading], skip_existing: bool = False) -> int:
+        """Insert many power readings in one transaction (e.g. `powermonitor import`).
+
+        Rows are written in batches of INSERT_BATCH_SIZE; if any batch fails, nothing is inserted.
+
+        With skip_existing, readings already stored with identical values are left out, so
+        importing the same file twice doesn't duplicate it. Matching counts copies rather than
+        using a unique constraint: a reading that appears twice in the input and once in the
+        database is inserted once, so identical rapid samples are never collapsed.
+
+        Args:
+            readings: Readings to insert
+            skip_existing: Leave out readings that are already stored
+
+        Returns:
+            Number of readings inserted
+        """
+        rows = [_reading_row(reading) for reading in readings]
+        count = 0
+        with self.db.connection_context(), self.db.atomic():
+            if skip_existing and rows:
+                rows = self._without_stored(rows)
+            for batch in chunked(rows, INSERT_BATCH_SIZE):
+                self.PowerReadingModel.insert_many(batch).execute()
+                count += len(batch)
+        if count and self.max_rows > 0:
+            self.prune_to_max_rows(self.max_rows)
+        return count
+
+    def _without_stored(self, rows: list[dict]) -> list[dict]:
+        """Drop one row for each stored reading with identical values (see insert_readings)."""
+        wanted = {tuple(row.values()) for row in rows}
+        model = self.PowerReadingModel
+        timestamps = [row["timestamp"] for row in rows]
+        stored: Counter[tuple] = Counter()
+        query = model.select().where(model.timestamp.between(min(timestamps), max(timestamps)))
+        for r in query.iterator():
+            key = tuple(_reading_row(self._to_reading(r)).values())
+            if key in wanted:
+                stored[key] += 1
+
+        new_rows = []
+        for row in rows:
+            key = tuple(row.values())
+            if stored[key]:
+                stored[key] -= 1
+            else:
+                new_rows.append(row)
+        return new_rows
+
+    def _maybe_prune(self) -> None:
+        """Enforce max_rows on the first insert and every PRUNE_CHECK_EVERY inserts after that."""
+        if self.max_rows <= 0:
+            return
+        if self._inserts_since_prune_check is not None:
+            self._inserts_since_prune_check += 1
+            if self._inserts_since_prune_check < PRUNE_CHECK_EVERY:
+                return
+        self._inserts_since_prune_check = 0
+        removed = self.prune_to_max_rows(self.max_rows)
+        if removed:
+            logger.info(f"Automatically pruned {removed} oldest readings (database.max_rows = {self.max_rows})")
+
+    def prune_to_max_rows(self, max_rows: int) -> int:
+        """Delete the oldest power readings beyond a row limit.
+
+        Args:
+            max_rows: Number of most recent readings to keep
 
-    def query_history(self, limit: int | None = 20) -> list[PowerReading]:
+        Returns:
+            Number of rows deleted
+        """
+        with self.db.connection_context():
+            model = self.PowerReadingModel
+            excess = model.select().count() - max_rows
+            if excess <= 0:
+                return 0
+            oldest = model.select(model.id).order_by(model.timestamp.asc(), model.id.asc()).limit(excess)
+            return model.delete().where(model.id.in_(oldest)).execute()
+
+    def query_history(
+        self,
+        limit: int | None = 20,
+        label: str | None = None,
+        since: datetime | None = None,
+        until: datetime | None = None,
+    ) -> list[PowerReading]:
         """Query most recent power readings.
 
         Args:
             limit: Maximum number of readings to return. None = all readings.
+            label: Only return readings tagged with this session label. None = all readings.
+            since: Only return readings at or after this time. None = no lower bound.
+            until: Only return readings at or before this time. None = no upper bound.
 
         Returns:
             List of PowerReading objects, ordered by timestamp DESC
         """
         with self.db.connection_context():
             query = self.PowerReadingModel.select().order_by(self.PowerReadingModel.timestamp.desc())
+            if label is not None:
+                query = query.where(self.PowerReadingModel.label == label)
+            if since is not None:
+                query = query.where(self.PowerReadingModel.timestamp >= since.astimezone(UTC))
+            if until is not None:
+                query = query.where(self.PowerReadingModel.timestamp <= until.astimezone(UTC))
+            if limit is not None:
+                query = query.limit(limit)
+
+            return [self._to_reading(r) for r in query]
+
+    def query_bucketed(
+        self, bucket_seconds: int, limit: int | None = 20, label: str | None = None
+    ) -> list[PowerReading]:
+        """Query readings averaged over fixed time buckets (e.g. 5-minute averages).
+
+        Rows are grouped in SQL by the Unix time of their timestamp divided by the
+        bucket size, so long histories are reduced before they leave SQLite.
+
+        Each bucket becomes one PowerReading: the timestamp is the bucket start (UTC),
+        numeric fields are averages (integer fields rounded), and is_charging /
+        external_connected are true when they were true for at least half of the bucket.
+        Charger names and per-reading details are not aggregated and are None.
+
+        Args:
+            bucket_seconds: Bucket width in seconds (at least 1)
+            limit: Maximum number of buckets to return. None = all buckets.
+            label: Only include readings tagged with this session label. None = all readings.
+
+        Returns:
+            List of averaged PowerReading objects, ordered by bucket start DESC
+
+        Raises:
+            ValueError: If bucket_seconds is less than 1
+        """
+        if bucket_seconds < 1:
+            raise ValueError("bucket_seconds must be at least 1")
+
+        model = self.PowerReadingModel
+        # strftime('%s') normalizes the stored UTC offset; integer division floors to the bucket
+        bucket = fn.strftime("%s", model.timestamp).cast("INTEGER") / bucket_seconds
+
+        def avg(field):
+            # coerce(False): keep the float average instead of converting it with the field's type
+            return fn.AVG(field).coerce(False)
+
+        with self.db.connection_context():
+            query = model.select(
+                bucket.alias("bucket"),
+                avg(model.watts_actual).alias("watts_actual"),
+                avg(model.watts_negotiated).alias("watts_negotiated"),
+                avg(model.voltage).alias("voltage"),
+                avg(model.amperage).alias("amperage"),
+                avg(model.current_capacity).alias("current_capacity"),
+                avg(model.max_capacity).alias("max_capacity"),
+                avg(model.battery_percent).alias("battery_percent"),
+                avg(model.is_charging).alias("is_charging"),
+                avg(model.external_connected).alias("external_connected"),
+                avg(model.design_capacity).alias("design_capacity"),
+                avg(model.battery_temp).alias("battery_temp"),
+                avg(model.system_power).alias("system_power"),
+                avg(model.low_power_mode).alias("low_power_mode"),
+            )
+            if label is not None:
+                query = query.where(model.label == label)
+            query = query.group_by(bucket).order_by(bucket.desc())
             if limit is not None:
                 query = query.limit(limit)
 
             return [
                 PowerReading(
-                    timestamp=r.timestamp if isinstance(r.timestamp, datetime) else datetime.fromisoformat(r.timestamp),
-                    watts_actual=r.watts_actual,
-                    watts_negotiated=r.watts_negotiated,
-                    voltage=r.voltage,
-                    amperage=r.amperage,
-                    current_capacity=r.current_capacity,
-                    max_capacity=r.max_capacity,
-                    battery_percent=r.battery_percent,
-                    is_charging=r.is_charging,
-                    external_connected=r.external_connected,
-                    charger_name=r.charger_name,
-                    charger_manufacturer=r.charger_manufacturer,
+                    timestamp=datetime.fromtimestamp(row["bucket"] * bucket_seconds, tz=UTC),
+                    watts_actual=row["watts_actual"],
+                    watts_negotiated=round(row["watts_negotiated"]),
+                    voltage=row["voltage"],
+                    amperage=row["amperage"],
+                    current_capacity=round(row["current_capacity"]),
+                    max_capacity=round(row["max_capacity"]),
+                    battery_percent=round(row["battery_percent"]),
+                    is_charging=row["is_charging"] >= 0.5,
+                    external_connected=row["external_connected"] >= 0.5,
+                    charger_name=None,
+                    charger_manufacturer=None,
+                    design_capacity=round(row["design_capacity"]) if row["design_capacity"] is not None else None,
+                    battery_temp=row["battery_temp"],
+                    system_power=row["system_power"],
+                    low_power_mode=row["low_power_mode"] >= 0.5 if row["low_power_mode"] is not None else None,
+                    label=label,
+                )
+                for row in query.dicts()
+            ]
+
+    def average_watts(self, since: datetime, until: datetime | None = None) -> float | None:
+        """Average watts_actual over a time range.
+
+        Args:
+            since: Start of the range (inclusive)
+            until: End of the range (inclusive). None = no upper bound.
+
+        Returns:
+            Mean watts_actual of the readings in the range, or None if there are none
+        """
+        model = self.PowerReadingModel
+        with self.db.connection_context():
+            query = model.select(fn.AVG(model.watts_actual).coerce(False)).where(
+                model.timestamp >= since.astimezone(UTC)
+            )
+            if until is not None:
+                query = query.where(model.timestamp <= until.astimezone(UTC))
+            return query.scalar()
+
+    def average_watts_over(self, window: timedelta, reading: PowerReading) -> float:
+        """Average watts_actual over the window ending at a reading (e.g. a 5-minute average draw).
+
+        Args:
+            window: How far back from the reading to average
+            reading: Current reading; its timestamp ends the window
+
+        Returns:
+            Mean watts_actual of the recorded readings in the window, or the reading's own
+            watts_actual if nothing was recorded in it
+        """
+        average = self.average_watts(reading.timestamp - window, reading.timestamp)
+        return reading.watts_actual if average is None else average
+
+    def iter_history(self, limit: int | None = None) -> Iterator[PowerReading]:
+        """Stream most recent power readings without loading them all into memory.
+
+        Rows are fetched lazily with a server-side cursor, so exporting very large
+        histories keeps memory usage flat.
+
+        Args:
+            limit: Maximum number of readings to yield. None = all readings.
+
+        Yields:
+            PowerReading objects, ordered by timestamp DESC
+        """
+        with self.db.connection_context():
+            query = self.PowerReadingModel.select().order_by(self.PowerReadingModel.timestamp.desc())
+            if limit is not None:
+                query = query.limit(limit)
+
+            for r in query.iterator():
+                yield self._to_reading(r)
+
+    @staticmethod
+    def _to_reading(r) -> PowerReading:
+        """Convert a PowerReadingModel row into a PowerReading.
+
+        Args:
+            r: PowerReadingModel instance
+
+        Returns:
+            PowerReading with the row's values
+        """
+        return PowerReading(
+            timestamp=r.timestamp if isinstance(r.timestamp, datetime) else datetime.fromisoformat(r.timestamp),
+            watts_actual=r.watts_actual,
+            watts_negotiated=r.watts_negotiated,
+            voltage=r.voltage,
+            amperage=r.amperage,
+            current_capacity=r.current_capacity,
+            max_capacity=r.max_capacity,
+            battery_percent=r.battery_percent,
+            is_charging=r.is_charging,
+            external_connected=r.external_connected,
+            charger_name=r.charger_name,
+            charger_manufacturer=r.charger_manufacturer,
+            watts_negotiated_stable=r.watts_negotiated_stable,
+            label=r.label,
+            thermal_state=ThermalState(r.thermal_state) if r.thermal_state else None,
+            design_capacity=r.design_capacity,
+            battery_temp=r.battery_temp,
+            system_power=r.system_power,
+            low_power_mode=r.low_power_mode,
+        )
+
+    def insert_burst(self, burst: BurstResult) -> int:
+        """Store all samples of a burst under a new burst ID.
+
+        Args:
+            burst: BurstResult to store
+
+        Returns:
+            Burst ID (increments from 1)
+        """
+        with self.db.connection_context(), self.db.atomic():
+            last_id = self.BurstSampleModel.select(fn.MAX(self.BurstSampleModel.burst_id)).scalar()
+            burst_id = (last_id or 0) + 1
+            rows = [
+                {
+                    "burst_id": burst_id,
+                    "timestamp": sample.timestamp,
+                    "rate_hz": burst.rate_hz,
+                    "power_input": sample.power_input,
+                    "system_power": sample.system_power,
+                }
+                for sample in burst.samples
+            ]
+            if rows:
+                self.BurstSampleModel.insert_many(rows).execute()
+            return burst_id
+
+    def get_burst(self, burst_id: int) -> BurstResult | None:
+        """Load a stored burst.
+
+        Args:
+            burst_id: ID returned by insert_burst()
+
+        Returns:
+            BurstResult with samples in capture order, or None if no such burst
+        """
+        with self.db.connection_context():
+            rows = list(
+                self.BurstSampleModel.select()
+                .where(self.BurstSampleModel.burst_id == burst_id)
+                .order_by(self.BurstSampleModel.timestamp)
+            )
+        if not rows:
+            return None
+
+        samples = [
+            BurstSample(
+                timestamp=r.timestamp if isinstance(r.timestamp, datetime) else datetime.fromisoformat(r.timestamp),
+                power_input=r.power_input,
+                system_power=r.system_power,
+            )
+            for r in rows
+        ]
+        return BurstResult(started_at=samples[0].timestamp, rate_hz=rows[0].rate_hz, samples=samples)
+
+    def insert_charge_session(self, session: ChargeSession) -> int:
+        """Store a finished charge session.
+
+        Args:
+            session: ChargeSession to store
+
+        Returns:
+            ID of inserted row
+        """
+        with self.db.connection_context():
+            return self.ChargeSessionModel.create(
+                started_at=session.started_at,
+                ended_at=session.ended_at,
+                start_percent=session.start_percent,
+                end_percent=session.end_percent,
+                total_wh=session.total_wh,
+            ).id
+
+    def query_charge_sessions(self, limit: int | None = 10) -> list[ChargeSession]:
+        """Get the most recent charge sessions.
+
+        Args:
+            limit: Maximum number of sessions. None = all sessions.
+
+        Returns:
+            Sessions, most recent first
+        """
+        with self.db.connection_context():
+            query = self.ChargeSessionModel.select().order_by(self.ChargeSessionModel.started_at.desc())
+            if limit is not None:
+                query = query.limit(limit)
+            return [
+                ChargeSession(
+                    started_at=_to_datetime(row.started_at),
+                    ended_at=_to_datetime(row.ended_at),
+                    start_percent=row.start_percent,
+                    end_percent=row.end_percent,
+                    total_wh=row.total_wh,
                 )
-                for r in query
+                for row in query
             ]
 
+    def get_summary(self) -> DatabaseSummary:
+        """Aggregate the whole history for comparing databases (`powermonitor compare-db`).
+
+        Only selects columns that exist in the file, so databases written by older
+        versions can be summarized without migrating them (see read_only).
+
+        Returns:
+            DatabaseSummary (fields are None when there is nothing to aggregate)
+        """
+        model = self.PowerReadingModel
+        with self.db.connection_context():
+            columns = {column.name for column in self.db.get_columns("power_readings")}
+            count, earliest, latest, avg_watts, peak_charge = (
+                model.select(
+                    fn.COUNT(model.id),
+                    fn.MIN(model.timestamp),
+                    fn.MAX(model.timestamp),
+                    fn.AVG(model.watts_actual),
+                    fn.MAX(model.watts_actual),
+                )
+                .tuples()
+                .get()
+            )
+            if not count:
+                return DatabaseSummary(database_path=str(self.db_path), count=0)
+
+            avg_draw, lowest = (
+                model.select(fn.AVG(model.watts_actual), fn.MIN(model.watts_actual))
+                .where(~model.external_connected)
+                .tuples()
+                .get()
+            )
+            latest_fields = [model.max_capacity]
+            if "design_capacity" in columns:
+                latest_fields.append(model.design_capacity)
+            latest_row = model.select(*latest_fields).order_by(model.timestamp.desc()).tuples().first()
+            max_capacity = latest_row[0]
+            design_capacity = latest_row[1] if len(latest_row) > 1 else None
+
+        return DatabaseSummary(
+            database_path=str(self.db_path),
+            count=count,
+            earliest=_isoformat(earliest),
+            latest=_isoformat(latest),
+            avg_watts=avg_watts,
+            avg_draw_watts=-avg_draw if avg_draw is not None and avg_draw < 0 else None,
+            peak_draw_watts=-lowest if lowest is not None and lowest < 0 else None,
+            peak_charge_watts=peak_charge if peak_charge > 0 else None,
+            max_capacity=max_capacity,
+            battery_health_percent=max_capacity / design_capacity * 100 if design_capacity else None,
+        )
+
+    def update_charger_peak(self, reading: PowerReading) -> ChargerPeak | None:
+        """Fold a reading into its charger's stored peak negotiated power.
+
+        Kept separate from the readings so the peak outlives pruning and cleanup.
+
+        Args:
+            reading: Reading just collected
+
+        Returns:
+            The charger's updated record, or None if the reading has no named charger
+            negotiating power
+        """
+        name = tracked_charger(reading)
+        if name is None:
+            return None
+        model = self.ChargerPeakModel
+        with self.db.connection_context(), self.db.atomic():
+            row = model.get_or_none(model.name == name)
+            if row is None:
+                peak = ChargerPeak(name=name, peak_watts=reading.watts_negotiated, last_watts=reading.watts_negotiated)
+                model.create(name=name, peak_watts=peak.peak_watts, last_watts=peak.last_watts, low_readings=0)
+                return peak
+            peak = _to_charger_peak(row).observe(reading.watts_negotiated)
+            row.peak_watts, row.last_watts, row.low_readings = peak.peak_watts, peak.last_watts, peak.low_readings
+            row.save()
+            return peak
+
+    def get_charger_peaks(self) -> dict[str, ChargerPeak]:
+        """Get the stored peak record of every charger.
+
+        Returns:
+            Records keyed by charger name
+        """
+        with self.db.connection_context():
+            return {row.name: _to_charger_peak(row) for row in self.ChargerPeakModel.select()}
+
     def get_statistics(self, limit: int | None = 100) -> dict:
         """Calculate statistics from recent readings.
 
@@ -233,27 +780,73 @@ class Database:
                 "count": len(readings),
             }
 
-    def clear_history(self) -> int:
+    def get_charger_stats(self) -> list[ChargerStats]:
+        """Summarize readings per charger (by reported name and manufacturer).
+
+        Returns:
+            One entry per charger, most recently seen first, with its stored peak record
+        """
+        model = self.PowerReadingModel
+        peaks = self.get_charger_peaks()
+        with self.db.connection_context():
+            query = (
+                model.select(
+                    model.charger_name,
+                    model.charger_manufacturer,
+                    fn.COUNT(model.id).alias("readings"),
+                    fn.MAX(model.watts_negotiated).alias("max_watts_negotiated"),
+                    fn.AVG(model.watts_actual).alias("avg_watts_actual"),
+                    fn.MIN(model.timestamp).alias("first_seen"),
+                    fn.MAX(model.timestamp).alias("last_seen"),
+                )
+                .where(model.charger_name.is_null(False) & (model.charger_name != ""))
+                .group_by(model.charger_name, model.charger_manufacturer)
+                .order_by(fn.MAX(model.timestamp).desc())
+            )
+            return [
+                ChargerStats(
+                    name=row.charger_name,
+                    manufacturer=row.charger_manufacturer,
+                    readings=row.readings,
+                    max_watts_negotiated=row.max_watts_negotiated,
+                    avg_watts_actual=row.avg_watts_actual,
+                    first_seen=_isoformat(row.first_seen),
+                    last_seen=_isoformat(row.last_seen),
+                    peak=peaks.get(row.charger_name),
+                )
+                for row in query
+            ]
+
+    def clear_history(self, dry_run: bool = False) -> int:
         """Clear all power readings from database.
 
+        Args:
+            dry_run: Only count the readings that would be deleted
+
         Returns:
-            Number of rows deleted
+            Number of rows deleted (or that would be deleted)
         """
         with self.db.connection_context():
+            if dry_run:
+                return self.PowerReadingModel.select().count()
             return self.PowerReadingModel.delete().execute()
 
-    def cleanup_old_data(self, days: int) -> int:
+    def cleanup_old_data(self, days: int, dry_run: bool = False) -> int:
         """Delete power readings older than specified number of days.
 
         Args:
             days: Number of days - readings older than this will be deleted
+            dry_run: Only count the readings that would be deleted
 
         Returns:
-            Number of rows deleted
+            Number of rows deleted (or that would be deleted)
         """
         with self.db.connection_context():
-            cutoff = datetime.now(UTC) - timedelta(days=days)
-            return self.PowerReadingModel.delete().where(self.PowerReadingModel.timestamp < cutoff).execute()
+            # The same condition selects rows for the count and the delete
+            older = self.PowerReadingModel.timestamp < datetime.now(UTC) - timedelta(days=days)
+            if dry_run:
+                return self.PowerReadingModel.select().where(older).count()
+            return self.PowerReadingModel.delete().where(older).execute()
 
     def get_battery_health_trend(self, days: int = 30) -> list[tuple[str, float, int]]:
         """Get daily average battery health (max_capacity) over specified period.
@@ -292,6 +885,81 @@ class Database:
                 result.append((date_str, row.avg_max_capacity, row.reading_count))
             return result
 
+    def get_daily_capacity(self, days: int | None = None) -> list[tuple[str, float, float | None, int]]:
+        """Get daily average max and design capacity for long-horizon wear analysis.
+
+        Args:
+            days: Only include the last N days. None = entire history.
+
+        Returns:
+            List of tuples: (date, avg_max_capacity, avg_design_capacity, reading_count)
+            Ordered by date ascending. avg_design_capacity is None for days recorded
+            before design capacity was stored.
+        """
+        if days is not None and days <= 0:
+            raise ValueError("days must be a positive integer")
+
+        with self.db.connection_context():
+            day = fn.DATE(self.PowerReadingModel.timestamp)
+            query = self.PowerReadingModel.select(
+                day.alias("date"),
+                fn.AVG(self.PowerReadingModel.max_capacity).alias("avg_max_capacity"),
+                fn.AVG(self.PowerReadingModel.design_capacity).alias("avg_design_capacity"),
+                fn.COUNT(self.PowerReadingModel.id).alias("reading_count"),
+            )
+            if days is not None:
+                cutoff = datetime.now(UTC) - timedelta(days=days)
+                query = query.where(self.PowerReadingModel.timestamp >= cutoff)
+            query = query.group_by(day).order_by(day)
+
+            result = []
+            for row in query:
+                date_str = row.date.strftime("%Y-%m-%d") if isinstance(row.date, datetime) else str(row.date)
+                result.append((date_str, row.avg_max_capacity, row.avg_design_capacity, row.reading_count))
+            return result
+
+
+def _reading_row(reading: PowerReading) -> dict:
+    """Map a reading to power_readings column values."""
+    return {
+        "timestamp": reading.timestamp,
+        "watts_actual": reading.watts_actual,
+        "watts_negotiated": reading.watts_negotiated,
+        "voltage": reading.voltage,
+        "amperage": reading.amperage,
+        "current_capacity": reading.current_capacity,
+        "max_capacity": reading.max_capacity,
+        "battery_percent": reading.battery_percent,
+        "is_charging": reading.is_charging,
+        "external_connected": reading.external_connected,
+        "charger_name": reading.charger_name,
+        "charger_manufacturer": reading.charger_manufacturer,
+        "watts_negotiated_stable": reading.watts_negotiated_stable,
+        "label": reading.label,
+        "thermal_state": reading.thermal_state,
+        "design_capacity": reading.design_capacity,
+        "battery_temp": reading.battery_temp,
+        "system_power": reading.system_power,
+        "low_power_mode": reading.low_power_mode,
+    }
+
+
+def _to_charger_peak(row) -> ChargerPeak:
+    """Convert a charger_peaks row to a ChargerPeak."""
+    return ChargerPeak(
+        name=row.name, peak_watts=row.peak_watts, last_watts=row.last_watts, low_readings=row.low_readings
+    )
+
+
+def _to_datetime(value: datetime | str) -> datetime:
+    """Parse a stored timestamp, which SQLite may hand back as text."""
+    return value if isinstance(value, datetime) else datetime.fromisoformat(value)
+
+
+def _isoformat(value: datetime | str) -> str:
+    """Format an aggregated timestamp, which SQLite may hand back as text."""
+    return _to_datetime(value).isoformat()
+
 
 # Module-level convenience functions
 _db_instances: dict[Path, Database] = {}
@@ -331,17 +999,37 @@ def insert_reading(reading: PowerReading, db_path: Path | str = DB_PATH) -> int:
     return get_database(db_path).insert_reading(reading)
 
 
-def query_history(limit: int | None = 20, db_path: Path | str = DB_PATH) -> list[PowerReading]:
+def query_history(
+    limit: int | None = 20, db_path: Path | str = DB_PATH, label: str | None = None
+) -> list[PowerReading]:
     """Convenience function to query history using default database.
 
     Args:
         limit: Maximum number of readings. None = all readings.
         db_path: Path to database file
+        label: Only return readings with this session label. None = all readings.
 
     Returns:
         List of PowerReading objects
     """
-    return get_database(db_path).query_history(limit)
+    return get_database(db_path).query_history(limit, label=label)
+
+
+def query_bucketed(
+    bucket_seconds: int, limit: int | None = 20, db_path: Path | str = DB_PATH, label: str | None = None
+) -> list[PowerReading]:
+    """Convenience function to query time-bucketed averages using default database.
+
+    Args:
+        bucket_seconds: Bucket width in seconds
+        limit: Maximum number of buckets. None = all buckets.
+        db_path: Path to database file
+        label: Only include readings with this session label. None = all readings.
+
+    Returns:
+        List of averaged PowerReading objects
+    """
+    return get_database(db_path).query_bucketed(bucket_seconds, limit, label=label)
 
 
 def get_statistics(limit: int | None = 100, db_path: Path | str = DB_PATH) -> dict:
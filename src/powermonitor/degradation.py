@@ -0,0 +1,83 @@
+"""Charger degradation: compare each charger's negotiated power with the most it ever negotiated."""
+
+from dataclasses import dataclass
+from dataclasses import replace
+
+from .labels import sanitize_label
+from .models import PowerReading
+
+# A connected reading below this share of the charger's peak counts as low
+DEGRADED_RATIO = 0.8
+# Consecutive low readings before a charger is flagged (one-off renegotiations are normal)
+DEGRADED_READINGS = 5
+
+
+@dataclass(frozen=True, slots=True)
+class ChargerPeak:
+    """Longitudinal negotiated-power record of one charger, keyed by the name it reports.
+
+    A charger that used to negotiate 96W but now settles at 60W usually has a failing
+    cable, port, or power stage. Unlike a single reading, this survives restarts: it is
+    stored in the database and updated with every saved reading.
+
+    Attributes:
+        name: Charger name
+        peak_watts: Highest negotiated power ever seen (W)
+        last_watts: Negotiated power of the latest reading (W)
+        low_readings: Consecutive latest readings below DEGRADED_RATIO of the peak
+    """
+
+    name: str
+    peak_watts: int
+    last_watts: int
+    low_readings: int = 0
+
+    @property
+    def degraded(self) -> bool:
+        """Whether the charger has stayed well below its peak for DEGRADED_READINGS readings."""
+        return self.low_readings >= DEGRADED_READINGS
+
+    def observe(self, watts: int) -> "ChargerPeak":
+        """Record the negotiated power of another reading with this charger.
+
+        Args:
+            watts: Negotiated power (W)
+
+        Returns:
+            Updated record (the peak only ever grows)
+        """
+        if watts < self.peak_watts * DEGRADED_RATIO:
+            return replace(self, last_watts=watts, low_readings=self.low_readings + 1)
+        return replace(self, peak_watts=max(self.peak_watts, watts), last_watts=watts, low_readings=0)
+
+    def warning(self) -> str:
+        """Human-readable degradation warning."""
+        return (
+            f"Charger may be degraded: {sanitize_label(self.name)} negotiates {self.last_watts}W, "
+            f"down from {self.peak_watts}W (check the cable and port)"
+        )
+
+    def to_dict(self) -> dict:
+        """Convert to a JSON-serializable dictionary."""
+        return {
+            "name": self.name,
+            "peak_watts": self.peak_watts,
+            "last_watts": self.last_watts,
+            "low_readings": self.low_readings,
+            "degraded": self.degraded,
+        }
+
+
+def tracked_charger(reading: PowerReading) -> str | None:
+    """Name of the charger a reading counts towards, or None if it carries no usable power figure.
+
+    Args:
+        reading: Reading to check
+
+    Returns:
+        Charger name when plugged in to a named charger that negotiated power
+    """
+    if not reading.external_connected or reading.watts_negotiated <= 0:
+        return None
+    name = (reading.charger_name or "").strip()
+    return name or None
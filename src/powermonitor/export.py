@@ -0,0 +1,164 @@
+"""Write readings to CSV, JSON, or NDJSON files (`powermonitor export` and the TUI's session export)."""
+
+import csv
+import json
+from collections.abc import Iterable
+from pathlib import Path
+from typing import TextIO
+
+from .models import PowerReading
+
+EXPORT_FORMATS = ("csv", "json", "ndjson")
+
+# Format implied by each file extension; .tsv is CSV with tab-separated fields
+EXTENSION_FORMATS = {".csv": "csv", ".tsv": "csv", ".json": "json", ".ndjson": "ndjson", ".jsonl": "ndjson"}
+
+# CSV header, in column order (csv_import reads the same columns back)
+CSV_COLUMNS = (
+    "timestamp",
+    "watts_actual",
+    "watts_negotiated",
+    "voltage",
+    "amperage",
+    "current_capacity",
+    "max_capacity",
+    "battery_percent",
+    "is_charging",
+    "external_connected",
+    "charger_name",
+    "charger_manufacturer",
+    "watts_negotiated_stable",
+    "label",
+    "thermal_state",
+    "design_capacity",
+    "battery_temp",
+    "system_power",
+    "low_power_mode",
+)
+
+
+def export_readings(
+    output_path: Path, readings: Iterable[PowerReading], format_type: str, delimiter: str = ",", header: bool = True
+) -> int:
+    """Write readings to a file in one of EXPORT_FORMATS.
+
+    Rows are streamed to the file, so very large histories aren't held in memory.
+
+    Args:
+        output_path: File to write
+        readings: Readings to export
+        format_type: "csv", "json", or "ndjson"
+        delimiter: CSV only: single-character field separator
+        header: CSV only: whether to write the column name row
+
+    Returns:
+        Number of readings written
+
+    Raises:
+        ValueError: If the format is unknown
+        OSError: If the file can't be written
+    """
+    if format_type == "csv":
+        return export_csv(output_path, readings, delimiter=delimiter, header=header)
+    if format_type == "json":
+        return export_json(output_path, readings)
+    if format_type == "ndjson":
+        return export_ndjson(output_path, readings)
+    raise ValueError(f"Invalid format '{format_type}'. Must be 'csv', 'json', or 'ndjson'")
+
+
+def export_csv(output_path: Path, readings: Iterable[PowerReading], delimiter: str = ",", header: bool = True) -> int:
+    """Export readings to CSV file.
+
+    Args:
+        output_path: File to write
+        readings: Readings to export
+        delimiter: Single-character field separator
+        header: Whether to write the column name row
+
+    Returns:
+        Number of readings written
+    """
+    count = 0
+    with open(output_path, "w", newline="") as f:
+        writer = csv.writer(f, delimiter=delimiter)
+
+        # Header
+        if header:
+            writer.writerow(CSV_COLUMNS)
+
+        # Data
+        for r in readings:
+            count += 1
+            writer.writerow(
+                [
+                    r.timestamp.isoformat(),
+                    r.watts_actual,
+                    r.watts_negotiated,
+                    r.voltage,
+                    r.amperage,
+                    r.current_capacity,
+                    r.max_capacity,
+                    r.battery_percent,
+                    r.is_charging,
+                    r.external_connected,
+                    r.charger_name or "",
+                    r.charger_manufacturer or "",
+                    r.watts_negotiated_stable if r.watts_negotiated_stable is not None else "",
+                    r.label or "",
+                    r.thermal_state or "",
+                    r.design_capacity if r.design_capacity is not None else "",
+                    r.battery_temp if r.battery_temp is not None else "",
+                    r.system_power if r.system_power is not None else "",
+                    r.low_power_mode if r.low_power_mode is not None else "",
+                ]
+            )
+
+    return count
+
+
+def export_json(output_path: Path, readings: Iterable[PowerReading]) -> int:
+    """Export readings to JSON file.
+
+    Returns:
+        Number of readings written
+    """
+    with open(output_path, "w") as f:
+        return write_json_array(f, readings)
+
+
+def write_json_array(sink: TextIO, readings: Iterable[PowerReading]) -> int:
+    """Stream readings to a sink as a JSON array, one element at a time.
+
+    Unlike json.dump() on a list, only one reading is serialized at a time, so
+    exporting very large histories does not build the whole document in memory.
+
+    Args:
+        sink: Writable text stream
+        readings: Readings to serialize (consumed lazily)
+
+    Returns:
+        Number of readings written
+    """
+    count = 0
+    sink.write("[")
+    for reading in readings:
+        sink.write(",\n  " if count else "\n  ")
+        sink.write(json.dumps(reading.to_dict()))
+        count += 1
+    sink.write("\n]\n" if count else "]\n")
+    return count
+
+
+def export_ndjson(output_path: Path, readings: Iterable[PowerReading]) -> int:
+    """Export readings to NDJSON file (one JSON object per line).
+
+    Returns:
+        Number of readings written
+    """
+    count = 0
+    with open(output_path, "w") as f:
+        for reading in readings:
+            f.write(json.dumps(reading.to_dict()) + "\n")
+            count += 1
+    return count
@@ -0,0 +1,72 @@
+"""Status glyphs for human output: emoji by default, ASCII stand-ins for --ascii."""
+
+from dataclasses import dataclass
+
+from .models import PowerDirection
+
+
+@dataclass(frozen=True, slots=True)
+class Glyphs:
+    """Icons and marks used in tables, status lines, and the TUI.
+
+    Attributes:
+        charging: Charging from the adapter
+        ac: On AC, neither charging nor discharging
+        ac_discharging: On AC but draining the battery
+        battery: Running on battery (also "fully charged")
+        warning: Warning prefix
+        ok: Check passed / operation succeeded
+        error: Check failed
+        arrow: From-to separator (e.g. "30% → 80%")
+        separator: Separator between items on one line
+        paused: Collection is paused (TUI header)
+    """
+
+    charging: str
+    ac: str
+    ac_discharging: str
+    battery: str
+    warning: str
+    ok: str
+    error: str
+    arrow: str
+    separator: str
+    paused: str
+
+    def icon(self, direction: PowerDirection) -> str:
+        """Icon for a power flow direction."""
+        return {
+            PowerDirection.CHARGING: self.charging,
+            PowerDirection.AC_IDLE: self.ac,
+            PowerDirection.AC_DISCHARGING: self.ac_discharging,
+            PowerDirection.DISCHARGING: self.battery,
+        }[direction]
+
+
+EMOJI = Glyphs(
+    charging="⚡",
+    ac="🔌",
+    ac_discharging="🔌⚠",
+    battery="🔋",
+    warning="⚠",
+    ok="✓",
+    error="✗",
+    arrow="→",
+    separator="·",
+    paused="⏸",
+)
+
+# Pure ASCII: safe for log aggregators and terminals without emoji, and one column per character.
+# Bracketed words start with a capital so Rich doesn't read them as markup tags.
+ASCII = Glyphs(
+    charging="[CHG]",
+    ac="[AC]",
+    ac_discharging="[AC!]",
+    battery="[BAT]",
+    warning="[!]",
+    ok="[OK]",
+    error="[X]",
+    arrow="->",
+    separator="|",
+    paused="[PAUSED]",
+)
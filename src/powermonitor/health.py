@@ -0,0 +1,216 @@
+"""Long-horizon battery wear analysis over recorded history (`health-trend`, `capacity-trend`)."""
+
+from dataclasses import dataclass
+from datetime import date
+from datetime import timedelta
+
+PERIODS = ("day", "week", "month")
+
+# Apple considers a battery consumed (service recommended) below this share of design capacity
+REPLACEMENT_HEALTH_PERCENT = 80.0
+DAYS_PER_MONTH = 365.25 / 12
+# Projections further out than this are reported as none: the fitted decline is effectively flat
+MAX_PROJECTION_DAYS = 50 * 365
+
+
+@dataclass(slots=True)
+class HealthPoint:
+    """Battery health for a single day.
+
+    Attributes:
+        day: Calendar day
+        health_percent: Max capacity as a percentage of design capacity
+        interpolated: True if no readings were recorded that day and the value was interpolated
+    """
+
+    day: date
+    health_percent: float
+    interpolated: bool = False
+
+
+@dataclass(slots=True)
+class HealthBucket:
+    """Average battery health over a week or month.
+
+    Attributes:
+        label: Period label (e.g., "2026-01-05", "2026-W02", "2026-01")
+        start: First day in the period
+        health_percent: Mean daily health over the period
+        measured_days: Days in the period with actual readings
+        total_days: Days in the period covered by the trend
+    """
+
+    label: str
+    start: date
+    health_percent: float
+    measured_days: int
+    total_days: int
+
+
+def daily_health(rows: list[tuple[str, float, float | None, int]]) -> list[HealthPoint]:
+    """Convert daily capacity averages into a gap-free series of health percentages.
+
+    Health is max capacity relative to design capacity. When design capacity was never
+    recorded (older readings), the first day's max capacity is used as the 100% baseline.
+    Days without readings between two measured days are linearly interpolated.
+
+    Args:
+        rows: (date "YYYY-MM-DD", avg_max_capacity, avg_design_capacity, reading_count),
+            ordered by date ascending (see Database.get_daily_capacity)
+
+    Returns:
+        One HealthPoint per day from the first to the last measured day
+    """
+    measured: list[HealthPoint] = []
+    baseline = next((design for _, _, design, _ in rows if design), None) or (rows[0][1] if rows else None)
+    for day_str, avg_max, avg_design, _count in rows:
+        reference = avg_design or baseline
+        if not reference:
+            continue
+        measured.append(HealthPoint(date.fromisoformat(day_str), avg_max / reference * 100))
+
+    points: list[HealthPoint] = []
+    for previous, current in zip(measured, measured[1:], strict=False):
+        points.append(previous)
+        gap_days = (current.day - previous.day).days
+        for offset in range(1, gap_days):
+            fraction = offset / gap_days
+            value = previous.health_percent + (current.health_percent - previous.health_percent) * fraction
+            points.append(HealthPoint(previous.day + timedelta(days=offset), value, interpolated=True))
+    if measured:
+        points.append(measured[-1])
+    return points
+
+
+def _period_start(day: date, period: str) -> tuple[date, str]:
+    """Get the first day and display label of the period containing day."""
+    if period == "week":
+        start = day - timedelta(days=day.weekday())
+        year, week, _ = start.isocalendar()
+        return start, f"{year}-W{week:02d}"
+    if period == "month":
+        start = day.replace(day=1)
+        return start, start.strftime("%Y-%m")
+    return day, day.isoformat()
+
+
+def bucket_health(points: list[HealthPoint], period: str = "week") -> list[HealthBucket]:
+    """Average daily health points per day, ISO week, or calendar month.
+
+    Args:
+        points: Gap-free daily series from daily_health()
+        period: One of PERIODS
+
+    Returns:
+        Buckets ordered by start date
+
+    Raises:
+        ValueError: If period is not one of PERIODS
+    """
+    if period not in PERIODS:
+        raise ValueError(f"period must be one of {', '.join(PERIODS)}, got {period!r}")
+
+    buckets: dict[date, tuple[str, list[HealthPoint]]] = {}
+    for point in points:
+        start, label = _period_start(point.day, period)
+        buckets.setdefault(start, (label, []))[1].append(point)
+
+    return [
+        HealthBucket(
+            label=label,
+            start=start,
+            health_percent=sum(p.health_percent for p in members) / len(members),
+            measured_days=sum(1 for p in members if not p.interpolated),
+            total_days=len(members),
+        )
+        for start, (label, members) in sorted(buckets.items())
+    ]
+
+
+@dataclass(slots=True)
+class CapacityTrend:
+    """Linear fit of full-charge capacity over time, projected to the replacement threshold.
+
+    Attributes:
+        first_day: First day with readings
+        last_day: Last day with readings
+        measured_days: Days with readings (the points of the fit)
+        current_capacity: Average max capacity on the last day (mAh)
+        design_capacity: Most recently recorded design capacity (mAh, None if never recorded)
+        decline_per_month: Fitted change in max capacity per month (mAh, negative while wearing;
+            None with fewer than two measured days)
+        threshold_capacity: REPLACEMENT_HEALTH_PERCENT of design capacity (mAh, None without design capacity)
+        projected_date: When the fitted line reaches threshold_capacity (None if it isn't declining,
+            design capacity is unknown, capacity is already below the threshold, or the date is
+            more than MAX_PROJECTION_DAYS away)
+    """
+
+    first_day: date
+    last_day: date
+    measured_days: int
+    current_capacity: float
+    design_capacity: float | None
+    decline_per_month: float | None
+    threshold_capacity: float | None
+    projected_date: date | None
+
+    @property
+    def health_percent(self) -> float | None:
+        """Current capacity as a percentage of design capacity."""
+        return self.current_capacity / self.design_capacity * 100 if self.design_capacity else None
+
+    @property
+    def below_threshold(self) -> bool:
+        """Whether capacity is already below the replacement threshold."""
+        return self.threshold_capacity is not None and self.current_capacity < self.threshold_capacity
+
+
+def capacity_trend(
+    rows: list[tuple[str, float, float | None, int]], threshold_percent: float = REPLACEMENT_HEALTH_PERCENT
+) -> CapacityTrend | None:
+    """Fit a least-squares line through daily max capacity and project when it crosses a threshold.
+
+    Each measured day counts once, however many readings it has, so days with long
+    sessions don't outweigh the rest. Days without readings are skipped, not interpolated.
+
+    Args:
+        rows: (date "YYYY-MM-DD", avg_max_capacity, avg_design_capacity, reading_count),
+            ordered by date ascending (see Database.get_daily_capacity)
+        threshold_percent: Share of design capacity to project to
+
+    Returns:
+        CapacityTrend, or None if there are no rows
+    """
+    if not rows:
+        return None
+
+    days = [date.fromisoformat(day_str) for day_str, _, _, _ in rows]
+    capacities = [avg_max for _, avg_max, _, _ in rows]
+    design = next((avg_design for _, _, avg_design, _ in reversed(rows) if avg_design), None)
+    threshold = design * threshold_percent / 100 if design else None
+
+    slope = None  # mAh per day
+    projected = None
+    if len(rows) >= 2:
+        xs = [(day - days[0]).days for day in days]
+        mean_x = sum(xs) / len(xs)
+        mean_y = sum(capacities) / len(capacities)
+        slope = sum((x - mean_x) * (y - mean_y) for x, y in zip(xs, capacities, strict=True)) / sum(
+            (x - mean_x) ** 2 for x in xs
+        )
+        fitted_last = mean_y + slope * (xs[-1] - mean_x)
+        if threshold is not None and slope < 0 and capacities[-1] >= threshold:
+            days_left = max((threshold - fitted_last) / slope, 0.0)
+            if days_left <= MAX_PROJECTION_DAYS:
+                projected = days[-1] + timedelta(days=round(days_left))
+
+    return CapacityTrend(
+        first_day=days[0],
+        last_day=days[-1],
+        measured_days=len(rows),
+        current_capacity=capacities[-1],
+        design_capacity=design,
+        decline_per_month=slope * DAYS_PER_MONTH if slope is not None else None,
+        threshold_capacity=threshold,
+        projected_date=projected,
+    )
@@ -0,0 +1,217 @@
+"""Run user commands when power events happen (e.g. `powermonitor --on-unplug 'pmset sleepnow'`)."""
+
+import os
+import subprocess
+import time
+from collections.abc import Callable
+
+from loguru import logger
+
+from .models import PowerReading
+from .schedule import parse_interval
+
+# Events that can trigger a command
+EVENTS = ("plug", "unplug", "low", "full", "hot")
+
+DEFAULT_DEBOUNCE_SECONDS = 30.0  # A flapping connector shouldn't spawn a command per reading
+
+# Sustained conditions fire on every reading while they hold, so their commands get a longer
+# default rate budget than the edge-triggered events' debounce
+DEFAULT_BUDGETS = {"hot": 600.0}
+
+
+def parse_budgets(spec: str) -> dict[str, float]:
+    """Parse per-event rate budgets such as "hot=10m, low=1h".
+
+    Args:
+        spec: Comma-separated `event=interval` entries (interval as in schedule.parse_interval)
+
+    Returns:
+        Minimum seconds between two runs of each listed event's command
+
+    Raises:
+        ValueError: If an entry is malformed or names an unknown event
+    """
+    budgets = {}
+    for entry in filter(None, (part.strip() for part in spec.split(","))):
+        event, sep, interval = entry.partition("=")
+        event = event.strip()
+        if not sep:
+            raise ValueError(f"Invalid hook budget {entry!r}; expected event=interval, e.g. hot=10m")
+        if event not in EVENTS:
+            raise ValueError(f"Unknown event {event!r} in hook budget; valid events: {', '.join(EVENTS)}")
+        budgets[event] = parse_interval(interval)
+    return budgets
+
+
+def detect_events(
+    previous: PowerReading, current: PowerReading, low_battery: int, overheat_temp: float | None = None
+) -> list[str]:
+    """Find the events triggered by moving from one reading to the next.
+
+    "hot" is a sustained condition: it is reported for every reading at or above
+    overheat_temp, and EventHooks rate-limits it. The other events are transitions.
+
+    Args:
+        previous: Earlier reading
+        current: Latest reading
+        low_battery: Battery percentage at or below which "low" fires
+        overheat_temp: Battery temperature (°C) at or above which "hot" fires (None = never)
+
+    Returns:
+        Names from EVENTS, in EVENTS order
+    """
+    events = []
+    if current.external_connected and not previous.external_connected:
+        events.append("plug")
+    if previous.external_connected and not current.external_connected:
+        events.append("unplug")
+    if (
+        not current.external_connected
+        and current.battery_percent <= low_battery
+        and (previous.battery_percent > low_battery or previous.external_connected)
+    ):
+        events.append("low")
+    if current.fully_charged and not previous.fully_charged:
+        events.append("full")
+    if overheat_temp is not None and current.battery_temp is not None and current.battery_temp >= overheat_temp:
+        events.append("hot")
+    return events
+
+
+def hook_environment(event: str, reading: PowerReading) -> dict[str, str]:
+    """Build the environment variables describing an event for its command.
+
+    Args:
+        event: Event name
+        reading: Reading that triggered the event
+
+    Returns:
+        POWERMONITOR_* variables (merged over the current environment when spawning)
+    """
+    return {
+        "POWERMONITOR_EVENT": event,
+        "POWERMONITOR_WATTS": f"{reading.watts_actual:.2f}",
+        "POWERMONITOR_PERCENT": str(reading.battery_percent),
+        "POWERMONITOR_CHARGING": "1" if reading.is_charging else "0",
+        "POWERMONITOR_PLUGGED": "1" if reading.external_connected else "0",
+        "POWERMONITOR_VOLTAGE": f"{reading.voltage:.3f}",
+        "POWERMONITOR_TEMP": f"{reading.battery_temp:.1f}" if reading.battery_temp is not None else "",
+        "POWERMONITOR_TIMESTAMP": reading.timestamp.isoformat(),
+    }
+
+
+class EventHooks:
+    """Spawn a shell command when a configured event fires.
+
+    Commands run detached (own session, no inherited stdio), so a slow command never
+    blocks collection. Each event has its own rate budget: its command runs at most once
+    per budget window, and occurrences inside the window are suppressed. The budget is
+    debounce_seconds unless overridden per event (sustained events default to DEFAULT_BUDGETS).
+
+    Usage:
+        hooks = EventHooks({"unplug": "say unplugged"}, low_battery=20)
+        for reading in readings:
+            hooks.observe(reading)
+    """
+
+    def __init__(
+        self,
+        commands: dict[str, str],
+        low_battery: int = 20,
+        debounce_seconds: float = DEFAULT_DEBOUNCE_SECONDS,
+        overheat_temp: float | None = None,
+        budgets: dict[str, float] | None = None,
+        clock: Callable[[], float] = time.monotonic,
+        spawn: Callable[..., object] = subprocess.Popen,
+    ):
+        """Initialize event hooks.
+
+        Args:
+            commands: Shell command per event name (see EVENTS)
+            low_battery: Battery percentage at or below which "low" fires
+            debounce_seconds: Minimum time between two runs of the same event's command
+            overheat_temp: Battery temperature (°C) at or above which "hot" fires
+            budgets: Per-event minimum seconds between runs, overriding debounce_seconds
+                and DEFAULT_BUDGETS (see parse_budgets)
+            clock: Monotonic clock (injectable for tests)
+            spawn: Process launcher with the subprocess.Popen signature (injectable for tests)
+
+        Raises:
+            ValueError: If an event name is unknown or debounce_seconds or a budget is negative
+        """
+        budgets = budgets or {}
+        unknown = (set(commands) | set(budgets)) - set(EVENTS)
+        if unknown:
+            raise ValueError(f"Unknown event(s): {', '.join(sorted(unknown))}; valid events: {', '.join(EVENTS)}")
+        if debounce_seconds < 0:
+            raise ValueError(f"debounce_seconds must be non-negative, got {debounce_seconds}")
+        for event, budget in budgets.items():
+            if budget < 0:
+                raise ValueError(f"Budget for '{event}' must be non-negative, got {budget}")
+        self.commands = dict(commands)
+        self.low_battery = low_battery
+        self.debounce_seconds = debounce_seconds
+        self.overheat_temp = overheat_temp
+        self.budgets = {event: budgets.get(event, DEFAULT_BUDGETS.get(event, debounce_seconds)) for event in EVENTS}
+        self._clock = clock
+        self._spawn = spawn
+        self._previous: PowerReading | None = None
+        self._last_fired: dict[str, float] = {}
+        self._suppressed: dict[str, int] = {}
+
+    def observe(self, reading: PowerReading) -> list[str]:
+        """Check a new reading for events and run their commands.
+
+        The first reading only sets the baseline; events are transitions between readings.
+
+        Args:
+            reading: Latest reading
+
+        Returns:
+            Events whose commands were started
+        """
+        previous, self._previous = self._previous, reading
+        if previous is None:
+            return []
+
+        fired = []
+        for event in detect_events(previous, reading, self.low_battery, self.overheat_temp):
+            command = self.commands.get(event)
+            if command is None:
+                continue
+            now = self._clock()
+            last = self._last_fired.get(event)
+            if last is not None and now - last < self.budgets[event]:
+                self._suppressed[event] = self._suppressed.get(event, 0) + 1
+                logger.debug(f"Skipping '{event}' hook (rate budget {self.budgets[event]:g}s)")
+                continue
+            if self._run(command, event, reading):
+                suppressed = self._suppressed.pop(event, 0)
+                if suppressed:
+                    logger.info(f"'{event}' occurred {suppressed} more time(s) since its hook last ran")
+                self._last_fired[event] = now
+                fired.append(event)
+        return fired
+
+    def _run(self, command: str, event: str, reading: PowerReading) -> bool:
+        """Start a command detached from powermonitor.
+
+        Returns:
+            True if the process was started
+        """
+        try:
+            self._spawn(
+                command,
+                shell=True,
+                env={**os.environ, **hook_environment(event, reading)},
+                stdin=subprocess.DEVNULL,
+                stdout=subprocess.DEVNULL,
+                stderr=subprocess.DEVNULL,
+                start_new_session=True,
+            )
+        except OSError as e:
+            logger.warning(f"Failed to run '{event}' hook {command!r}: {e}")
+            return False
+        logger.info(f"Ran '{event}' hook: {command}")
+        return True
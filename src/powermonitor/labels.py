@@ -0,0 +1,26 @@
+"""Make device-reported text (charger names, manufacturers, session labels) safe to show on one line."""
+
+import re
+
+# C0/C1 control characters (newlines, tabs, escape sequences) and Unicode line/paragraph separators
+_CONTROL_RE = re.compile(r"[\x00-\x1f\x7f-\x9f\u2028\u2029]")
+_SPACES_RE = re.compile(r"\s{2,}")
+
+
+def sanitize_label(text: str | None) -> str:
+    """Flatten text for single-line output such as table cells, tab-separated fields, and notifications.
+
+    Control characters (including newlines and tabs) become spaces, runs of whitespace
+    collapse to one space, and the ends are trimmed. Quotes, commas, markup brackets,
+    and other printable Unicode are kept: escaping those is up to each output format
+    (csv quoting, json.dumps, rich.markup.escape, html.escape).
+
+    Args:
+        text: Text as reported by the device or entered by the user (None counts as empty)
+
+    Returns:
+        Single-line text, possibly empty
+    """
+    if not text:
+        return ""
+    return _SPACES_RE.sub(" ", _CONTROL_RE.sub(" ", text)).strip()
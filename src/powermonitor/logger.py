@@ -6,16 +6,17 @@ from pathlib import Path
 from loguru import logger
 
 
-def setup_logger(level: str = "INFO", log_to_file: bool = True, enqueue: bool = True) -> None:
+def setup_logger(level: str = "INFO", log_to_file: bool = True, enqueue: bool = True, quiet: bool = False) -> None:
     """Configure loguru logger for powermonitor.
 
     Args:
         level: Logging level (DEBUG, INFO, WARNING, ERROR)
         log_to_file: Whether to log to file in ~/.powermonitor/
         enqueue: Whether to enqueue log messages (async, thread-safe). Set to False for synchronous logging in tests.
+        quiet: Only print errors to the console (warnings still reach the log file)
 
     Features:
-        - Console output for WARNING and above
+        - Console output for WARNING and above (ERROR and above when quiet)
         - File logging with rotation (10 MB max, 7 days retention)
         - Automatic log directory creation
     """
@@ -25,7 +26,7 @@ def setup_logger(level: str = "INFO", log_to_file: bool = True, enqueue: bool =
     # Console handler (only warnings and errors)
     logger.add(
         sys.stderr,
-        level="WARNING",
+        level="ERROR" if quiet else "WARNING",
         format="<level>{level}</level>: {message}",
         colorize=True,
     )
@@ -1,7 +1,57 @@
 """Data models for powermonitor."""
 
 from dataclasses import dataclass
+from dataclasses import fields
+from datetime import date
 from datetime import datetime
+from datetime import timedelta
+from enum import StrEnum
+
+from .calibration import is_calibration_suspect
+
+# Net battery drain (W) on AC before a reading counts as discharging rather than idle;
+# keeps small transition noise in watts_actual from flapping the direction
+AC_DRAIN_THRESHOLD = 1.0
+
+# Charging counts as throttled when the current is below this share of max_charge_current...
+THROTTLED_RATIO = 0.5
+# ...and the battery is below this percent (above it, current tapers off by design)
+THROTTLE_MAX_PERCENT = 80
+
+
+class PowerDirection(StrEnum):
+    """Which way power is flowing through the battery."""
+
+    CHARGING = "charging"  # External power connected and battery charging
+    AC_IDLE = "ac_idle"  # External power connected but battery not charging
+    AC_DISCHARGING = "ac_discharging"  # External power connected but load exceeds the adapter
+    DISCHARGING = "discharging"  # Running on battery
+
+    @property
+    def icon(self) -> str:
+        """Glyph used for this direction in compact displays."""
+        return {
+            PowerDirection.CHARGING: "⚡",
+            PowerDirection.AC_IDLE: "🔌",
+            PowerDirection.AC_DISCHARGING: "🔌⚠",
+            PowerDirection.DISCHARGING: "🔋",
+        }[self]
+
+
+class ThermalState(StrEnum):
+    """System thermal pressure level (mirrors NSProcessInfo.thermalState)."""
+
+    NOMINAL = "nominal"
+    FAIR = "fair"
+    SERIOUS = "serious"
+    CRITICAL = "critical"
+
+
+class AdapterSource(StrEnum):
+    """Where a reading's adapter information came from."""
+
+    IOREG = "ioreg"  # The adapter's own descriptor (AdapterDetails)
+    SMC = "smc"  # Inferred from SMC input power because ioreg had no adapter details
 
 
 @dataclass
@@ -30,6 +80,190 @@ class PowerReading:
     charger_name: str | None  # Charger/adapter name
     charger_manufacturer: str | None  # Charger manufacturer
 
+    # Battery flags reported by ioreg (None when the collector doesn't provide them)
+    battery_installed: bool | None = None  # Is a battery present
+    fully_charged: bool | None = None  # Battery reports charge complete
+
+    # Battery design
+    design_capacity: int | None = None  # Factory design capacity (mAh)
+
+    # System state
+    thermal_state: ThermalState | None = None  # Thermal pressure level (None if unknown)
+    low_power_mode: bool | None = None  # macOS Low Power Mode is on (None if unknown)
+    battery_temp: float | None = None  # Battery temperature (°C)
+    system_power: float | None = None  # Whole-machine power draw from SMC PSTR (W); None without IOKit
+
+    # GPU power from powermetrics (not stored; None unless enabled and running as root on Apple Silicon)
+    gpu_power: float | None = None  # W
+
+    # Hottest SMC temperature sensor (not stored; None without IOKit or when no sensor reads)
+    max_temp: float | None = None  # °C
+    max_temp_sensor: str | None = None  # SMC key, e.g. "TC0P" (see collector.iokit.collector.SMC_TEMPERATURE_SENSORS)
+
+    # Share of adapter input (SMC PDTR) reaching the battery (SMC PPBR) while charging
+    # (not stored; None without IOKit, when not charging, or outside 0-100%)
+    charge_efficiency: float | None = None  # %
+
+    # Where the adapter info came from (not stored; None when no adapter was found)
+    adapter_source: AdapterSource | None = None
+
+    # Why a connected charger isn't charging (not stored; e.g. "Optimized Battery Charging")
+    charge_inhibit_reason: str | None = None
+
+    # Whether the charger looks certified (not stored; None when unknown or unplugged)
+    charger_certified: bool | None = None
+
+    # Most current the battery accepts while charging, from ioreg MaxChargeCurrent or ChargerData.ChargingCurrent
+    # (not stored; None when the battery doesn't report it)
+    max_charge_current: float | None = None  # Current (A)
+
+    # Instantaneous current from ioreg InstantAmperage (not stored; amperage is the averaged value)
+    instant_amperage: float | None = None  # Current (A)
+
+    # Raw ioreg amperage when the SMC battery current replaced it (not stored; for comparison)
+    amperage_ioreg: float | None = None  # Current reported by ioreg (A)
+
+    # Charger identity (not stored; shown in verbose mode)
+    charger_serial: str | None = None  # Charger serial number
+    charger_firmware: str | None = None  # Charger firmware version
+
+    # Battery identity (not stored; shown in verbose mode)
+    battery_serial: str | None = None  # Battery serial number
+    battery_manufacture_date: date | None = None  # Manufacture date (None if absent or not decodable)
+
+    # USB-C port supplying power (not stored; None when unplugged or not reported)
+    charger_port: str | None = None
+
+    # Derived
+    watts_negotiated_stable: int | None = None  # Negotiated power with PD renegotiation dips smoothed out (W)
+
+    # Annotation
+    label: str | None = None  # User-supplied session label (e.g., "video render")
+
+    @property
+    def direction(self) -> PowerDirection:
+        """Power direction reconciled from the status flags.
+
+        The connected/charging flags come straight from the battery and decide the
+        direction; collectors disagree on the sign of watts_actual during transitions.
+        The sign is only used to tell a plugged-in battery that is draining (load
+        exceeds the adapter) from one that is idle, and only past AC_DRAIN_THRESHOLD.
+        """
+        if not self.external_connected:
+            return PowerDirection.DISCHARGING
+        if self.is_charging:
+            return PowerDirection.CHARGING
+        if self.watts_actual <= -AC_DRAIN_THRESHOLD:
+            return PowerDirection.AC_DISCHARGING
+        return PowerDirection.AC_IDLE
+
+    @property
+    def calibration_suspect(self) -> bool:
+        """Whether battery_percent looks miscalibrated for the pack voltage (see calibration.py)."""
+        return is_calibration_suspect(self.voltage, self.amperage, self.battery_percent)
+
+    @property
+    def charging_throttled(self) -> bool:
+        """Whether charging runs well below the current the battery accepts (thermal or optimized charging).
+
+        Explains slow charging that isn't the adapter's fault. Only judged while charging
+        below THROTTLE_MAX_PERCENT, and False when max_charge_current is unknown.
+        """
+        if self.max_charge_current is None or self.direction is not PowerDirection.CHARGING:
+            return False
+        if self.battery_percent >= THROTTLE_MAX_PERCENT:
+            return False
+        return self.amperage < self.max_charge_current * THROTTLED_RATIO
+
+    @property
+    def display_watts_negotiated(self) -> int:
+        """Negotiated power to show to users, preferring the stabilized value."""
+        if self.watts_negotiated_stable is not None:
+            return self.watts_negotiated_stable
+        return self.watts_negotiated
+
+    def to_dict(self) -> dict:
+        """Convert reading to a JSON-serializable dictionary.
+
+        Returns:
+            Dictionary with all fields; timestamp as ISO 8601 string
+        """
+        return {
+            "timestamp": self.timestamp.isoformat(),
+            "watts_actual": self.watts_actual,
+            "watts_negotiated": self.watts_negotiated,
+            "voltage": self.voltage,
+            "amperage": self.amperage,
+            "current_capacity": self.current_capacity,
+            "max_capacity": self.max_capacity,
+            "battery_percent": self.battery_percent,
+            "is_charging": self.is_charging,
+            "external_connected": self.external_connected,
+            "charger_name": self.charger_name,
+            "charger_manufacturer": self.charger_manufacturer,
+            "watts_negotiated_stable": self.watts_negotiated_stable,
+            "label": self.label,
+            "thermal_state": self.thermal_state,
+            "design_capacity": self.design_capacity,
+            "battery_temp": self.battery_temp,
+            "system_power": self.system_power,
+            "low_power_mode": self.low_power_mode,
+        }
+
+    @classmethod
+    def from_dict(cls, data: dict) -> "PowerReading":
+        """Rebuild a reading from the output of to_dict().
+
+        Args:
+            data: Dictionary with at least the required PowerReading fields
+
+        Returns:
+            PowerReading; optional fields missing from data are left at their defaults
+
+        Raises:
+            KeyError: If a required field is missing
+            ValueError: If the timestamp is not ISO 8601
+        """
+        return cls(
+            timestamp=datetime.fromisoformat(data["timestamp"]),
+            watts_actual=data["watts_actual"],
+            watts_negotiated=data["watts_negotiated"],
+            voltage=data["voltage"],
+            amperage=data["amperage"],
+            current_capacity=data["current_capacity"],
+            max_capacity=data["max_capacity"],
+            battery_percent=data["battery_percent"],
+            is_charging=data["is_charging"],
+            external_connected=data["external_connected"],
+            charger_name=data.get("charger_name"),
+            charger_manufacturer=data.get("charger_manufacturer"),
+            watts_negotiated_stable=data.get("watts_negotiated_stable"),
+            label=data.get("label"),
+            thermal_state=ThermalState(data["thermal_state"]) if data.get("thermal_state") else None,
+            design_capacity=data.get("design_capacity"),
+            battery_temp=data.get("battery_temp"),
+            system_power=data.get("system_power"),
+            low_power_mode=data.get("low_power_mode"),
+        )
+
+    def diff(self, other: "PowerReading") -> "PowerDiff":
+        """Compare this reading (before) with another (after), field by field.
+
+        Args:
+            other: Reading to compare against
+
+        Returns:
+            PowerDiff with one FieldDiff per field (timestamp is reported as elapsed time)
+        """
+        return PowerDiff(
+            elapsed_seconds=(other.timestamp - self.timestamp).total_seconds(),
+            fields=[
+                FieldDiff(f.name, getattr(self, f.name), getattr(other, f.name))
+                for f in fields(self)
+                if f.name != "timestamp"
+            ],
+        )
+
     @staticmethod
     def calculate_watts(voltage: float, amperage: float) -> float:
         """Calculate actual wattage from voltage and amperage.
@@ -44,6 +278,190 @@ class PowerReading:
         return voltage * amperage
 
 
+@dataclass(frozen=True, slots=True)
+class DerivedReading:
+    """A reading plus values computed from it, so JSON consumers don't re-derive them.
+
+    Serialized by `status --json-derived` as {"reading": ..., "derived": ...}.
+    """
+
+    reading: PowerReading
+
+    @property
+    def battery_health_percent(self) -> float | None:
+        """Max capacity as a percentage of design capacity (None if design capacity is unknown)."""
+        if not self.reading.design_capacity:
+            return None
+        return self.reading.max_capacity / self.reading.design_capacity * 100
+
+    @property
+    def time_to_full_minutes(self) -> int | None:
+        """Minutes until full at the current charge current (None unless charging)."""
+        r = self.reading
+        if r.direction is not PowerDirection.CHARGING or r.amperage <= 0:
+            return None
+        remaining_mah = max(r.max_capacity - r.current_capacity, 0)
+        return round(remaining_mah / (r.amperage * 1000) * 60)
+
+    @property
+    def energy_wh_estimate(self) -> float | None:
+        """Energy left in the battery (current capacity × pack voltage), in Wh."""
+        if self.reading.current_capacity <= 0 or self.reading.voltage <= 0:
+            return None
+        return self.reading.current_capacity * self.reading.voltage / 1000
+
+    def to_dict(self) -> dict:
+        """Convert to a JSON-serializable dictionary.
+
+        Returns:
+            {"reading": reading.to_dict(), "derived": {...}}
+        """
+        health = self.battery_health_percent
+        energy = self.energy_wh_estimate
+        return {
+            "reading": self.reading.to_dict(),
+            "derived": {
+                "direction": self.reading.direction.value,
+                "battery_health_percent": round(health, 1) if health is not None else None,
+                "time_to_full_minutes": self.time_to_full_minutes,
+                "energy_wh_estimate": round(energy, 2) if energy is not None else None,
+                "charging_throttled": self.reading.charging_throttled,
+            },
+        }
+
+
+@dataclass
+class FieldDiff:
+    """Before/after values of one PowerReading field."""
+
+    name: str
+    before: object
+    after: object
+
+    @property
+    def delta(self) -> float | None:
+        """Numeric change (after - before), or None if either side is missing or not a number."""
+        numeric = (int, float)
+        if isinstance(self.before, bool) or isinstance(self.after, bool):
+            return None
+        if isinstance(self.before, numeric) and isinstance(self.after, numeric):
+            return self.after - self.before
+        return None
+
+    @property
+    def changed(self) -> bool:
+        """Whether the value differs between the two readings."""
+        return self.before != self.after
+
+
+@dataclass
+class PowerDiff:
+    """Field-by-field comparison of two PowerReadings (see PowerReading.diff)."""
+
+    elapsed_seconds: float  # after.timestamp - before.timestamp
+    fields: list[FieldDiff]
+
+    def changed(self) -> list[FieldDiff]:
+        """Fields whose value differs."""
+        return [f for f in self.fields if f.changed]
+
+    def __getitem__(self, name: str) -> FieldDiff:
+        """Look up the diff for a field by name."""
+        for field_diff in self.fields:
+            if field_diff.name == name:
+                return field_diff
+        raise KeyError(name)
+
+    def __str__(self) -> str:
+        """Render a side-by-side table: field, before, after, delta (changed rows marked with *)."""
+
+        def fmt(value: object) -> str:
+            if value is None:
+                return "-"
+            if isinstance(value, float):
+                return f"{value:.2f}"
+            return str(value)
+
+        rows = [("  field", "before", "after", "delta")]
+        for f in self.fields:
+            delta = f"{f.delta:+.2f}" if f.delta is not None else ""
+            rows.append((("* " if f.changed else "  ") + f.name, fmt(f.before), fmt(f.after), delta))
+
+        widths = [max(len(row[i]) for row in rows) for i in range(4)]
+        lines = [f"elapsed: {self.elapsed_seconds:+.1f}s"]
+        for row in rows:
+            lines.append(
+                "  ".join(
+                    cell.ljust(width) if i == 0 else cell.rjust(width)
+                    for i, (cell, width) in enumerate(zip(row, widths, strict=True))
+                ).rstrip()
+            )
+        return "\n".join(lines)
+
+
+@dataclass
+class BurstSample:
+    """Single fast SMC sample taken during a burst."""
+
+    timestamp: datetime
+    power_input: float | None  # PDTR (W)
+    system_power: float | None  # PSTR (W)
+
+
+@dataclass
+class BurstResult:
+    """High-frequency SMC samples captured over a short window."""
+
+    started_at: datetime
+    rate_hz: float  # Requested sampling rate
+    samples: list[BurstSample]
+
+    def stats(self, field: str) -> tuple[float, float, float] | None:
+        """Summarize one sampled field.
+
+        Args:
+            field: "power_input" or "system_power"
+
+        Returns:
+            (min, max, mean) over samples where the sensor was read, or None if it never was
+        """
+        values = [v for v in (getattr(s, field) for s in self.samples) if v is not None]
+        if not values:
+            return None
+        return min(values), max(values), sum(values) / len(values)
+
+
+@dataclass
+class ChargeSession:
+    """One continuous charge, from charging starting to it stopping or the battery filling."""
+
+    started_at: datetime
+    ended_at: datetime
+    start_percent: int
+    end_percent: int
+    total_wh: float  # Energy delivered to the battery (charge power integrated over time)
+
+    @property
+    def duration(self) -> timedelta:
+        """Time from the first to the last reading of the session."""
+        return self.ended_at - self.started_at
+
+    def to_dict(self) -> dict:
+        """Convert to a JSON-serializable dictionary.
+
+        Returns:
+            Dictionary with ISO timestamps, duration in seconds, and energy rounded to 0.01Wh
+        """
+        return {
+            "started_at": self.started_at.isoformat(),
+            "ended_at": self.ended_at.isoformat(),
+            "duration_seconds": round(self.duration.total_seconds()),
+            "start_percent": self.start_percent,
+            "end_percent": self.end_percent,
+            "total_wh": round(self.total_wh, 2),
+        }
+
+
 @dataclass
 class AdapterDetail:
     """Raw adapter details from ioreg."""
@@ -54,6 +472,51 @@ class AdapterDetail:
     manufacturer: str | None  # Manufacturer
     voltage: int | None  # Charger voltage (mV)
     current: int | None  # Charger current (mA)
+    serial_number: str | None = None  # Charger serial number
+    firmware_version: str | None = None  # Charger firmware version
+    hardware_version: str | None = None  # Charger hardware revision
+
+    def likely_certified(self) -> bool | None:
+        """Guess whether this is a certified (Apple or properly identified) charger.
+
+        Certified chargers identify themselves (manufacturer or serial number);
+        cheap uncertified ones typically report neither.
+
+        Returns:
+            True for Apple chargers, False if the charger reports no manufacturer or
+            serial number, None otherwise (identified third-party charger: can't tell)
+        """
+        manufacturer = (self.manufacturer or "").strip()
+        if "apple" in manufacturer.lower():
+            return True
+        if not manufacturer and not (self.serial_number or "").strip():
+            return False
+        return None
+
+    @classmethod
+    def from_ioreg(cls, adapter: dict) -> "AdapterDetail":
+        """Build adapter details from an ioreg adapter dictionary.
+
+        Key names vary between adapters and macOS versions (e.g. Apple chargers report
+        SerialString/FwVersion), so common aliases are accepted.
+
+        Args:
+            adapter: Entry from AppleRawAdapterDetails
+
+        Returns:
+            AdapterDetail with available fields populated
+        """
+        return cls(
+            watts=adapter.get("Watts"),
+            name=adapter.get("Name"),
+            description=adapter.get("Description"),
+            manufacturer=adapter.get("Manufacturer"),
+            voltage=adapter.get("AdapterVoltage", adapter.get("Voltage")),
+            current=adapter.get("Current"),
+            serial_number=adapter.get("SerialNumber", adapter.get("SerialString")),
+            firmware_version=adapter.get("FirmwareVersion", adapter.get("FwVersion")),
+            hardware_version=adapter.get("HwVersion", adapter.get("HardwareVersion")),
+        )
 
 
 class PowerCollectorError(Exception):
@@ -63,11 +526,27 @@ class PowerCollectorError(Exception):
 class CommandFailedError(PowerCollectorError):
     """ioreg command execution failed."""
 
+    def __init__(self, message: str, attempts: int = 1):
+        super().__init__(message)
+        self.attempts = attempts  # Number of times the command was tried
+
+
+class CollectorUnavailableError(PowerCollectorError):
+    """A command-line tool the collector needs is not installed (as opposed to failing when run)."""
+
+    def __init__(self, tool: str, hint: str):
+        super().__init__(f"{tool} not found on PATH; {hint}")
+        self.tool = tool  # Name of the missing executable
+
 
 class ParseError(PowerCollectorError):
     """Plist/data parsing failed."""
 
 
+class NoBatteryError(PowerCollectorError):
+    """No battery is installed (e.g., desktop Mac)."""
+
+
 class MissingFieldError(PowerCollectorError):
     """Required field is missing from power data."""
 
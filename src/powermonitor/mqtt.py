@@ -0,0 +1,256 @@
+"""Publish readings to an MQTT broker with Home Assistant discovery (`powermonitor mqtt`).
+
+Needs the optional paho-mqtt dependency: `pip install 'powermonitor[mqtt]'`.
+"""
+
+import json
+import re
+import socket
+from dataclasses import dataclass
+from typing import Any
+from urllib.parse import urlsplit
+
+from loguru import logger
+
+from .config import MqttConfig
+from .models import PowerReading
+from .system_info import get_version
+
+DEFAULT_PORT = 1883
+DEFAULT_TLS_PORT = 8883
+
+
+@dataclass(frozen=True, slots=True)
+class MqttSensor:
+    """One reading field exposed as a Home Assistant entity.
+
+    Attributes:
+        object_id: Topic suffix and entity id suffix, e.g. "watts"
+        name: Entity name shown in Home Assistant
+        field: PowerReading attribute the state comes from
+        component: Home Assistant component ("sensor" or "binary_sensor")
+        device_class: Home Assistant device class
+        unit: Unit of measurement (sensors only)
+    """
+
+    object_id: str
+    name: str
+    field: str
+    component: str = "sensor"
+    device_class: str | None = None
+    unit: str | None = None
+
+
+MQTT_SENSORS = (
+    MqttSensor("watts", "Power flow", "watts_actual", device_class="power", unit="W"),
+    MqttSensor("battery", "Battery", "battery_percent", device_class="battery", unit="%"),
+    MqttSensor("voltage", "Voltage", "voltage", device_class="voltage", unit="V"),
+    MqttSensor("current", "Current", "amperage", device_class="current", unit="A"),
+    MqttSensor("negotiated", "Charger rating", "watts_negotiated", device_class="power", unit="W"),
+    MqttSensor("battery_temp", "Battery temperature", "battery_temp", device_class="temperature", unit="°C"),
+    MqttSensor("system_power", "System power", "system_power", device_class="power", unit="W"),
+    MqttSensor("charging", "Charging", "is_charging", component="binary_sensor", device_class="battery_charging"),
+    MqttSensor("plugged_in", "Plugged in", "external_connected", component="binary_sensor", device_class="plug"),
+)
+
+
+@dataclass(frozen=True, slots=True)
+class BrokerAddress:
+    """Broker connection details parsed from an mqtt:// or mqtts:// URL."""
+
+    host: str
+    port: int
+    tls: bool
+
+
+def parse_broker_url(url: str) -> BrokerAddress:
+    """Parse a broker URL.
+
+    Args:
+        url: mqtt://host[:port] or mqtts://host[:port] (port defaults to 1883, or 8883 for mqtts)
+
+    Returns:
+        Host, port and whether to use TLS
+
+    Raises:
+        ValueError: If the scheme is not mqtt/mqtts, the host is missing, or the port is invalid
+    """
+    parts = urlsplit(url)
+    if parts.scheme not in ("mqtt", "mqtts"):
+        raise ValueError(f"Broker URL must start with mqtt:// or mqtts://, got {url!r}")
+    if not parts.hostname:
+        raise ValueError(f"Broker URL has no host: {url!r}")
+    tls = parts.scheme == "mqtts"
+    try:
+        port = parts.port or (DEFAULT_TLS_PORT if tls else DEFAULT_PORT)
+    except ValueError:
+        raise ValueError(f"Broker URL has an invalid port: {url!r}") from None
+    return BrokerAddress(host=parts.hostname, port=port, tls=tls)
+
+
+def node_id(hostname: str | None = None) -> str:
+    """Identify this Mac in discovery topics and unique ids.
+
+    Args:
+        hostname: Host name (default: this machine's)
+
+    Returns:
+        Lowercase host name with anything other than letters, digits and underscores replaced
+    """
+    name = (hostname or socket.gethostname()).split(".")[0].lower()
+    return re.sub(r"[^a-z0-9_]+", "_", name).strip("_") or "mac"
+
+
+def _strip(prefix: str) -> str:
+    return prefix.strip("/")
+
+
+def availability_topic(topic_prefix: str) -> str:
+    """Topic carrying "online"/"offline" (retained; "offline" is the last will)."""
+    return f"{_strip(topic_prefix)}/status"
+
+
+def state_topic(topic_prefix: str, sensor: MqttSensor) -> str:
+    """Topic carrying one sensor's state."""
+    return f"{_strip(topic_prefix)}/{sensor.object_id}"
+
+
+def discovery_messages(settings: MqttConfig, node: str) -> list[tuple[str, str]]:
+    """Build the Home Assistant discovery config for every sensor.
+
+    Args:
+        settings: Topic prefixes
+        node: Node id for this Mac (see node_id)
+
+    Returns:
+        (topic, JSON payload) pairs, to be published retained
+    """
+    device = {
+        "identifiers": [f"powermonitor_{node}"],
+        "name": f"powermonitor ({node})",
+        "manufacturer": "Apple",
+        "sw_version": get_version(),
+    }
+    messages = []
+    for sensor in MQTT_SENSORS:
+        payload: dict[str, Any] = {
+            "name": sensor.name,
+            "unique_id": f"powermonitor_{node}_{sensor.object_id}",
+            "state_topic": state_topic(settings.topic_prefix, sensor),
+            "availability_topic": availability_topic(settings.topic_prefix),
+            "device": device,
+        }
+        if sensor.device_class:
+            payload["device_class"] = sensor.device_class
+        if sensor.unit:
+            payload["unit_of_measurement"] = sensor.unit
+            payload["state_class"] = "measurement"
+        topic = f"{_strip(settings.discovery_prefix)}/{sensor.component}/{node}/{sensor.object_id}/config"
+        messages.append((topic, json.dumps(payload)))
+    return messages
+
+
+def state_messages(reading: PowerReading, topic_prefix: str) -> list[tuple[str, str]]:
+    """Build the state updates for one reading.
+
+    Args:
+        reading: Reading to publish
+        topic_prefix: State topic prefix
+
+    Returns:
+        (topic, payload) pairs; binary sensors are "ON"/"OFF" and fields without a value are skipped
+    """
+    messages = []
+    for sensor in MQTT_SENSORS:
+        value = getattr(reading, sensor.field)
+        if value is None:
+            continue
+        if isinstance(value, bool):
+            payload = "ON" if value else "OFF"
+        elif isinstance(value, float):
+            payload = f"{value:.2f}"
+        else:
+            payload = str(value)
+        messages.append((state_topic(topic_prefix, sensor), payload))
+    return messages
+
+
+class MqttPublisher:
+    """Publish readings to a broker.
+
+    Discovery configs and "online" are published (retained) on every connect, so
+    Home Assistant picks the sensors up again after a broker restart. The broker
+    publishes "offline" if the connection drops.
+
+    Usage:
+        publisher = MqttPublisher(settings)
+        publisher.connect()
+        publisher.publish(collector.collect())
+        publisher.close()
+    """
+
+    def __init__(self, settings: MqttConfig, node: str | None = None):
+        """Create the client (does not connect yet).
+
+        Args:
+            settings: Broker URL, credentials and topics (broker must be set)
+            node: Node id for this Mac (default: derived from the host name)
+
+        Raises:
+            ValueError: If the broker URL is missing or invalid
+            RuntimeError: If paho-mqtt is not installed
+        """
+        if not settings.broker:
+            raise ValueError("No MQTT broker configured (use --broker or [mqtt] broker in config.toml)")
+        self.settings = settings
+        self.address = parse_broker_url(settings.broker)
+        self.node = node or node_id()
+
+        try:
+            from paho.mqtt.client import CallbackAPIVersion
+            from paho.mqtt.client import Client
+        except ImportError:
+            raise RuntimeError(
+                "MQTT publishing needs paho-mqtt; install it with: pip install 'powermonitor[mqtt]'"
+            ) from None
+
+        self._client = Client(callback_api_version=CallbackAPIVersion.VERSION2, client_id=f"powermonitor-{self.node}")
+        if settings.username:
+            self._client.username_pw_set(settings.username, settings.password or None)
+        if self.address.tls:
+            self._client.tls_set()
+        self._client.will_set(availability_topic(settings.topic_prefix), "offline", qos=1, retain=True)
+        self._client.on_connect = self._on_connect
+
+    def _on_connect(self, client: Any, userdata: Any, flags: Any, reason_code: Any, properties: Any) -> None:
+        if reason_code.is_failure:
+            logger.warning(f"MQTT broker refused the connection: {reason_code}")
+            return
+        for topic, payload in discovery_messages(self.settings, self.node):
+            client.publish(topic, payload, qos=1, retain=True)
+        client.publish(availability_topic(self.settings.topic_prefix), "online", qos=1, retain=True)
+        logger.info(f"Connected to MQTT broker {self.address.host}:{self.address.port}")
+
+    def connect(self) -> None:
+        """Connect and start the network loop (reconnects automatically).
+
+        Raises:
+            OSError: If the broker can't be reached
+        """
+        self._client.connect(self.address.host, self.address.port)
+        self._client.loop_start()
+
+    def publish(self, reading: PowerReading) -> None:
+        """Publish one reading's state updates."""
+        for topic, payload in state_messages(reading, self.settings.topic_prefix):
+            self._client.publish(topic, payload)
+
+    def close(self) -> None:
+        """Publish "offline" and disconnect."""
+        info = self._client.publish(availability_topic(self.settings.topic_prefix), "offline", qos=1, retain=True)
+        try:
+            info.wait_for_publish(timeout=2)
+        except (RuntimeError, ValueError):
+            pass  # Not connected; the broker's last will covers it
+        self._client.disconnect()
+        self._client.loop_stop()
@@ -0,0 +1,94 @@
+"""Locale-aware number formatting for human-readable output (`--locale`).
+
+Only tables and status text go through here; JSON and CSV always use canonical
+machine formatting (`.` decimals, no grouping).
+"""
+
+import locale
+from dataclasses import dataclass
+
+
+@dataclass(frozen=True, slots=True)
+class NumberFormat:
+    """Decimal and thousands separators for displayed numbers.
+
+    Digits are grouped in threes, which covers the common European and American
+    conventions; locales with other group sizes still get their separators.
+
+    Attributes:
+        decimal_point: Decimal separator
+        thousands_sep: Thousands separator ("" for no grouping)
+    """
+
+    decimal_point: str = "."
+    thousands_sep: str = ","
+
+    def format_number(self, value: float, decimals: int = 1, sign: bool = False, grouping: bool = False) -> str:
+        """Format a number with this format's separators.
+
+        Args:
+            value: Number to format
+            decimals: Digits after the decimal point
+            sign: Always show the sign (e.g. +12.5)
+            grouping: Separate thousands
+
+        Returns:
+            Formatted number
+        """
+        spec = f"{'+' if sign else ''}{',' if grouping else ''}.{decimals}f"
+        text = format(value, spec)
+        return text.translate(str.maketrans({",": self.thousands_sep, ".": self.decimal_point}))
+
+    def format_watts(
+        self, watts: float, reference_voltage: float | None = None, sign: bool = False, deadzone: float = 0.0
+    ) -> str:
+        """Format power, optionally with the equivalent current at a reference voltage.
+
+        Args:
+            watts: Power in W
+            reference_voltage: Also show watts / reference_voltage in A (None = watts only)
+            sign: Always show the sign of the power
+            deadzone: Show power smaller than this many W (either way) as an unsigned 0
+
+        Returns:
+            e.g. "15.0W", or "15.0W (≈3.0A @ 5V)" with reference_voltage=5
+        """
+        if abs(watts) < deadzone:
+            watts, sign = 0.0, False
+        text = f"{self.format_number(watts, sign=sign)}W"
+        if reference_voltage:
+            amps = self.format_number(abs(watts) / reference_voltage)
+            volts = format(reference_voltage, "g").replace(".", self.decimal_point)
+            text += f" (≈{amps}A @ {volts}V)"
+        return text
+
+
+CANONICAL = NumberFormat()
+
+
+def number_format_for_locale(name: str) -> NumberFormat:
+    """Look up the number format of a locale.
+
+    The process locale is left unchanged, so nothing else (JSON, CSV, logging)
+    picks up the locale's separators.
+
+    Args:
+        name: Locale name such as "de_DE.UTF-8", or "" for the environment's
+            LC_ALL / LC_NUMERIC / LANG
+
+    Returns:
+        NumberFormat for the locale
+
+    Raises:
+        ValueError: If the locale is not installed
+    """
+    previous = locale.setlocale(locale.LC_NUMERIC)
+    try:
+        locale.setlocale(locale.LC_NUMERIC, name)
+    except locale.Error as e:
+        raise ValueError(f"Unknown locale {name!r}") from e
+    try:
+        conv = locale.localeconv()
+    finally:
+        locale.setlocale(locale.LC_NUMERIC, previous)
+    return NumberFormat(decimal_point=conv["decimal_point"] or ".", thousands_sep=conv["thousands_sep"])
@@ -0,0 +1,175 @@
+"""Probe every data source at once (`powermonitor probe`).
+
+Where `doctor` prints a checklist for a person, the probe reports each source -
+ioreg, every SMC key, pmset, and powermetrics - as one JSON document that can be
+attached to a bug report. Each source section has `available` and `error`; a
+failing source is reported, never raised, so one broken interface doesn't hide
+the others.
+"""
+
+import math
+import os
+import shutil
+import sys
+from collections.abc import Callable
+from typing import TYPE_CHECKING
+from typing import Any
+
+from .collector.gpu import GpuPowerReader
+from .collector.ioreg import IORegCollector
+from .collector.lowpower import read_low_power_mode
+from .collector.pmset import PmsetCollector
+from .collector.thermal import read_thermal_state
+from .models import PowerCollectorError
+from .system_info import get_system_info
+
+if TYPE_CHECKING:
+    from .collector.iokit.reader import SmcReader
+
+
+def probe_ioreg(collector: IORegCollector | None = None) -> dict[str, Any]:
+    """Check that ioreg lists a battery and that its entry parses into a reading.
+
+    Args:
+        collector: ioreg collector to use (overridable for tests)
+
+    Returns:
+        {"available", "parses", "error", "keys": battery entry keys, "sample": reading.to_dict()}
+    """
+    collector = collector or IORegCollector()
+    result: dict[str, Any] = {"available": False, "parses": False, "error": None, "keys": [], "sample": None}
+    try:
+        battery = collector.read_battery_dict()
+    except PowerCollectorError as e:
+        result["error"] = str(e)
+        return result
+    result["available"] = True
+    result["keys"] = sorted(battery)
+    try:
+        reading = collector._parse_battery_data(battery)
+    except PowerCollectorError as e:
+        result["error"] = str(e)
+        return result
+    result["parses"] = True
+    result["sample"] = reading.to_dict()
+    return result
+
+
+def _probe_key(smc: "SmcReader", key: str, description: str, error_type: type[Exception]) -> dict[str, Any]:
+    """Read one SMC key, reporting rather than raising a failure."""
+    try:
+        value = smc.read_key(key)
+    except error_type as e:
+        return {"description": description, "readable": False, "value": None, "error": str(e)}
+    if not math.isfinite(value):
+        return {"description": description, "readable": True, "value": None, "error": f"non-finite value {value}"}
+    return {"description": description, "readable": True, "value": value, "error": None}
+
+
+def probe_smc(smc_factory: Callable[[], "SmcReader"] | None = None) -> dict[str, Any]:
+    """Read every known SMC key, including those the collector skips on this architecture.
+
+    Args:
+        smc_factory: Opens the SMC reader; defaults to SMCConnection (pass a MockSmcReader factory for tests)
+
+    Returns:
+        {"available", "error", "keys": {key: {"description", "readable", "value", "error"}},
+        "temperature_keys": same for the SMC temperature sensors}
+    """
+    result: dict[str, Any] = {"available": False, "error": None, "keys": {}, "temperature_keys": {}}
+    if smc_factory is None and sys.platform != "darwin":
+        result["error"] = "IOKit requires macOS"
+        return result
+
+    try:
+        # Imported lazily: loading the IOKit bindings fails on systems without the framework
+        from .collector.iokit.collector import SMC_SENSORS
+        from .collector.iokit.collector import SMC_TEMPERATURE_SENSORS
+        from .collector.iokit.connection import SMCConnection
+        from .collector.iokit.structures import SMCError
+    except (ImportError, OSError) as e:
+        result["error"] = f"IOKit unavailable: {e}"
+        return result
+
+    try:
+        with (smc_factory or SMCConnection)() as smc:
+            result["available"] = True
+            for key, description in SMC_SENSORS.items():
+                result["keys"][key] = _probe_key(smc, key, description, SMCError)
+            for key, description in SMC_TEMPERATURE_SENSORS.items():
+                result["temperature_keys"][key] = _probe_key(smc, key, description, SMCError)
+    except SMCError as e:
+        result["available"] = False
+        result["error"] = str(e)
+    return result
+
+
+def probe_pmset(collector: PmsetCollector | None = None) -> dict[str, Any]:
+    """Check that `pmset -g batt` reports the battery, and read the thermal and Low Power Mode state.
+
+    Args:
+        collector: pmset collector to use (overridable for tests)
+
+    Returns:
+        {"available", "error", "sample": {battery state, thermal_state, low_power_mode}}
+    """
+    collector = collector or PmsetCollector()
+    try:
+        reading = collector.collect()
+    except PowerCollectorError as e:
+        return {"available": False, "error": str(e), "sample": None}
+    return {
+        "available": True,
+        "error": None,
+        "sample": {
+            "battery_percent": reading.battery_percent,
+            "is_charging": reading.is_charging,
+            "external_connected": reading.external_connected,
+            "fully_charged": reading.fully_charged,
+            # Both are None when pmset doesn't report them
+            "thermal_state": read_thermal_state(),
+            "low_power_mode": read_low_power_mode(reading.external_connected),
+        },
+    }
+
+
+def probe_powermetrics(reader: GpuPowerReader | None = None) -> dict[str, Any]:
+    """Check whether powermetrics is installed and permitted, and sample GPU power if so.
+
+    Args:
+        reader: GPU power reader to use (overridable for tests)
+
+    Returns:
+        {"available": installed, "permitted": running as root, "error", "sample": {"gpu_power": W}}
+    """
+    reader = reader or GpuPowerReader()
+    result: dict[str, Any] = {
+        "available": shutil.which(reader.command[0]) is not None,
+        "permitted": os.geteuid() == 0,
+        "error": None,
+        "sample": None,
+    }
+    if not result["available"]:
+        result["error"] = f"{reader.command[0]} not found on PATH"
+    elif not result["permitted"]:
+        result["error"] = "powermetrics needs root - run powermonitor with sudo"
+    else:
+        gpu_power = reader.read()
+        result["error"] = reader.disabled_reason
+        result["sample"] = {"gpu_power": gpu_power}
+    return result
+
+
+def probe_all() -> dict[str, Any]:
+    """Probe every data source.
+
+    Returns:
+        {"system": get_system_info(), "ioreg", "smc", "pmset", "powermetrics"}
+    """
+    return {
+        "system": get_system_info(),
+        "ioreg": probe_ioreg(),
+        "smc": probe_smc(),
+        "pmset": probe_pmset(),
+        "powermetrics": probe_powermetrics(),
+    }
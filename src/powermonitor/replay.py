@@ -0,0 +1,131 @@
+"""Replay recorded power readings from NDJSON files."""
+
+import json
+from collections.abc import Iterable
+from collections.abc import Iterator
+from dataclasses import replace
+from datetime import datetime
+from pathlib import Path
+
+from .models import PowerCollectorError
+from .models import PowerReading
+
+DEFAULT_MAX_DELAY = 10.0  # seconds; caps gaps between recording sessions
+
+
+class ReplayFinishedError(PowerCollectorError):
+    """Every recorded reading has already been replayed."""
+
+
+def read_ndjson(path: Path) -> Iterator[PowerReading]:
+    """Read readings from an NDJSON file (one JSON object per line).
+
+    Blank lines are skipped.
+
+    Args:
+        path: File written by `powermonitor export <file>.ndjson`
+
+    Yields:
+        PowerReading for each line
+
+    Raises:
+        ValueError: If a line is not a valid reading (message includes the line number)
+    """
+    with open(path) as f:
+        for line_number, line in enumerate(f, start=1):
+            line = line.strip()
+            if not line:
+                continue
+            try:
+                yield PowerReading.from_dict(json.loads(line))
+            except (json.JSONDecodeError, KeyError, TypeError, ValueError) as e:
+                raise ValueError(f"{path}:{line_number}: invalid reading: {e}") from e
+
+
+def replay_schedule(
+    readings: Iterable[PowerReading],
+    speed: float = 1.0,
+    max_delay: float = DEFAULT_MAX_DELAY,
+) -> Iterator[tuple[float, PowerReading]]:
+    """Pair each reading with the delay to wait before showing it.
+
+    Readings are replayed in timestamp order. Delays are the recorded gaps divided
+    by speed, clamped to max_delay so gaps between sessions don't stall playback.
+
+    Args:
+        readings: Recorded readings in any order
+        speed: Playback speed multiplier (2.0 = twice as fast, must be > 0)
+        max_delay: Longest delay between two readings in seconds (must be >= 0)
+
+    Yields:
+        (delay_seconds, reading) tuples; the first delay is always 0
+
+    Raises:
+        ValueError: If speed or max_delay is out of range
+    """
+    if speed <= 0:
+        raise ValueError(f"speed must be positive, got {speed}")
+    if max_delay < 0:
+        raise ValueError(f"max_delay must be non-negative, got {max_delay}")
+
+    previous: PowerReading | None = None
+    for reading in sorted(readings, key=lambda r: r.timestamp):
+        delay = 0.0
+        if previous is not None:
+            gap = (reading.timestamp - previous.timestamp).total_seconds()
+            delay = min(gap / speed, max_delay)
+        yield delay, reading
+        previous = reading
+
+
+class ReplayCollector:
+    """Collector that hands out recorded readings on their replay schedule (`replay --tui`).
+
+    collect() returns the readings in order with their recorded timestamps, and
+    next_delay() says how long to wait before the next one, so the TUI uses it
+    instead of collection_interval. Once every reading has been collected,
+    next_delay() returns None and collect() raises.
+
+    Usage:
+        collector = ReplayCollector(replay_schedule(read_ndjson(path), speed=10))
+        PowerMonitorApp(collector=collector).run()
+    """
+
+    def __init__(self, schedule: Iterable[tuple[float, PowerReading]]):
+        """Initialize replay collector.
+
+        Args:
+            schedule: (delay_seconds, reading) pairs from replay_schedule
+        """
+        self._schedule = list(schedule)
+        self._index = 0
+
+    @property
+    def started(self) -> datetime | None:
+        """Timestamp of the first recorded reading (None for an empty recording)."""
+        return self._schedule[0][1].timestamp if self._schedule else None
+
+    def next_delay(self) -> float | None:
+        """Get the delay before the next recorded reading.
+
+        Returns:
+            Seconds to wait, or None when the recording is exhausted
+        """
+        if self._index >= len(self._schedule):
+            return None
+        return self._schedule[self._index][0]
+
+    def collect(self) -> PowerReading:
+        """Return the next recorded reading.
+
+        Returns:
+            Copy of the recorded PowerReading
+
+        Raises:
+            ReplayFinishedError: If every reading has been replayed
+        """
+        if self._index >= len(self._schedule):
+            raise ReplayFinishedError(f"All {len(self._schedule)} recorded readings have been replayed")
+        reading = self._schedule[self._index][1]
+        self._index += 1
+        return replace(reading)
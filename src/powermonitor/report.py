@@ -0,0 +1,391 @@
+"""Self-contained HTML reports (`powermonitor report`).
+
+A report summarizes the readings of a time range - a power and battery chart,
+statistics, a per-charger breakdown, and battery health - in one HTML file with
+inline CSS and SVG, so it can be attached to a support ticket or archived.
+"""
+
+import re
+from collections.abc import Sequence
+from dataclasses import dataclass
+from datetime import datetime
+from datetime import timedelta
+from html import escape
+from pathlib import Path
+
+from .aggregates import ChargerStats
+from .aggregates import StatsResult
+from .models import PowerReading
+
+MAX_CHART_POINTS = 1000  # longer ranges are thinned so the SVG stays small
+
+_AGO_RE = re.compile(r"^(\d+(?:\.\d+)?)\s*([smhdw])$")
+_AGO_UNITS = {"s": 1, "m": 60, "h": 3600, "d": 86400, "w": 7 * 86400}
+
+_CHART_WIDTH = 800
+_CHART_HEIGHT = 260
+_CHART_MARGIN = 40
+
+# Background shading per power source region: (CSS class, fill color, legend label)
+CHART_REGIONS = {
+    "charging": ("region-charging", "#ffbf00", "Charging"),
+    "ac": ("region-ac", "#8c8c8c", "On AC, not charging"),
+    "battery": ("region-battery", "#d62728", "On battery"),
+}
+_REGION_OPACITY = 0.12
+
+_STYLE = """
+body { font-family: -apple-system, BlinkMacSystemFont, "Helvetica Neue", sans-serif; margin: 2em auto;
+       max-width: 860px; color: #222; }
+h1 { font-size: 1.6em; margin-bottom: 0.2em; }
+h2 { font-size: 1.2em; margin-top: 1.6em; border-bottom: 1px solid #ddd; padding-bottom: 0.2em; }
+.meta { color: #666; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; padding: 0.3em 0.6em; border-bottom: 1px solid #eee; }
+td.num, th.num { text-align: right; font-variant-numeric: tabular-nums; }
+.legend span { margin-right: 1.2em; }
+.power { color: #1f77b4; }
+.battery { color: #2ca02c; }
+.region-charging { color: #e6a800; }
+.region-ac { color: #8c8c8c; }
+.region-battery { color: #d62728; }
+"""
+
+
+def parse_time_bound(text: str, now: datetime) -> datetime:
+    """Parse a --since/--until value.
+
+    Accepts an ISO 8601 date or date-time ("2025-12-28", "2025-12-28T09:00",
+    naive values are local time) or an age such as "30m", "24h", "7d", or "2w"
+    meaning that long before now.
+
+    Args:
+        text: Value to parse
+        now: Current time (timezone-aware), for ages
+
+    Returns:
+        Timezone-aware datetime
+
+    Raises:
+        ValueError: If the text is neither a date nor an age
+    """
+    text = text.strip()
+    match = _AGO_RE.match(text)
+    if match is not None:
+        return now - timedelta(seconds=float(match.group(1)) * _AGO_UNITS[match.group(2)])
+    try:
+        value = datetime.fromisoformat(text)
+    except ValueError:
+        raise ValueError(f"Invalid time {text!r}; expected a date such as 2025-12-28 or an age such as 7d") from None
+    return value if value.tzinfo is not None else value.astimezone()
+
+
+@dataclass(frozen=True, slots=True)
+class HealthSummary:
+    """Battery capacity over the report range.
+
+    Attributes:
+        first_max_capacity: Max capacity at the start of the range (mAh)
+        last_max_capacity: Max capacity at the end of the range (mAh)
+        design_capacity: Factory design capacity (mAh), None if never recorded
+    """
+
+    first_max_capacity: int
+    last_max_capacity: int
+    design_capacity: int | None
+
+    @property
+    def health_percent(self) -> float | None:
+        """Latest max capacity as a percentage of design capacity."""
+        if not self.design_capacity:
+            return None
+        return self.last_max_capacity / self.design_capacity * 100
+
+    @property
+    def change_mah(self) -> int:
+        """Change in max capacity over the range."""
+        return self.last_max_capacity - self.first_max_capacity
+
+
+@dataclass(frozen=True, slots=True)
+class Report:
+    """Everything a report shows, computed from the readings of one time range.
+
+    Attributes:
+        generated_at: When the report was made
+        since: Requested start of the range (None = from the first reading)
+        until: Requested end of the range (None = up to the latest reading)
+        readings: Readings in the range, oldest first
+        stats: Statistics over the readings
+        chargers: Per-charger breakdown, most recently seen first
+        health: Battery capacity over the range
+    """
+
+    generated_at: datetime
+    since: datetime | None
+    until: datetime | None
+    readings: list[PowerReading]
+    stats: StatsResult
+    chargers: list[ChargerStats]
+    health: HealthSummary
+
+    @classmethod
+    def build(
+        cls,
+        readings: Sequence[PowerReading],
+        db_path: Path,
+        generated_at: datetime,
+        since: datetime | None = None,
+        until: datetime | None = None,
+    ) -> "Report":
+        """Compute the report for a range.
+
+        Args:
+            readings: PowerReadings in the range, in any order (at least one)
+            db_path: Database the readings came from
+            generated_at: Report time
+            since: Requested start of the range
+            until: Requested end of the range
+
+        Returns:
+            Report
+
+        Raises:
+            ValueError: If there are no readings
+        """
+        if not readings:
+            raise ValueError("A report needs at least one reading")
+        ordered = sorted(readings, key=lambda r: r.timestamp)
+        stat_data = {
+            "avg_watts": sum(r.watts_actual for r in ordered) / len(ordered),
+            "min_watts": min(r.watts_actual for r in ordered),
+            "max_watts": max(r.watts_actual for r in ordered),
+            "avg_battery": sum(r.battery_percent for r in ordered) / len(ordered),
+            "earliest": ordered[0].timestamp.isoformat(),
+            "latest": ordered[-1].timestamp.isoformat(),
+            "count": len(ordered),
+        }
+        size = db_path.stat().st_size if db_path.exists() else 0
+        return cls(
+            generated_at=generated_at,
+            since=since,
+            until=until,
+            readings=ordered,
+            stats=StatsResult.from_statistics(stat_data, db_path, size),
+            chargers=charger_breakdown(ordered),
+            health=HealthSummary(
+                first_max_capacity=ordered[0].max_capacity,
+                last_max_capacity=ordered[-1].max_capacity,
+                design_capacity=next((r.design_capacity for r in reversed(ordered) if r.design_capacity), None),
+            ),
+        )
+
+
+def charger_breakdown(readings: Sequence[PowerReading]) -> list[ChargerStats]:
+    """Summarize readings per charger, like Database.get_charger_stats() for a subset.
+
+    Args:
+        readings: PowerReadings, oldest first
+
+    Returns:
+        One entry per charger (by name and manufacturer), most recently seen first
+    """
+    groups: dict[tuple[str, str | None], list[PowerReading]] = {}
+    for reading in readings:
+        if reading.charger_name:
+            groups.setdefault((reading.charger_name, reading.charger_manufacturer), []).append(reading)
+    chargers = [
+        ChargerStats(
+            name=name,
+            manufacturer=manufacturer,
+            readings=len(group),
+            max_watts_negotiated=max(r.watts_negotiated for r in group),
+            avg_watts_actual=sum(r.watts_actual for r in group) / len(group),
+            first_seen=group[0].timestamp.isoformat(),
+            last_seen=group[-1].timestamp.isoformat(),
+        )
+        for (name, manufacturer), group in groups.items()
+    ]
+    return sorted(chargers, key=lambda c: c.last_seen, reverse=True)
+
+
+def reading_region(reading: PowerReading) -> str:
+    """Chart region of a reading: "charging", "ac" (plugged in, not charging), or "battery"."""
+    if reading.is_charging:
+        return "charging"
+    return "ac" if reading.external_connected else "battery"
+
+
+def chart_regions(readings: Sequence[PowerReading]) -> list[tuple[str, int, int]]:
+    """Split readings into runs with the same power source.
+
+    Args:
+        readings: PowerReadings, oldest first
+
+    Returns:
+        (region, first index, last index) per run, in order
+    """
+    runs: list[tuple[str, int, int]] = []
+    for index, reading in enumerate(readings):
+        region = reading_region(reading)
+        if runs and runs[-1][0] == region:
+            runs[-1] = (region, runs[-1][1], index)
+        else:
+            runs.append((region, index, index))
+    return runs
+
+
+def render_chart_svg(readings: Sequence[PowerReading], width: int = _CHART_WIDTH, height: int = _CHART_HEIGHT) -> str:
+    """Draw power (left axis) and battery percent (right axis) over time as inline SVG.
+
+    The background is shaded by power source (see CHART_REGIONS), so it is obvious
+    which spikes happened while plugged in. A region runs until the next one starts.
+
+    Args:
+        readings: PowerReadings, oldest first (at least one)
+        width: SVG width in pixels
+        height: SVG height in pixels
+
+    Returns:
+        SVG element markup
+    """
+    step = max(1, -(-len(readings) // MAX_CHART_POINTS))
+    points = list(readings[::step])
+    if points[-1] is not readings[-1]:
+        points.append(readings[-1])
+
+    start = points[0].timestamp
+    span = max((points[-1].timestamp - start).total_seconds(), 1.0)
+    low = min(0.0, min(r.watts_actual for r in points))
+    high = max(1.0, max(r.watts_actual for r in points))
+    plot_w = width - 2 * _CHART_MARGIN
+    plot_h = height - 2 * _CHART_MARGIN
+
+    def x(reading: PowerReading) -> float:
+        return _CHART_MARGIN + (reading.timestamp - start).total_seconds() / span * plot_w
+
+    def y_watts(watts: float) -> float:
+        return _CHART_MARGIN + (high - watts) / (high - low) * plot_h
+
+    def y_percent(percent: float) -> float:
+        return _CHART_MARGIN + (100 - percent) / 100 * plot_h
+
+    power = " ".join(f"{x(r):.1f},{y_watts(r.watts_actual):.1f}" for r in points)
+    battery = " ".join(f"{x(r):.1f},{y_percent(r.battery_percent):.1f}" for r in points)
+    bottom = height - _CHART_MARGIN
+    right = width - _CHART_MARGIN
+    shading = "".join(
+        f'<rect class="{CHART_REGIONS[region][0]}" x="{x(points[first]):.1f}" y="{_CHART_MARGIN}" '
+        f'width="{x(points[min(last + 1, len(points) - 1)]) - x(points[first]):.1f}" height="{plot_h}" '
+        f'fill="{CHART_REGIONS[region][1]}" fill-opacity="{_REGION_OPACITY}"/>\n'
+        for region, first, last in chart_regions(points)
+    )
+    start_label = escape(start.astimezone().strftime("%Y-%m-%d %H:%M"))
+    end_label = escape(points[-1].timestamp.astimezone().strftime("%Y-%m-%d %H:%M"))
+    return (
+        f'<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" '
+        f'viewBox="0 0 {width} {height}" font-size="11" font-family="sans-serif">\n'
+        f"{shading}"
+        f'<rect x="{_CHART_MARGIN}" y="{_CHART_MARGIN}" width="{plot_w}" height="{plot_h}" '
+        'fill="none" stroke="#ccc"/>\n'
+        f'<line x1="{_CHART_MARGIN}" y1="{y_watts(0):.1f}" x2="{right}" y2="{y_watts(0):.1f}" '
+        'stroke="#ddd" stroke-dasharray="4 3"/>\n'
+        f'<polyline points="{power}" fill="none" stroke="#1f77b4" stroke-width="1.5"/>\n'
+        f'<polyline points="{battery}" fill="none" stroke="#2ca02c" stroke-width="1.5"/>\n'
+        f'<text x="{_CHART_MARGIN - 4}" y="{_CHART_MARGIN + 4}" text-anchor="end" fill="#1f77b4">{high:.0f}W</text>\n'
+        f'<text x="{_CHART_MARGIN - 4}" y="{bottom}" text-anchor="end" fill="#1f77b4">{low:.0f}W</text>\n'
+        f'<text x="{right + 4}" y="{_CHART_MARGIN + 4}" fill="#2ca02c">100%</text>\n'
+        f'<text x="{right + 4}" y="{bottom}" fill="#2ca02c">0%</text>\n'
+        f'<text x="{_CHART_MARGIN}" y="{bottom + 16}">{start_label}</text>\n'
+        f'<text x="{right}" y="{bottom + 16}" text-anchor="end">{end_label}</text>\n'
+        "</svg>"
+    )
+
+
+def _row(label: str, value: str) -> str:
+    """Format a two-column table row (escaped)."""
+    return f"<tr><th>{escape(label)}</th><td>{escape(value)}</td></tr>"
+
+
+def _local(iso: str | None) -> str:
+    """Format an ISO timestamp in local time for display."""
+    return datetime.fromisoformat(iso).astimezone().strftime("%Y-%m-%d %H:%M") if iso else "N/A"
+
+
+def render_report(report: Report) -> str:
+    """Render a report as a self-contained HTML document.
+
+    Args:
+        report: Computed report
+
+    Returns:
+        HTML text (no external stylesheets, scripts, or images)
+    """
+    stats = report.stats
+    since = report.since.astimezone().strftime("%Y-%m-%d %H:%M") if report.since else _local(stats.earliest)
+    until = report.until.astimezone().strftime("%Y-%m-%d %H:%M") if report.until else _local(stats.latest)
+
+    stats_rows = "\n".join(
+        [
+            _row("Readings", f"{stats.count:,}"),
+            _row("First reading", _local(stats.earliest)),
+            _row("Last reading", _local(stats.latest)),
+            _row("Average power", f"{stats.avg_watts:.2f} W"),
+            _row("Power range", f"{stats.min_watts:.2f} W to {stats.max_watts:.2f} W"),
+            _row("Average battery", f"{stats.avg_battery:.1f}%"),
+        ]
+    )
+
+    if report.chargers:
+        charger_rows = "\n".join(
+            "<tr>"
+            f"<td>{escape(c.name)}</td><td>{escape(c.manufacturer or '-')}</td>"
+            f'<td class="num">{c.readings:,}</td><td class="num">{c.max_watts_negotiated} W</td>'
+            f'<td class="num">{c.avg_watts_actual:.2f} W</td>'
+            f"<td>{escape(_local(c.first_seen))}</td><td>{escape(_local(c.last_seen))}</td>"
+            "</tr>"
+            for c in report.chargers
+        )
+        chargers = (
+            "<table>\n<tr><th>Charger</th><th>Manufacturer</th><th class=\"num\">Readings</th>"
+            '<th class="num">Max negotiated</th><th class="num">Average power</th>'
+            "<th>First seen</th><th>Last seen</th></tr>\n"
+            f"{charger_rows}\n</table>"
+        )
+    else:
+        chargers = "<p>No charger was connected in this range.</p>"
+
+    health = report.health
+    health_rows = [
+        _row("Max capacity", f"{health.last_max_capacity} mAh"),
+        _row("Change over range", f"{health.change_mah:+d} mAh"),
+        _row("Design capacity", f"{health.design_capacity} mAh" if health.design_capacity else "Not recorded"),
+    ]
+    if health.health_percent is not None:
+        health_rows.append(_row("Health", f"{health.health_percent:.1f}% of design capacity"))
+    health_table = "\n".join(health_rows)
+    region_legend = "".join(
+        f'<span class="{css_class}">&#9632; {escape(label)}</span>' for css_class, _, label in CHART_REGIONS.values()
+    )
+
+    title = f"Power report: {since} to {until}"
+    return (
+        "<!DOCTYPE html>\n"
+        '<html lang="en">\n<head>\n<meta charset="utf-8">\n'
+        f"<title>{escape(title)}</title>\n<style>{_STYLE}</style>\n</head>\n<body>\n"
+        f"<h1>{escape(title)}</h1>\n"
+        f'<p class="meta">Generated {escape(report.generated_at.astimezone().strftime("%Y-%m-%d %H:%M"))} '
+        "by powermonitor</p>\n"
+        "<h2>Power and battery</h2>\n"
+        f"{render_chart_svg(report.readings)}\n"
+        '<p class="legend"><span class="power">&#9644; Power (W)</span>'
+        '<span class="battery">&#9644; Battery (%)</span>'
+        f"{region_legend}</p>\n"
+        "<h2>Statistics</h2>\n"
+        f"<table>\n{stats_rows}\n</table>\n"
+        "<h2>Chargers</h2>\n"
+        f"{chargers}\n"
+        "<h2>Battery health</h2>\n"
+        f"<table>\n{health_table}\n</table>\n"
+        "</body>\n</html>\n"
+    )
@@ -0,0 +1,159 @@
+"""Time-of-day sampling schedules (e.g. every minute during work hours, every 10 minutes overnight)."""
+
+import re
+import time
+from dataclasses import dataclass
+
+MIN_DELAY = 0.1  # seconds; floor for delays computed around DST jumps
+
+_WINDOW_RE = re.compile(r"^(\d{1,2}):(\d{2})-(\d{1,2}):(\d{2})$")
+_INTERVAL_RE = re.compile(r"^(\d+(?:\.\d+)?)\s*([smh]?)$")
+_UNIT_SECONDS = {"": 1, "s": 1, "m": 60, "h": 3600}
+_DAY = 24 * 3600
+
+
+def _parse_clock(hours: str, minutes: str, text: str) -> int:
+    """Convert HH:MM to seconds since midnight."""
+    h, m = int(hours), int(minutes)
+    if h > 24 or m > 59 or (h == 24 and m != 0):
+        raise ValueError(f"Invalid time in schedule window {text!r}")
+    return h * 3600 + m * 60
+
+
+def parse_interval(text: str) -> float:
+    """Parse an interval such as "30", "30s", "5m", or "1h".
+
+    Args:
+        text: Number with optional unit (s, m, h); bare numbers are seconds
+
+    Returns:
+        Interval in seconds
+
+    Raises:
+        ValueError: If the text isn't a positive interval
+    """
+    match = _INTERVAL_RE.match(text.strip())
+    if match is None:
+        raise ValueError(f"Invalid interval {text!r}; expected e.g. 30s, 5m, or 1h")
+    seconds = float(match.group(1)) * _UNIT_SECONDS[match.group(2)]
+    if seconds <= 0:
+        raise ValueError(f"Interval must be positive, got {text!r}")
+    return seconds
+
+
+@dataclass(frozen=True, slots=True)
+class ScheduleWindow:
+    """Sampling interval for a daily wall-clock window.
+
+    Attributes:
+        start: Window start in seconds since local midnight
+        end: Window end in seconds since local midnight (less than start if the window wraps midnight)
+        interval: Seconds between samples inside the window
+    """
+
+    start: int
+    end: int
+    interval: float
+
+    def contains(self, second_of_day: int) -> bool:
+        """Check whether a wall-clock time falls in this window (start inclusive, end exclusive)."""
+        if self.start <= self.end:
+            return self.start <= second_of_day < self.end
+        return second_of_day >= self.start or second_of_day < self.end
+
+
+@dataclass(frozen=True, slots=True)
+class SampleSchedule:
+    """Sampling intervals by local time of day.
+
+    Attributes:
+        windows: Windows checked in order; the first containing the current time wins
+        default_interval: Interval outside every window
+    """
+
+    windows: tuple[ScheduleWindow, ...]
+    default_interval: float
+
+    def interval_at(self, second_of_day: int) -> float:
+        """Get the sampling interval for a wall-clock time.
+
+        Args:
+            second_of_day: Seconds since local midnight
+
+        Returns:
+            Interval in seconds
+        """
+        for window in self.windows:
+            if window.contains(second_of_day):
+                return window.interval
+        return self.default_interval
+
+    def next_delay(self, now: float | None = None) -> float:
+        """Get the delay until the next sample.
+
+        The delay is the current window's interval, cut short at the next window
+        boundary so each window starts with a sample. Boundaries are local wall-clock
+        times converted with the system time zone, so windows follow the clock across
+        DST changes.
+
+        Args:
+            now: Current Unix time (defaults to time.time())
+
+        Returns:
+            Seconds to wait (at least MIN_DELAY)
+        """
+        now = time.time() if now is None else now
+        local = time.localtime(now)
+        second_of_day = local.tm_hour * 3600 + local.tm_min * 60 + local.tm_sec
+        delay = self.interval_at(second_of_day)
+
+        boundaries = sorted({w.start % _DAY for w in self.windows} | {w.end % _DAY for w in self.windows})
+        for boundary in boundaries + [b + _DAY for b in boundaries]:
+            if boundary <= second_of_day:
+                continue
+            days, seconds = divmod(boundary, _DAY)
+            # tm_isdst=-1 lets mktime resolve the offset in effect at the boundary
+            boundary_time = time.mktime(
+                (local.tm_year, local.tm_mon, local.tm_mday + days, seconds // 3600, seconds % 3600 // 60, 0, 0, 0, -1)
+            )
+            if boundary_time > now:
+                delay = min(delay, boundary_time - now)
+                break
+        return max(delay, MIN_DELAY)
+
+
+def parse_schedule(spec: str, default_interval: float) -> SampleSchedule:
+    """Parse a schedule such as "09:00-18:00=1m, 18:00-23:00=5m, *=10m".
+
+    Entries are comma-separated `HH:MM-HH:MM=interval` windows in local time; a window
+    may wrap midnight (22:00-06:00). `*=interval` sets the interval outside every window.
+
+    Args:
+        spec: Schedule text
+        default_interval: Interval outside every window when the spec has no `*` entry
+
+    Returns:
+        Parsed SampleSchedule
+
+    Raises:
+        ValueError: If an entry is malformed
+    """
+    windows: list[ScheduleWindow] = []
+    for entry in filter(None, (part.strip() for part in spec.split(","))):
+        target, sep, interval_text = entry.partition("=")
+        if not sep:
+            raise ValueError(f"Invalid schedule entry {entry!r}; expected HH:MM-HH:MM=interval or *=interval")
+        target = target.strip()
+        interval = parse_interval(interval_text)
+        if target == "*":
+            default_interval = interval
+            continue
+        match = _WINDOW_RE.match(target)
+        if match is None:
+            raise ValueError(f"Invalid schedule window {target!r}; expected HH:MM-HH:MM")
+        start = _parse_clock(match.group(1), match.group(2), target)
+        end = _parse_clock(match.group(3), match.group(4), target)
+        if start % _DAY == end % _DAY:
+            raise ValueError(f"Schedule window {target!r} is empty")
+        windows.append(ScheduleWindow(start=start % _DAY, end=end % _DAY, interval=interval))
+    return SampleSchedule(windows=tuple(windows), default_interval=default_interval)
@@ -0,0 +1,97 @@
+"""Charge session detection: split the reading stream into continuous charges."""
+
+from .models import ChargeSession
+from .models import PowerDirection
+from .models import PowerReading
+
+# Shorter charges are flickers (e.g. the charging flag dropping during USB-PD renegotiation)
+MIN_SESSION_SECONDS = 60.0
+
+
+class ChargeSessionTracker:
+    """Detect charge sessions from consecutive readings.
+
+    A session starts with the first charging reading and ends with the first reading
+    that is no longer charging (unplugged, idle on AC, or fully charged). Energy is
+    the charge power integrated over the session with the trapezoidal rule.
+
+    Usage:
+        tracker = ChargeSessionTracker()
+        session = tracker.observe(reading)  # ChargeSession when one just ended
+        ...
+        session = tracker.finish()  # when collection stops mid-charge
+    """
+
+    def __init__(self, min_seconds: float = MIN_SESSION_SECONDS):
+        """Initialize tracker.
+
+        Args:
+            min_seconds: Sessions shorter than this are discarded (must be >= 0)
+        """
+        if min_seconds < 0:
+            raise ValueError(f"min_seconds must be non-negative, got {min_seconds}")
+        self.min_seconds = min_seconds
+        self._first: PowerReading | None = None
+        self._last: PowerReading | None = None
+        self._energy_wh = 0.0
+
+    @property
+    def active(self) -> bool:
+        """Whether a charge session is in progress."""
+        return self._first is not None
+
+    def observe(self, reading: PowerReading) -> ChargeSession | None:
+        """Feed the next reading.
+
+        Args:
+            reading: Latest PowerReading (timestamps must not go backwards)
+
+        Returns:
+            The session that this reading ended, or None
+        """
+        charging = reading.direction is PowerDirection.CHARGING and not reading.fully_charged
+        if self._first is None or self._last is None:
+            if charging:
+                self._first = self._last = reading
+                self._energy_wh = 0.0
+            return None
+
+        self._energy_wh += _energy_between(self._last, reading)
+        self._last = reading
+        if charging:
+            return None
+        return self._close(self._first, reading)
+
+    def finish(self) -> ChargeSession | None:
+        """End the session in progress at the last reading seen.
+
+        Returns:
+            The session, or None if none was in progress (or it was too short)
+        """
+        if self._first is None or self._last is None:
+            return None
+        return self._close(self._first, self._last)
+
+    def _close(self, first: PowerReading, last: PowerReading) -> ChargeSession | None:
+        """Build the finished session and reset for the next one."""
+        session = ChargeSession(
+            started_at=first.timestamp,
+            ended_at=last.timestamp,
+            start_percent=first.battery_percent,
+            end_percent=last.battery_percent,
+            total_wh=self._energy_wh,
+        )
+        self._first = self._last = None
+        self._energy_wh = 0.0
+        if session.duration.total_seconds() < self.min_seconds:
+            return None
+        return session
+
+
+def _energy_between(previous: PowerReading, reading: PowerReading) -> float:
+    """Energy charged between two readings (Wh), by the trapezoidal rule."""
+    hours = (reading.timestamp - previous.timestamp).total_seconds() / 3600
+    if hours <= 0:
+        return 0.0
+    # Only power flowing into the battery counts; the reading that ends a session may be draining
+    return (max(previous.watts_actual, 0.0) + max(reading.watts_actual, 0.0)) / 2 * hours
@@ -0,0 +1,155 @@
+"""Stream readings as NDJSON to local clients over a Unix domain socket (`powermonitor socket`)."""
+
+import json
+import socket
+import stat
+from pathlib import Path
+
+from loguru import logger
+
+from .models import PowerReading
+
+
+def default_socket_path() -> Path:
+    """Get the default socket path.
+
+    Returns:
+        Path to ~/.powermonitor/powermonitor.sock
+    """
+    return Path.home() / ".powermonitor" / "powermonitor.sock"
+
+
+class SocketInUseError(Exception):
+    """Another process is already serving on the socket path, or the path is not a socket."""
+
+
+class ReadingBroadcaster:
+    """Unix domain socket server that sends every reading to all connected clients.
+
+    Each reading is one JSON object per line (the same shape as NDJSON export).
+    Clients that disconnect or stop reading are dropped without affecting the others.
+    The socket file is removed on close.
+
+    Usage:
+        with ReadingBroadcaster(path) as server:
+            while True:
+                server.accept_pending()
+                server.broadcast(collector.collect())
+    """
+
+    def __init__(self, path: Path):
+        """Initialize the broadcaster (call open() or use as a context manager to start listening).
+
+        Args:
+            path: Socket file path
+        """
+        self.path = path
+        self._server: socket.socket | None = None
+        self._clients: list[socket.socket] = []
+
+    @property
+    def client_count(self) -> int:
+        """Number of connected clients."""
+        return len(self._clients)
+
+    def open(self) -> None:
+        """Bind and listen on the socket path.
+
+        A socket file left behind by a crashed server is replaced.
+
+        Raises:
+            SocketInUseError: If a server is already listening there or the path is not a socket
+            OSError: If the socket can't be created
+        """
+        self._remove_stale_socket()
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        server = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            server.bind(str(self.path))
+            server.listen()
+            server.setblocking(False)
+        except OSError:
+            server.close()
+            raise
+        self._server = server
+        logger.info(f"Serving readings on {self.path}")
+
+    def _remove_stale_socket(self) -> None:
+        """Remove a leftover socket file nobody is listening on."""
+        try:
+            mode = self.path.lstat().st_mode
+        except FileNotFoundError:
+            return
+        if not stat.S_ISSOCK(mode):
+            raise SocketInUseError(f"{self.path} exists and is not a socket")
+
+        probe = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+        try:
+            probe.connect(str(self.path))
+        except OSError:
+            logger.debug(f"Removing stale socket {self.path}")
+            self.path.unlink(missing_ok=True)
+        else:
+            raise SocketInUseError(f"Another powermonitor is already serving on {self.path}")
+        finally:
+            probe.close()
+
+    def accept_pending(self) -> int:
+        """Accept every client waiting to connect, without blocking.
+
+        Returns:
+            Number of new clients
+        """
+        if self._server is None:
+            return 0
+        accepted = 0
+        while True:
+            try:
+                client, _ = self._server.accept()
+            except BlockingIOError:
+                return accepted
+            # Never let one slow client stall the collection loop
+            client.setblocking(False)
+            self._clients.append(client)
+            accepted += 1
+            logger.debug(f"Socket client connected ({len(self._clients)} total)")
+
+    def broadcast(self, reading: PowerReading) -> int:
+        """Send a reading to every connected client.
+
+        Args:
+            reading: Reading to send
+
+        Returns:
+            Number of clients the reading was delivered to
+        """
+        line = (json.dumps(reading.to_dict()) + "\n").encode()
+        delivered = []
+        for client in self._clients:
+            try:
+                client.sendall(line)
+            except OSError as e:
+                # Disconnected (BrokenPipe/ConnectionReset) or not reading (buffer full)
+                logger.debug(f"Dropping socket client: {e}")
+                client.close()
+            else:
+                delivered.append(client)
+        self._clients = delivered
+        return len(delivered)
+
+    def close(self) -> None:
+        """Disconnect all clients, stop listening, and remove the socket file."""
+        for client in self._clients:
+            client.close()
+        self._clients = []
+        if self._server is not None:
+            self._server.close()
+            self._server = None
+            self.path.unlink(missing_ok=True)
+
+    def __enter__(self) -> "ReadingBroadcaster":
+        self.open()
+        return self
+
+    def __exit__(self, *exc_info: object) -> None:
+        self.close()
@@ -0,0 +1,69 @@
+"""Smoothing of transient negotiated-power dips during USB-PD renegotiation."""
+
+from datetime import datetime
+
+from .models import PowerReading
+
+DEFAULT_CARRY_FORWARD_SECONDS = 10.0
+
+
+class NegotiatedWattsStabilizer:
+    """Carry forward the last non-zero negotiated wattage across short dips.
+
+    While a USB-PD charger renegotiates, ioreg can briefly report 0W negotiated even though
+    the adapter is still connected. This state machine remembers the last non-zero value and
+    reuses it for readings that arrive within ``window_seconds`` while external power is
+    connected. The raw ``watts_negotiated`` value is never modified.
+
+    Usage:
+        stabilizer = NegotiatedWattsStabilizer()
+        reading = stabilizer.apply(collector.collect())
+    """
+
+    def __init__(self, window_seconds: float = DEFAULT_CARRY_FORWARD_SECONDS):
+        """Initialize stabilizer.
+
+        Args:
+            window_seconds: How long a non-zero value may be carried forward (must be >= 0)
+        """
+        if window_seconds < 0:
+            raise ValueError(f"window_seconds must be non-negative, got {window_seconds}")
+        self.window_seconds = window_seconds
+        self._last_watts: int | None = None
+        self._last_seen: datetime | None = None
+
+    def apply(self, reading: PowerReading) -> PowerReading:
+        """Set ``watts_negotiated_stable`` on a reading.
+
+        Args:
+            reading: Freshly collected PowerReading
+
+        Returns:
+            The same PowerReading with ``watts_negotiated_stable`` populated
+        """
+        if reading.watts_negotiated > 0:
+            self._last_watts = reading.watts_negotiated
+            self._last_seen = reading.timestamp
+            reading.watts_negotiated_stable = reading.watts_negotiated
+            return reading
+
+        if not reading.external_connected:
+            # Adapter unplugged: the previous value no longer applies
+            self.reset()
+        elif self._within_window(reading.timestamp):
+            reading.watts_negotiated_stable = self._last_watts
+            return reading
+
+        reading.watts_negotiated_stable = reading.watts_negotiated
+        return reading
+
+    def reset(self) -> None:
+        """Forget the last observed non-zero value."""
+        self._last_watts = None
+        self._last_seen = None
+
+    def _within_window(self, timestamp: datetime) -> bool:
+        if self._last_watts is None or self._last_seen is None:
+            return False
+        elapsed = (timestamp - self._last_seen).total_seconds()
+        return 0 <= elapsed <= self.window_seconds
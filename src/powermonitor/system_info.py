@@ -0,0 +1,123 @@
+"""Runtime platform and build information for powermonitor."""
+
+import platform
+import subprocess
+import sys
+from importlib.metadata import PackageNotFoundError
+from importlib.metadata import version
+
+
+def get_version() -> str:
+    """Get the installed powermonitor package version.
+
+    Returns:
+        Version string, or "unknown" when running from an uninstalled source tree
+    """
+    try:
+        return version("powermonitor")
+    except PackageNotFoundError:
+        return "unknown"
+
+
+def get_macos_version() -> str | None:
+    """Detect the running macOS version.
+
+    Returns:
+        macOS version string (e.g., "14.5"), or None if not running on macOS
+    """
+    if sys.platform != "darwin":
+        return None
+
+    release = platform.mac_ver()[0]
+    return release or None
+
+
+def _sysctl(name: str) -> str | None:
+    """Read a sysctl value.
+
+    Args:
+        name: sysctl name, e.g. "hw.optional.arm64"
+
+    Returns:
+        The value, or None if the name doesn't exist here or sysctl can't run
+    """
+    try:
+        result = subprocess.run(["sysctl", "-in", name], capture_output=True, text=True, check=True, timeout=5)
+    except (subprocess.SubprocessError, OSError):
+        return None
+    return result.stdout.strip() or None
+
+
+def get_hardware_model() -> str | None:
+    """Get the Mac model identifier.
+
+    Returns:
+        e.g. "Mac14,5", or None when not on macOS or sysctl can't tell
+    """
+    if sys.platform != "darwin":
+        return None
+    return _sysctl("hw.model")
+
+
+def running_under_rosetta() -> bool:
+    """Check whether this process is an x86_64 build translated by Rosetta 2.
+
+    Returns:
+        True if sysctl.proc_translated is 1 (only exists on Apple Silicon)
+    """
+    if sys.platform != "darwin":
+        return False
+    return _sysctl("sysctl.proc_translated") == "1"
+
+
+def native_architecture() -> str:
+    """Detect the hardware architecture, which differs from the process's under Rosetta.
+
+    Returns:
+        "arm64" on Apple Silicon (even from a translated x86_64 process), otherwise platform.machine()
+    """
+    if sys.platform == "darwin" and _sysctl("hw.optional.arm64") == "1":
+        return "arm64"
+    return platform.machine()
+
+
+def describe_architecture() -> str:
+    """Describe the process and hardware architectures for diagnostics.
+
+    Returns:
+        e.g. "arm64", or "x86_64 under Rosetta (native arm64)"
+    """
+    process = platform.machine()
+    native = native_architecture()
+    if running_under_rosetta():
+        return f"{process} under Rosetta (native {native})"
+    return process if process == native else f"{process} (native {native})"
+
+
+def iokit_available() -> bool:
+    """Check whether the IOKit/SMC backend can be loaded on this system.
+
+    Returns:
+        True if the IOKit framework bindings load successfully
+    """
+    try:
+        from .collector.iokit import bindings  # noqa: F401
+    except (ImportError, OSError):
+        return False
+    return True
+
+
+def get_system_info() -> dict[str, str]:
+    """Collect version and platform details for bug reports.
+
+    Returns:
+        Ordered mapping of label to value
+    """
+    return {
+        "powermonitor": get_version(),
+        "Python": platform.python_version(),
+        "Platform": f"{sys.platform}-{platform.machine()}",
+        "macOS": get_macos_version() or "N/A",
+        "Architecture": describe_architecture(),
+        "IOKit backend": "available" if iokit_available() else "unavailable",
+    }
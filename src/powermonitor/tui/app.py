@@ -2,22 +2,98 @@
 
 import asyncio
 import contextlib
+from collections import deque
+from datetime import UTC
+from datetime import datetime
+from pathlib import Path
 
+from rich.markup import escape
+from textual import events
 from textual.app import App
 from textual.app import ComposeResult
 from textual.binding import Binding
 from textual.containers import Vertical
 from textual.widgets import Footer
 from textual.widgets import Header
+from textual.widgets import Static
 
+from ..adaptive import AdaptiveInterval
+from ..averaging import ReadingAverager
+from ..collector import PowerCollector
 from ..collector import default_collector
+from ..config import TITLE_FORMATS
 from ..config import PowerMonitorConfig
+from ..config_loader import save_title_format
 from ..database import Database
+from ..degradation import DEGRADED_READINGS
+from ..degradation import ChargerPeak
+from ..export import EXTENSION_FORMATS
+from ..export import export_readings
+from ..glyphs import EMOJI
+from ..glyphs import Glyphs
+from ..hooks import EventHooks
 from ..models import PowerReading
+from ..replay import ReplayCollector
+from ..schedule import parse_schedule
+from ..sessions import ChargeSessionTracker
+from ..stabilizer import NegotiatedWattsStabilizer
 from .widgets import ChartWidget
+from .widgets import ExportDialog
 from .widgets import LiveDataPanel
 from .widgets import StatsPanel
 
+# Terminal heights (rows) at which panels are collapsed, least important first
+MIN_TERMINAL_HEIGHT = 12  # Below this only a "terminal too small" message is shown
+CHART_MIN_HEIGHT = 20  # Below this the chart is hidden
+STATS_MIN_HEIGHT = 32  # Below this the statistics panel is hidden
+
+SHUTDOWN_TIMEOUT = 10.0  # Seconds to let an in-flight collection finish on quit before cancelling it
+
+
+def panels_for_height(height: int) -> set[str]:
+    """Decide which panels fit in a terminal of the given height.
+
+    The live data panel is always kept; the statistics panel is dropped first,
+    then the chart.
+
+    Args:
+        height: Terminal height in rows
+
+    Returns:
+        Set of panel IDs to display (empty if the terminal is too small for anything)
+    """
+    if height < MIN_TERMINAL_HEIGHT:
+        return set()
+
+    panels = {"live-data"}
+    if height >= CHART_MIN_HEIGHT:
+        panels.add("chart")
+    if height >= STATS_MIN_HEIGHT:
+        panels.add("stats")
+    return panels
+
+
+def format_title(reading: PowerReading, title_format: str, glyphs: Glyphs = EMOJI) -> str:
+    """Render the compact header summary for a reading.
+
+    Args:
+        reading: Latest PowerReading
+        title_format: One of TITLE_FORMATS ("watts", "percent", "watts+percent")
+        glyphs: Icons to use (ASCII with --ascii)
+
+    Returns:
+        Summary string such as "⚡ 45.2W", "🔌 100%" or "🔋 8.0W · 74%"
+    """
+    icon = glyphs.icon(reading.direction)
+    watts = f"{abs(reading.watts_actual):.1f}W"
+    percent = f"{reading.battery_percent}%"
+
+    if title_format == "percent":
+        return f"{icon} {percent}"
+    if title_format == "watts+percent":
+        return f"{icon} {watts} {glyphs.separator} {percent}"
+    return f"{icon} {watts}"
+
 
 class PowerMonitorApp(App):
     """powermonitor TUI application with auto-updating power data.
@@ -49,11 +125,19 @@ class PowerMonitorApp(App):
     }
 
     #chart {
-        height: 20;
+        height: 1fr;
+        min-height: 8;
+        max-height: 22;
         border: solid blue;
         padding: 1;
         margin: 1;
     }
+
+    #too-small {
+        display: none;
+        content-align: center middle;
+        height: 1fr;
+    }
     """
 
     BINDINGS = [
@@ -61,16 +145,62 @@ class PowerMonitorApp(App):
         Binding("r", "refresh", "Refresh", key_display="R"),
         Binding("escape", "quit", "Quit", show=False),
         Binding("c", "clear_history", "Clear History", key_display="C"),
+        Binding("t", "cycle_title_format", "Title Format", key_display="T"),
+        Binding("e", "export_session", "Export", key_display="E"),
+        Binding("p", "toggle_pause", "Pause/Resume", key_display="P"),
     ]
 
     TITLE = "powermonitor - macOS Power Monitoring"
 
-    def __init__(self, config: PowerMonitorConfig | None = None, **kwargs) -> None:
+    def __init__(
+        self,
+        config: PowerMonitorConfig | None = None,
+        collector: PowerCollector | None = None,
+        label: str | None = None,
+        verbose: bool = False,
+        hooks: EventHooks | None = None,
+        reference_voltage: float | None = None,
+        deadzone: float = 0.0,
+        glyphs: Glyphs = EMOJI,
+        **kwargs,
+    ) -> None:
         super().__init__(**kwargs)
         self.config = config or PowerMonitorConfig()
-        self.collector = default_collector()
-        self.database = Database(self.config.database_path)
+        self.collector = collector or default_collector(config=self.config.collector)
+        # A replay brings its own timing and can't be sampled on demand (see ReplayCollector)
+        self.replay = self.collector if isinstance(self.collector, ReplayCollector) else None
+        self.label = label
+        self.verbose = verbose
+        self.hooks = hooks
+        self.reference_voltage = reference_voltage
+        self.deadzone = deadzone
+        self.glyphs = glyphs
+        self.title_format = self.config.title_format
+        # Checked by the collection loop on every tick; manual refresh (R) still collects
+        self.paused = False
+        self._last_reading: PowerReading | None = None
+        self._session_started = datetime.now(UTC)
+        if self.replay and self.replay.started:
+            # Replayed readings keep their recorded timestamps, so the session starts with the recording
+            self._session_started = self.replay.started
+        # Live chart window, newest first; the oldest reading drops off once full
+        self._chart_window: deque[PowerReading] = deque(maxlen=self.config.chart_history_limit)
+        self.database = Database(self.config.database_path, max_rows=self.config.max_db_rows)
+        self.stabilizer = NegotiatedWattsStabilizer()
+        self.charge_sessions = ChargeSessionTracker()
+        self.schedule = (
+            parse_schedule(self.config.schedule, self.config.collection_interval) if self.config.schedule else None
+        )
+        self.adaptive = (
+            AdaptiveInterval(self.config.collection_interval, self.config.max_interval)
+            if self.config.max_interval
+            else None
+        )
+        # Samples are averaged into one stored reading per store_interval when it is set
+        self.averager = ReadingAverager(self.config.store_interval) if self.config.store_interval else None
+        self._next_delay = self.config.collection_interval
         self._collector_task: asyncio.Task | None = None
+        self._stop_requested = asyncio.Event()
 
     def compose(self) -> ComposeResult:
         """Compose the TUI layout.
@@ -82,14 +212,43 @@ class PowerMonitorApp(App):
         """
         yield Header()
         yield Vertical(
-            LiveDataPanel(id="live-data"),
+            LiveDataPanel(
+                verbose=self.verbose,
+                thresholds=self.config.thresholds,
+                reference_voltage=self.reference_voltage,
+                deadzone=self.deadzone,
+                glyphs=self.glyphs,
+                id="live-data",
+            ),
             StatsPanel(id="stats"),
-            ChartWidget(id="chart"),
+            ChartWidget(series=self.config.chart_series, id="chart"),
+            Static(
+                f"[bold yellow]Terminal too small[/bold yellow]\nResize to at least {MIN_TERMINAL_HEIGHT} rows",
+                id="too-small",
+            ),
         )
         yield Footer()
 
+    def on_resize(self, event: events.Resize) -> None:
+        """Re-layout panels whenever the terminal is resized."""
+        self._apply_layout(event.size.height)
+
+    def _apply_layout(self, height: int) -> None:
+        """Show or hide panels so the layout fits the terminal height.
+
+        Args:
+            height: Terminal height in rows
+        """
+        visible = panels_for_height(height)
+        # query() rather than query_one(): resize events can arrive before the layout is composed
+        for panel_id in ("live-data", "stats", "chart"):
+            self.query(f"#{panel_id}").set(display=panel_id in visible)
+        self.query("#too-small").set(display=not visible)
+
     def on_mount(self) -> None:
         """Start background data collection when app mounts."""
+        self._apply_layout(self.size.height)
+
         # Start periodic data collection
         self._collector_task = asyncio.create_task(self._collection_loop())
 
@@ -97,11 +256,14 @@ class PowerMonitorApp(App):
         self.refresh_all_data()
 
     async def on_unmount(self) -> None:
-        """Clean up when app unmounts."""
-        if self._collector_task:
-            self._collector_task.cancel()
-            with contextlib.suppress(asyncio.CancelledError):
-                await self._collector_task
+        """Clean up when app unmounts.
+
+        Stops collection, saves the averaged samples and charge session still in
+        progress, then closes the database.
+        """
+        await self._stop_collection()
+        self._save_pending_samples()
+        self._save_open_charge_session()
 
         # Close database resources
         self.database.close()
@@ -109,17 +271,55 @@ class PowerMonitorApp(App):
     async def _collection_loop(self) -> None:
         """Background loop for periodic power data collection.
 
-        Runs every collection_interval seconds, collecting data and updating UI.
+        Runs every collection_interval seconds (or as the sampling schedule, adaptive
+        interval, or replay says), collecting data and updating UI, until
+        _stop_requested is set. Ticks are skipped while paused.
         """
         while True:
+            if self.replay:
+                delay = self.replay.next_delay()
+            else:
+                delay = self.schedule.next_delay() if self.schedule else self._next_delay
+            if delay is None:
+                # The replay has run out of readings: keep showing the last one until quit
+                self.notify("Replay finished", timeout=5)
+                return
+            try:
+                # Sleep, but wake immediately when shutdown is requested
+                await asyncio.wait_for(self._stop_requested.wait(), timeout=delay)
+                break
+            except TimeoutError:
+                pass
+            except asyncio.CancelledError:
+                break
+            if self.paused:
+                continue
+
             try:
-                await asyncio.sleep(self.config.collection_interval)
                 await self._collect_and_update()
             except asyncio.CancelledError:
                 break
             except Exception as e:
                 self.notify(f"Collection error: {e}", severity="error", timeout=5)
 
+    async def _stop_collection(self) -> None:
+        """Stop the collection loop, letting an in-flight collection finish and save.
+
+        The loop is signalled rather than cancelled so a reading that is being collected
+        or written is not abandoned mid-flight. It is only cancelled if it doesn't
+        stop within SHUTDOWN_TIMEOUT (e.g. a hung collector).
+        """
+        self._stop_requested.set()
+        task = self._collector_task
+        if task is None or task.done():
+            return
+
+        done, _ = await asyncio.wait({task}, timeout=SHUTDOWN_TIMEOUT)
+        if not done:
+            task.cancel()
+            with contextlib.suppress(asyncio.CancelledError):
+                await task
+
     async def _collect_and_update(self) -> None:
         """Collect power data and update all widgets.
 
@@ -128,11 +328,14 @@ class PowerMonitorApp(App):
         try:
             # Run blocking collector in executor
             loop = asyncio.get_event_loop()
-            reading = await loop.run_in_executor(None, self.collector.collect)
+            reading = await loop.run_in_executor(None, self._collect_reading)
 
             # Try to save to database, but continue updating UI even if it fails
             try:
-                await loop.run_in_executor(None, self.database.insert_reading, reading)
+                charger_peak = await loop.run_in_executor(None, self._save_reading, reading)
+                if charger_peak is not None and charger_peak.low_readings == DEGRADED_READINGS:
+                    # Only when it becomes degraded, not on every reading after that
+                    self.notify(escape(charger_peak.warning()), severity="warning", timeout=10)
             except Exception as db_error:
                 self.notify(
                     f"Warning: Failed to save reading to database: {db_error}",
@@ -143,15 +346,80 @@ class PowerMonitorApp(App):
             # Update all widgets (already on main thread after await)
             self._update_all_widgets(reading)
 
+            if self.adaptive is not None:
+                self._next_delay = self.adaptive.observe(reading)
+
         except Exception as e:
             self.notify(f"Failed to collect data: {e}", severity="error", timeout=5)
 
+    def _collect_reading(self) -> PowerReading:
+        """Collect a reading, apply derived-field smoothing, and tag it with the session label.
+
+        Returns:
+            PowerReading with watts_negotiated_stable and label populated
+        """
+        reading = self.stabilizer.apply(self.collector.collect())
+        if self.label is not None:
+            reading.label = self.label
+        return reading
+
+    def _save_reading(self, reading: PowerReading) -> ChargerPeak | None:
+        """Store a reading, the charge session it ended (if any), and its charger's peak power.
+
+        With a store interval, the sample is only added to the averaging window, and
+        nothing is stored until the window is complete.
+
+        Args:
+            reading: Collected PowerReading
+
+        Returns:
+            The charger's updated peak record, or None without a charger or when nothing was stored
+        """
+        if self.averager is not None:
+            reading = self.averager.add(reading)
+            if reading is None:
+                return None
+        return self._store(reading)
+
+    def _store(self, reading: PowerReading) -> ChargerPeak | None:
+        """Insert a reading and update the charge session and charger peak records it affects."""
+        session = self.charge_sessions.observe(reading)
+        self.database.insert_reading(reading)
+        if session is not None:
+            self.database.insert_charge_session(session)
+        return self.database.update_charger_peak(reading)
+
+    def _save_pending_samples(self) -> None:
+        """Store the average of a partly filled store interval when collection stops."""
+        reading = self.averager.flush() if self.averager is not None else None
+        if reading is None:
+            return
+        try:
+            self._store(reading)
+        except Exception as e:
+            self.notify(f"Warning: Failed to save pending readings: {e}", severity="warning", timeout=3)
+
+    def _save_open_charge_session(self) -> None:
+        """Store the charge session in progress when collection stops, so it isn't lost."""
+        session = self.charge_sessions.finish()
+        if session is None:
+            return
+        try:
+            self.database.insert_charge_session(session)
+        except Exception as e:
+            self.notify(f"Warning: Failed to save charge session: {e}", severity="warning", timeout=3)
+
     def _update_all_widgets(self, reading: PowerReading) -> None:
         """Update all widgets with new data.
 
         Args:
             reading: Latest PowerReading
         """
+        if self.hooks is not None:
+            self.hooks.observe(reading)
+        self._last_reading = reading
+        self._update_title()
+
         # Update live data panel
         live_panel = self.query_one("#live-data", LiveDataPanel)
         live_panel.update_reading(reading)
@@ -161,19 +429,22 @@ class PowerMonitorApp(App):
         stats_panel = self.query_one("#stats", StatsPanel)
         stats_panel.update_stats(stats)
 
-        # Update chart with last 60 readings
-        history = self.database.query_history(limit=self.config.chart_history_limit)
+        # Update chart with the last chart_history_limit readings
         chart = self.query_one("#chart", ChartWidget)
-        chart.update_chart(history)
+        if self.config.live_chart:
+            self._chart_window.appendleft(reading)
+            chart.update_chart(list(self._chart_window))
+        else:
+            chart.update_chart(self.database.query_history(limit=self.config.chart_history_limit))
 
     def refresh_all_data(self) -> None:
         """Force refresh all data (for 'r' key binding)."""
         try:
-            reading = self.collector.collect()
+            reading = self._collect_reading()
 
             # Try to save to database, but continue even if it fails
             try:
-                self.database.insert_reading(reading)
+                self._save_reading(reading)
             except Exception as db_error:
                 self.notify(
                     f"Warning: Failed to save reading: {db_error}",
@@ -187,7 +458,13 @@ class PowerMonitorApp(App):
             self.notify(f"Refresh failed: {e}", severity="error", timeout=5)
 
     def action_refresh(self) -> None:
-        """Handle refresh key binding (R)."""
+        """Handle refresh key binding (R).
+
+        Does nothing while replaying: the next reading comes on the recorded schedule.
+        """
+        if self.replay:
+            self.notify("Refresh is not available during a replay", timeout=2)
+            return
         self.run_worker(self._async_refresh, exclusive=True)
 
     async def _async_refresh(self) -> None:
@@ -201,31 +478,97 @@ class PowerMonitorApp(App):
         Clears all historical readings from database.
         """
         rows_deleted = self.database.clear_history()
+        self._chart_window.clear()
         self.notify(f"Cleared {rows_deleted} historical readings", timeout=3)
         # Refresh display
         self.refresh_all_data()
 
-    async def action_quit(self) -> None:
-        """Handle quit action (Q or ESC).
+    def action_cycle_title_format(self) -> None:
+        """Handle title format key binding (T).
 
-        Ensures background collection task is cancelled cleanly
-        and any in-flight data is saved before exiting.
+        Switches to the next header preset, re-renders it immediately, and saves it
+        to the config file so the next session starts with it.
         """
-        # Show shutting down notification
-        self.notify("Shutting down...", timeout=1)
+        index = TITLE_FORMATS.index(self.title_format)
+        self.title_format = TITLE_FORMATS[(index + 1) % len(TITLE_FORMATS)]
+        self._update_title()
+        try:
+            save_title_format(self.title_format)
+        except OSError as e:
+            self.notify(f"Title format: {self.title_format} (not saved: {e})", severity="warning", timeout=5)
+            return
+        self.notify(f"Title format: {self.title_format}", timeout=2)
+
+    def _update_title(self) -> None:
+        """Render the header summary of the latest reading, marked while collection is paused."""
+        title = format_title(self._last_reading, self.title_format, self.glyphs) if self._last_reading else ""
+        if self.paused:
+            title = f"{self.glyphs.paused} {title or 'Paused'}"
+        self.sub_title = title
+
+    def action_toggle_pause(self) -> None:
+        """Handle pause key binding (P).
+
+        Stops or restarts periodic collection from the next tick, e.g. to keep ioreg and
+        SMC reads out of a benchmark. A partly filled store interval is saved on pause,
+        so its average doesn't span the gap.
+        """
+        self.paused = not self.paused
+        if self.paused:
+            self._save_pending_samples()
+        self._update_title()
+        self.notify("Monitoring paused" if self.paused else "Monitoring resumed", timeout=2)
 
-        # Cancel collection task if it's running
-        if self._collector_task and not self._collector_task.done():
-            self._collector_task.cancel()
-            with contextlib.suppress(asyncio.CancelledError):
-                await self._collector_task
+    def export_session(self, path: Path) -> int:
+        """Write the readings saved since the app started (or the whole replay) to a file.
 
-        # Give any pending database writes a moment to complete
-        # (executor tasks may still be running)
-        await asyncio.sleep(0.1)
+        Args:
+            path: Output file; its extension picks CSV (.csv, .tsv), JSON, or NDJSON (.ndjson, .jsonl)
 
-        # Close database resources
-        self.database.close()
+        Returns:
+            Number of readings written (0 writes no file)
 
-        # Now safe to exit
+        Raises:
+            ValueError: If the extension isn't a known export format
+            OSError: If the file can't be written
+        """
+        ext = path.suffix.lower()
+        format_type = EXTENSION_FORMATS.get(ext)
+        if format_type is None:
+            raise ValueError(f"Cannot detect format from extension '{ext}'; use .csv, .tsv, .json, or .ndjson")
+        readings = self.database.query_history(limit=None, since=self._session_started)
+        if not readings:
+            return 0
+        return export_readings(path, readings, format_type, delimiter="\t" if ext == ".tsv" else ",")
+
+    def action_export_session(self) -> None:
+        """Handle export key binding (E).
+
+        Asks for a file and saves this session's readings to it, like `powermonitor export`.
+        """
+        default_path = Path.cwd() / f"powermonitor-session-{self._session_started.astimezone():%Y%m%d-%H%M%S}.csv"
+        self.push_screen(ExportDialog(str(default_path)), self._finish_export)
+
+    def _finish_export(self, path: str | None) -> None:
+        """Export to the path chosen in the export dialog and report the outcome."""
+        if path is None:
+            return
+        output = Path(path).expanduser()
+        try:
+            count = self.export_session(output)
+        except (ValueError, OSError) as e:
+            self.notify(f"Export failed: {e}", severity="error", timeout=5)
+            return
+        if count == 0:
+            self.notify("Nothing to export: no readings saved this session yet", severity="warning", timeout=3)
+        else:
+            self.notify(f"Exported {count} readings to {output}", timeout=5)
+
+    async def action_quit(self) -> None:
+        """Handle quit action (Q or ESC).
+
+        Stopping the collection task (saving any in-flight reading) and closing the
+        database happen in on_unmount, which runs however the app exits.
+        """
+        self.notify("Shutting down...", timeout=1)
         self.exit()
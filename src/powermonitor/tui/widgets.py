@@ -1,22 +1,52 @@
 """Reactive widgets for powermonitor TUI."""
 
+from rich.markup import escape
 from textual.app import ComposeResult
+from textual.binding import Binding
 from textual.containers import Container
+from textual.containers import Vertical
+from textual.screen import ModalScreen
+from textual.widgets import Input
+from textual.widgets import Label
 from textual.widgets import Static
 from textual_plotext import PlotextPlot
 
+from ..config import Thresholds
+from ..config import parse_chart_series
+from ..glyphs import EMOJI
+from ..glyphs import Glyphs
+from ..labels import sanitize_label
+from ..models import AdapterSource
+from ..models import PowerDirection
 from ..models import PowerReading
+from ..models import ThermalState
+from ..numformat import CANONICAL
+from ..report import chart_regions
 
 
 class LiveDataPanel(Static):
     """Real-time power data display panel.
 
-    Shows current power, voltage, amperage, battery %, and charging status.
+    Shows current power, voltage, amperage, battery %, and charging status,
+    plus a warning line for each exceeded threshold.
     Updates reactively when new PowerReading is received.
     """
 
-    def __init__(self, **kwargs):
+    def __init__(
+        self,
+        verbose: bool = False,
+        thresholds: Thresholds | None = None,
+        reference_voltage: float | None = None,
+        deadzone: float = 0.0,
+        glyphs: Glyphs = EMOJI,
+        **kwargs,
+    ):
         super().__init__(**kwargs)
+        self.verbose = verbose
+        self.thresholds = thresholds or Thresholds()
+        self.reference_voltage = reference_voltage  # Also show power as amps at this voltage
+        self.deadzone = deadzone  # Show smaller power as 0.0W (display only)
+        self.glyphs = glyphs  # Emoji, or ASCII stand-ins with --ascii
         self.current_reading: PowerReading | None = None
 
     def update_reading(self, reading: PowerReading) -> None:
@@ -40,18 +70,25 @@ class LiveDataPanel(Static):
         r = self.current_reading
 
         # Status emoji and color
-        if r.is_charging:
-            status = "[green]⚡ Charging[/green]"
-        elif r.external_connected:
-            status = "[yellow]🔌 On AC Power (Not Charging)[/yellow]"
+        direction = r.direction
+        icon = self.glyphs.icon(direction)
+        if r.fully_charged and r.external_connected:
+            status = f"[green]{self.glyphs.battery} Fully charged ({r.battery_percent}%)[/green]"
+        elif direction is PowerDirection.CHARGING:
+            status = f"[green]{icon} Charging[/green]"
+        elif direction is PowerDirection.AC_IDLE and r.charge_inhibit_reason:
+            status = f"[yellow]{icon} Not charging: {escape(sanitize_label(r.charge_inhibit_reason))}[/yellow]"
+        elif direction is PowerDirection.AC_IDLE:
+            status = f"[yellow]{icon} On AC Power (Not Charging)[/yellow]"
+        elif direction is PowerDirection.AC_DISCHARGING:
+            status = f"[red]{icon} On AC but discharging (load exceeds adapter)[/red]"
         else:
-            status = "[red]🔋 On Battery[/red]"
+            status = f"[red]{icon} On Battery[/red]"
 
         # Format power display
-        if r.watts_negotiated > 0:
-            power_display = f"{r.watts_actual:.1f}W / {r.watts_negotiated}W max"
-        else:
-            power_display = f"{r.watts_actual:.1f}W"
+        power_display = CANONICAL.format_watts(r.watts_actual, self.reference_voltage, deadzone=self.deadzone)
+        if r.display_watts_negotiated > 0:
+            power_display += f" / {r.display_watts_negotiated}W max"
 
         # Build output
         lines = [
@@ -62,15 +99,68 @@ class LiveDataPanel(Static):
         ]
 
         # Optional charger info
-        if r.charger_name:
-            charger_display = r.charger_name
-            if r.charger_manufacturer:
-                charger_display += f" ({r.charger_manufacturer})"
+        # Device-reported text: flattened to one line and kept out of the markup
+        if charger_name := sanitize_label(r.charger_name):
+            charger_display = escape(charger_name)
+            if manufacturer := sanitize_label(r.charger_manufacturer):
+                charger_display += f" ({escape(manufacturer)})"
             lines.append(f"   Charger: {charger_display}")
+        if r.adapter_source is AdapterSource.SMC:
+            lines.append("   Adapter: detected from SMC input power (no ioreg details)")
+        if r.charger_port is not None:
+            lines.append(f"   Charger on port {escape(sanitize_label(r.charger_port))}")
+        if r.charger_certified is False:
+            lines.append(f"[yellow]   {self.glyphs.warning} Possibly uncertified charger[/yellow]")
+        if r.charging_throttled:
+            lines.append(
+                f"[yellow]   {self.glyphs.warning} Charging throttled (thermal/optimized): "
+                f"{r.amperage:.2f}A of {r.max_charge_current:.2f}A max[/yellow]"
+            )
+        if r.charge_efficiency is not None:
+            lines.append(f"   Efficiency: ~{r.charge_efficiency:.0f}%")
+
+        # SMC battery current replaced ioreg's coarser value; show both for comparison
+        if self.verbose and r.amperage_ioreg is not None:
+            lines.append(f"   Current (SMC / ioreg): {r.amperage:.3f}A / {r.amperage_ioreg:.3f}A")
+
+        # Charger identity is only useful when telling identical chargers apart
+        if self.verbose and (r.charger_serial or r.charger_firmware):
+            serial = escape(sanitize_label(r.charger_serial) or "N/A")
+            firmware = escape(sanitize_label(r.charger_firmware) or "N/A")
+            lines.append(f"   Charger S/N: {serial}  Firmware: {firmware}")
+
+        # Battery identity, for warranty and replacement decisions
+        if self.verbose and (r.battery_serial or r.battery_manufacture_date):
+            manufactured = r.battery_manufacture_date.isoformat() if r.battery_manufacture_date else "N/A"
+            serial = escape(sanitize_label(r.battery_serial) or "N/A")
+            lines.append(f"   Battery S/N: {serial}  Manufactured: {manufactured}")
+
+        if r.battery_temp is not None:
+            temperature = f"   Temperature: {r.battery_temp:.1f}°C"
+            if r.max_temp is not None and r.max_temp_sensor != "TB0T":
+                temperature += f" (hottest {r.max_temp:.1f}°C, {r.max_temp_sensor})"
+            lines.append(temperature)
+        if r.gpu_power is not None:
+            lines.append(f"   GPU: {r.gpu_power:.2f}W")
+
+        # Thermal pressure (only shown once it is known)
+        if r.thermal_state is not None:
+            color = {
+                ThermalState.NOMINAL: "green",
+                ThermalState.FAIR: "yellow",
+                ThermalState.SERIOUS: "red",
+                ThermalState.CRITICAL: "bold red",
+            }[r.thermal_state]
+            lines.append(f"   Thermal: [{color}]{r.thermal_state}[/{color}]")
+        if r.low_power_mode:
+            lines.append("   Low Power Mode: on")
 
         # Timestamp
         lines.append(f"   Time: {r.timestamp.strftime('%Y-%m-%d %H:%M:%S')}")
 
+        for warning in self.thresholds.check(r):
+            lines.append(f"[bold yellow]{self.glyphs.warning} {warning}[/bold yellow]")
+
         return "\n".join(lines)
 
     def on_mount(self) -> None:
@@ -125,14 +215,71 @@ class StatsPanel(Static):
         self.update(self._render_stats())
 
 
+# Line style per chart series: (legend label, color, value getter, unit group).
+# Series whose unit isn't watts go on the right-hand axis when watts are also charted.
+SERIES_STYLES = {
+    "watts": ("Power (W)", "red", lambda r: r.watts_actual, "W"),
+    "negotiated": ("Max Power (W)", "blue", lambda r: float(r.display_watts_negotiated), "W"),
+    "battery": ("Battery (%)", "green", lambda r: float(r.battery_percent), "%"),
+    "voltage": ("Voltage (V)", "cyan", lambda r: r.voltage, "V"),
+    "amperage": ("Current (A)", "magenta", lambda r: r.amperage, "A"),
+    "temp": ("Battery Temp (°C)", "yellow", lambda r: r.battery_temp, "°C"),
+}
+
+
+def chart_series_points(
+    readings: list[PowerReading], series: tuple[str, ...]
+) -> list[tuple[str, str, list[int], list[float], str]]:
+    """Compute the lines to draw for the selected chart series.
+
+    Args:
+        readings: Readings, newest first
+        series: Series names from config.CHART_SERIES
+
+    Returns:
+        (label, color, x, y, yside) per series with at least one value, oldest reading at x=0.
+        Readings missing a value (e.g. no battery temperature) are skipped for that series.
+    """
+    oldest_first = list(reversed(readings))
+    mixed_units = any(SERIES_STYLES[name][3] == "W" for name in series)
+    lines = []
+    for name in series:
+        label, color, getter, unit = SERIES_STYLES[name]
+        points = [(x, getter(r)) for x, r in enumerate(oldest_first) if getter(r) is not None]
+        if not points:
+            continue
+        yside = "right" if mixed_units and unit != "W" else "left"
+        lines.append((label, color, [x for x, _ in points], [y for _, y in points], yside))
+    return lines
+
+
+# Terminal charts can't shade a background, so a vertical line marks where each power source region starts
+REGION_LINE_COLORS = {"charging": "orange", "ac": "gray", "battery": "red"}
+
+
+def chart_region_changes(readings: list[PowerReading]) -> list[tuple[int, str]]:
+    """Find where the chart switches between charging, on AC, and on battery.
+
+    Args:
+        readings: Readings, newest first
+
+    Returns:
+        (x, region) for each region after the first, oldest reading at x=0
+    """
+    return [(first, region) for region, first, _ in chart_regions(list(reversed(readings)))[1:]]
+
+
 class ChartWidget(Container):
-    """Power chart widget using textual-plotext.
+    """Chart widget using textual-plotext.
 
-    Displays a line chart of power over time (last N readings).
+    Displays a line per selected series over time (last N readings). Power series use
+    the left axis; other metrics use the right axis when power is also charted.
+    Vertical lines mark plugging in, unplugging, and charging starting or stopping.
     """
 
-    def __init__(self, **kwargs):
+    def __init__(self, series: str = "watts,negotiated", **kwargs):
         super().__init__(**kwargs)
+        self.series = parse_chart_series(series)
         self.readings: list[PowerReading] = []
 
     def compose(self) -> ComposeResult:
@@ -157,24 +304,25 @@ class ChartWidget(Container):
         plot.plt.clear_data()
         plot.plt.clear_figure()
 
-        # Prepare data (reverse to show oldest→newest)
-        readings_reversed = list(reversed(self.readings))
-        x = list(range(len(readings_reversed)))
-        watts = [r.watts_actual for r in readings_reversed]
-        max_watts = [float(r.watts_negotiated) for r in readings_reversed]
-
-        # Plot lines
-        plot.plt.plot(x, watts, label="Power (W)", color="red")
-        plot.plt.plot(x, max_watts, label="Max Power (W)", color="blue")
+        # Plot lines (oldest→newest)
+        lines = chart_series_points(self.readings, self.series)
+        for label, color, x, y, yside in lines:
+            plot.plt.plot(x, y, label=label, color=color, yside=yside)
+        for x, region in chart_region_changes(self.readings):
+            plot.plt.vline(x, color=REGION_LINE_COLORS[region])
 
         # Configure chart with dynamic title based on actual readings count
         reading_count = len(self.readings)
-        plot.plt.title(f"Power Over Time (Last {reading_count} readings)")
+        subject = "Power" if any(SERIES_STYLES[name][3] == "W" for name in self.series) else "Battery"
+        plot.plt.title(f"{subject} Over Time (Last {reading_count} readings)")
         plot.plt.xlabel("Time (Oldest → Newest)")
-        plot.plt.ylabel("Power (W)")
+        for side in ("left", "right"):
+            labels = [label for label, _, _, _, yside in lines if yside == side]
+            if labels:
+                plot.plt.ylabel(" / ".join(labels), yside=side)
 
         # Show legend if there's data
-        if len(x) > 0:
+        if lines:
             plot.plt.theme("pro")
 
         plot.refresh()
@@ -182,3 +330,47 @@ class ChartWidget(Container):
     def on_mount(self) -> None:
         """Initialize chart on mount."""
         self._render_chart()
+
+
+class ExportDialog(ModalScreen[str | None]):
+    """Ask where to save the session's readings.
+
+    Dismisses with the entered path (the extension picks CSV, JSON, or NDJSON),
+    or None when cancelled with Escape or left empty.
+    """
+
+    DEFAULT_CSS = """
+    ExportDialog {
+        align: center middle;
+    }
+
+    ExportDialog > Vertical {
+        width: 80;
+        height: auto;
+        border: thick $primary;
+        background: $surface;
+        padding: 1 2;
+    }
+    """
+
+    BINDINGS = [Binding("escape", "cancel", "Cancel")]
+
+    def __init__(self, default_path: str, **kwargs):
+        super().__init__(**kwargs)
+        self.default_path = default_path
+
+    def compose(self) -> ComposeResult:
+        """Compose the dialog."""
+        yield Vertical(
+            Label("Export this session's readings to (.csv, .tsv, .json, or .ndjson):"),
+            Input(value=self.default_path, id="export-path"),
+            Label("[dim]Enter to save, Escape to cancel[/dim]"),
+        )
+
+    def on_input_submitted(self, event: Input.Submitted) -> None:
+        """Close the dialog with the entered path."""
+        self.dismiss(event.value.strip() or None)
+
+    def action_cancel(self) -> None:
+        """Close the dialog without exporting."""
+        self.dismiss(None)
@@ -0,0 +1,127 @@
+"""Send readings as JSON datagrams to a remote collector (`powermonitor udp`).
+
+A lightweight way to gather readings from several Macs in one place. Datagrams are
+unencrypted and unauthenticated, and UDP may drop or reorder them, so this is meant
+for trusted networks where an occasional lost reading doesn't matter.
+"""
+
+import json
+import socket
+
+from loguru import logger
+
+from .models import PowerReading
+from .system_info import get_hardware_model
+
+
+def parse_collector_address(text: str) -> tuple[str, int]:
+    """Parse a collector address.
+
+    Args:
+        text: host:port, or [IPv6 address]:port
+
+    Returns:
+        (host, port)
+
+    Raises:
+        ValueError: If the host or port is missing or the port is out of range
+    """
+    if text.startswith("["):
+        host, separator, port_text = text[1:].partition("]:")
+    else:
+        host, separator, port_text = text.rpartition(":")
+    if not separator or not host or (":" in host and not text.startswith("[")):
+        raise ValueError(f"Expected host:port (IPv6 as [address]:port), got {text!r}")
+    try:
+        port = int(port_text)
+    except ValueError:
+        raise ValueError(f"Invalid port in {text!r}") from None
+    if not 0 < port < 65536:
+        raise ValueError(f"Port must be between 1 and 65535, got {port}")
+    return host, port
+
+
+def datagram(reading: PowerReading, hostname: str, model: str | None) -> bytes:
+    """Encode one reading as a compact JSON datagram.
+
+    Args:
+        reading: Reading to send
+        hostname: Sending machine, so the receiver can tell Macs apart
+        model: Mac model identifier (None if unknown)
+
+    Returns:
+        UTF-8 JSON object: "hostname" and "model", then the reading's to_dict() fields
+    """
+    payload = {"hostname": hostname, "model": model, **reading.to_dict()}
+    return json.dumps(payload, separators=(",", ":")).encode()
+
+
+class ReadingSender:
+    """Send every reading to one UDP address.
+
+    Send errors (e.g. network down, host unreachable) are logged and the reading is
+    dropped, so a collection loop keeps running while the collector is away.
+
+    Usage:
+        with ReadingSender("collector.local", 9999) as sender:
+            while True:
+                sender.send(collector.collect())
+    """
+
+    def __init__(self, host: str, port: int, hostname: str | None = None, model: str | None = None):
+        """Initialize the sender (call open() or use as a context manager to resolve the address).
+
+        Args:
+            host: Collector host name or IP address
+            port: Collector UDP port
+            hostname: Name sent with each reading (default: this machine's host name)
+            model: Model sent with each reading (default: this Mac's model identifier)
+        """
+        self.host = host
+        self.port = port
+        self.hostname = hostname or socket.gethostname()
+        self.model = model if model is not None else get_hardware_model()
+        self._socket: socket.socket | None = None
+        self._address: tuple | None = None
+
+    def open(self) -> None:
+        """Resolve the collector address and create the socket.
+
+        Raises:
+            OSError: If the host can't be resolved or the socket can't be created
+        """
+        family, kind, proto, _, address = socket.getaddrinfo(self.host, self.port, type=socket.SOCK_DGRAM)[0]
+        self._socket = socket.socket(family, kind, proto)
+        self._address = address
+
+    def send(self, reading: PowerReading) -> bool:
+        """Send one reading.
+
+        Args:
+            reading: Reading to send
+
+        Returns:
+            True if the datagram was handed to the network, False if sending failed (logged)
+        """
+        if self._socket is None:
+            raise RuntimeError("ReadingSender is not open")
+        try:
+            self._socket.sendto(datagram(reading, self.hostname, self.model), self._address)
+        except OSError as e:
+            logger.warning(f"Could not send reading to {self.host}:{self.port}: {e}")
+            return False
+        return True
+
+    def close(self) -> None:
+        """Close the socket."""
+        if self._socket is not None:
+            self._socket.close()
+            self._socket = None
+
+    def __enter__(self) -> "ReadingSender":
+        self.open()
+        return self
+
+    def __exit__(self, exc_type, exc_val, exc_tb) -> bool:
+        self.close()
+        return False
@@ -0,0 +1,56 @@
+"""Tests for adaptive sampling intervals."""
+
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.adaptive import AdaptiveInterval
+
+
+def test_interval_grows_while_idle_on_ac(sample_reading):
+    """Test stable idle readings double the interval up to max_interval."""
+    adaptive = AdaptiveInterval(min_interval=1.0, max_interval=5.0)
+    idle = replace(sample_reading, is_charging=False, watts_actual=0.0, battery_percent=100)
+
+    delays = [adaptive.observe(idle) for _ in range(5)]
+
+    # The first reading has nothing to compare against
+    assert delays == [1.0, 2.0, 4.0, 5.0, 5.0]
+
+
+def test_interval_resets_on_activity(sample_reading):
+    """Test charging, unplugging, and power jumps drop back to min_interval."""
+    idle = replace(sample_reading, is_charging=False, watts_actual=0.0, battery_percent=100)
+    activity = [
+        sample_reading,  # Charging
+        replace(idle, external_connected=False, watts_actual=-8.0),  # On battery
+        replace(idle, watts_actual=3.0),  # Still idle, but power jumped
+        replace(idle, battery_percent=99),
+    ]
+    for reading in activity:
+        adaptive = AdaptiveInterval(min_interval=1.0, max_interval=60.0)
+        for _ in range(3):
+            adaptive.observe(idle)
+        assert adaptive.interval == 4.0
+
+        assert adaptive.observe(reading) == 1.0
+
+
+def test_small_power_changes_count_as_stable(sample_reading):
+    """Test power wobbling within watts_delta doesn't reset the interval."""
+    adaptive = AdaptiveInterval(min_interval=1.0, max_interval=60.0, watts_delta=0.5)
+    idle = replace(sample_reading, is_charging=False, watts_actual=0.0)
+
+    adaptive.observe(idle)
+    assert adaptive.observe(replace(idle, watts_actual=0.3)) == 2.0
+    assert adaptive.observe(replace(idle, watts_actual=-0.1)) == 4.0
+
+
+def test_bounds_validation():
+    """Test invalid bounds are rejected."""
+    with pytest.raises(ValueError, match="min_interval"):
+        AdaptiveInterval(min_interval=0, max_interval=10.0)
+    with pytest.raises(ValueError, match="max_interval"):
+        AdaptiveInterval(min_interval=10.0, max_interval=5.0)
+    with pytest.raises(ValueError, match="watts_delta"):
+        AdaptiveInterval(min_interval=1.0, max_interval=5.0, watts_delta=-1.0)
@@ -0,0 +1,76 @@
+"""Tests for aggregate command result types."""
+
+import json
+from pathlib import Path
+
+from powermonitor.aggregates import ChargerStats
+from powermonitor.aggregates import StatsResult
+
+
+def test_stats_result_field_names_are_stable():
+    """Test the StatsResult JSON keys (a public schema) don't change."""
+    result = StatsResult(database_path="/tmp/pm.db", database_size_bytes=4096, count=0)
+
+    assert list(result.to_dict()) == [
+        "database_path",
+        "database_size_bytes",
+        "count",
+        "earliest",
+        "latest",
+        "avg_watts",
+        "min_watts",
+        "max_watts",
+        "avg_battery",
+    ]
+
+
+def test_stats_result_from_statistics():
+    """Test statistics are rounded for JSON and empty databases give nulls instead of zeros."""
+    stat_data = {
+        "avg_watts": 12.3456,
+        "min_watts": 1.0,
+        "max_watts": 30.0,
+        "avg_battery": 55.56,
+        "earliest": "2025-12-28T12:00:00+00:00",
+        "latest": "2025-12-28T13:00:00+00:00",
+        "count": 3,
+    }
+
+    data = StatsResult.from_statistics(stat_data, Path("/tmp/pm.db"), 4096).to_dict()
+
+    assert data["avg_watts"] == 12.35
+    assert data["avg_battery"] == 55.6
+    assert data["count"] == 3
+    assert json.loads(json.dumps(data)) == data
+
+    empty = {**stat_data, "avg_watts": 0.0, "min_watts": 0.0, "max_watts": 0.0, "avg_battery": 0.0, "count": 0}
+    empty_data = StatsResult.from_statistics(empty, Path("/tmp/pm.db"), 0).to_dict()
+    assert empty_data["avg_watts"] is None
+    assert empty_data["avg_battery"] is None
+
+
+def test_charger_stats_field_names_are_stable():
+    """Test the ChargerStats JSON keys (a public schema) don't change."""
+    charger = ChargerStats(
+        name="96W USB-C Power Adapter",
+        manufacturer="Apple Inc.",
+        readings=10,
+        max_watts_negotiated=96,
+        avg_watts_actual=41.234,
+        first_seen="2025-12-28T12:00:00+00:00",
+        last_seen="2025-12-28T13:00:00+00:00",
+    )
+
+    data = charger.to_dict()
+
+    assert list(data) == [
+        "name",
+        "manufacturer",
+        "readings",
+        "max_watts_negotiated",
+        "avg_watts_actual",
+        "first_seen",
+        "last_seen",
+    ]
+    assert data["avg_watts_actual"] == 41.23
+    assert json.loads(json.dumps(data)) == data
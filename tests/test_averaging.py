@@ -0,0 +1,53 @@
+"""Tests for averaging samples into stored readings."""
+
+from dataclasses import replace
+from datetime import timedelta
+
+import pytest
+
+from powermonitor.averaging import ReadingAverager
+from powermonitor.averaging import average_readings
+
+
+def test_average_readings(sample_reading):
+    """Test measurements are averaged and state comes from the latest sample."""
+    first = replace(sample_reading, watts_actual=10.0, voltage=12.0, battery_temp=None, battery_percent=70)
+    last = replace(
+        sample_reading,
+        timestamp=sample_reading.timestamp + timedelta(seconds=1),
+        watts_actual=20.0,
+        voltage=13.0,
+        battery_temp=31.0,
+        battery_percent=71,
+    )
+
+    averaged = average_readings([first, last])
+
+    assert averaged.watts_actual == 15.0
+    assert averaged.voltage == 12.5
+    assert averaged.battery_temp == 31.0  # Only one sample had a temperature
+    assert averaged.system_power is None
+    assert averaged.battery_percent == 71
+    assert averaged.timestamp == last.timestamp
+
+    with pytest.raises(ValueError, match="empty"):
+        average_readings([])
+
+
+def test_averager_releases_one_reading_per_interval(sample_reading):
+    """Test samples are held until the store interval has passed, then averaged together."""
+    averager = ReadingAverager(interval=10.0)
+    samples = [
+        replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=s), watts_actual=float(s))
+        for s in range(0, 25, 5)
+    ]  # t = 0, 5, 10, 15, 20
+
+    stored = [averager.add(sample) for sample in samples]
+
+    assert [r is not None for r in stored] == [False, False, True, False, False]
+    assert stored[2].watts_actual == 5.0  # Mean of 0, 5, 10
+    assert averager.flush().watts_actual == 17.5  # Partial window: 15, 20
+    assert averager.flush() is None
+
+    with pytest.raises(ValueError, match="positive"):
+        ReadingAverager(interval=0)
@@ -0,0 +1,62 @@
+"""Tests for battery calibration heuristics."""
+
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.calibration import estimate_percent
+from powermonitor.calibration import infer_cell_count
+from powermonitor.calibration import is_calibration_suspect
+
+
+def test_infer_cell_count():
+    """Test series cell count is inferred across each pack's voltage range."""
+    assert infer_cell_count(8.4) == 2  # 2S full
+    assert infer_cell_count(6.6) == 2  # 2S empty
+    assert infer_cell_count(12.9) == 3  # 3S full
+    assert infer_cell_count(9.9) == 3  # 3S empty
+    assert infer_cell_count(17.2) == 4  # 4S full
+    assert infer_cell_count(14.4) == 4  # 4S low
+
+
+def test_estimate_percent_follows_ocv_curve():
+    """Test representative 3S resting voltages map to the expected charge."""
+    assert estimate_percent(11.52) == pytest.approx(50.0)  # 3.84V/cell
+    assert estimate_percent(12.9) == pytest.approx(100.0)  # 4.30V/cell
+    assert estimate_percent(11.07) == pytest.approx(10.0)  # 3.69V/cell
+    assert estimate_percent(12.255, cells=3) == pytest.approx(85.0)  # Halfway between 80% and 90%
+
+
+def test_estimate_percent_clamps():
+    """Test voltages beyond the curve clamp to 0% and 100%."""
+    assert estimate_percent(9.0, cells=3) == 0.0
+    assert estimate_percent(13.05, cells=3) == 100.0
+
+
+def test_suspect_when_percent_contradicts_voltage():
+    """Test a battery reporting 50% at a near-full resting voltage is flagged."""
+    assert is_calibration_suspect(voltage=12.9, amperage=0.1, battery_percent=50)
+    assert is_calibration_suspect(voltage=11.1, amperage=-0.3, battery_percent=95)
+
+
+def test_not_suspect_when_consistent():
+    """Test a reported percentage near the voltage estimate is not flagged."""
+    assert not is_calibration_suspect(voltage=11.52, amperage=0.0, battery_percent=50)
+    assert not is_calibration_suspect(voltage=12.714, amperage=0.0, battery_percent=82)  # real_mac.txt fixture
+
+
+def test_not_judged_under_load():
+    """Test voltage under heavy charge/discharge current is ignored."""
+    assert not is_calibration_suspect(voltage=12.9, amperage=3.0, battery_percent=50)
+    assert not is_calibration_suspect(voltage=11.1, amperage=-2.5, battery_percent=95)
+
+
+def test_not_judged_without_voltage():
+    """Test readings without a voltage are never flagged."""
+    assert not is_calibration_suspect(voltage=0.0, amperage=0.0, battery_percent=50)
+
+
+def test_reading_calibration_suspect(sample_reading):
+    """Test the heuristic is surfaced on PowerReading."""
+    assert not sample_reading.calibration_suspect  # 2.3A charging current: not judged
+    assert replace(sample_reading, voltage=12.9, amperage=0.0, battery_percent=50).calibration_suspect
@@ -1,16 +1,30 @@
 """Tests for CLI commands."""
 
 import json
+import plistlib
 import re
+import socket
+import tomllib
+from dataclasses import replace
 from datetime import UTC
 from datetime import datetime
 from datetime import timedelta
+from datetime import timezone
+from pathlib import Path
 
 from typer.testing import CliRunner
 
 from powermonitor.cli import app
+from powermonitor.collector.mock import MockCollector
+from powermonitor.collector.mock import default_mock_reading
+from powermonitor.collector.tasks import TaskEnergy
 from powermonitor.database import Database
+from powermonitor.degradation import DEGRADED_READINGS
+from powermonitor.models import AdapterSource
+from powermonitor.models import ChargeSession
+from powermonitor.models import CollectorUnavailableError
 from powermonitor.models import PowerReading
+from powermonitor.numformat import NumberFormat
 
 runner = CliRunner()
 
@@ -108,6 +122,8 @@ def test_export_json(database, temp_config, tmp_path):
     assert len(data) == 3
     assert "timestamp" in data[0]
     assert "watts_actual" in data[0]
+    assert "watts_negotiated" in data[0]
+    assert "watts_negotiated_stable" in data[0]
 
 
 def test_export_auto_detect_format(database, temp_config, tmp_path):
@@ -142,7 +158,126 @@ def test_export_invalid_format(temp_config, tmp_path):
         ["export", str(output_file)],
     )
     assert result.exit_code == 1
-    assert "Cannot detect format" in result.stdout
+    assert "Cannot detect format" in result.stderr
+
+
+def test_export_tsv_without_header(database, temp_config, tmp_path):
+    """Test .tsv exports tab-separated rows and --no-header drops the column row."""
+    create_test_readings(database, count=3)
+
+    output_file = tmp_path / "readings.tsv"
+    result = runner.invoke(app, ["export", str(output_file), "--no-header"])
+
+    assert result.exit_code == 0
+    lines = output_file.read_text().strip().split("\n")
+    assert len(lines) == 3
+    assert not lines[0].startswith("timestamp")
+    assert lines[0].count("\t") == 17
+
+
+def test_export_custom_delimiter(database, temp_config, tmp_path):
+    """Test --delimiter accepts a single character or a literal \\t."""
+    create_test_readings(database, count=2)
+
+    output_file = tmp_path / "readings.txt"
+    result = runner.invoke(app, ["export", str(output_file), "--format", "csv", "--delimiter", ";"])
+    assert result.exit_code == 0
+    assert output_file.read_text().startswith("timestamp;watts_actual;")
+
+    result = runner.invoke(app, ["export", str(output_file), "--format", "csv", "-d", "\\t"])
+    assert result.exit_code == 0
+    assert output_file.read_text().startswith("timestamp\twatts_actual\t")
+
+
+def test_export_rejects_invalid_delimiter(temp_config, tmp_path):
+    """Test multi-character delimiters and CSV-only options on other formats are rejected."""
+    result = runner.invoke(app, ["export", str(tmp_path / "out.csv"), "--delimiter", "::"])
+    assert result.exit_code == 1
+    assert "single character" in strip_ansi(result.stderr)
+
+    result = runner.invoke(app, ["export", str(tmp_path / "out.json"), "--no-header"])
+    assert result.exit_code == 1
+    assert "only apply to CSV" in strip_ansi(result.stderr)
+
+
+def test_import_round_trips_export(database, temp_config, tmp_path):
+    """Test exported readings imported into an emptied database come back identical."""
+    create_test_readings(database, count=5)
+    original = list(database.iter_history())
+
+    backup = tmp_path / "backup.csv"
+    assert runner.invoke(app, ["export", str(backup)]).exit_code == 0
+    database.clear_history()
+
+    result = runner.invoke(app, ["import", str(backup), "--dry-run"])
+    assert result.exit_code == 0
+    assert "Would import 5 readings" in strip_ansi(result.stdout)
+    assert list(database.iter_history()) == []
+
+    result = runner.invoke(app, ["import", str(backup)])
+    assert result.exit_code == 0
+    assert "Imported 5 readings" in strip_ansi(result.stdout)
+    assert list(database.iter_history()) == original
+
+    # Importing the same file again adds nothing
+    result = runner.invoke(app, ["import", str(backup)])
+    assert result.exit_code == 0
+    assert "Imported 0 readings" in strip_ansi(result.stdout)
+    assert "5 already in the database" in strip_ansi(result.stdout)
+    assert list(database.iter_history()) == original
+
+
+def test_import_reports_malformed_rows(database, temp_config, tmp_path):
+    """Test bad rows are listed and skipped, and a foreign header is rejected outright."""
+    create_test_readings(database, count=2)
+    backup = tmp_path / "backup.csv"
+    runner.invoke(app, ["export", str(backup)])
+    database.clear_history()
+    backup.write_text(backup.read_text() + "not,a,reading\n")
+
+    result = runner.invoke(app, ["import", str(backup)])
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "Skipped line 4: expected 19 fields, got 3" in output
+    assert "Imported 2 readings" in output
+    assert "skipped 1 malformed rows" in output
+    assert len(list(database.iter_history())) == 2
+
+    other = tmp_path / "other.csv"
+    other.write_text("time,power\n2025-12-28T12:00:00,45.5\n")
+    result = runner.invoke(app, ["import", str(other)])
+    assert result.exit_code == 1
+    assert "Unknown column(s): time, power" in strip_ansi(result.stderr)
+
+
+def test_import_converts_offsets_to_utc(database, temp_config, tmp_path):
+    """Test +09:00 timestamps are stored as UTC, so they dedupe against UTC rows, and naive ones are skipped."""
+    create_test_readings(database, count=2)
+    original = list(database.iter_history())
+    backup = tmp_path / "backup.csv"
+    runner.invoke(app, ["export", str(backup)])
+    database.clear_history()
+
+    tokyo = timezone(timedelta(hours=9))
+    lines = backup.read_text().splitlines()
+    for i, reading in enumerate(original, start=1):
+        lines[i] = lines[i].replace(reading.timestamp.isoformat(), reading.timestamp.astimezone(tokyo).isoformat())
+    assert "+09:00" in lines[1]
+    naive = lines[1].replace(original[0].timestamp.astimezone(tokyo).isoformat(), "2025-12-28T12:00:00")
+    shifted = tmp_path / "tokyo.csv"
+    shifted.write_text("\n".join([*lines, naive]) + "\n")
+
+    result = runner.invoke(app, ["import", str(shifted)])
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "Skipped line 4: invalid timestamp: '2025-12-28T12:00:00'" in output
+    assert "Imported 2 readings" in output
+    stored = list(database.iter_history())
+    assert stored == original
+    assert all(reading.timestamp.utcoffset() == timedelta(0) for reading in stored)
+
+    result = runner.invoke(app, ["import", str(backup)])
+    assert "Imported 0 readings" in strip_ansi(result.stdout)
 
 
 def test_export_no_readings(database, temp_config, tmp_path):
@@ -196,6 +331,175 @@ def test_stats_empty_database(database, temp_config):
     assert "No readings in database" in result.stdout
 
 
+def test_stats_json(database, temp_config):
+    """Test stats --json prints the same numbers as the table, with stable keys."""
+    create_test_readings(database, count=5)
+
+    result = runner.invoke(app, ["stats", "--json"])
+
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert data["count"] == 5
+    assert data["min_watts"] == 40.0
+    assert data["max_watts"] == 44.0
+    assert data["database_size_bytes"] > 0
+
+
+def test_compare_db(database, temp_config, tmp_path):
+    """Test compare-db shows both databases side by side and as JSON."""
+    create_test_readings(database, count=3)
+    other_path = tmp_path / "old-mac.db"
+    with Database(other_path) as other:
+        create_test_readings(other, count=2)
+
+    result = runner.invoke(app, ["compare-db", str(other_path)])
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "Database Comparison" in output
+    assert "Other (old-mac.db)" in output
+    assert "Average power" in output
+    assert "-1 (-33%)" in output  # Readings: 2 vs 3
+
+    result = runner.invoke(app, ["compare-db", str(other_path), "--json"])
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert data["current"]["count"] == 3
+    assert data["other"]["count"] == 2
+    assert data["other"]["database_path"] == str(other_path)
+
+    result = runner.invoke(app, ["compare-db", str(tmp_path / "missing.db")])
+    assert result.exit_code == 1
+    assert "Database file not found" in strip_ansi(result.stderr)
+    assert not (tmp_path / "missing.db").exists()
+
+
+def test_stats_json_empty_database(database, temp_config):
+    """Test stats --json reports an empty database as count 0 instead of a message."""
+    result = runner.invoke(app, ["stats", "--json"])
+
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert data["count"] == 0
+    assert data["avg_watts"] is None
+
+
+def test_chargers_command(database, temp_config):
+    """Test chargers lists each recorded charger, as a table and as JSON."""
+    create_test_readings(database, count=3)
+
+    result = runner.invoke(app, ["chargers"])
+    assert result.exit_code == 0
+    assert "Chargers" in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["chargers", "--json"])
+    assert result.exit_code == 0
+    chargers = json.loads(result.stdout)["chargers"]
+    assert len(chargers) == 1
+    assert chargers[0]["readings"] == 2  # Every other test reading has a charger
+    assert chargers[0]["max_watts_negotiated"] == 67
+
+
+def test_chargers_command_flags_degraded_charger(database, temp_config, sample_reading):
+    """Test a charger stuck well below its stored peak is marked degraded."""
+    database.update_charger_peak(replace(sample_reading, watts_negotiated=96))
+    for _ in range(DEGRADED_READINGS):
+        database.insert_reading(replace(sample_reading, watts_negotiated=60))
+        database.update_charger_peak(replace(sample_reading, watts_negotiated=60))
+
+    result = runner.invoke(app, ["chargers"])
+    assert result.exit_code == 0
+    assert "Degraded" in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["chargers", "--json"])
+    charger = json.loads(result.stdout)["chargers"][0]
+    assert charger["peak_watts_negotiated"] == 96
+    assert charger["last_watts_negotiated"] == 60
+    assert charger["degraded"] is True
+
+
+def test_chargers_empty_database(database, temp_config):
+    """Test chargers with no charger history prints a message, or an empty JSON list."""
+    result = runner.invoke(app, ["chargers"])
+    assert result.exit_code == 0
+    assert "No chargers recorded yet" in result.stdout
+
+    result = runner.invoke(app, ["chargers", "--json"])
+    assert json.loads(result.stdout) == {"chargers": []}
+
+
+def test_sessions_command(database, temp_config):
+    """Test sessions lists recorded charge sessions, as a table and as JSON."""
+    started = datetime(2025, 12, 28, 12, 0, 0, tzinfo=UTC)
+    database.insert_charge_session(
+        ChargeSession(
+            started_at=started,
+            ended_at=started + timedelta(minutes=65),
+            start_percent=40,
+            end_percent=95,
+            total_wh=45.0,
+        )
+    )
+
+    result = runner.invoke(app, ["sessions"])
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "1h 05m" in output
+    assert "40% → 95%" in output
+    assert "45.00 Wh" in output
+
+    result = runner.invoke(app, ["sessions", "--json"])
+    assert result.exit_code == 0
+    sessions = json.loads(result.stdout)["sessions"]
+    assert len(sessions) == 1
+    assert sessions[0]["duration_seconds"] == 3900
+    assert sessions[0]["total_wh"] == 45.0
+
+
+def test_sessions_empty_and_invalid_limit(database, temp_config):
+    """Test sessions with no history prints a message, and a non-positive limit is rejected."""
+    result = runner.invoke(app, ["sessions"])
+    assert result.exit_code == 0
+    assert "No charge sessions recorded yet" in result.stdout
+
+    result = runner.invoke(app, ["sessions", "--json"])
+    assert json.loads(result.stdout) == {"sessions": []}
+
+    result = runner.invoke(app, ["sessions", "--limit", "0"])
+    assert result.exit_code == 1
+
+
+def test_report_command(database, temp_config, tmp_path):
+    """Test report writes a self-contained HTML file for the selected range."""
+    create_test_readings(database, count=3)
+    output = tmp_path / "report.html"
+
+    result = runner.invoke(app, ["report", str(output), "--since", "7d"])
+
+    assert result.exit_code == 0
+    assert "Wrote report of 3 readings" in strip_ansi(result.stdout)
+    html = output.read_text(encoding="utf-8")
+    assert "<svg" in html
+    assert "<h2>Chargers</h2>" in html
+
+
+def test_report_empty_range_and_invalid_bounds(database, temp_config, tmp_path):
+    """Test an empty range writes nothing and invalid --since/--until values are rejected."""
+    create_test_readings(database, count=3)
+    output = tmp_path / "report.html"
+
+    result = runner.invoke(app, ["report", str(output), "--until", "2000-01-01"])
+    assert result.exit_code == 0
+    assert "No readings in the selected range" in result.stdout
+    assert not output.exists()
+
+    result = runner.invoke(app, ["report", str(output), "--since", "yesterday-ish"])
+    assert result.exit_code == 1
+
+    result = runner.invoke(app, ["report", str(output), "--since", "1d", "--until", "7d"])
+    assert result.exit_code == 1
+    assert "--since must be before --until" in strip_ansi(result.stderr)
+
+
 def test_cleanup_with_days(database, temp_config):
     """Test cleanup command with --days parameter."""
     # Create readings with different timestamps
@@ -268,6 +572,22 @@ def test_cleanup_all_cancelled(database, temp_config):
     assert len(remaining) == 3
 
 
+def test_cleanup_dry_run(database, temp_config):
+    """Test cleanup --dry-run reports counts without deleting or asking for confirmation."""
+    create_test_readings(database, count=4)
+
+    result = runner.invoke(app, ["cleanup", "--all", "--dry-run"])
+    assert result.exit_code == 0
+    assert "would delete all 4 readings" in strip_ansi(result.stdout)
+    assert "Are you sure" not in result.stdout
+
+    result = runner.invoke(app, ["cleanup", "--days", "30", "--dry-run"])
+    assert result.exit_code == 0
+    assert "would delete 0 readings older than 30 days" in strip_ansi(result.stdout)
+
+    assert len(database.query_history(limit=None)) == 4
+
+
 def test_cleanup_missing_parameters(temp_config):
     """Test cleanup command with missing parameters."""
     result = runner.invoke(
@@ -275,7 +595,7 @@ def test_cleanup_missing_parameters(temp_config):
         ["cleanup"],
     )
     assert result.exit_code == 1
-    assert "Must specify either --days N or --all" in result.stdout
+    assert "Must specify either --days N or --all" in result.stderr
 
 
 def test_history_command(database, temp_config):
@@ -342,3 +662,861 @@ def test_health_no_data(database, temp_config):
     )
     assert result.exit_code == 0
     assert "No readings found" in result.stdout
+
+
+def test_version_command():
+    """Test version command prints the package version."""
+    result = runner.invoke(app, ["version"])
+    assert result.exit_code == 0
+    assert "powermonitor" in result.stdout
+
+
+def test_version_verbose():
+    """Test version --verbose reports platform and backend details."""
+    result = runner.invoke(app, ["version", "--verbose"])
+    assert result.exit_code == 0
+    assert "Python" in result.stdout
+    assert "Platform" in result.stdout
+    assert "macOS" in result.stdout
+    assert "Architecture" in result.stdout
+    assert "IOKit backend" in result.stdout
+
+
+def test_history_filter_by_label(database, temp_config):
+    """Test history --label only shows tagged readings."""
+    base_time = datetime.now(UTC)
+    for i, label in enumerate(["render", "render", None]):
+        database.insert_reading(
+            PowerReading(
+                timestamp=base_time - timedelta(seconds=i * 60),
+                watts_actual=30.0 + i,
+                watts_negotiated=67,
+                voltage=20.0,
+                amperage=1.5,
+                current_capacity=3500,
+                max_capacity=4709,
+                battery_percent=74,
+                is_charging=True,
+                external_connected=True,
+                charger_name=None,
+                charger_manufacturer=None,
+                label=label,
+            )
+        )
+
+    result = runner.invoke(app, ["history", "--label", "render"])
+    assert result.exit_code == 0
+    assert "Last 2" in strip_ansi(result.stdout)
+    assert "render" in result.stdout
+
+
+def test_history_unknown_label(database, temp_config):
+    """Test history --label with no matching readings."""
+    create_test_readings(database, count=2)
+
+    result = runner.invoke(app, ["history", "--label", "missing"])
+    assert result.exit_code == 0
+    assert "No readings with label 'missing'" in result.stdout
+
+
+def test_history_bucketed(database, temp_config):
+    """Test history --bucket shows averaged buckets and rejects bad durations."""
+    create_test_readings(database, count=5)
+
+    result = runner.invoke(app, ["history", "--bucket", "1h"])
+    assert result.exit_code == 0
+    assert "1h averages" in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["history", "--bucket", "soon"])
+    assert result.exit_code == 1
+    assert "Invalid interval" in result.stderr
+
+
+def test_history_columns(database, temp_config, sample_reading):
+    """Test --columns picks and orders the columns, in the table and in --text output."""
+    database.insert_reading(replace(sample_reading, timestamp=datetime.now(UTC), battery_temp=31.5))
+
+    result = runner.invoke(app, ["history", "--columns", "temp,watts"])
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert output.index("Temp") < output.index("Power")
+    assert "Voltage" not in output
+
+    result = runner.invoke(app, ["history", "--text", "--columns", "battery, temp,label"])
+    assert result.exit_code == 0
+    assert result.stdout.splitlines() == ["Battery    Temp  Label", "    74%  31.5°C  -"]
+
+    for spec, message in (("watts,fan", "Unknown column 'fan'"), ("watts,watts", "more than once"), (",", "at least")):
+        result = runner.invoke(app, ["history", "--columns", spec])
+        assert result.exit_code == 1
+        assert message in strip_ansi(result.stderr)
+
+
+def test_history_text_aligns_wide_glyphs(database, temp_config, sample_reading):
+    """Test --text pads columns by display width, so emoji in the status column don't shift the next column."""
+    now = datetime.now(UTC)
+    database.insert_reading(replace(sample_reading, timestamp=now - timedelta(minutes=1)))
+    database.insert_reading(
+        replace(sample_reading, timestamp=now, is_charging=False, external_connected=False, watts_actual=-8.0)
+    )
+
+    result = runner.invoke(app, ["history", "--text", "--columns", "status,battery"])
+
+    assert result.exit_code == 0
+    assert result.stdout.splitlines() == [
+        "Status       Battery",
+        "⚡ Charging      74%",
+        "🔋 Battery       74%",
+    ]
+
+
+def test_charger_names_stay_on_one_line(database, temp_config, sample_reading):
+    """Test a charger name with markup, tabs, and newlines can't break history --text or status --field."""
+    name = '[red]Acme "Pro",\t96W\nAdapter'
+    database.insert_reading(replace(sample_reading, timestamp=datetime.now(UTC), charger_name=name))
+
+    result = runner.invoke(app, ["history", "--text", "--columns", "charger,battery"])
+    assert result.exit_code == 0
+    assert result.stdout.splitlines()[1] == '[red]Acme "Pro", 96W Adapter      74%'
+
+    result = runner.invoke(app, ["history", "--columns", "charger"])
+    assert result.exit_code == 0
+    assert '[red]Acme "Pro", 96W' in strip_ansi(result.stdout)
+
+
+def test_history_milliseconds(database, temp_config, sample_reading):
+    """Test history --ms tells apart readings within the same second."""
+    base = datetime.now(UTC).replace(microsecond=0)
+    for offset_ms in (0, 250):
+        database.insert_reading(replace(sample_reading, timestamp=base + timedelta(milliseconds=offset_ms)))
+
+    result = runner.invoke(app, ["history", "--ms"])
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert ".000" in output
+    assert ".250" in output
+
+    result = runner.invoke(app, ["history", "--ms", "--bucket", "1h"])
+    assert result.exit_code == 1
+    assert "--ms" in strip_ansi(result.stderr)
+
+
+def test_top_shows_processes(temp_config, monkeypatch):
+    """Test top lists the highest energy processes under the overall reading."""
+    tasks = [TaskEnergy("Xcode", 920, 95.0, 210.5), TaskEnergy("WindowServer", 152, 48.7, 72.3)]
+    monkeypatch.setattr("powermonitor.cli.TaskEnergyReader.read", lambda self: tasks)
+
+    result = runner.invoke(app, ["top", "--count", "1", "--limit", "1", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "+45.5W" in output
+    assert "Xcode" in output
+    assert "210.5" in output
+    assert "WindowServer" not in output
+
+
+def test_top_without_powermetrics(temp_config, monkeypatch):
+    """Test top falls back to the overall reading when per-process energy is unavailable."""
+    monkeypatch.setattr("powermonitor.cli.TaskEnergyReader.read", lambda self: None)
+
+    result = runner.invoke(app, ["top", "--count", "1", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "+45.5W" in output
+    assert "sudo powermonitor top" in output
+
+
+def test_export_json_large_history_roundtrip(database, temp_config, tmp_path):
+    """Test exporting many rows streams valid JSON that parses back."""
+    create_test_readings(database, count=250)
+
+    output_file = tmp_path / "large.json"
+    result = runner.invoke(app, ["export", str(output_file), "--limit", "250"])
+
+    assert result.exit_code == 0
+    assert "Exported 250 readings" in strip_ansi(result.stdout)
+    with output_file.open() as f:
+        data = json.load(f)
+    assert len(data) == 250
+    assert data[0]["watts_actual"] == 40.0  # Newest first
+
+
+def test_export_ndjson_and_replay(database, temp_config, tmp_path):
+    """Test an NDJSON export can be replayed in chronological order."""
+    create_test_readings(database, count=3)
+
+    output_file = tmp_path / "session.ndjson"
+    result = runner.invoke(app, ["export", str(output_file)])
+    assert result.exit_code == 0
+    assert "Exported 3 readings" in strip_ansi(result.stdout)
+    assert len(output_file.read_text().splitlines()) == 3
+
+    result = runner.invoke(app, ["replay", str(output_file), "--max-delay", "0"])
+    output = strip_ansi(result.stdout)
+
+    assert result.exit_code == 0
+    assert "Replayed 3 readings" in output
+    # Oldest reading (42W) is replayed first even though the export is newest first
+    assert output.index("+42.0W") < output.index("+40.0W")
+
+
+def test_replay_tui(temp_config, tmp_path, monkeypatch, sample_reading):
+    """Test replay --tui runs the TUI on the recording with a throwaway database."""
+    launched = []
+
+    def fake_run(self):
+        launched.append(self)
+
+    monkeypatch.setattr("powermonitor.cli.PowerMonitorApp.run", fake_run)
+    path = tmp_path / "session.ndjson"
+    path.write_text(json.dumps(sample_reading.to_dict()) + "\n")
+
+    result = runner.invoke(app, ["replay", str(path), "--tui", "--speed", "10"])
+
+    assert result.exit_code == 0, result.stderr
+    [tui] = launched
+    assert tui.replay is tui.collector
+    assert tui.collector.collect() == sample_reading
+    assert tui.config.database_path.name == "replay.db"
+
+    result = runner.invoke(app, ["replay", str(path), "--tui", "--ndjson"])
+    assert result.exit_code == 1
+    assert "--tui can't be combined with --ndjson" in strip_ansi(result.stderr)
+
+
+def test_replay_ndjson_stream(temp_config, tmp_path):
+    """Test replay --ndjson writes only the readings, one JSON object per line, buffered or not."""
+    base = default_mock_reading()
+    path = tmp_path / "session.ndjson"
+    path.write_text(
+        "".join(
+            json.dumps(replace(base, timestamp=base.timestamp + timedelta(seconds=i), watts_actual=40.0 + i).to_dict())
+            + "\n"
+            for i in range(3)
+        )
+    )
+
+    for buffering in ("--line-buffered", "--no-line-buffered"):
+        result = runner.invoke(app, ["replay", str(path), "--ndjson", "--max-delay", "0", buffering])
+
+        assert result.exit_code == 0
+        lines = result.stdout.splitlines()
+        assert [json.loads(line)["watts_actual"] for line in lines] == [40.0, 41.0, 42.0]
+
+
+def test_replay_lines_follow_display_options(temp_config, tmp_path, monkeypatch):
+    """Test replayed status lines use the --locale separators and the unsigned 0.0W of --deadzone."""
+    monkeypatch.setattr(
+        "powermonitor.cli.number_format_for_locale", lambda name: NumberFormat(decimal_point=",", thousands_sep=".")
+    )
+    base = default_mock_reading()
+    path = tmp_path / "session.ndjson"
+    path.write_text(
+        json.dumps(base.to_dict()) + "\n" + json.dumps(replace(base, watts_actual=-0.3).to_dict()) + "\n"
+    )
+
+    result = runner.invoke(
+        app, ["--locale", "de_DE.UTF-8", "--deadzone", "0.5", "replay", str(path), "--max-delay", "0"]
+    )
+
+    assert result.exit_code == 0
+    lines = strip_ansi(result.stdout).splitlines()
+    assert "+45,5W  74%  20,00V" in lines[0]
+    assert " 0,0W  74%" in lines[1]
+
+
+def test_replay_invalid_line(temp_config, tmp_path):
+    """Test replay reports the offending line of a malformed file."""
+    path = tmp_path / "broken.ndjson"
+    path.write_text('{"timestamp": "not a date"}\n')
+
+    result = runner.invoke(app, ["replay", str(path)])
+
+    assert result.exit_code == 1
+    assert "broken.ndjson:1" in strip_ansi(result.stderr)
+
+
+def test_replay_rejects_non_positive_speed(temp_config, tmp_path):
+    """Test replay validates the speed multiplier."""
+    path = tmp_path / "session.ndjson"
+    path.write_text("")
+
+    result = runner.invoke(app, ["replay", str(path), "--speed", "0"])
+
+    assert result.exit_code == 1
+    assert "--speed must be positive" in strip_ansi(result.stderr)
+
+
+def test_health_trend_command(database, temp_config):
+    """Test health-trend groups daily health and reports interpolated gaps."""
+    base_time = datetime.now(UTC)
+    # Readings 4 days apart leave a gap to interpolate
+    for day, max_capacity in ((8, 4600), (4, 4550), (0, 4500)):
+        reading = PowerReading(
+            timestamp=base_time - timedelta(days=day),
+            watts_actual=40.0,
+            watts_negotiated=67,
+            voltage=20.0,
+            amperage=2.0,
+            current_capacity=3500,
+            max_capacity=max_capacity,
+            battery_percent=74,
+            is_charging=True,
+            external_connected=True,
+            charger_name=None,
+            charger_manufacturer=None,
+            design_capacity=5000,
+        )
+        database.insert_reading(reading)
+
+    result = runner.invoke(app, ["health-trend", "--period", "day"])
+    output = strip_ansi(result.stdout)
+
+    assert result.exit_code == 0
+    assert "Battery Health by Day" in output
+    assert "92.0%" in output
+    assert "90.0%" in output
+    assert "6 day(s) without readings were interpolated" in output
+
+
+def test_capacity_trend_command(database, temp_config):
+    """Test capacity-trend reports the decline and projects the 80% date."""
+    base_time = datetime.now(UTC)
+    for day, max_capacity in ((60, 4600), (30, 4550), (0, 4500)):
+        reading = PowerReading(
+            timestamp=base_time - timedelta(days=day),
+            watts_actual=40.0,
+            watts_negotiated=67,
+            voltage=20.0,
+            amperage=2.0,
+            current_capacity=3500,
+            max_capacity=max_capacity,
+            battery_percent=74,
+            is_charging=True,
+            external_connected=True,
+            charger_name=None,
+            charger_manufacturer=None,
+            design_capacity=5000,
+        )
+        database.insert_reading(reading)
+
+    result = runner.invoke(app, ["capacity-trend"])
+    output = strip_ansi(result.stdout)
+
+    assert result.exit_code == 0
+    assert "Battery Capacity Trend" in output
+    assert "4,500 mAh (90.0% of design)" in output
+    assert "-50.7 mAh/month (-1.01% of design)" in output
+    assert "Reaches 80% of design" in output
+    assert "(in about 10 months)" in output
+
+
+def test_capacity_trend_distant_or_no_decline(database, temp_config):
+    """Test a decline too slow to project is reported as such, and only a flat trend as not declining."""
+    base_time = datetime.now(UTC)
+    template = PowerReading(
+        timestamp=base_time,
+        watts_actual=40.0,
+        watts_negotiated=67,
+        voltage=20.0,
+        amperage=2.0,
+        current_capacity=3500,
+        max_capacity=4500,
+        battery_percent=74,
+        is_charging=True,
+        external_connected=True,
+        charger_name=None,
+        charger_manufacturer=None,
+        design_capacity=5000,
+    )
+    for day, max_capacity in ((60, 4501), (30, 4501), (0, 4500)):
+        database.insert_reading(replace(template, timestamp=base_time - timedelta(days=day), max_capacity=max_capacity))
+
+    output = strip_ansi(runner.invoke(app, ["capacity-trend"]).stdout)
+    assert "Declining, but not within 50 years" in output
+    assert "Not declining" not in output
+
+    database.clear_history()
+    for day in (60, 30, 0):
+        database.insert_reading(replace(template, timestamp=base_time - timedelta(days=day)))
+
+    output = strip_ansi(runner.invoke(app, ["capacity-trend"]).stdout)
+    assert "Not declining toward it" in output
+
+
+def test_health_trend_invalid_period(temp_config):
+    """Test health-trend rejects unknown periods."""
+    result = runner.invoke(app, ["health-trend", "--period", "year"])
+    assert result.exit_code == 1
+    assert "Invalid period" in strip_ansi(result.stderr)
+
+
+def test_wait_for_condition_met(temp_config):
+    """Test wait-for exits 0 once the condition holds."""
+    result = runner.invoke(app, ["wait-for", "battery>=50", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    assert "Condition met: battery_percent >= 50" in strip_ansi(result.stdout)
+
+
+def test_wait_for_timeout(temp_config):
+    """Test wait-for exits 124 when the condition never holds."""
+    result = runner.invoke(
+        app,
+        ["wait-for", "charging==false", "--backend", "mock", "--interval", "0.01", "--timeout", "0.05"],
+    )
+
+    assert result.exit_code == 124
+    assert "Timed out" in strip_ansi(result.stdout)
+
+
+def test_wait_for_invalid_condition(temp_config):
+    """Test wait-for rejects malformed conditions."""
+    result = runner.invoke(app, ["wait-for", "battery=>80", "--backend", "mock"])
+
+    assert result.exit_code == 1
+    assert "Invalid condition" in strip_ansi(result.stderr)
+
+
+def test_udp_sends_readings(temp_config, monkeypatch):
+    """Test udp sends each reading as a datagram tagged with the host name until interrupted."""
+    receiver = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    receiver.settimeout(2)
+    receiver.bind(("127.0.0.1", 0))
+    port = receiver.getsockname()[1]
+
+    def stop(seconds):
+        raise KeyboardInterrupt
+
+    monkeypatch.setattr("powermonitor.cli.time.sleep", stop)
+    result = runner.invoke(app, ["udp", f"127.0.0.1:{port}", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    assert "unencrypted" in strip_ansi(result.stdout)
+    data = json.loads(receiver.recv(65535))
+    receiver.close()
+    assert data["hostname"] == socket.gethostname()
+    assert data["battery_percent"] == 74
+
+
+def test_udp_invalid_address(temp_config):
+    """Test udp rejects an address without a port."""
+    result = runner.invoke(app, ["udp", "collector.local", "--backend", "mock"])
+
+    assert result.exit_code == 1
+    assert "Expected host:port" in strip_ansi(result.stderr)
+
+
+def test_output_option_redirects_command_output(database, temp_config, tmp_path):
+    """Test --output writes command output to a file instead of stdout."""
+    create_test_readings(database, count=2)
+    output_file = tmp_path / "history.txt"
+
+    result = runner.invoke(app, ["--output", str(output_file), "history"])
+
+    assert result.exit_code == 0
+    assert "Recent Power Readings" not in result.stdout
+    content = output_file.read_text()
+    assert "Recent Power Readings" in content
+    assert "\x1b[" not in content  # No terminal escape codes in files
+
+    # Output goes back to stdout for later invocations
+    result = runner.invoke(app, ["history"])
+    assert "Recent Power Readings" in strip_ansi(result.stdout)
+
+
+def test_output_option_keeps_errors_on_stderr(temp_config, tmp_path):
+    """Test errors are not redirected by --output."""
+    output_file = tmp_path / "out.txt"
+
+    result = runner.invoke(app, ["-o", str(output_file), "cleanup"])
+
+    assert result.exit_code == 1
+    assert "Must specify either --days N or --all" in result.stderr
+    assert "Must specify" not in output_file.read_text()
+
+
+def test_status_command(temp_config):
+    """Test status prints a single reading."""
+    result = runner.invoke(app, ["status", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "Charging" in output
+    assert "+45.5W" in output
+
+
+def test_status_json(temp_config):
+    """Test status --json prints a parseable reading."""
+    result = runner.invoke(app, ["status", "--json", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert data["watts_actual"] == 45.5
+    assert data["is_charging"] is True
+    # Only the IOKit backend measures whole-machine power
+    assert data["system_power"] is None
+
+
+def test_status_field(temp_config):
+    """Test status --field prints bare values, tab-separated for several fields."""
+    result = runner.invoke(app, ["status", "--field", "battery_percent", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert result.stdout == "74\n"
+
+    result = runner.invoke(app, ["status", "--field", "watts_actual, is_charging,system_power", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert result.stdout == "45.5\ttrue\t\n"
+
+
+def test_status_field_rejects_unknown_and_conflicting_options(temp_config):
+    """Test unknown field names and --field with --json are errors."""
+    result = runner.invoke(app, ["status", "--field", "battery_percent,wats", "--backend", "mock"])
+    assert result.exit_code == 1
+    stderr = strip_ansi(result.stderr)
+    assert "Unknown field 'wats'" in stderr
+    assert "watts_actual" in stderr  # Known fields are listed
+
+    result = runner.invoke(app, ["status", "--field", "battery_percent", "--json", "--backend", "mock"])
+    assert result.exit_code == 1
+
+
+def test_status_avg(database, temp_config):
+    """Test status --avg averages recent recorded readings, and uses the current reading without any."""
+    result = runner.invoke(app, ["status", "--avg", "5m", "--json", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert json.loads(result.stdout)["avg_watts"] == 45.5
+
+    now = datetime.now(UTC)
+    for minutes, watts in ((30, 90.0), (3, -10.0), (1, -20.0)):
+        reading = replace(default_mock_reading(), timestamp=now - timedelta(minutes=minutes), watts_actual=watts)
+        database.insert_reading(reading)
+
+    result = runner.invoke(app, ["status", "--avg", "5m", "--field", "avg_watts,watts_actual", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert result.stdout == "-15.0\t45.5\n"
+
+    result = runner.invoke(app, ["status", "--avg", "5m", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert "-15.0W over the last 5m" in strip_ansi(result.stdout)
+
+    for args in (["--avg", "soon"], ["--avg", "5m", "--minimal"], ["--field", "avg_watts"]):
+        result = runner.invoke(app, ["status", *args, "--backend", "mock"])
+        assert result.exit_code == 1
+
+
+def test_status_json_derived(temp_config):
+    """Test status --json-derived wraps the --json reading with derived values."""
+    plain = json.loads(runner.invoke(app, ["status", "--json", "--backend", "mock"]).stdout)
+    result = runner.invoke(app, ["status", "--json-derived", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert set(data) == {"reading", "derived"}
+    assert data["reading"].keys() == plain.keys()
+    assert data["derived"]["direction"] == "charging"
+    assert set(data["derived"]) == {
+        "direction",
+        "battery_health_percent",
+        "time_to_full_minutes",
+        "energy_wh_estimate",
+        "charging_throttled",
+    }
+
+
+def test_status_locale_formatting(temp_config, monkeypatch):
+    """Test --locale changes displayed separators but not JSON output."""
+    monkeypatch.setattr(
+        "powermonitor.cli.number_format_for_locale", lambda name: NumberFormat(decimal_point=",", thousands_sep=".")
+    )
+
+    result = runner.invoke(app, ["--locale", "de_DE.UTF-8", "status", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert "+45,5W" in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["--locale", "de_DE.UTF-8", "status", "--json", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert json.loads(result.stdout)["watts_actual"] == 45.5
+
+    # Without --locale the next run is canonical again
+    result = runner.invoke(app, ["status", "--backend", "mock"])
+    assert "+45.5W" in strip_ansi(result.stdout)
+
+
+def test_unknown_locale(temp_config):
+    """Test an uninstalled --locale is reported as an error."""
+    result = runner.invoke(app, ["--locale", "xx_NOPE.UTF-8", "status", "--backend", "mock"])
+
+    assert result.exit_code == 1
+    assert "Unknown locale" in strip_ansi(result.stderr)
+
+
+def test_status_reference_voltage(temp_config):
+    """Test --reference-voltage adds the equivalent current to displayed power only."""
+    result = runner.invoke(app, ["--reference-voltage", "5", "status", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert "+45.5W (≈9.1A @ 5V)" in strip_ansi(result.stdout)
+
+    # Off by default, including for the next run in the same process
+    result = runner.invoke(app, ["status", "--backend", "mock"])
+    assert "@ 5V" not in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["--reference-voltage", "0", "status", "--backend", "mock"])
+    assert result.exit_code == 1
+    assert "--reference-voltage" in strip_ansi(result.stderr)
+
+
+def test_status_deadzone(temp_config, monkeypatch):
+    """Test --deadzone shows jitter at rest as 0.0W while JSON keeps the measured value."""
+    reading = replace(default_mock_reading(), watts_actual=0.3)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    result = runner.invoke(app, ["--deadzone", "0.5", "status"])
+    assert result.exit_code == 0
+    assert "0.0W (negotiated" in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["--deadzone", "0.5", "status", "--json"])
+    assert json.loads(result.stdout)["watts_actual"] == 0.3
+
+    # Off by default
+    assert "+0.3W" in strip_ansi(runner.invoke(app, ["status"]).stdout)
+
+    result = runner.invoke(app, ["--deadzone", "-1", "status"])
+    assert result.exit_code == 1
+    assert "--deadzone" in strip_ansi(result.stderr)
+
+
+def test_status_uncertified_charger(temp_config, monkeypatch):
+    """Test status flags a charger that looks uncertified."""
+    reading = replace(default_mock_reading(), charger_name=None, charger_manufacturer=None, charger_certified=False)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    result = runner.invoke(app, ["status"])
+
+    assert result.exit_code == 0
+    assert "Unknown ⚠ Possibly uncertified charger" in strip_ansi(result.stdout)
+
+
+def test_status_charge_efficiency(temp_config, monkeypatch):
+    """Test status shows the estimated charging efficiency only when the collector provides it."""
+    reading = replace(default_mock_reading(), charge_efficiency=88.6)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    result = runner.invoke(app, ["status"])
+
+    assert result.exit_code == 0
+    assert "~89% of adapter power reaches the battery" in strip_ansi(result.stdout)
+
+    plain = default_mock_reading()
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(plain))
+    assert "Efficiency" not in strip_ansi(runner.invoke(app, ["status"]).stdout)
+
+
+def test_status_adapter_from_smc(temp_config, monkeypatch):
+    """Test status says when the adapter was only detected from SMC input power."""
+    reading = replace(default_mock_reading(), watts_negotiated=0, adapter_source=AdapterSource.SMC)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    output = strip_ansi(runner.invoke(app, ["status"]).stdout)
+
+    assert "adapter wattage unknown" in output
+    assert "detected from SMC input power" in output
+    assert "negotiated 0W" not in output
+
+
+def test_status_minimal(temp_config):
+    """Test status --minimal prints only battery percent and charging state."""
+    result = runner.invoke(app, ["status", "--minimal", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    assert strip_ansi(result.stdout).strip() == "⚡ 74% Charging"
+
+    result = runner.invoke(app, ["status", "--minimal", "--json", "--backend", "mock"])
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert set(data) == {"timestamp", "battery_percent", "is_charging", "external_connected", "fully_charged"}
+    assert data["battery_percent"] == 74
+
+
+def test_status_minimal_rejects_save(temp_config):
+    """Test --minimal readings aren't saved, since most fields are empty."""
+    result = runner.invoke(app, ["status", "--minimal", "--save", "--backend", "mock"])
+
+    assert result.exit_code == 1
+    assert "--minimal" in strip_ansi(result.stderr)
+
+
+def test_status_export_plist(temp_config, monkeypatch):
+    """Test status --export-plist prints a sanitized plist that parses back."""
+    battery = {"Voltage": 12000, "Amperage": 1500, "Serial": "F8Y1234567", "DeviceName": "bq40z651"}
+    monkeypatch.setattr("powermonitor.cli.IORegCollector.read_battery_dict", lambda self: battery)
+
+    result = runner.invoke(app, ["status", "--export-plist"])
+
+    assert result.exit_code == 0
+    assert plistlib.loads(result.stdout.encode()) == [{"Voltage": 12000, "Amperage": 1500}]
+
+    result = runner.invoke(app, ["status", "--export-plist", "--backend", "mock"])
+    assert result.exit_code == 1
+
+
+def test_status_smc_json_rejects_other_backends(temp_config):
+    """Test status --smc-json explains that SMC sensors need the IOKit backend."""
+    result = runner.invoke(app, ["status", "--smc-json", "--backend", "ioreg"])
+
+    assert result.exit_code == 1
+    assert "--smc-json" in strip_ansi(result.stderr)
+
+
+def test_status_smc_record_rejects_other_backends(temp_config, tmp_path):
+    """Test status --smc-record needs the IOKit backend and writes nothing otherwise."""
+    recording = tmp_path / "smc.json"
+    result = runner.invoke(app, ["status", "--smc-record", str(recording), "--backend", "mock"])
+
+    assert result.exit_code == 1
+    assert "--smc-record" in strip_ansi(result.stderr)
+    assert not recording.exists()
+
+
+def test_smc_read_requires_macos(temp_config, monkeypatch):
+    """Test smc read explains that SMC keys are only readable on macOS."""
+    monkeypatch.setattr("powermonitor.cli.sys.platform", "linux")
+
+    result = runner.invoke(app, ["smc", "read", "PDTR"])
+
+    assert result.exit_code == 1
+    assert "requires macOS" in strip_ansi(result.stderr)
+
+
+def test_status_threshold_warning(temp_config):
+    """Test status warns about readings past a threshold given on the command line."""
+    result = runner.invoke(app, ["--high-power", "40", "status", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    assert "High power: 45.5W (threshold 40W)" in strip_ansi(result.stdout)
+
+    result = runner.invoke(app, ["--low-battery", "101", "status", "--backend", "mock"])
+    assert result.exit_code == 1
+    assert "low_battery must be between 0 and 100" in strip_ansi(result.stderr)
+
+
+def test_config_path(temp_config, monkeypatch):
+    """Test config path prints the config file location."""
+    monkeypatch.setattr("powermonitor.cli.get_config_path", lambda: Path(temp_config))
+
+    result = runner.invoke(app, ["config", "path"])
+
+    assert result.exit_code == 0
+    assert result.stdout.strip() == temp_config
+
+
+def test_config_show_applies_overrides(temp_config, temp_db):
+    """Test config show prints the merged configuration as TOML."""
+    result = runner.invoke(app, ["--overheat-temp", "40", "config", "show"])
+
+    assert result.exit_code == 0
+    shown = tomllib.loads(result.stdout)
+    assert shown["database"]["path"] == str(temp_db)
+    assert shown["thresholds"] == {"low_battery": 20, "high_power": 60.0, "overheat_temp": 40.0}
+
+
+def test_quiet_suppresses_notices(temp_config, tmp_path, monkeypatch):
+    """Test --quiet drops informational stderr output but keeps the requested output."""
+    missing = tmp_path / "missing.toml"
+    monkeypatch.setattr("powermonitor.cli.get_config_path", lambda: missing)
+
+    result = runner.invoke(app, ["config", "path"])
+    assert "does not exist" in result.stderr
+
+    result = runner.invoke(app, ["--quiet", "config", "path"])
+    assert result.exit_code == 0
+    assert result.stdout.strip() == str(missing)
+    assert result.stderr == ""
+
+
+def test_probe_prints_every_source_as_json(temp_config, monkeypatch):
+    """Test probe prints one JSON document with a section per data source, even when they fail."""
+    monkeypatch.setattr("powermonitor.probe.sys.platform", "linux")
+    monkeypatch.setattr("powermonitor.probe.shutil.which", lambda name: None)
+
+    result = runner.invoke(app, ["probe"])
+
+    assert result.exit_code == 0
+    data = json.loads(result.stdout)
+    assert list(data) == ["system", "ioreg", "smc", "pmset", "powermetrics"]
+    assert data["smc"]["error"] == "IOKit requires macOS"
+    assert data["powermetrics"]["available"] is False
+    assert "powermonitor" in data["system"]
+
+
+def test_doctor_healthy(temp_config, monkeypatch):
+    """Test doctor reports a consistent battery at rest."""
+    reading = replace(default_mock_reading(), voltage=11.52, amperage=0.0, battery_percent=50)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    result = runner.invoke(app, ["doctor"])
+
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "✓ Collection: MockCollector" in output
+    assert "✓ Calibration: 50% is consistent with 11.52V" in output
+    assert "✓ Thresholds" in output
+
+
+def test_ascii_replaces_emoji(temp_config, monkeypatch):
+    """Test --ascii (alias --plain) prints ASCII stand-ins instead of emoji and symbols."""
+    reading = replace(default_mock_reading(), voltage=11.52, amperage=0.0, battery_percent=50)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    result = runner.invoke(app, ["--ascii", "doctor"])
+    assert result.exit_code == 0
+    assert "[OK] Collection: MockCollector" in strip_ansi(result.stdout)
+    assert result.stdout.isascii()
+
+    result = runner.invoke(app, ["--plain", "status", "--backend", "mock"])
+    assert result.exit_code == 0
+    assert "[CHG] Charging" in strip_ansi(result.stdout)
+
+    # Emoji stay the default
+    result = runner.invoke(app, ["status", "--backend", "mock"])
+    assert "⚡ Charging" in strip_ansi(result.stdout)
+
+
+def test_doctor_suggests_calibration(temp_config, monkeypatch):
+    """Test doctor flags a percentage that contradicts the battery voltage."""
+    reading = replace(default_mock_reading(), voltage=12.9, amperage=0.1, battery_percent=50)
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MockCollector(reading))
+
+    result = runner.invoke(app, ["doctor"])
+
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "battery reports 50% but 12.90V suggests about 100%" in output
+    assert "calibration cycle" in output
+
+
+def test_doctor_skips_calibration_under_load(temp_config):
+    """Test doctor doesn't judge calibration while current is flowing, but still checks thresholds."""
+    result = runner.invoke(app, ["--high-power", "40", "doctor", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    output = strip_ansi(result.stdout)
+    assert "Calibration: not checked" in output
+    assert "High power: 45.5W" in output
+
+
+def test_doctor_reports_missing_tool(temp_config, monkeypatch):
+    """Test doctor distinguishes a missing ioreg from a failing one."""
+
+    class MissingTool:
+        def collect(self):
+            raise CollectorUnavailableError("ioreg", "add /usr/sbin to PATH")
+
+    monkeypatch.setattr("powermonitor.cli.create_collector", lambda backend, **_: MissingTool())
+
+    result = runner.invoke(app, ["doctor"])
+
+    assert result.exit_code == 1
+    assert "required tool missing - ioreg not found on PATH" in strip_ansi(result.stderr)
@@ -0,0 +1,110 @@
+"""End-to-end tests running CLI commands against a temporary database with the mock backend."""
+
+import csv
+import json
+import os
+import subprocess
+import sys
+from datetime import timedelta
+
+from typer.testing import CliRunner
+
+from powermonitor.cli import app
+from powermonitor.collector.mock import default_mock_reading
+from powermonitor.database import Database
+
+runner = CliRunner()
+
+
+def test_status_history_export_workflow(temp_config, temp_db, tmp_path):
+    """Test readings recorded by status show up in history and exports."""
+    for _ in range(3):
+        result = runner.invoke(app, ["status", "--save", "--backend", "mock"])
+        assert result.exit_code == 0, result.stderr
+        assert "Charging" in result.stdout
+
+    with Database(temp_db) as db:
+        stored = db.query_history(limit=None)
+    assert len(stored) == 3
+    assert all(r.watts_actual == 45.5 for r in stored)
+
+    result = runner.invoke(app, ["history"])
+    assert result.exit_code == 0, result.stderr
+    assert "Recent Power Readings (Last 3)" in result.stdout
+    assert "+45.5W" in result.stdout
+
+    csv_path = tmp_path / "readings.csv"
+    result = runner.invoke(app, ["export", str(csv_path)])
+    assert result.exit_code == 0, result.stderr
+    with open(csv_path) as f:
+        rows = list(csv.DictReader(f))
+    assert len(rows) == 3
+    assert {row["charger_name"] for row in rows} == {"USB-C Power Adapter"}
+
+    json_path = tmp_path / "readings.json"
+    result = runner.invoke(app, ["export", str(json_path)])
+    assert result.exit_code == 0, result.stderr
+    assert [r["battery_percent"] for r in json.loads(json_path.read_text())] == [74, 74, 74]
+
+
+def test_status_save_with_label(temp_config, temp_db):
+    """Test status --save --label tags the stored reading so history --label finds it."""
+    runner.invoke(app, ["status", "--save", "--backend", "mock"])
+    result = runner.invoke(app, ["status", "--save", "-l", "overnight", "--backend", "mock"])
+    assert result.exit_code == 0, result.stderr
+
+    with Database(temp_db) as db:
+        assert [r.label for r in db.query_history(limit=None, label="overnight")] == ["overnight"]
+
+    result = runner.invoke(app, ["history", "--label", "overnight"])
+    assert result.exit_code == 0, result.stderr
+    assert "Last 1" in result.stdout
+
+
+def test_status_json_without_save_leaves_database_empty(temp_config, temp_db):
+    """Test status only writes to the database when --save is given."""
+    result = runner.invoke(app, ["status", "--json", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    assert json.loads(result.stdout)["battery_percent"] == 74
+
+    result = runner.invoke(app, ["history"])
+    assert "No readings in database" in result.stdout
+
+
+def test_wait_for_and_output_redirect(temp_config, tmp_path):
+    """Test global options wire through to subcommands end to end."""
+    output_file = tmp_path / "wait.txt"
+
+    result = runner.invoke(app, ["-q", "-o", str(output_file), "wait-for", "plugged==true", "--backend", "mock"])
+
+    assert result.exit_code == 0
+    assert result.stdout == ""
+    assert "Condition met: external_connected == true" in output_file.read_text()
+
+
+def test_replay_ndjson_exits_cleanly_when_reader_closes(tmp_path):
+    """Test a streaming replay piped into a reader that quits (like `| head -n 1`) exits 0 without a traceback."""
+    base = default_mock_reading()
+    path = tmp_path / "session.ndjson"
+    with path.open("w") as f:
+        for i in range(500):
+            f.write(json.dumps({**base.to_dict(), "timestamp": (base.timestamp + timedelta(seconds=i)).isoformat()}))
+            f.write("\n")
+
+    process = subprocess.Popen(
+        [sys.executable, "-c", "from powermonitor.cli import app; app()", "replay", str(path), "--ndjson"]
+        + ["--speed", "100"],
+        stdout=subprocess.PIPE,
+        stderr=subprocess.PIPE,
+        env={**os.environ, "HOME": str(tmp_path)},  # No user config or logs
+    )
+    # Line buffering delivers the first reading right away, not when a block fills
+    first = process.stdout.readline()
+    process.stdout.close()
+    _, stderr = process.communicate(timeout=60)
+
+    assert json.loads(first)["battery_percent"] == 74
+    assert process.returncode == 0
+    assert b"Traceback" not in stderr
+    assert b"BrokenPipe" not in stderr
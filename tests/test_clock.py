@@ -0,0 +1,41 @@
+"""Tests for injectable clocks."""
+
+from datetime import UTC
+from datetime import datetime
+from datetime import timedelta
+
+import pytest
+
+from powermonitor.clock import MockClock
+from powermonitor.clock import SystemClock
+from powermonitor.collector.base import CollectorConfig
+
+
+def test_system_clock_is_utc():
+    """Test the system clock returns aware UTC times."""
+    now = SystemClock().now()
+
+    assert now.tzinfo is UTC
+    assert abs(now - datetime.now(tz=UTC)) < timedelta(seconds=5)
+
+
+def test_mock_clock_only_moves_when_advanced():
+    """Test MockClock holds its time until advanced."""
+    start = datetime(2025, 12, 28, 12, 0, tzinfo=UTC)
+    clock = MockClock(start)
+
+    assert clock.now() == clock.now() == start
+    assert clock.advance(90) == start + timedelta(seconds=90)
+    assert clock.now() == start + timedelta(seconds=90)
+
+
+def test_mock_clock_rejects_naive_start():
+    """Test a naive start time is rejected so readings stay timezone-aware."""
+    with pytest.raises(ValueError, match="timezone-aware"):
+        MockClock(datetime(2025, 12, 28, 12, 0))
+
+
+def test_collector_config_clock_is_not_compared():
+    """Test configs differing only in clock compare equal, and the system clock is the default."""
+    assert CollectorConfig(clock=MockClock()) == CollectorConfig()
+    assert isinstance(CollectorConfig().clock, SystemClock)
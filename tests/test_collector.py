@@ -2,11 +2,34 @@
 
 import plistlib
 import sys
+from dataclasses import replace
+from datetime import UTC
+from datetime import date
 from datetime import datetime
+from datetime import timedelta
+from unittest.mock import patch
+from xml.parsers.expat import ExpatError
 
 import pytest
 
+from powermonitor.clock import MockClock
+from powermonitor.collector.base import CollectorConfig
 from powermonitor.collector.ioreg import IORegCollector
+from powermonitor.collector.ioreg import decode_charger_port
+from powermonitor.collector.ioreg import decode_manufacture_date
+from powermonitor.collector.ioreg import decode_not_charging_reason
+from powermonitor.collector.ioreg import export_battery_plist
+from powermonitor.collector.ioreg import first_adapter_details
+from powermonitor.collector.ioreg import parse_ioreg_output
+from powermonitor.collector.ioreg import sanitize_battery
+from powermonitor.collector.ioreg import signed_milliamps
+from powermonitor.collector.mock import MockCollector
+from powermonitor.models import AdapterDetail
+from powermonitor.models import AdapterSource
+from powermonitor.models import CollectorUnavailableError
+from powermonitor.models import CommandFailedError
+from powermonitor.models import NoBatteryError
+from powermonitor.models import ParseError
 from powermonitor.models import PowerReading
 
 
@@ -50,6 +73,174 @@ def test_ioreg_collector_with_real_data(ioreg_fixture_path):
     # Verify voltage is reasonable (typical range: 10-21V for MacBooks)
     assert 10.0 <= reading.voltage <= 21.0
 
+    # Verify battery flags
+    assert reading.battery_installed is True
+    assert reading.fully_charged is False
+
+    # Temperature is reported in hundredths of a degree Celsius
+    assert reading.battery_temp == pytest.approx(30.18)
+
+    # Plugged in at 86% but held by ChargerData.NotChargingReason
+    assert reading.charge_inhibit_reason == "Optimized Battery Charging"
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_parse_ioreg_output_trailing_garbage(ioreg_fixture_path):
+    """Test output with embedded NULs and trailing garbage is recovered."""
+    garbage_path = ioreg_fixture_path.with_name("real_mac_trailing_garbage.txt")
+    with open(garbage_path, "rb") as f:
+        raw = f.read()
+
+    with pytest.raises(ExpatError):
+        plistlib.loads(raw)
+
+    plist_data = parse_ioreg_output(raw)
+
+    assert isinstance(plist_data, list)
+    assert plist_data[0]["AdapterDetails"]["Description"] == "pd charger"
+    reading = IORegCollector()._parse_battery_data(plist_data[0])
+    assert 0 <= reading.battery_percent <= 100
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_parse_ioreg_output_truncated(ioreg_fixture_path):
+    """Test truncated output falls back to parsing just the battery dictionary."""
+    with open(ioreg_fixture_path, "rb") as f:
+        raw = f.read()
+
+    # Simulate a buffer limit cutting the document after the battery dictionary
+    truncated = raw[: raw.rindex(b"</array>")] + b"\t<dict>\n\t\t<key>Partial"
+
+    plist_data = parse_ioreg_output(truncated)
+
+    assert plist_data[0] == plistlib.loads(raw)[0]
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_parse_ioreg_output_tolerant_fallback(ioreg_fixture_path):
+    """Test output plistlib rejects even per dictionary is read by the tolerant reader."""
+    malformed = ioreg_fixture_path.with_name("real_mac_malformed.txt").read_bytes()
+    with open(ioreg_fixture_path, "rb") as f:
+        expected = plistlib.loads(f.read())[0]
+
+    # Unescaped "&", a raw control character, and an empty <integer> (Temperature)
+    with pytest.raises(ExpatError):
+        plistlib.loads(malformed)
+
+    battery = parse_ioreg_output(malformed)[0]
+
+    assert battery["AdapterDetails"]["Description"] == "pd charger & cable"
+    assert "Temperature" not in battery  # Dropped, not guessed
+    for key in ("Voltage", "Amperage", "AppleRawCurrentCapacity", "AppleRawMaxCapacity", "ChargerData"):
+        assert battery[key] == sanitize_battery(expected)[key]
+    reading = IORegCollector()._parse_battery_data(battery)
+    assert reading.battery_serial == expected["Serial"]
+    assert reading.battery_temp is None
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_parse_ioreg_output_unrecoverable():
+    """Test output without any complete dictionary raises ParseError."""
+    with pytest.raises(ParseError):
+        parse_ioreg_output(b"<?xml version=\"1.0\"?><plist><array><dict><key>Vol")
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_export_battery_plist_strips_identifiers(ioreg_fixture_path):
+    """Test the exported plist drops serials and device names and parses to the same reading otherwise."""
+    with open(ioreg_fixture_path, "rb") as f:
+        battery = plistlib.load(f)[0]
+
+    exported = export_battery_plist(battery)
+
+    assert b"Serial" not in exported
+    assert b"DeviceName" not in exported
+    collector = IORegCollector()
+    original = collector._parse_battery_data(battery)
+    roundtrip = collector._parse_battery_data(parse_ioreg_output(exported)[0])
+    identifiers_removed = replace(original, charger_serial=None, battery_serial=None)
+    assert replace(roundtrip, timestamp=original.timestamp) == identifiers_removed
+
+
+def test_sanitize_battery_filters_nested_dictionaries():
+    """Test charger and adapter dictionaries keep only parsed keys."""
+    battery = {
+        "Voltage": 12000,
+        "Amperage": 1500,
+        "Serial": "F8Y1234567",
+        "ChargerData": {"NotChargingReason": 0x400000, "ChargerID": 7},
+        "AppleRawAdapterDetails": [{"Watts": 96, "Name": "96W USB-C", "SerialString": "C06123"}],
+        "FedDetails": [{"FedExternalConnected": 1, "FedVendorID": 0x5AC}],
+    }
+
+    assert sanitize_battery(battery) == {
+        "Voltage": 12000,
+        "Amperage": 1500,
+        "ChargerData": {"NotChargingReason": 0x400000},
+        "AppleRawAdapterDetails": [{"Watts": 96, "Name": "96W USB-C"}],
+        "FedDetails": [{"FedExternalConnected": 1}],
+    }
+
+
+def test_ioreg_collector_retries_spawn_failure(monkeypatch):
+    """Test a command that cannot be started (e.g., process table full) is retried with backoff."""
+    delays = []
+    monkeypatch.setattr("time.sleep", delays.append)
+    collector = IORegCollector(spawn_attempts=3, retry_delay=0.1)
+
+    with patch("powermonitor.collector.ioreg.subprocess.run", side_effect=BlockingIOError("Resource unavailable")):
+        with pytest.raises(CommandFailedError, match="after 3 attempt") as exc_info:
+            collector.collect()
+
+    assert exc_info.value.attempts == 3
+    assert delays == [0.1, 0.2]
+
+
+def test_ioreg_collector_missing_binary(monkeypatch, tmp_path):
+    """Test a missing ioreg is reported as unavailable immediately, without retries."""
+    delays = []
+    monkeypatch.setattr("time.sleep", delays.append)
+    collector = IORegCollector(command=[str(tmp_path / "no-such-ioreg")], spawn_attempts=3)
+
+    with pytest.raises(CollectorUnavailableError, match="not found on PATH") as exc_info:
+        collector.collect()
+
+    assert exc_info.value.tool == str(tmp_path / "no-such-ioreg")
+    assert not isinstance(exc_info.value, CommandFailedError)
+    assert delays == []
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_does_not_retry_nonzero_exit(monkeypatch):
+    """Test a command that runs but exits non-zero fails immediately."""
+    delays = []
+    monkeypatch.setattr("time.sleep", delays.append)
+    collector = IORegCollector(command=["false"])
+
+    with pytest.raises(CommandFailedError, match="ioreg command failed") as exc_info:
+        collector.collect()
+
+    assert exc_info.value.attempts == 1
+    assert delays == []
+
 
 @pytest.mark.skipif(
     sys.platform != "darwin",
@@ -197,6 +388,163 @@ def test_ioreg_collector_charger_info():
     assert reading.watts_negotiated == 67
     assert reading.charger_name == "USB-C Power Adapter"
     assert reading.charger_manufacturer == "Apple Inc."
+    assert reading.charger_serial is None
+    assert reading.charger_firmware is None
+    assert reading.charger_certified is True
+    assert reading.adapter_source is AdapterSource.IOREG
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_charger_serial_and_firmware():
+    """Test charger serial number and firmware extraction."""
+    collector = IORegCollector()
+
+    battery_data = {
+        "Voltage": 20000,
+        "Amperage": 3000,
+        "CurrentCapacity": 3000,
+        "MaxCapacity": 4000,
+        "IsCharging": True,
+        "ExternalConnected": True,
+        "AppleRawAdapterDetails": [
+            {
+                "Watts": 96,
+                "Name": "96W USB-C Power Adapter",
+                "SerialString": "C4H1234567890",
+                "FwVersion": "01080001",
+            }
+        ],
+    }
+
+    reading = collector._parse_battery_data(battery_data)
+
+    assert reading.charger_serial == "C4H1234567890"
+    assert reading.charger_firmware == "01080001"
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_battery_identity(ioreg_fixture_path):
+    """Test battery serial number and manufacture date extraction."""
+    collector = IORegCollector()
+    with open(ioreg_fixture_path, "rb") as f:
+        battery = plistlib.load(f)[0]
+
+    reading = collector._parse_battery_data(battery)
+    assert reading.battery_serial == "F5DHJ2001E20000G44"
+    assert reading.battery_manufacture_date is None  # Apple Silicon encoding isn't decoded
+
+    intel = {**battery, "BatterySerialNumber": "D8612345ABCDE", "ManufactureDate": (2021 - 1980) << 9 | 5 << 5 | 3}
+    reading = collector._parse_battery_data(intel)
+    assert reading.battery_serial == "D8612345ABCDE"
+    assert reading.battery_manufacture_date == date(2021, 5, 3)
+
+
+def test_adapter_detail_likely_certified():
+    """Test charger certification is guessed from the identity the adapter reports."""
+    assert AdapterDetail.from_ioreg({"Watts": 67, "Manufacturer": "Apple Inc."}).likely_certified() is True
+    assert AdapterDetail.from_ioreg({"Watts": 65, "Manufacturer": "Anker"}).likely_certified() is None
+    assert AdapterDetail.from_ioreg({"Watts": 96, "SerialString": "C4H1234567890"}).likely_certified() is None
+    assert AdapterDetail.from_ioreg({"Watts": 30, "Manufacturer": " "}).likely_certified() is False
+    assert AdapterDetail.from_ioreg({"Watts": 30}).likely_certified() is False
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_uncertified_charger():
+    """Test an anonymous charger is flagged only while it is connected."""
+    collector = IORegCollector()
+    battery_data = {
+        "Voltage": 12000,
+        "Amperage": 500,
+        "CurrentCapacity": 2500,
+        "MaxCapacity": 4000,
+        "IsCharging": True,
+        "ExternalConnected": True,
+        "AppleRawAdapterDetails": [{"Watts": 30}],
+    }
+
+    assert collector._parse_battery_data(battery_data).charger_certified is False
+
+    battery_data["ExternalConnected"] = False
+    assert collector._parse_battery_data(battery_data).charger_certified is None
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_adapter_details_fallback(ioreg_fixture_path):
+    """Test charger info comes from AdapterDetails when AppleRawAdapterDetails is missing."""
+    fixture = ioreg_fixture_path.with_name("adapter_details_only.txt")
+    battery = parse_ioreg_output(fixture.read_bytes())[0]
+    assert "AppleRawAdapterDetails" not in battery
+
+    reading = IORegCollector()._parse_battery_data(battery)
+
+    assert reading.charger_name == "96W USB-C Power Adapter"
+    assert reading.charger_manufacturer == "Apple Inc."
+    assert reading.watts_negotiated == 96
+
+
+def test_first_adapter_details_prefers_raw_key():
+    """Test AppleRawAdapterDetails wins, and an empty raw array falls back to AdapterDetails."""
+    raw = {"Watts": 96}
+    alt = {"Watts": 70}
+
+    assert first_adapter_details({"AppleRawAdapterDetails": [raw], "AdapterDetails": alt}) == raw
+    assert first_adapter_details({"AppleRawAdapterDetails": [], "AdapterDetails": alt}) == alt
+    assert first_adapter_details({"AdapterDetails": {}}) is None
+    assert first_adapter_details({}) is None
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_charger_port(ioreg_fixture_path):
+    """Test the charger port comes from FedDetails and is cleared when unplugged."""
+    with open(ioreg_fixture_path, "rb") as f:
+        battery_data = plistlib.load(f)[0]
+
+    collector = IORegCollector()
+    assert collector._parse_battery_data(battery_data).charger_port == "3"
+
+    battery_data["ExternalConnected"] = False
+    assert collector._parse_battery_data(battery_data).charger_port is None
+
+
+def test_decode_charger_port():
+    """Test an adapter Port wins over FedDetails, and missing data gives None."""
+    fed_details = [{"FedExternalConnected": 0}, {"FedExternalConnected": 1}]
+
+    assert decode_charger_port({"FedDetails": fed_details}, None) == "2"
+    assert decode_charger_port({"FedDetails": fed_details}, {"Port": 1}) == "1"
+    assert decode_charger_port({"FedDetails": fed_details}, {"Port": " "}) == "2"
+    assert decode_charger_port({"FedDetails": [{"FedExternalConnected": 0}]}, {"Watts": 96}) is None
+    assert decode_charger_port({}, None) is None
+
+
+def test_adapter_detail_from_ioreg():
+    """Test AdapterDetail accepts both generic and Apple key names."""
+    generic = AdapterDetail.from_ioreg(
+        {"Watts": 65, "SerialNumber": "ABC123", "FirmwareVersion": "2.1", "HwVersion": "1.0"}
+    )
+    assert generic.watts == 65
+    assert generic.serial_number == "ABC123"
+    assert generic.firmware_version == "2.1"
+    assert generic.hardware_version == "1.0"
+
+    empty = AdapterDetail.from_ioreg({})
+    assert empty.serial_number is None
+    assert empty.firmware_version is None
 
 
 @pytest.mark.skipif(
@@ -222,6 +570,186 @@ def test_ioreg_collector_no_charger():
     assert reading.charger_name is None
     assert reading.charger_manufacturer is None
     assert reading.external_connected is False
+    assert reading.battery_installed is None
+    assert reading.fully_charged is None
+    assert reading.charger_certified is None
+    assert reading.adapter_source is None
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_fully_charged():
+    """Test FullyCharged flag is surfaced on the reading."""
+    collector = IORegCollector()
+
+    battery_data = {
+        "Voltage": 13000,
+        "Amperage": 0,
+        "CurrentCapacity": 4000,
+        "MaxCapacity": 4000,
+        "IsCharging": False,
+        "ExternalConnected": True,
+        "BatteryInstalled": True,
+        "FullyCharged": True,
+    }
+
+    reading = collector._parse_battery_data(battery_data)
+
+    assert reading.battery_installed is True
+    assert reading.fully_charged is True
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_uses_clock(ioreg_fixture_path):
+    """Test IORegCollector stamps readings with the configured clock."""
+    clock = MockClock(datetime(2025, 12, 28, 12, 0, tzinfo=UTC))
+    collector = IORegCollector(config=CollectorConfig(clock=clock))
+    with open(ioreg_fixture_path, "rb") as f:
+        battery = plistlib.load(f)[0]
+
+    assert collector._parse_battery_data(battery).timestamp == clock.now()
+
+
+def test_decode_manufacture_date():
+    """Test packed Smart Battery dates and ISO strings decode, and unknown encodings give None."""
+    assert decode_manufacture_date((2021 - 1980) << 9 | 5 << 5 | 3) == date(2021, 5, 3)
+    assert decode_manufacture_date("2023-11-20") == date(2023, 11, 20)
+    assert decode_manufacture_date(60688797020979) is None  # Apple Silicon BatteryData encoding
+    assert decode_manufacture_date((2021 - 1980) << 9 | 13 << 5 | 3) is None  # Month 13
+    assert decode_manufacture_date("soon") is None
+    assert decode_manufacture_date(None) is None
+
+
+def test_decode_not_charging_reason():
+    """Test charge-inhibit bitmasks and strings are decoded into readable reasons."""
+    assert decode_not_charging_reason(0) is None
+    assert decode_not_charging_reason(None) is None
+    assert decode_not_charging_reason(True) is None
+    assert decode_not_charging_reason(0x400000) == "Optimized Battery Charging"
+    assert decode_not_charging_reason(0x400000 | 0x8) == "Optimized Battery Charging, reason 0x8"
+    assert decode_not_charging_reason("Temperature") == "Temperature"
+
+
+def test_signed_milliamps():
+    """Test 64-bit two's complement currents are turned back into negative values."""
+    assert signed_milliamps(1500) == 1500
+    assert signed_milliamps(-1500) == -1500
+    assert signed_milliamps(2**64 - 1000) == -1000
+    assert signed_milliamps(2**63 - 1) == 2**63 - 1
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_instant_amperage():
+    """Test InstantAmperage is exposed and drives watts only when configured."""
+    battery_data = {
+        "Voltage": 12000,
+        "Amperage": -500,
+        "InstantAmperage": 2**64 - 2000,  # -2000 mA as printed by some models
+        "CurrentCapacity": 3000,
+        "MaxCapacity": 4000,
+        "IsCharging": False,
+        "ExternalConnected": False,
+    }
+
+    averaged = IORegCollector()._parse_battery_data(battery_data)
+    assert averaged.amperage == -0.5
+    assert averaged.instant_amperage == -2.0
+    assert averaged.watts_actual == pytest.approx(-6.0)
+
+    instant = IORegCollector(config=CollectorConfig(instant_amperage=True))._parse_battery_data(battery_data)
+    assert instant.amperage == -0.5  # Averaged value is still reported
+    assert instant.watts_actual == pytest.approx(-24.0)
+
+    # Falls back to Amperage on models without InstantAmperage
+    del battery_data["InstantAmperage"]
+    fallback = IORegCollector(config=CollectorConfig(instant_amperage=True))._parse_battery_data(battery_data)
+    assert fallback.instant_amperage is None
+    assert fallback.watts_actual == pytest.approx(-6.0)
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_charge_inhibit_reason():
+    """Test the charge-inhibit reason and AppleRawExternalConnected fallback."""
+    collector = IORegCollector()
+
+    battery_data = {
+        "Voltage": 12500,
+        "Amperage": 0,
+        "CurrentCapacity": 3000,
+        "MaxCapacity": 4000,
+        "IsCharging": False,
+        "AppleRawExternalConnected": True,
+        "ChargerData": {"ChargerInhibitReason": 0, "NotChargingReason": 0x400000},
+    }
+
+    reading = collector._parse_battery_data(battery_data)
+
+    assert reading.external_connected is True
+    assert reading.charge_inhibit_reason == "Optimized Battery Charging"
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_max_charge_current():
+    """Test MaxChargeCurrent (or ChargerData.ChargingCurrent) is parsed, and missing keys leave it unknown."""
+    collector = IORegCollector()
+    battery_data = {
+        "Voltage": 12500,
+        "Amperage": 1200,
+        "CurrentCapacity": 2000,
+        "MaxCapacity": 4000,
+        "IsCharging": True,
+        "ExternalConnected": True,
+        "MaxChargeCurrent": 4500,
+        "ChargerData": {"ChargingCurrent": 3000},
+    }
+
+    reading = collector._parse_battery_data(battery_data)
+    assert reading.max_charge_current == 4.5
+    assert reading.charging_throttled
+
+    del battery_data["MaxChargeCurrent"]
+    assert collector._parse_battery_data(battery_data).max_charge_current == 3.0
+
+    battery_data["ChargerData"] = {"ChargingCurrent": 0}  # Not charging: no usable limit
+    assert collector._parse_battery_data(battery_data).max_charge_current is None
+
+    del battery_data["ChargerData"]
+    reading = collector._parse_battery_data(battery_data)
+    assert reading.max_charge_current is None
+    assert not reading.charging_throttled
+
+
+@pytest.mark.skipif(
+    sys.platform != "darwin",
+    reason="IORegCollector requires macOS",
+)
+def test_ioreg_collector_battery_not_installed():
+    """Test BatteryInstalled = false raises NoBatteryError."""
+    collector = IORegCollector()
+
+    battery_data = {
+        "Voltage": 0,
+        "Amperage": 0,
+        "BatteryInstalled": False,
+        "ExternalConnected": True,
+    }
+
+    with pytest.raises(NoBatteryError):
+        collector._parse_battery_data(battery_data)
 
 
 @pytest.mark.skipif(
@@ -284,3 +812,55 @@ def test_iokit_collector_live():
 
     except Exception as e:
         pytest.skip(f"IOKitCollector not available: {e}")
+
+
+def test_mock_collector_default_reading():
+    """Test MockCollector returns a canned reading with a fresh timestamp."""
+    collector = MockCollector()
+
+    first = collector.collect()
+    second = collector.collect()
+
+    assert isinstance(first, PowerReading)
+    assert first.timestamp.tzinfo is not None
+    assert first.watts_actual == second.watts_actual
+    assert first is not second
+
+
+def test_mock_collector_cycles_sequence(sample_reading):
+    """Test MockCollector cycles through a scripted sequence."""
+    unplugged = replace(sample_reading, is_charging=False, external_connected=False, watts_actual=-8.0)
+    collector = MockCollector([sample_reading, unplugged], cycle=True)
+
+    results = [collector.collect().external_connected for _ in range(4)]
+
+    assert results == [True, False, True, False]
+
+
+def test_mock_collector_holds_last_reading(sample_reading):
+    """Test MockCollector repeats the final reading when not cycling."""
+    unplugged = replace(sample_reading, external_connected=False)
+    collector = MockCollector([sample_reading, unplugged], cycle=False)
+
+    results = [collector.collect().external_connected for _ in range(4)]
+
+    assert results == [True, False, False, False]
+
+
+def test_mock_collector_uses_clock(sample_reading):
+    """Test MockCollector stamps readings with the injected clock."""
+    clock = MockClock(datetime(2025, 12, 28, 12, 0, tzinfo=UTC))
+    collector = MockCollector(sample_reading, clock=clock)
+
+    first = collector.collect()
+    clock.advance(30)
+    second = collector.collect()
+
+    assert first.timestamp == datetime(2025, 12, 28, 12, 0, tzinfo=UTC)
+    assert second.timestamp - first.timestamp == timedelta(seconds=30)
+
+
+def test_mock_collector_rejects_empty_sequence():
+    """Test MockCollector requires at least one reading."""
+    with pytest.raises(ValueError, match="at least one reading"):
+        MockCollector([])
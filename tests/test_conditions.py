@@ -0,0 +1,52 @@
+"""Tests for wait-for condition parsing."""
+
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.conditions import parse_condition
+
+
+def test_parse_numeric_condition(sample_reading):
+    """Test numeric comparisons against reading fields."""
+    condition = parse_condition("battery>=74")
+
+    assert condition.field == "battery_percent"
+    assert condition.op == ">="
+    assert condition.value == 74.0
+    assert condition.matches(sample_reading)
+    assert not condition.matches(replace(sample_reading, battery_percent=73))
+
+
+def test_parse_condition_allows_spaces_and_percent(sample_reading):
+    """Test whitespace and a trailing percent sign are accepted."""
+    assert parse_condition(" battery < 80% ").matches(replace(sample_reading, battery_percent=79))
+    assert parse_condition("watts > -5").matches(replace(sample_reading, watts_actual=-1.0))
+
+
+def test_parse_bool_condition(sample_reading):
+    """Test true/false fields only support equality."""
+    condition = parse_condition("charging==false")
+
+    assert condition.value is False
+    assert condition.matches(replace(sample_reading, is_charging=False))
+    assert not condition.matches(replace(sample_reading, is_charging=True))
+    assert parse_condition("plugged!=TRUE").matches(replace(sample_reading, external_connected=False))
+
+    with pytest.raises(ValueError, match="only supports == and !="):
+        parse_condition("charging>true")
+    with pytest.raises(ValueError, match="expects true or false"):
+        parse_condition("charging==1")
+
+
+def test_parse_condition_errors():
+    """Test malformed conditions raise descriptive errors."""
+    cases = [
+        ("battery", "Invalid condition"),
+        ("battery=>80", "Invalid condition"),
+        ("humidity>40", "Unknown field"),
+        ("battery>=eighty", "expects a number"),
+    ]
+    for text, message in cases:
+        with pytest.raises(ValueError, match=message):
+            parse_condition(text)
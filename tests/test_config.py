@@ -1,10 +1,14 @@
 """Tests for PowerMonitorConfig."""
 
 import warnings
+from dataclasses import replace
 
 import pytest
 
+from powermonitor.config import MqttConfig
 from powermonitor.config import PowerMonitorConfig
+from powermonitor.config import Thresholds
+from powermonitor.config import parse_chart_series
 
 
 def test_config_default_values():
@@ -29,6 +33,12 @@ def test_config_custom_values():
     assert config.chart_history_limit == 120
 
 
+def test_config_invalid_title_format():
+    """Test that an unknown title_format raises ValueError."""
+    with pytest.raises(ValueError, match="title_format must be one of"):
+        PowerMonitorConfig(title_format="amps")
+
+
 def test_config_negative_collection_interval():
     """Test that negative collection_interval raises ValueError."""
     with pytest.raises(ValueError, match="collection_interval must be positive"):
@@ -47,6 +57,13 @@ def test_config_negative_stats_limit():
         PowerMonitorConfig(stats_history_limit=-10)
 
 
+def test_config_negative_max_db_rows():
+    """Test that max_db_rows must be 0 (unlimited) or positive."""
+    assert PowerMonitorConfig(max_db_rows=0).max_db_rows == 0
+    with pytest.raises(ValueError, match="max_db_rows must be 0"):
+        PowerMonitorConfig(max_db_rows=-1)
+
+
 def test_config_zero_stats_limit():
     """Test that zero stats_history_limit raises ValueError."""
     with pytest.raises(ValueError, match="stats_history_limit must be positive"):
@@ -65,6 +82,47 @@ def test_config_zero_chart_limit():
         PowerMonitorConfig(chart_history_limit=0)
 
 
+def test_config_invalid_schedule():
+    """Test that a malformed sampling schedule raises ValueError."""
+    with pytest.raises(ValueError, match="Invalid interval"):
+        PowerMonitorConfig(schedule="09:00-18:00=soon")
+
+    assert PowerMonitorConfig(schedule="09:00-18:00=1m").schedule == "09:00-18:00=1m"
+
+
+def test_config_max_interval_validation():
+    """Test the adaptive sampling bound must cover the base interval and excludes a schedule."""
+    assert PowerMonitorConfig(collection_interval=2.0, max_interval=60.0).max_interval == 60.0
+
+    with pytest.raises(ValueError, match="at least collection_interval"):
+        PowerMonitorConfig(collection_interval=2.0, max_interval=1.0)
+
+    with pytest.raises(ValueError, match="schedule"):
+        PowerMonitorConfig(max_interval=60.0, schedule="09:00-18:00=1m")
+
+
+def test_config_store_interval_validation():
+    """Test stored readings can't be finer than the samples they average."""
+    assert PowerMonitorConfig(collection_interval=2.0, store_interval=60.0).store_interval == 60.0
+    assert PowerMonitorConfig(store_interval=0).store_interval == 0
+
+    with pytest.raises(ValueError, match="store_interval must be 0"):
+        PowerMonitorConfig(collection_interval=2.0, store_interval=1.0)
+
+
+def test_parse_chart_series():
+    """Test chart series lists are normalized and unknown names rejected."""
+    assert parse_chart_series("watts,negotiated") == ("watts", "negotiated")
+    assert parse_chart_series(" Battery, temp ,battery") == ("battery", "temp")
+
+    with pytest.raises(ValueError, match="Unknown chart series 'power'"):
+        parse_chart_series("watts,power")
+    with pytest.raises(ValueError, match="empty"):
+        parse_chart_series(" , ")
+    with pytest.raises(ValueError, match="Unknown chart series"):
+        PowerMonitorConfig(chart_series="humidity")
+
+
 def test_config_very_short_interval_warning():
     """Test that very short interval triggers a warning."""
     with warnings.catch_warnings(record=True) as w:
@@ -103,3 +161,65 @@ def test_config_normal_interval_no_warning():
 
         assert len(w) == 0
         assert config.collection_interval == 1.0
+
+
+def test_thresholds_validation():
+    """Test that out-of-range thresholds raise ValueError."""
+    with pytest.raises(ValueError, match="low_battery"):
+        Thresholds(low_battery=120)
+    with pytest.raises(ValueError, match="high_power"):
+        Thresholds(high_power=0)
+    with pytest.raises(ValueError, match="overheat_temp"):
+        Thresholds(overheat_temp=-5)
+
+
+def test_thresholds_with_overrides_ignores_unset():
+    """Test that None overrides keep the configured value."""
+    thresholds = Thresholds(low_battery=15, high_power=80.0).with_overrides(low_battery=None, high_power=50.0)
+
+    assert thresholds == Thresholds(low_battery=15, high_power=50.0)
+    with pytest.raises(ValueError, match="low_battery"):
+        thresholds.with_overrides(low_battery=-1)
+
+
+def test_mqtt_config_validation():
+    """Test that the broker must be an MQTT URL and topic prefixes can't contain wildcards."""
+    assert MqttConfig().broker == ""
+    assert MqttConfig(broker="mqtts://broker.local:8883").broker == "mqtts://broker.local:8883"
+    with pytest.raises(ValueError, match="broker"):
+        MqttConfig(broker="http://broker.local")
+    with pytest.raises(ValueError, match="topic_prefix"):
+        MqttConfig(topic_prefix="power/#")
+    with pytest.raises(ValueError, match="discovery_prefix"):
+        MqttConfig(discovery_prefix="/")
+
+    settings = MqttConfig(broker="mqtt://a", username="mac").with_overrides(broker=None, username="other")
+    assert (settings.broker, settings.username) == ("mqtt://a", "other")
+    assert "secret" not in repr(MqttConfig(password="secret"))
+
+
+def test_thresholds_check(sample_reading):
+    """Test that each exceeded threshold produces one warning."""
+    thresholds = Thresholds(low_battery=20, high_power=60.0, overheat_temp=45.0)
+    on_battery = replace(
+        sample_reading, watts_actual=-12.0, battery_percent=20, is_charging=False, external_connected=False
+    )
+
+    assert thresholds.check(replace(on_battery, battery_percent=50)) == []
+
+    warnings_found = thresholds.check(replace(on_battery, watts_actual=-72.0, battery_temp=47.5))
+    assert len(warnings_found) == 3
+    assert warnings_found[0].startswith("Low battery: 20%")
+    assert warnings_found[1].startswith("High power: 72.0W")
+    assert warnings_found[2].startswith("Battery overheating: 47.5°C")
+
+    # Low battery is only a concern while running on battery
+    assert thresholds.check(replace(on_battery, external_connected=True)) == []
+
+
+def test_config_default_thresholds():
+    """Test that each config gets its own default thresholds."""
+    config = PowerMonitorConfig()
+
+    assert config.thresholds == Thresholds()
+    assert config.thresholds.low_battery == 20
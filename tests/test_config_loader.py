@@ -7,12 +7,15 @@ from unittest.mock import patch
 import pytest
 from loguru import logger
 
+from powermonitor.collector.base import WattsBlend
 from powermonitor.config_loader import _convert_to_type
 from powermonitor.config_loader import _get_nested_value
 from powermonitor.config_loader import _load_toml_file
 from powermonitor.config_loader import _validate_config_structure
 from powermonitor.config_loader import _warn_unknown_keys
+from powermonitor.config_loader import format_config
 from powermonitor.config_loader import load_config
+from powermonitor.config_loader import save_title_format
 
 
 class TestConvertToType:
@@ -420,6 +423,245 @@ level = 123
         finally:
             temp_path.unlink()
 
+    def test_load_config_title_format(self):
+        """Test that a valid title_format is loaded and an invalid one falls back to default."""
+        for raw, expected in (('"watts+percent"', "watts+percent"), ('"amps"', "watts")):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"""
+[tui]
+stats_limit = 200
+title_format = {raw}
+""")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    config = load_config()
+
+                    assert config.title_format == expected
+                    assert config.stats_history_limit == 200
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_thresholds(self):
+        """Test that [thresholds] is loaded and an out-of-range value falls back to default thresholds."""
+        for body, expected in (
+            ("low_battery = 15\nhigh_power = 90\noverheat_temp = 40.5", (15, 90.0, 40.5)),
+            ("low_battery = 150\nhigh_power = 90", (20, 60.0, 45.0)),
+        ):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[tui]\nstats_limit = 200\n\n[thresholds]\n{body}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    config = load_config()
+
+                    thresholds = config.thresholds
+                    assert (thresholds.low_battery, thresholds.high_power, thresholds.overheat_temp) == expected
+                    assert config.stats_history_limit == 200
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_live_chart(self):
+        """Test that tui.live_chart accepts only TOML booleans."""
+        for raw, expected in (("true", True), ('"yes"', False)):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[tui]\nlive_chart = {raw}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().live_chart is expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_schedule(self):
+        """Test that tui.schedule is loaded and a malformed schedule falls back to a fixed interval."""
+        for raw, expected in (('"09:00-18:00=1m, *=10m"', "09:00-18:00=1m, *=10m"), ('"always"', ""), ("5", "")):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[tui]\nschedule = {raw}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().schedule == expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_max_interval(self):
+        """Test that tui.max_interval is loaded and invalid bounds fall back to a fixed interval."""
+        cases = (
+            ("max_interval = 60", 60.0),
+            ("max_interval = 0.5", 0.0),  # Below the 1s interval
+            ('max_interval = 60\nschedule = "*=5m"', 0.0),
+        )
+        for body, expected in cases:
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[tui]\n{body}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().max_interval == expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_store_interval(self):
+        """Test that tui.store_interval is loaded and values below the interval store every sample."""
+        cases = (
+            ("store_interval = 60", 60.0),
+            ("store_interval = 0.5", 0.0),  # Below the 1s interval
+            ("interval = 5\nstore_interval = 5", 5.0),
+        )
+        for body, expected in cases:
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[tui]\n{body}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().store_interval == expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_chart_series(self):
+        """Test that tui.chart_series is loaded and invalid lists fall back to the default."""
+        for raw, expected in (
+            ('"watts,battery"', "watts,battery"),
+            ('"watts,power"', "watts,negotiated"),
+            ("3", "watts,negotiated"),
+        ):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[tui]\nchart_series = {raw}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().chart_series == expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_collector(self):
+        """Test that [collector] is loaded and invalid values fall back to defaults."""
+        for body, expected in (
+            ('watts_blend = "weighted"\nsmc_weight = 0.75', (WattsBlend.WEIGHTED, 0.75)),
+            ('watts_blend = "average"\nsmc_weight = 0.75', (WattsBlend.SMC, 0.75)),
+            ('watts_blend = "ioreg"\nsmc_weight = 2', (WattsBlend.SMC, 0.5)),
+        ):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[collector]\n{body}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    collector = load_config().collector
+                    assert (collector.watts_blend, collector.smc_weight) == expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_instant_amperage(self):
+        """Test that collector.instant_amperage accepts only TOML booleans."""
+        for raw, expected in (("true", True), ("false", False), ('"yes"', False)):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[collector]\ninstant_amperage = {raw}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().collector.instant_amperage is expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_database_max_rows(self):
+        """Test that database.max_rows is loaded and invalid values fall back to unlimited."""
+        for raw, expected in (("50000", 50000), ('"lots"', 0)):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[database]\nmax_rows = {raw}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().max_db_rows == expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_gpu_power(self):
+        """Test that collector.gpu_power accepts only TOML booleans."""
+        for raw, expected in (("true", True), ("false", False), ("1", False)):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[collector]\ngpu_power = {raw}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    assert load_config().collector.gpu_power is expected
+            finally:
+                temp_path.unlink()
+
+    def test_load_config_mqtt(self):
+        """Test that [mqtt] is loaded, invalid settings fall back to defaults, and the password isn't printed."""
+        for body, expected in (
+            ('broker = "mqtt://ha.local"\npassword = "secret"\ntopic_prefix = "mac"', ("mqtt://ha.local", "mac")),
+            ('broker = "ha.local"', ("", "powermonitor")),
+        ):
+            with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+                f.write(f"[mqtt]\n{body}\n")
+                temp_path = Path(f.name)
+
+            try:
+                with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                    mock_path.return_value = temp_path
+                    config = load_config()
+                    assert (config.mqtt.broker, config.mqtt.topic_prefix) == expected
+                    assert "secret" not in format_config(config)
+            finally:
+                temp_path.unlink()
+
+    def test_format_config_roundtrip(self):
+        """Test that format_config output loads back to the same configuration."""
+        with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
+            f.write("""
+[tui]
+interval = 2.5
+title_format = "percent"
+
+[database]
+path = "/tmp/power data.db"
+
+[thresholds]
+low_battery = 10
+
+[collector]
+watts_blend = "weighted"
+smc_weight = 0.8
+""")
+            temp_path = Path(f.name)
+
+        try:
+            with patch("powermonitor.config_loader.get_config_path") as mock_path:
+                mock_path.return_value = temp_path
+                original = load_config()
+                temp_path.write_text(format_config(original))
+                reloaded = load_config()
+
+            assert reloaded == original
+            assert "[thresholds]\nlow_battery = 10\n" in format_config(original)
+            assert '[collector]\nwatts_blend = "weighted"\nsmc_weight = 0.8\n' in format_config(original)
+        finally:
+            temp_path.unlink()
+
     def test_load_config_lowercase_log_level_normalized(self):
         """Test that lowercase log level is normalized to uppercase."""
         with tempfile.NamedTemporaryFile(mode="w", suffix=".toml", delete=False) as f:
@@ -457,3 +699,49 @@ path = "~/test.db"
                 assert str(config.database_path).startswith(str(Path.home()))
         finally:
             temp_path.unlink()
+
+
+class TestSaveTitleFormat:
+    """Tests for save_title_format function."""
+
+    def test_replaces_existing_setting_and_keeps_the_rest(self, tmp_path):
+        """Test only the title_format line changes; comments and other settings are kept."""
+        config_path = tmp_path / "config.toml"
+        config_path.write_text(
+            '# my settings\n[tui]\ninterval = 2.0\ntitle_format = "watts"  # header\n\n[cli]\ntitle_format = "x"\n'
+        )
+
+        with patch("powermonitor.config_loader.get_config_path", return_value=config_path):
+            save_title_format("percent")
+            config = load_config()
+
+        assert config_path.read_text() == (
+            '# my settings\n[tui]\ninterval = 2.0\ntitle_format = "percent"\n\n[cli]\ntitle_format = "x"\n'
+        )
+        assert config.title_format == "percent"
+        assert config.collection_interval == 2.0
+
+    def test_adds_setting_to_tui_table_or_file(self, tmp_path):
+        """Test the setting is added under an existing [tui] table, or in a new one (creating the file)."""
+        with_tui = tmp_path / "with_tui.toml"
+        with_tui.write_text("[logging]\nlevel = \"DEBUG\"\n\n[tui]\ninterval = 2.0\n")
+        without_tui = tmp_path / "without_tui.toml"
+        without_tui.write_text("[logging]\nlevel = \"DEBUG\"\n")
+        missing = tmp_path / "new" / "config.toml"
+
+        for config_path in (with_tui, without_tui, missing):
+            with patch("powermonitor.config_loader.get_config_path", return_value=config_path):
+                save_title_format("watts+percent")
+                config = load_config()
+            assert config.title_format == "watts+percent"
+
+        assert with_tui.read_text().endswith('[tui]\ntitle_format = "watts+percent"\ninterval = 2.0\n')
+        assert without_tui.read_text().endswith('\n\n[tui]\ntitle_format = "watts+percent"\n')
+        assert missing.read_text() == '[tui]\ntitle_format = "watts+percent"\n'
+
+    def test_rejects_unknown_preset(self, tmp_path):
+        """Test an unknown preset raises instead of writing an invalid config."""
+        config_path = tmp_path / "config.toml"
+        with patch("powermonitor.config_loader.get_config_path", return_value=config_path), pytest.raises(ValueError):
+            save_title_format("volts")
+        assert not config_path.exists()
@@ -0,0 +1,85 @@
+"""Tests for reading CSV exports back."""
+
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.csv_import import check_header
+from powermonitor.csv_import import read_csv
+from powermonitor.export import CSV_COLUMNS
+from powermonitor.export import export_csv
+from powermonitor.models import ThermalState
+
+
+def test_read_csv_round_trips_export(sample_reading, tmp_path):
+    """Test every exported column reads back to an equal reading, with and without optional values."""
+    full = replace(
+        sample_reading,
+        charger_name='Acme "Pro", 96W\nUSB-C',
+        watts_negotiated_stable=65,
+        label="video render",
+        thermal_state=ThermalState.FAIR,
+        design_capacity=5103,
+        battery_temp=31.25,
+        system_power=12.5,
+        low_power_mode=False,
+    )
+    bare = replace(sample_reading, charger_name=None, charger_manufacturer=None, watts_actual=-7.125)
+    path = tmp_path / "r.csv"
+
+    for delimiter in (",", "\t"):
+        export_csv(path, [full, bare], delimiter=delimiter)
+        result = read_csv(path, delimiter=delimiter)
+        assert result.readings == [full, bare]
+        assert result.skipped == []
+
+
+def test_read_csv_skips_malformed_rows(sample_reading, tmp_path):
+    """Test bad rows are reported with their line numbers while the valid ones are kept."""
+    path = tmp_path / "r.csv"
+    export_csv(path, [sample_reading])
+    good = path.read_text().splitlines()[1]
+    cells = good.split(",")
+    bad_percent = ",".join(cells[:7] + ["lots"] + cells[8:])
+    bad_flag = ",".join(cells[:8] + ["maybe"] + cells[9:])
+    empty_required = ",".join(cells[:1] + [""] + cells[2:])
+    path.write_text(
+        "\n".join([",".join(CSV_COLUMNS), good, bad_percent, "2025-12-28T12:00:00,1.0", "", bad_flag, empty_required])
+        + "\n"
+    )
+
+    result = read_csv(path)
+
+    assert result.readings == [sample_reading]
+    assert [line for line, _ in result.skipped] == [3, 4, 6, 7]
+    reasons = [reason for _, reason in result.skipped]
+    assert "invalid battery_percent: 'lots'" in reasons[0]
+    assert "expected 19 fields, got 2" in reasons[1]
+    assert "invalid is_charging: 'maybe'" in reasons[2]
+    assert "watts_actual is empty" in reasons[3]
+
+
+def test_read_csv_accepts_older_exports(sample_reading, tmp_path):
+    """Test files from before the optional columns existed import with those fields unset."""
+    path = tmp_path / "old.csv"
+    export_csv(path, [sample_reading])
+    lines = [",".join(line.split(",")[:12]) for line in path.read_text().splitlines()]
+    path.write_text("\n".join(lines) + "\n")
+
+    assert read_csv(path).readings == [sample_reading]
+
+
+def test_check_header_rejects_mismatched_columns(tmp_path):
+    """Test unknown, repeated, and missing columns are rejected before any row is read."""
+    with pytest.raises(ValueError, match="Unknown column"):
+        check_header([*CSV_COLUMNS, "watts"])
+    with pytest.raises(ValueError, match="Repeated column"):
+        check_header([*CSV_COLUMNS, "label"])
+    with pytest.raises(ValueError, match="Missing column.*voltage"):
+        check_header([name for name in CSV_COLUMNS if name != "voltage"])
+    check_header(list(reversed(CSV_COLUMNS)))
+
+    empty = tmp_path / "empty.csv"
+    empty.write_text("")
+    with pytest.raises(ValueError, match="is empty"):
+        read_csv(empty)
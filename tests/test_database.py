@@ -1,12 +1,24 @@
 """Tests for database operations."""
 
 import sqlite3
+import threading
+from dataclasses import replace
 from datetime import UTC
 from datetime import datetime
 from datetime import timedelta
 
+import pytest
+
+from powermonitor.database import COLUMN_MIGRATIONS
+from powermonitor.database import INSERT_BATCH_SIZE
+from powermonitor.database import PRUNE_CHECK_EVERY
 from powermonitor.database import Database
+from powermonitor.degradation import DEGRADED_READINGS
+from powermonitor.models import BurstResult
+from powermonitor.models import BurstSample
+from powermonitor.models import ChargeSession
 from powermonitor.models import PowerReading
+from powermonitor.models import ThermalState
 
 
 def test_database_initialization(database, temp_db):
@@ -36,6 +48,13 @@ def test_database_initialization(database, temp_db):
         "external_connected",
         "charger_name",
         "charger_manufacturer",
+        "watts_negotiated_stable",
+        "label",
+        "thermal_state",
+        "design_capacity",
+        "battery_temp",
+        "system_power",
+        "low_power_mode",
     }
 
     assert columns == expected_columns
@@ -107,6 +126,88 @@ def test_query_history(database, sample_reading):
     assert history[4].battery_percent == 69  # 5th from last (74 - 5)
 
 
+def test_query_history_time_range(database, sample_reading):
+    """Test since and until bound the returned readings, inclusively."""
+    base_time = datetime(2025, 12, 28, 12, 0, 0, tzinfo=UTC)
+    for i in range(5):
+        database.insert_reading(replace(sample_reading, timestamp=base_time + timedelta(hours=i)))
+
+    readings = database.query_history(
+        limit=None, since=base_time + timedelta(hours=1), until=base_time + timedelta(hours=3)
+    )
+
+    assert [r.timestamp for r in readings] == [base_time + timedelta(hours=h) for h in (3, 2, 1)]
+    assert len(database.query_history(limit=None, since=base_time + timedelta(hours=4))) == 1
+    assert len(database.query_history(limit=None, until=base_time - timedelta(seconds=1))) == 0
+
+
+def test_average_watts_over_window(database, sample_reading):
+    """Test the windowed average covers only recent readings and falls back to the current reading."""
+    now = sample_reading.timestamp
+    for minutes, watts in ((10, 90.0), (4, 20.0), (2, 30.0), (0, 40.0)):
+        database.insert_reading(replace(sample_reading, timestamp=now - timedelta(minutes=minutes), watts_actual=watts))
+
+    assert database.average_watts(now - timedelta(minutes=5)) == pytest.approx(30.0)
+    assert database.average_watts(now - timedelta(minutes=5), until=now - timedelta(minutes=3)) == 20.0
+    assert database.average_watts(now + timedelta(minutes=1)) is None
+
+    assert database.average_watts_over(timedelta(minutes=5), sample_reading) == pytest.approx(30.0)
+    later = replace(sample_reading, timestamp=now + timedelta(hours=1), watts_actual=-12.5)
+    assert database.average_watts_over(timedelta(minutes=5), later) == -12.5
+
+
+def test_query_bucketed_averages(database, sample_reading):
+    """Test readings are averaged per time bucket, newest bucket first."""
+    start = sample_reading.timestamp  # 12:00:00 UTC, on a 5-minute boundary
+    rows = [
+        # First bucket (12:00-12:05): two readings
+        (0, 10.0, 70, True, 60),
+        (299, 20.0, 72, False, 67),
+        # Second bucket (12:05-12:10): three readings
+        (300, 30.0, 80, False, 67),
+        (360, 40.0, 81, True, 67),
+        (420, 50.0, 82, True, 67),
+    ]
+    for offset, watts, percent, charging, negotiated in rows:
+        database.insert_reading(
+            replace(
+                sample_reading,
+                timestamp=start + timedelta(seconds=offset),
+                watts_actual=watts,
+                battery_percent=percent,
+                is_charging=charging,
+                watts_negotiated=negotiated,
+            )
+        )
+
+    buckets = database.query_bucketed(300, limit=None)
+
+    assert [b.timestamp for b in buckets] == [start + timedelta(minutes=5), start]
+    newest, oldest = buckets
+    assert newest.watts_actual == 40.0
+    assert newest.battery_percent == 81
+    assert newest.is_charging is True  # 2 of 3 readings charging
+    assert oldest.watts_actual == 15.0
+    assert oldest.battery_percent == 71
+    assert oldest.watts_negotiated == 64  # 63.5 rounded, not truncated by the integer column
+    assert oldest.charger_name is None
+
+    assert len(database.query_bucketed(300, limit=1)) == 1
+
+
+def test_query_bucketed_label_and_validation(database, sample_reading):
+    """Test bucketing respects the label filter and rejects empty buckets."""
+    database.insert_reading(replace(sample_reading, watts_actual=10.0, label="render"))
+    database.insert_reading(replace(sample_reading, watts_actual=90.0))
+
+    [bucket] = database.query_bucketed(3600, label="render")
+    assert bucket.watts_actual == 10.0
+    assert bucket.label == "render"
+
+    with pytest.raises(ValueError, match="bucket_seconds"):
+        database.query_bucketed(0)
+
+
 def test_query_history_empty(database):
     """Test querying history when database is empty."""
     history = database.query_history()
@@ -149,6 +250,84 @@ def test_get_statistics(database):
     assert stats["latest"] is not None
 
 
+def test_get_charger_stats(database, sample_reading):
+    """Test readings are grouped per charger, most recently seen first, skipping unnamed chargers."""
+    base_time = datetime(2025, 12, 28, 12, 0, 0, tzinfo=UTC)
+    database.insert_reading(replace(sample_reading, timestamp=base_time, watts_actual=40.0, watts_negotiated=60))
+    database.insert_reading(
+        replace(sample_reading, timestamp=base_time + timedelta(minutes=1), watts_actual=50.0, watts_negotiated=67)
+    )
+    database.insert_reading(
+        replace(
+            sample_reading,
+            timestamp=base_time + timedelta(minutes=2),
+            charger_name="30W Charger",
+            charger_manufacturer=None,
+        )
+    )
+    unplugged = replace(sample_reading, charger_name=None, charger_manufacturer=None)
+    database.insert_reading(replace(unplugged, timestamp=base_time + timedelta(minutes=3)))
+
+    chargers = database.get_charger_stats()
+
+    assert [c.name for c in chargers] == ["30W Charger", "USB-C Power Adapter"]
+    assert chargers[0].manufacturer is None
+    apple = chargers[1]
+    assert apple.manufacturer == "Apple Inc."
+    assert apple.readings == 2
+    assert apple.max_watts_negotiated == 67
+    assert apple.avg_watts_actual == 45.0
+    assert datetime.fromisoformat(apple.first_seen) == base_time
+    assert datetime.fromisoformat(apple.last_seen) == base_time + timedelta(minutes=1)
+
+
+def test_charger_peak_is_stored(database, sample_reading):
+    """Test each charger's peak negotiated power is stored, survives clearing history, and is reported."""
+    assert database.update_charger_peak(replace(sample_reading, external_connected=False)) is None
+
+    database.update_charger_peak(replace(sample_reading, watts_negotiated=96))
+    for _ in range(DEGRADED_READINGS):
+        database.insert_reading(replace(sample_reading, watts_negotiated=60))
+        peak = database.update_charger_peak(replace(sample_reading, watts_negotiated=60))
+
+    assert peak is not None
+    assert peak.degraded
+    assert database.get_charger_peaks()["USB-C Power Adapter"] == peak
+
+    chargers = database.get_charger_stats()
+    assert chargers[0].max_watts_negotiated == 60  # Only the stored readings
+    assert chargers[0].peak == peak
+    assert chargers[0].to_dict()["peak_watts_negotiated"] == 96
+    assert chargers[0].degraded
+
+    database.clear_history()
+    assert database.get_charger_peaks()["USB-C Power Adapter"].peak_watts == 96
+
+
+def test_charge_sessions_roundtrip(database):
+    """Test charge sessions are stored and listed most recent first, up to the limit."""
+    base_time = datetime(2025, 12, 28, 12, 0, 0, tzinfo=UTC)
+    for i in range(3):
+        started = base_time + timedelta(hours=i * 2)
+        database.insert_charge_session(
+            ChargeSession(
+                started_at=started,
+                ended_at=started + timedelta(minutes=45),
+                start_percent=30 + i,
+                end_percent=80 + i,
+                total_wh=25.5 + i,
+            )
+        )
+
+    sessions = database.query_charge_sessions(limit=2)
+
+    assert [s.start_percent for s in sessions] == [32, 31]
+    assert sessions[0].started_at == base_time + timedelta(hours=4)
+    assert sessions[0].duration == timedelta(minutes=45)
+    assert sessions[0].total_wh == 27.5
+    assert database.query_charge_sessions(limit=10)[-1].start_percent == 30
+
+
 def test_get_statistics_empty(database):
     """Test statistics when database is empty."""
     stats = database.get_statistics()
@@ -162,6 +341,67 @@ def test_get_statistics_empty(database):
     assert stats["latest"] is None
 
 
+def test_connection_uses_wal_and_busy_timeout(database):
+    """Test connections are opened in WAL mode with a busy timeout."""
+    with database.db.connection_context():
+        assert database.db.execute_sql("PRAGMA journal_mode").fetchone()[0] == "wal"
+        assert database.db.execute_sql("PRAGMA busy_timeout").fetchone()[0] == 5000
+
+
+def test_concurrent_connections_can_both_write(temp_db, sample_reading):
+    """Test two connections write to the same file, waiting out a competing writer."""
+    tui = Database(temp_db)
+    cli = Database(temp_db)
+    tui.insert_reading(sample_reading)
+    cli.insert_reading(replace(sample_reading, watts_actual=10.0))
+
+    # Another process holding the write lock briefly makes the writer wait rather than fail
+    other = sqlite3.connect(temp_db, check_same_thread=False)
+    other.execute("BEGIN IMMEDIATE")
+    release = threading.Timer(0.2, other.commit)
+    release.start()
+    cli.insert_reading(replace(sample_reading, watts_actual=20.0))
+    release.join()
+    other.close()
+
+    assert len(tui.query_history(limit=None)) == 3
+    tui.close()
+    cli.close()
+
+
+def test_insert_readings_in_batches(temp_db, sample_reading):
+    """Test bulk inserts span several batches, keep every value, and still honor max_rows."""
+    base = sample_reading.timestamp
+    readings = [
+        replace(sample_reading, timestamp=base + timedelta(seconds=i), thermal_state=ThermalState.NOMINAL)
+        for i in range(INSERT_BATCH_SIZE * 2 + 7)
+    ]
+
+    with Database(temp_db) as db:
+        assert db.insert_readings(readings) == len(readings)
+        assert db.insert_readings([]) == 0
+        assert list(db.iter_history()) == readings[::-1]
+
+    with Database(temp_db, max_rows=10) as db:
+        db.insert_readings(readings[:3])
+        assert len(db.query_history(limit=None)) == 10
+
+
+def test_insert_readings_skip_existing(database, sample_reading):
+    """Test stored readings are left out copy for copy, keeping identical rapid samples."""
+    later = replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=1))
+    database.insert_reading(sample_reading)
+
+    # One copy of sample_reading is stored, so only the second identical copy is new
+    assert database.insert_readings([sample_reading, sample_reading, later], skip_existing=True) == 2
+    assert len(database.query_history(limit=None)) == 3
+
+    assert database.insert_readings([sample_reading, sample_reading, later], skip_existing=True) == 0
+    assert database.insert_readings([replace(later, watts_actual=1.0)], skip_existing=True) == 1
+    assert database.insert_readings([later]) == 1
+    assert len(database.query_history(limit=None)) == 5
+
+
 def test_clear_history(database, sample_reading):
     """Test clearing all historical readings."""
     # Insert some readings
@@ -261,6 +501,50 @@ def test_cleanup_old_data(database):
     assert len(remaining) == 3
 
 
+def test_cleanup_dry_run_counts_without_deleting(database, sample_reading):
+    """Test dry runs report the rows a cleanup would delete and leave them in place."""
+    base_time = datetime.now(UTC)
+    for days_old in (0, 10, 30, 40):
+        database.insert_reading(replace(sample_reading, timestamp=base_time - timedelta(days=days_old)))
+
+    assert database.cleanup_old_data(days=25, dry_run=True) == 2
+    assert database.clear_history(dry_run=True) == 4
+    assert len(database.query_history(limit=None)) == 4
+
+    # The real cleanup deletes exactly what the dry run counted
+    assert database.cleanup_old_data(days=25) == 2
+
+
+def test_prune_to_max_rows_keeps_newest(database, sample_reading):
+    """Test pruning deletes the oldest readings beyond the limit."""
+    base_time = datetime.now(UTC)
+    for minutes_old in (4, 3, 2, 1, 0):
+        database.insert_reading(replace(sample_reading, timestamp=base_time - timedelta(minutes=minutes_old)))
+
+    assert database.prune_to_max_rows(3) == 2
+    remaining = database.query_history(limit=None)
+    assert [r.timestamp for r in remaining] == [base_time - timedelta(minutes=m) for m in (0, 1, 2)]
+    assert database.prune_to_max_rows(3) == 0
+
+
+def test_insert_prunes_to_max_rows_periodically(temp_db, sample_reading):
+    """Test max_rows is enforced on the first insert and then every PRUNE_CHECK_EVERY inserts."""
+    with Database(temp_db) as unlimited:
+        for _ in range(5):
+            unlimited.insert_reading(sample_reading)
+
+    with Database(temp_db, max_rows=3) as db:
+        db.insert_reading(sample_reading)  # First insert checks: 6 -> 3
+        assert len(db.query_history(limit=None)) == 3
+
+        for _ in range(PRUNE_CHECK_EVERY - 1):
+            db.insert_reading(sample_reading)
+        assert len(db.query_history(limit=None)) == 3 + PRUNE_CHECK_EVERY - 1
+
+        db.insert_reading(sample_reading)  # PRUNE_CHECK_EVERY-th insert since the last check
+        assert len(db.query_history(limit=None)) == 3
+
+
 def test_cleanup_old_data_none_old(database):
     """Test cleanup_old_data when no readings are old enough."""
     # Create recent readings (all within last day)
@@ -375,3 +659,265 @@ def test_get_battery_health_trend_partial_days(database):
     assert len(results) == 3
     for result in results:
         assert result[2] == 3  # 3 readings per day
+
+
+def test_database_migrates_old_schema(temp_db):
+    """Test opening a database created before newer columns adds them in place."""
+    conn = sqlite3.connect(temp_db)
+    conn.execute(
+        """
+        CREATE TABLE power_readings (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp TEXT NOT NULL,
+            watts_actual REAL NOT NULL,
+            watts_negotiated INTEGER NOT NULL,
+            voltage REAL NOT NULL,
+            amperage REAL NOT NULL,
+            current_capacity INTEGER NOT NULL,
+            max_capacity INTEGER NOT NULL,
+            battery_percent INTEGER NOT NULL,
+            is_charging INTEGER NOT NULL,
+            external_connected INTEGER NOT NULL,
+            charger_name TEXT,
+            charger_manufacturer TEXT
+        )
+        """
+    )
+    conn.execute(
+        "INSERT INTO power_readings (timestamp, watts_actual, watts_negotiated, voltage, amperage, "
+        "current_capacity, max_capacity, battery_percent, is_charging, external_connected) "
+        "VALUES ('2025-12-28T12:00:00+00:00', 10.0, 67, 20.0, 0.5, 3500, 4709, 74, 1, 1)"
+    )
+    conn.commit()
+    conn.close()
+
+    with Database(temp_db) as db:
+        history = db.query_history(limit=None)
+
+    assert len(history) == 1
+    assert history[0].watts_negotiated_stable is None
+
+    conn = sqlite3.connect(temp_db)
+    columns = {row[1] for row in conn.execute("PRAGMA table_info(power_readings)")}
+    conn.close()
+    assert "watts_negotiated_stable" in columns
+
+
+def test_concurrent_schema_setup(temp_db):
+    """Test two connections opening and migrating the same file at once both succeed."""
+    conn = sqlite3.connect(temp_db)
+    conn.execute(
+        "CREATE TABLE power_readings (id INTEGER PRIMARY KEY AUTOINCREMENT, timestamp TEXT NOT NULL, "
+        "watts_actual REAL NOT NULL, watts_negotiated INTEGER NOT NULL, voltage REAL NOT NULL, "
+        "amperage REAL NOT NULL, current_capacity INTEGER NOT NULL, max_capacity INTEGER NOT NULL, "
+        "battery_percent INTEGER NOT NULL, is_charging INTEGER NOT NULL, external_connected INTEGER NOT NULL, "
+        "charger_name TEXT, charger_manufacturer TEXT)"
+    )
+    conn.commit()
+    conn.close()
+
+    start = threading.Barrier(2)
+    errors: list[Exception] = []
+
+    def open_database() -> None:
+        start.wait()
+        try:
+            Database(temp_db).close()
+        except Exception as e:
+            errors.append(e)
+
+    threads = [threading.Thread(target=open_database) for _ in range(2)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+
+    assert errors == []
+    conn = sqlite3.connect(temp_db)
+    columns = {row[1] for row in conn.execute("PRAGMA table_info(power_readings)")}
+    tables = {row[0] for row in conn.execute("SELECT name FROM sqlite_master WHERE type = 'table'")}
+    conn.close()
+    assert set(COLUMN_MIGRATIONS) <= columns
+    assert {"power_readings", "burst_samples", "charge_sessions", "charger_peaks"} <= tables
+
+
+def test_get_summary(database, sample_reading):
+    """Test the comparison summary separates battery draw from charging and reports battery health."""
+    assert database.get_summary().count == 0
+    assert database.get_summary().avg_watts is None
+
+    base = sample_reading.timestamp
+    on_battery = replace(sample_reading, is_charging=False, external_connected=False)
+    database.insert_reading(replace(sample_reading, watts_actual=45.0))
+    database.insert_reading(replace(on_battery, timestamp=base + timedelta(minutes=1), watts_actual=-8.0))
+    database.insert_reading(
+        replace(on_battery, timestamp=base + timedelta(minutes=2), watts_actual=-12.0, design_capacity=5000)
+    )
+
+    summary = database.get_summary()
+    assert summary.count == 3
+    assert summary.earliest == base.isoformat()
+    assert summary.avg_watts == pytest.approx(25.0 / 3)
+    assert summary.avg_draw_watts == 10.0
+    assert summary.peak_draw_watts == 12.0
+    assert summary.peak_charge_watts == 45.0
+    assert summary.max_capacity == sample_reading.max_capacity
+    assert summary.battery_health_percent == pytest.approx(4709 / 5000 * 100)
+    assert summary.to_dict()["battery_health_percent"] == 94.2
+
+
+def test_read_only_database_leaves_old_schema_alone(temp_db, tmp_path):
+    """Test a read-only database can be summarized without being migrated or created."""
+    conn = sqlite3.connect(temp_db)
+    conn.execute(
+        "CREATE TABLE power_readings (id INTEGER PRIMARY KEY AUTOINCREMENT, timestamp TEXT NOT NULL, "
+        "watts_actual REAL NOT NULL, watts_negotiated INTEGER NOT NULL, voltage REAL NOT NULL, "
+        "amperage REAL NOT NULL, current_capacity INTEGER NOT NULL, max_capacity INTEGER NOT NULL, "
+        "battery_percent INTEGER NOT NULL, is_charging INTEGER NOT NULL, external_connected INTEGER NOT NULL, "
+        "charger_name TEXT, charger_manufacturer TEXT)"
+    )
+    conn.execute(
+        "INSERT INTO power_readings (timestamp, watts_actual, watts_negotiated, voltage, amperage, "
+        "current_capacity, max_capacity, battery_percent, is_charging, external_connected) "
+        "VALUES ('2025-12-28T12:00:00+00:00', -10.0, 0, 12.0, -0.8, 3500, 4709, 74, 0, 0)"
+    )
+    conn.commit()
+    conn.close()
+
+    with Database(temp_db, read_only=True) as db:
+        summary = db.get_summary()
+    assert summary.count == 1
+    assert summary.avg_draw_watts == 10.0
+    assert summary.battery_health_percent is None  # No design_capacity column in this version
+
+    conn = sqlite3.connect(temp_db)
+    columns = {row[1] for row in conn.execute("PRAGMA table_info(power_readings)")}
+    tables = {row[0] for row in conn.execute("SELECT name FROM sqlite_master WHERE type = 'table'")}
+    conn.close()
+    assert "design_capacity" not in columns
+    assert "burst_samples" not in tables
+
+    with pytest.raises(FileNotFoundError):
+        Database(tmp_path / "missing.db", read_only=True)
+    assert not (tmp_path / "missing.db").exists()
+
+    unrelated = tmp_path / "unrelated.db"
+    conn = sqlite3.connect(unrelated)
+    conn.execute("CREATE TABLE notes (body TEXT)")
+    conn.close()
+    with pytest.raises(ValueError, match="Not a powermonitor database"):
+        Database(unrelated, read_only=True)
+
+
+def test_watts_negotiated_stable_roundtrip(database, sample_reading):
+    """Test stable negotiated watts are stored alongside the raw value."""
+    sample_reading.watts_negotiated = 0
+    sample_reading.watts_negotiated_stable = 67
+    database.insert_reading(sample_reading)
+
+    history = database.query_history(limit=1)
+    assert history[0].watts_negotiated == 0
+    assert history[0].watts_negotiated_stable == 67
+
+
+def test_get_daily_capacity_includes_design(database, sample_reading):
+    """Test daily capacity averages include design capacity when recorded."""
+    old_day = datetime.now(UTC) - timedelta(days=10)
+    new_day = datetime.now(UTC) - timedelta(days=1)
+    database.insert_reading(replace(sample_reading, timestamp=old_day, max_capacity=4600))
+    database.insert_reading(replace(sample_reading, timestamp=new_day, max_capacity=4500, design_capacity=5000))
+
+    rows = database.get_daily_capacity()
+
+    assert [(r[1], r[2], r[3]) for r in rows] == [(4600.0, None, 1), (4500.0, 5000.0, 1)]
+    assert len(database.get_daily_capacity(days=5)) == 1
+
+
+def test_insert_and_get_burst(database, sample_reading):
+    """Test burst samples are stored in their own table under incrementing IDs."""
+    start = sample_reading.timestamp
+    burst = BurstResult(
+        started_at=start,
+        rate_hz=10.0,
+        samples=[
+            BurstSample(timestamp=start + timedelta(milliseconds=100 * i), power_input=p, system_power=None)
+            for i, p in enumerate([20.0, 60.0, 25.0])
+        ],
+    )
+
+    first_id = database.insert_burst(burst)
+    second_id = database.insert_burst(burst)
+
+    assert (first_id, second_id) == (1, 2)
+    stored = database.get_burst(first_id)
+    assert [s.power_input for s in stored.samples] == [20.0, 60.0, 25.0]
+    assert stored.rate_hz == 10.0
+    assert stored.stats("power_input") == (20.0, 60.0, 35.0)
+    assert database.get_burst(99) is None
+    # Bursts don't show up as regular readings
+    assert database.query_history(limit=None) == []
+
+
+def test_thermal_state_roundtrip(database, sample_reading):
+    """Test thermal state is stored and restored as a ThermalState."""
+    database.insert_reading(replace(sample_reading, thermal_state=ThermalState.SERIOUS))
+    database.insert_reading(replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=1)))
+
+    history = database.query_history(limit=None)
+    assert history[0].thermal_state is None
+    assert history[1].thermal_state is ThermalState.SERIOUS
+
+
+def test_battery_temp_roundtrip(database, sample_reading):
+    """Test battery temperature and system power are stored and restored."""
+    database.insert_reading(replace(sample_reading, battery_temp=31.5, system_power=14.2))
+
+    stored = database.query_history(limit=None)[0]
+    assert stored.battery_temp == 31.5
+    assert stored.system_power == 14.2
+
+
+def test_low_power_mode_roundtrip(database, sample_reading):
+    """Test Low Power Mode is stored as on, off, or unknown."""
+    for offset, state in enumerate((True, False, None)):
+        timestamp = sample_reading.timestamp + timedelta(seconds=offset)
+        database.insert_reading(replace(sample_reading, timestamp=timestamp, low_power_mode=state))
+
+    history = database.query_history(limit=None)
+    assert [r.low_power_mode for r in history] == [None, False, True]  # Newest first
+
+
+def test_subsecond_timestamps_roundtrip(database, sample_reading):
+    """Test fractional seconds survive the database so fast samples stay distinct and ordered."""
+    base = sample_reading.timestamp.replace(microsecond=123456)
+    for offset_ms in (0, 200, 400):
+        database.insert_reading(replace(sample_reading, timestamp=base + timedelta(milliseconds=offset_ms)))
+
+    history = database.query_history(limit=None)
+    assert [r.timestamp for r in history] == [base + timedelta(milliseconds=ms) for ms in (400, 200, 0)]
+    assert history[0].timestamp.tzinfo is not None
+
+
+def test_query_history_filter_by_label(database, sample_reading):
+    """Test query_history only returns readings with the requested label."""
+    for i, label in enumerate(["render", None, "render", "idle"]):
+        reading = replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=i), label=label)
+        database.insert_reading(reading)
+
+    assert len(database.query_history(limit=None)) == 4
+    assert len(database.query_history(limit=None, label="render")) == 2
+    assert [r.label for r in database.query_history(limit=None, label="idle")] == ["idle"]
+    assert database.query_history(limit=None, label="missing") == []
+
+
+def test_iter_history_streams_newest_first(database, sample_reading):
+    """Test iter_history yields readings lazily in reverse chronological order."""
+    for i in range(5):
+        database.insert_reading(replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=i)))
+
+    readings = database.iter_history(limit=3)
+    first = next(readings)
+    rest = list(readings)
+
+    assert first.timestamp == sample_reading.timestamp + timedelta(seconds=4)
+    assert len(rest) == 2
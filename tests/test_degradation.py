@@ -0,0 +1,64 @@
+"""Tests for charger degradation tracking."""
+
+from dataclasses import replace
+
+from powermonitor.degradation import DEGRADED_READINGS
+from powermonitor.degradation import ChargerPeak
+from powermonitor.degradation import tracked_charger
+
+
+def test_peak_only_grows():
+    """Test the peak follows the highest negotiated power and ignores drops."""
+    peak = ChargerPeak(name="96W USB-C Power Adapter", peak_watts=60, last_watts=60)
+
+    peak = peak.observe(96)
+    assert (peak.peak_watts, peak.last_watts, peak.low_readings) == (96, 96, 0)
+
+    peak = peak.observe(90)  # Within DEGRADED_RATIO of the peak
+    assert (peak.peak_watts, peak.last_watts, peak.low_readings) == (96, 90, 0)
+
+
+def test_degraded_after_consecutive_low_readings():
+    """Test a charger is flagged only after DEGRADED_READINGS low readings in a row."""
+    peak = ChargerPeak(name="96W USB-C Power Adapter", peak_watts=96, last_watts=96)
+
+    for _ in range(DEGRADED_READINGS - 1):
+        peak = peak.observe(60)
+    assert not peak.degraded
+
+    peak = peak.observe(60)
+    assert peak.degraded
+    assert peak.peak_watts == 96
+    assert "negotiates 60W, down from 96W" in peak.warning()
+    assert peak.to_dict()["degraded"] is True
+
+    # One reading back near the peak clears the flag
+    assert not peak.observe(94).degraded
+
+
+def test_warning_keeps_charger_name_on_one_line():
+    """Test a charger name with control characters can't split the warning."""
+    peak = ChargerPeak(name='Acme "96W"\nAdapter', peak_watts=96, last_watts=60, low_readings=DEGRADED_READINGS)
+
+    assert peak.warning().startswith('Charger may be degraded: Acme "96W" Adapter negotiates 60W')
+    assert "\n" not in peak.warning()
+
+
+def test_brief_dips_are_not_degradation():
+    """Test an isolated low reading (e.g. PD renegotiation) resets once power recovers."""
+    peak = ChargerPeak(name="96W USB-C Power Adapter", peak_watts=96, last_watts=96)
+
+    for _ in range(DEGRADED_READINGS * 2):
+        peak = peak.observe(15).observe(96)
+
+    assert peak.low_readings == 0
+    assert not peak.degraded
+
+
+def test_tracked_charger(sample_reading):
+    """Test only readings plugged in to a named, negotiating charger are tracked."""
+    assert tracked_charger(sample_reading) == "USB-C Power Adapter"
+    assert tracked_charger(replace(sample_reading, external_connected=False)) is None
+    assert tracked_charger(replace(sample_reading, watts_negotiated=0)) is None
+    assert tracked_charger(replace(sample_reading, charger_name="  ")) is None
+    assert tracked_charger(replace(sample_reading, charger_name=None)) is None
@@ -0,0 +1,81 @@
+"""Tests for the reading file writers."""
+
+import csv
+import io
+import json
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.export import EXTENSION_FORMATS
+from powermonitor.export import export_readings
+from powermonitor.export import write_json_array
+
+
+def test_export_readings_formats(sample_reading, tmp_path):
+    """Test each format writes every reading and reports the count."""
+    readings = [sample_reading, sample_reading]
+
+    assert export_readings(tmp_path / "r.csv", readings, "csv", delimiter=";") == 2
+    rows = list(csv.reader((tmp_path / "r.csv").open(), delimiter=";"))
+    assert rows[0][:2] == ["timestamp", "watts_actual"]
+    assert len(rows) == 3
+
+    assert export_readings(tmp_path / "r.json", readings, "json") == 2
+    assert len(json.loads((tmp_path / "r.json").read_text())) == 2
+
+    assert export_readings(tmp_path / "r.ndjson", readings, "ndjson") == 2
+    assert len((tmp_path / "r.ndjson").read_text().splitlines()) == 2
+
+
+def test_export_readings_awkward_charger_names(sample_reading, tmp_path):
+    """Test quotes, commas, newlines, and unicode in charger names survive every format intact."""
+    name = 'Acme "Pro", 96W\nUSB-C, 充電器'
+    readings = [replace(sample_reading, charger_name=name, charger_manufacturer='Acme, "Inc."'), sample_reading]
+
+    export_readings(tmp_path / "r.csv", readings, "csv")
+    with (tmp_path / "r.csv").open(newline="") as f:
+        rows = list(csv.DictReader(f))
+    assert len(rows) == 2
+    assert rows[0]["charger_name"] == name
+    assert rows[0]["charger_manufacturer"] == 'Acme, "Inc."'
+    assert rows[1]["charger_name"] == sample_reading.charger_name
+
+    export_readings(tmp_path / "r.json", readings, "json")
+    assert json.loads((tmp_path / "r.json").read_text())[0]["charger_name"] == name
+
+    export_readings(tmp_path / "r.ndjson", readings, "ndjson")
+    lines = (tmp_path / "r.ndjson").read_text().splitlines()
+    assert len(lines) == 2
+    assert json.loads(lines[0])["charger_name"] == name
+
+
+def test_export_readings_unknown_format(sample_reading, tmp_path):
+    """Test an unknown format is rejected without creating a file."""
+    with pytest.raises(ValueError, match="Invalid format 'xml'"):
+        export_readings(tmp_path / "r.xml", [sample_reading], "xml")
+    assert not (tmp_path / "r.xml").exists()
+    assert EXTENSION_FORMATS[".tsv"] == "csv"
+
+
+def test_write_json_array_streams_valid_json(sample_reading):
+    """Test the streaming JSON writer produces a parseable array."""
+
+    def generate():
+        for i in range(3):
+            yield replace(sample_reading, watts_actual=float(i))
+
+    sink = io.StringIO()
+    count = write_json_array(sink, generate())
+
+    assert count == 3
+    data = json.loads(sink.getvalue())
+    assert [item["watts_actual"] for item in data] == [0.0, 1.0, 2.0]
+    assert data[0]["timestamp"] == sample_reading.timestamp.isoformat()
+
+
+def test_write_json_array_empty():
+    """Test the streaming JSON writer emits an empty array for no readings."""
+    sink = io.StringIO()
+    assert write_json_array(sink, iter([])) == 0
+    assert json.loads(sink.getvalue()) == []
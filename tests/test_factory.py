@@ -5,7 +5,12 @@ from unittest.mock import patch
 
 import pytest
 
+from powermonitor.collector.base import CollectLevel
+from powermonitor.collector.base import CollectorConfig
+from powermonitor.collector.factory import create_collector
 from powermonitor.collector.factory import default_collector
+from powermonitor.collector.mock import MockCollector
+from powermonitor.collector.pmset import PmsetCollector
 
 
 @pytest.mark.skipif(
@@ -37,3 +42,35 @@ def test_default_collector_non_macos():
     """Test that default_collector raises error on non-macOS platforms."""
     with pytest.raises(RuntimeError, match="only supports macOS"):
         default_collector()
+
+
+def test_create_collector_mock_any_platform():
+    """Test the mock backend is available regardless of platform."""
+    with patch("powermonitor.collector.factory.sys.platform", "linux"):
+        collector = create_collector("mock")
+
+    assert isinstance(collector, MockCollector)
+
+
+def test_create_collector_invalid_backend():
+    """Test unknown backend names are rejected."""
+    with pytest.raises(ValueError, match="Unknown backend"):
+        create_collector("battery")
+
+
+@patch("powermonitor.collector.factory.sys.platform", "linux")
+def test_create_collector_hardware_backend_non_macos():
+    """Test hardware backends still require macOS."""
+    with pytest.raises(RuntimeError, match="only supports macOS"):
+        create_collector("ioreg")
+
+
+@patch("powermonitor.collector.factory.sys.platform", "darwin")
+def test_create_collector_minimal_level_uses_pmset():
+    """Test CollectLevel.MINIMAL picks the pmset collector for every hardware backend."""
+    config = CollectorConfig(level=CollectLevel.MINIMAL)
+
+    for backend in ("auto", "iokit", "ioreg"):
+        assert isinstance(create_collector(backend, config=config), PmsetCollector)
+    # The mock backend keeps its canned full readings
+    assert isinstance(create_collector("mock", config=config), MockCollector)
@@ -0,0 +1,100 @@
+"""Tests for GPU power via powermetrics."""
+
+import subprocess
+
+from powermonitor.collector import gpu
+from powermonitor.collector import powermetrics
+from powermonitor.collector.gpu import GpuPowerReader
+from powermonitor.collector.gpu import parse_gpu_power
+
+POWERMETRICS_OUTPUT = """Machine model: Mac14,5
+OS version: 23A344
+
+*** Sampled system activity (Mon Oct 16 10:00:00 2026 +0900) (204.61ms elapsed) ***
+
+**** GPU usage ****
+
+GPU HW active frequency: 389 MHz
+GPU HW active residency:   3.12% (389 MHz: 3.1% 486 MHz:   0%)
+GPU idle residency:  96.88%
+GPU Power: 1234 mW
+"""
+
+
+def test_parse_gpu_power():
+    """Test the GPU power line is converted from mW to W."""
+    assert parse_gpu_power(POWERMETRICS_OUTPUT) == 1.234
+    assert parse_gpu_power("GPU Power: 12.5 mW") == 0.0125
+
+
+def test_parse_gpu_power_missing():
+    """Test output without a GPU power line (e.g. Intel) parses to None."""
+    assert parse_gpu_power("**** GPU usage ****\nGPU idle residency: 100%\n") is None
+    assert parse_gpu_power("") is None
+
+
+def test_reader_reads_gpu_power(monkeypatch):
+    """Test a successful powermetrics run is parsed on every read."""
+    calls = []
+
+    def fake_run(command, **kwargs):
+        calls.append(command)
+        return subprocess.CompletedProcess(command, 0, stdout=POWERMETRICS_OUTPUT, stderr="")
+
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 0)
+    monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+    reader = GpuPowerReader()
+    assert reader.read() == 1.234
+    assert reader.read() == 1.234
+    assert calls[0] == list(gpu.POWERMETRICS_COMMAND)
+    assert len(calls) == 2
+
+
+def test_reader_without_root_never_runs_powermetrics(monkeypatch):
+    """Test an unprivileged reader disables itself without spawning powermetrics."""
+
+    def fake_run(command, **kwargs):
+        raise AssertionError("powermetrics must not run without root")
+
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 501)
+    monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+    reader = GpuPowerReader()
+    assert reader.read() is None
+    assert reader.available is False
+
+
+def test_reader_disables_after_permanent_failure(monkeypatch):
+    """Test a failing or GPU-less powermetrics is only tried once."""
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 0)
+    for outcome in (
+        FileNotFoundError("powermetrics"),
+        subprocess.CalledProcessError(1, "powermetrics", stderr="powermetrics must be invoked as the superuser"),
+        subprocess.CompletedProcess("powermetrics", 0, stdout="**** GPU usage ****\n", stderr=""),
+    ):
+        calls = []
+
+        def fake_run(command, outcome=outcome, **kwargs):
+            calls.append(command)
+            if isinstance(outcome, Exception):
+                raise outcome
+            return outcome
+
+        monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+        reader = GpuPowerReader()
+        assert reader.read() is None
+        assert reader.read() is None
+        assert reader.available is False
+        assert len(calls) == 1
+
+
+def test_reader_retries_after_timeout(monkeypatch):
+    """Test a timeout returns None but keeps the reader enabled."""
+
+    def fake_run(command, **kwargs):
+        raise subprocess.TimeoutExpired(command, 5)
+
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 0)
+    monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+    reader = GpuPowerReader()
+    assert reader.read() is None
+    assert reader.available is True
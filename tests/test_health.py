@@ -0,0 +1,125 @@
+"""Tests for long-horizon battery health analysis."""
+
+from datetime import date
+
+import pytest
+
+from powermonitor.health import DAYS_PER_MONTH
+from powermonitor.health import bucket_health
+from powermonitor.health import capacity_trend
+from powermonitor.health import daily_health
+
+
+def test_daily_health_uses_design_capacity():
+    """Test health is max capacity relative to design capacity."""
+    points = daily_health([("2026-01-01", 4500.0, 5000.0, 10)])
+
+    assert len(points) == 1
+    assert points[0].day == date(2026, 1, 1)
+    assert points[0].health_percent == pytest.approx(90.0)
+    assert points[0].interpolated is False
+
+
+def test_daily_health_interpolates_gaps():
+    """Test missing days between measurements are linearly interpolated."""
+    points = daily_health(
+        [
+            ("2026-01-01", 4600.0, 5000.0, 3),
+            ("2026-01-05", 4400.0, 5000.0, 2),
+        ]
+    )
+
+    assert [p.day.day for p in points] == [1, 2, 3, 4, 5]
+    assert [p.interpolated for p in points] == [False, True, True, True, False]
+    assert [round(p.health_percent, 1) for p in points] == [92.0, 91.0, 90.0, 89.0, 88.0]
+
+
+def test_daily_health_without_design_capacity_uses_first_day():
+    """Test older data without design capacity is relative to the first day."""
+    points = daily_health(
+        [
+            ("2026-01-01", 4000.0, None, 1),
+            ("2026-01-02", 3960.0, None, 1),
+        ]
+    )
+
+    assert [p.health_percent for p in points] == pytest.approx([100.0, 99.0])
+
+
+def test_daily_health_empty():
+    """Test no rows produce no points."""
+    assert daily_health([]) == []
+
+
+def test_bucket_health_by_week_and_month():
+    """Test daily points are averaged per ISO week and calendar month."""
+    # 2026-01-04 is a Sunday, so the first week contains only one day
+    points = daily_health(
+        [
+            ("2026-01-04", 4500.0, 5000.0, 1),
+            ("2026-01-07", 4400.0, 5000.0, 1),
+        ]
+    )
+
+    weeks = bucket_health(points, "week")
+    assert [w.label for w in weeks] == ["2026-W01", "2026-W02"]
+    assert weeks[0].health_percent == pytest.approx(90.0)
+    assert (weeks[1].measured_days, weeks[1].total_days) == (1, 3)
+
+    months = bucket_health(points, "month")
+    assert [m.label for m in months] == ["2026-01"]
+    assert months[0].total_days == 4
+
+
+def test_bucket_health_invalid_period():
+    """Test unknown periods raise ValueError."""
+    with pytest.raises(ValueError, match="period must be one of"):
+        bucket_health([], "year")
+
+
+def test_capacity_trend_projects_replacement_date():
+    """Test the fitted decline is projected to 80% of design capacity."""
+    rows = [
+        ("2026-01-01", 4600.0, 5000.0, 10),
+        ("2026-01-31", 4550.0, 5000.0, 3),
+        ("2026-03-02", 4500.0, 5000.0, 20),
+    ]
+
+    trend = capacity_trend(rows)
+
+    assert trend.first_day == date(2026, 1, 1)
+    assert trend.last_day == date(2026, 3, 2)
+    assert trend.measured_days == 3
+    assert trend.current_capacity == 4500.0
+    assert trend.health_percent == pytest.approx(90.0)
+    assert trend.decline_per_month == pytest.approx(-50 / 30 * DAYS_PER_MONTH)
+    assert trend.threshold_capacity == 4000.0
+    # 500 mAh to go at 50 mAh per 30 days
+    assert trend.projected_date == date(2026, 12, 27)
+    assert not trend.below_threshold
+
+
+def test_capacity_trend_without_projection():
+    """Test no date is projected when capacity isn't declining, is already low, or design is unknown."""
+    flat = capacity_trend([("2026-01-01", 4500.0, 5000.0, 1), ("2026-02-01", 4510.0, 5000.0, 1)])
+    assert flat.decline_per_month > 0
+    assert flat.projected_date is None
+
+    worn = capacity_trend([("2026-01-01", 4000.0, 5000.0, 1), ("2026-02-01", 3900.0, 5000.0, 1)])
+    assert worn.below_threshold
+    assert worn.projected_date is None
+
+    glacial = capacity_trend([("2026-01-01", 4500.0, 5000.0, 1), ("2027-01-01", 4499.0, 5000.0, 1)])
+    assert glacial.projected_date is None  # Centuries away
+
+    no_design = capacity_trend([("2026-01-01", 4600.0, None, 1), ("2026-02-01", 4500.0, None, 1)])
+    assert no_design.design_capacity is None
+    assert no_design.health_percent is None
+    assert no_design.threshold_capacity is None
+    assert no_design.projected_date is None
+
+    single = capacity_trend([("2026-01-01", 4500.0, 5000.0, 1)])
+    assert single.decline_per_month is None
+    assert single.projected_date is None
+
+    assert capacity_trend([]) is None
@@ -0,0 +1,202 @@
+"""Tests for event hook commands."""
+
+import subprocess
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.hooks import EventHooks
+from powermonitor.hooks import detect_events
+from powermonitor.hooks import hook_environment
+from powermonitor.hooks import parse_budgets
+
+
+class FakeClock:
+    """Manually advanced monotonic clock."""
+
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self) -> float:
+        return self.now
+
+
+class FakeSpawn:
+    """Record commands instead of starting processes."""
+
+    def __init__(self, error: OSError | None = None):
+        self.calls: list[tuple[str, dict]] = []
+        self.error = error
+
+    def __call__(self, command, **kwargs):
+        if self.error is not None:
+            raise self.error
+        self.calls.append((command, kwargs))
+
+
+def test_detect_events_plug_and_unplug(sample_reading):
+    """Test charger connect/disconnect transitions."""
+    unplugged = replace(sample_reading, external_connected=False, is_charging=False)
+
+    assert detect_events(unplugged, sample_reading, low_battery=20) == ["plug"]
+    assert detect_events(sample_reading, unplugged, low_battery=20) == ["unplug"]
+    assert detect_events(sample_reading, sample_reading, low_battery=20) == []
+
+
+def test_detect_events_low_fires_once_when_crossing(sample_reading):
+    """Test "low" fires when crossing the threshold on battery, not on every low reading."""
+    on_battery = replace(sample_reading, external_connected=False, is_charging=False)
+    above = replace(on_battery, battery_percent=21)
+    at = replace(on_battery, battery_percent=20)
+    below = replace(on_battery, battery_percent=19)
+
+    assert detect_events(above, at, low_battery=20) == ["low"]
+    assert detect_events(at, below, low_battery=20) == []
+    # Unplugging an already-low battery counts as reaching low
+    assert detect_events(replace(at, external_connected=True), at, low_battery=20) == ["unplug", "low"]
+
+
+def test_detect_events_full(sample_reading):
+    """Test "full" fires when the battery becomes fully charged."""
+    full = replace(sample_reading, fully_charged=True)
+
+    assert detect_events(sample_reading, full, low_battery=20) == ["full"]
+    assert detect_events(full, full, low_battery=20) == []
+
+
+def test_detect_events_hot_is_sustained(sample_reading):
+    """Test "hot" fires on every reading at or above the threshold, not only when crossing it."""
+    hot = replace(sample_reading, battery_temp=46.0)
+
+    assert detect_events(hot, hot, low_battery=20, overheat_temp=45.0) == ["hot"]
+    assert detect_events(hot, replace(hot, battery_temp=44.9), low_battery=20, overheat_temp=45.0) == []
+    assert detect_events(hot, sample_reading, low_battery=20, overheat_temp=45.0) == []  # Unknown temp
+    assert detect_events(hot, hot, low_battery=20) == []  # No threshold
+
+
+def test_hook_environment(sample_reading):
+    """Test reading values are exposed as POWERMONITOR_* variables."""
+    env = hook_environment("unplug", sample_reading)
+
+    assert env["POWERMONITOR_EVENT"] == "unplug"
+    assert env["POWERMONITOR_WATTS"] == "45.50"
+    assert env["POWERMONITOR_PERCENT"] == "74"
+    assert env["POWERMONITOR_CHARGING"] == "1"
+    assert env["POWERMONITOR_PLUGGED"] == "1"
+    assert env["POWERMONITOR_VOLTAGE"] == "20.000"
+    assert env["POWERMONITOR_TIMESTAMP"] == "2025-12-28T12:00:00+00:00"
+
+
+def test_first_reading_sets_baseline(sample_reading):
+    """Test no event fires for the first reading."""
+    spawn = FakeSpawn()
+    hooks = EventHooks({"plug": "echo plugged"}, spawn=spawn)
+
+    assert hooks.observe(sample_reading) == []
+    assert spawn.calls == []
+
+
+def test_observe_spawns_detached_with_environment(sample_reading):
+    """Test a configured event runs its command detached with reading variables."""
+    spawn = FakeSpawn()
+    hooks = EventHooks({"unplug": "say unplugged"}, spawn=spawn, clock=FakeClock())
+    unplugged = replace(sample_reading, external_connected=False, is_charging=False)
+
+    hooks.observe(sample_reading)
+    assert hooks.observe(unplugged) == ["unplug"]
+
+    [(command, kwargs)] = spawn.calls
+    assert command == "say unplugged"
+    assert kwargs["shell"] is True
+    assert kwargs["start_new_session"] is True
+    assert kwargs["stdout"] == subprocess.DEVNULL
+    assert kwargs["env"]["POWERMONITOR_EVENT"] == "unplug"
+    assert kwargs["env"]["POWERMONITOR_PLUGGED"] == "0"
+    assert "PATH" in kwargs["env"]  # Inherits the current environment
+
+
+def test_unconfigured_events_are_ignored(sample_reading):
+    """Test events without a command don't spawn anything."""
+    spawn = FakeSpawn()
+    hooks = EventHooks({"full": "echo full"}, spawn=spawn)
+
+    hooks.observe(sample_reading)
+    assert hooks.observe(replace(sample_reading, external_connected=False)) == []
+    assert spawn.calls == []
+
+
+def test_debounce_per_event(sample_reading):
+    """Test a flapping charger runs each command at most once per debounce window."""
+    clock = FakeClock()
+    spawn = FakeSpawn()
+    hooks = EventHooks({"plug": "p", "unplug": "u"}, debounce_seconds=30, clock=clock, spawn=spawn)
+    unplugged = replace(sample_reading, external_connected=False)
+
+    hooks.observe(sample_reading)
+    assert hooks.observe(unplugged) == ["unplug"]
+    clock.now = 5
+    assert hooks.observe(sample_reading) == ["plug"]
+    clock.now = 10
+    assert hooks.observe(unplugged) == []  # Debounced
+    clock.now = 31
+    assert hooks.observe(sample_reading) == []  # plug last ran at 5
+    assert hooks.observe(unplugged) == ["unplug"]
+
+    assert [command for command, _ in spawn.calls] == ["u", "p", "u"]
+
+
+def test_sustained_event_rate_budget(sample_reading):
+    """Test a sustained condition runs its command once per budget window, not per reading."""
+    clock = FakeClock()
+    spawn = FakeSpawn()
+    hooks = EventHooks({"hot": "h"}, overheat_temp=45.0, clock=clock, spawn=spawn)
+    hot = replace(sample_reading, battery_temp=47.0)
+    assert hooks.budgets["hot"] == 600.0
+
+    hooks.observe(hot)
+    assert hooks.observe(hot) == ["hot"]
+    for second in range(1, 600, 60):
+        clock.now = second
+        assert hooks.observe(hot) == []
+    clock.now = 600
+    assert hooks.observe(hot) == ["hot"]
+    assert len(spawn.calls) == 2
+
+
+def test_budgets_override_defaults(sample_reading):
+    """Test per-event budgets replace the debounce and the sustained default."""
+    hooks = EventHooks({"plug": "p"}, budgets={"hot": 60.0, "plug": 0.0})
+
+    assert hooks.budgets["hot"] == 60.0
+    assert hooks.budgets["plug"] == 0.0
+    assert hooks.budgets["unplug"] == 30.0
+
+
+def test_parse_budgets():
+    """Test budget specs use schedule intervals and reject unknown events."""
+    assert parse_budgets("hot=10m, low=1h") == {"hot": 600.0, "low": 3600.0}
+    assert parse_budgets("") == {}
+    with pytest.raises(ValueError, match="Unknown event"):
+        parse_budgets("reboot=1m")
+    with pytest.raises(ValueError, match="expected event=interval"):
+        parse_budgets("hot")
+    with pytest.raises(ValueError, match="Invalid interval"):
+        parse_budgets("hot=soon")
+
+
+def test_spawn_failure_is_logged_not_raised(sample_reading):
+    """Test a command that can't start doesn't break collection."""
+    hooks = EventHooks({"unplug": "missing"}, spawn=FakeSpawn(error=OSError("no shell")))
+
+    hooks.observe(sample_reading)
+    assert hooks.observe(replace(sample_reading, external_connected=False)) == []
+
+
+def test_invalid_configuration():
+    """Test unknown events and negative debounce are rejected."""
+    with pytest.raises(ValueError, match="Unknown event"):
+        EventHooks({"reboot": "echo"})
+    with pytest.raises(ValueError, match="debounce_seconds"):
+        EventHooks({"plug": "echo"}, debounce_seconds=-1)
+    with pytest.raises(ValueError, match="Budget for 'hot'"):
+        EventHooks({"hot": "echo"}, budgets={"hot": -1.0})
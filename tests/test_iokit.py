@@ -5,6 +5,7 @@ import sys
 from typing import TYPE_CHECKING
 
 import pytest
+from loguru import logger
 
 # Skip entire module on non-macOS platforms
 pytestmark = pytest.mark.skipif(
@@ -15,6 +16,7 @@ pytestmark = pytest.mark.skipif(
 # Conditional imports to avoid loading IOKit on non-macOS
 if sys.platform == "darwin" or TYPE_CHECKING:
     from powermonitor.collector.iokit.parser import bytes_to_float
+    from powermonitor.collector.iokit.parser import describe_type
     from powermonitor.collector.iokit.structures import key_to_str
     from powermonitor.collector.iokit.structures import str_to_key
     from powermonitor.collector.iokit.structures import type_to_str
@@ -145,20 +147,60 @@ def test_bytes_to_float_unknown_type():
 
 
 def test_bytes_to_float_all_fixed_point_types():
-    """Test all fixed-point type variations."""
-    test_value = 2560  # = 10.0 when divided by 256
-
-    signed_types = ["sp78", "sp87", "sp96", "spa5", "spb4", "spf0"]
-    for dtype in signed_types:
-        raw_bytes = struct.pack(">h", test_value)
-        result = bytes_to_float(raw_bytes, dtype, 2)
-        assert abs(result - 10.0) < 0.01, f"Failed for {dtype}"
-
-    unsigned_types = ["fp88", "fp79", "fp6a", "fp4c"]
-    for dtype in unsigned_types:
-        raw_bytes = struct.pack(">H", test_value)
-        result = bytes_to_float(raw_bytes, dtype, 2)
-        assert abs(result - 10.0) < 0.01, f"Failed for {dtype}"
+    """Test each fixed-point type divides by 2**(fraction bits named in the type)."""
+    raw = 2560  # 0x0A00
+
+    signed_types = {"sp78": 10.0, "sp87": 20.0, "sp96": 40.0, "spa5": 80.0, "spb4": 160.0, "spf0": 2560.0}
+    for dtype, expected in signed_types.items():
+        result = bytes_to_float(struct.pack(">h", raw), dtype, 2)
+        assert result == expected, f"Failed for {dtype}"
+        assert bytes_to_float(struct.pack(">h", -raw), dtype, 2) == -expected, f"Failed for negative {dtype}"
+
+    unsigned_types = {"fp88": 10.0, "fp79": 5.0, "fp6a": 2.5, "fp4c": 0.625}
+    for dtype, expected in unsigned_types.items():
+        result = bytes_to_float(struct.pack(">H", raw), dtype, 2)
+        assert result == expected, f"Failed for {dtype}"
+
+
+def test_bytes_to_float_sensor_byte_sequences():
+    """Test byte sequences in the form the SMC returns them for common sensors."""
+    cases = [
+        (b"\x1e\x80", "sp78", 2, 30.5),  # TB0T battery temperature, 30.5°C
+        (b"\xf6\x80", "sp78", 2, -9.5),  # Below-zero sp78 (two's complement)
+        (b"\x80\x00", "sp78", 2, -128.0),  # Most negative sp78
+        (b"\x7f\xff", "sp78", 2, 32767 / 256),  # Most positive sp78
+        (b"\x17\x33", "fp88", 2, 0x1733 / 256),  # Fan-style fp88
+        (b"\xff\xff", "fp88", 2, 65535 / 256),  # fp88 is unsigned
+        (b"\x42\x36\x00\x00", "flt ", 4, 45.5),  # PDTR input power, 45.5W
+        (b"\x01", "ui8 ", 1, 1.0),  # CHCC charging flag
+        (b"\x10\x68", "ui16", 2, 4200.0),
+        (b"\xff\xff\xff\xff", "ui32", 4, 4294967295.0),  # Max ui32
+    ]
+    for data, dtype, size, expected in cases:
+        assert bytes_to_float(data, dtype, size) == pytest.approx(expected), f"Failed for {dtype} {data.hex()}"
+
+
+def test_bytes_to_float_truncated_data():
+    """Test every type returns 0.0 when fewer bytes arrive than it needs."""
+    for dtype, size in (("sp78", 2), ("fp4c", 2), ("flt ", 4), ("ui8 ", 1), ("ui16", 2), ("ui32", 4)):
+        assert bytes_to_float(b"\x01" * (size - 1), dtype, size) == 0.0, f"Failed for {dtype}"
+    assert bytes_to_float(b"\x01", "xxxx", 2) == 0.0
+
+
+def test_bytes_to_float_ignores_trailing_bytes():
+    """Test values are decoded from the leading bytes of a longer SMC buffer."""
+    assert bytes_to_float(b"\x1e\x80" + bytes(30), "sp78", 2) == 30.5
+    assert bytes_to_float(b"\x10\x68\xff\xff", "ui16", 2) == 4200.0
+
+
+def test_describe_type():
+    """Test the decoding of each SMC type is described for smc read."""
+    assert describe_type("sp78", 2) == "signed fixed-point, 8 fraction bits"
+    assert describe_type("fp4c", 2) == "unsigned fixed-point, 12 fraction bits"
+    assert describe_type("flt ", 4) == "32-bit float"
+    assert describe_type("ui8", 1) == "unsigned integer"  # Padded like bytes_to_float
+    assert describe_type("xxxx", 2) == "unknown type, decoded as an unsigned integer"
+    assert describe_type("ch8*", 12) == "unknown type, not decoded"
 
 
 def test_get_kern_return_name():
@@ -216,6 +258,117 @@ def test_smc_connection_read_key_invalid_length():
         conn.read_key("")  # Empty
 
 
+def test_smc_key_data_layout():
+    """Test SMCKeyData matches the kernel's 80-byte C layout, padding included."""
+    import ctypes
+
+    from powermonitor.collector.iokit.structures import SMC_KEY_DATA_SIZE
+    from powermonitor.collector.iokit.structures import KeyInfo
+    from powermonitor.collector.iokit.structures import SMCKeyData
+
+    assert ctypes.sizeof(SMCKeyData) == SMC_KEY_DATA_SIZE == 80
+    assert ctypes.sizeof(KeyInfo) == 12
+    offsets = {name: getattr(SMCKeyData, name).offset for name, _ in SMCKeyData._fields_}
+    assert offsets == {
+        "key": 0,
+        "vers": 4,
+        "p_limit_data": 12,
+        "key_info": 28,
+        "result": 40,
+        "status": 41,
+        "data8": 42,
+        "data32": 44,
+        "bytes": 48,
+    }
+
+
+def test_smc_connection_checks_reply_size(monkeypatch):
+    """Test a reply of unexpected size is used when it covers the value, and rejected when it doesn't."""
+    from powermonitor.collector.iokit import connection
+    from powermonitor.collector.iokit.structures import SMCError
+
+    def fake_call_returning(size):
+        def fake_call(conn, index, input_ref, input_size, output_ref, output_size_ref):
+            reply = output_ref._obj
+            reply.key_info.data_size = 2
+            reply.key_info.data_type = int.from_bytes(b"sp78", "big")
+            reply.bytes[0], reply.bytes[1] = 0x1E, 0x80
+            output_size_ref._obj.value = size
+            return 0
+
+        return fake_call
+
+    def open_connection():
+        conn = object.__new__(connection.SMCConnection)
+        conn.connection = 0
+        conn.service = 0
+        return conn
+
+    monkeypatch.setattr(connection.SMCConnection, "_warned_size_mismatch", False)
+    monkeypatch.setattr(connection, "IOConnectCallStructMethod", fake_call_returning(76))
+    messages = []
+    handler_id = logger.add(messages.append, format="{message}")
+    try:
+        assert open_connection().read_key("TB0T") == 30.5
+        # The collector opens a new connection per reading; the warning must not repeat
+        assert open_connection().read_key("TB0T") == 30.5
+    finally:
+        logger.remove(handler_id)
+    assert connection.SMCConnection._warned_size_mismatch
+    assert sum("76-byte SMCKeyData" in message for message in messages) == 1
+
+    conn = open_connection()
+
+    monkeypatch.setattr(connection, "IOConnectCallStructMethod", fake_call_returning(40))
+    with pytest.raises(SMCError, match="reply is 40 bytes, needed 41"):
+        conn.read_key("TB0T")
+
+    monkeypatch.setattr(connection, "IOConnectCallStructMethod", fake_call_returning(49))
+    with pytest.raises(SMCError, match="Read key bytes failed for 'TB0T': reply is 49 bytes, needed 50"):
+        conn.read_key("TB0T")
+
+
+def test_smc_connection_read_missing_key(monkeypatch):
+    """Test a key the SMC reports as unknown raises SMCKeyNotFoundError instead of reading zero bytes."""
+    from powermonitor.collector.iokit import connection
+    from powermonitor.collector.iokit.structures import SMC_RESULT_KEY_NOT_FOUND
+    from powermonitor.collector.iokit.structures import SMCKeyNotFoundError
+
+    def fake_call(conn, index, input_ref, input_size, output_ref, output_size_ref):
+        output_ref._obj.result = SMC_RESULT_KEY_NOT_FOUND
+        return 0  # The kernel call itself succeeds
+
+    monkeypatch.setattr(connection, "IOConnectCallStructMethod", fake_call)
+    conn = object.__new__(connection.SMCConnection)
+    conn.connection = 0
+    conn.service = 0
+
+    with pytest.raises(SMCKeyNotFoundError, match="'ZZZZ' does not exist on this Mac") as exc_info:
+        conn.read_raw("ZZZZ")
+    assert exc_info.value.key == "ZZZZ"
+    assert isinstance(exc_info.value, connection.SMCError)
+
+
+def test_smc_connection_read_key_invalid_key_error():
+    """Test read_key() raises InvalidKeyError instead of reading key 0."""
+    from powermonitor.collector.iokit.connection import SMCConnection
+    from powermonitor.collector.iokit.structures import InvalidKeyError
+
+    conn = object.__new__(SMCConnection)
+    conn.connection = 0
+    conn.service = 0
+
+    with pytest.raises(InvalidKeyError, match="must be exactly 4 characters, got 3") as exc_info:
+        conn.read_key("TB0")
+    assert exc_info.value.key == "TB0"
+
+    with pytest.raises(InvalidKeyError, match="must be exactly 4 characters, got 5"):
+        conn.read_key("TB0TX")
+
+    with pytest.raises(InvalidKeyError, match="ASCII"):
+        conn.read_key("TB0é")
+
+
 def test_iokit_collector_initialization():
     """Test IOKitCollector initialization."""
     from powermonitor.collector.iokit.collector import IOKitCollector
@@ -244,6 +397,7 @@ def test_smc_power_data_initialization():
     assert data.display_power is None
     assert data.battery_temp is None
     assert data.charging_status is None
+    assert data.battery_current is None
 
     # Test with values
     data = SMCPowerData(
@@ -264,6 +418,63 @@ def test_smc_power_data_initialization():
     assert data.charging_status == 1.0
 
 
+def test_smc_power_data_to_key_map():
+    """Test SMCPowerData serializes with SMC key names."""
+    import json
+
+    from powermonitor.collector.iokit.collector import SMCPowerData
+
+    data = SMCPowerData(power_input=20.3, battery_temp=35.0)
+    key_map = data.to_key_map()
+
+    assert list(key_map) == ["PPBR", "PDTR", "PSTR", "PHPC", "PDBR", "TB0T", "CHCC", "B0AC"]
+    assert key_map["PDTR"] == 20.3
+    assert key_map["TB0T"] == 35.0
+    assert key_map["PPBR"] is None
+    assert json.loads(json.dumps(key_map)) == key_map
+
+
+def test_smc_power_data_strict_read_collects_errors():
+    """Test a strict read reports every unreadable key, while the default read stays lenient."""
+    from powermonitor.collector.iokit.collector import SMC_FIELD_KEYS
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.collector.iokit.reader import SmcValue
+    from powermonitor.collector.iokit.structures import SMCMultipleError
+
+    readable = {"PDTR": SmcValue("flt ", b"\x42\x36\x00\x00"), "TB0T": SmcValue("sp78", b"\x1e\x80")}
+    smc = MockSmcReader(readable)
+
+    assert SMCPowerData.read(smc).power_input == 45.5
+
+    with pytest.raises(SMCMultipleError, match="6 SMC key\\(s\\) unreadable") as exc_info:
+        SMCPowerData.read(smc, strict=True)
+    assert list(exc_info.value.errors) == [key for key in SMC_FIELD_KEYS if key not in readable]
+    assert "PPBR: SMC key 'PPBR' not available in recording" in str(exc_info.value)
+
+    full = MockSmcReader(dict.fromkeys(SMC_FIELD_KEYS, SmcValue("ui8 ", b"\x01")))
+    assert SMCPowerData.read(full, strict=True).charging_status == 1.0
+
+
+def test_smc_field_keys_follow_native_architecture():
+    """Test that Intel Macs skip Apple Silicon-only keys and reads honor the selected keys."""
+    from powermonitor.collector.iokit.collector import SMC_FIELD_KEYS
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.collector.iokit.collector import smc_field_keys
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.collector.iokit.reader import SmcValue
+
+    assert smc_field_keys("arm64") == SMC_FIELD_KEYS
+    intel = smc_field_keys("x86_64")
+    assert "PDBR" not in intel
+    assert list(intel) == [key for key in SMC_FIELD_KEYS if key != "PDBR"]
+
+    smc = MockSmcReader(dict.fromkeys(SMC_FIELD_KEYS, SmcValue("ui8 ", b"\x01")))
+    data = SMCPowerData.read(smc, strict=True, keys=intel)
+    assert data.display_power is None
+    assert data.charging_status == 1.0
+
+
 def test_iokit_collector_fallback_on_smc_error(monkeypatch):
     """Test IOKitCollector falls back to IORegCollector when SMC fails."""
     from datetime import datetime
@@ -427,8 +638,8 @@ def test_collect_with_smc_enhances_reading(monkeypatch):
 
     collector = IOKitCollector(verbose=False)
 
-    # Mock _read_smc_sensors to return our mock data
-    monkeypatch.setattr(collector, "_read_smc_sensors", lambda: smc_data)
+    # Mock read_smc_sensors to return our mock data
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: smc_data)
 
     # Mock fallback_collector.collect to return base reading
     monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
@@ -439,6 +650,8 @@ def test_collect_with_smc_enhances_reading(monkeypatch):
     # Should use PDTR (power_input) for watts_actual
     assert reading.watts_actual == 18.5
     assert reading.battery_percent == 85.0
+    # PSTR is surfaced separately from the charge rate
+    assert reading.system_power == 15.0
 
 
 def test_collect_with_smc_no_power_input(monkeypatch):
@@ -473,7 +686,7 @@ def test_collect_with_smc_no_power_input(monkeypatch):
 
     collector = IOKitCollector(verbose=False)
 
-    monkeypatch.setattr(collector, "_read_smc_sensors", lambda: smc_data)
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: smc_data)
     monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
 
     # Collect with SMC
@@ -482,6 +695,122 @@ def test_collect_with_smc_no_power_input(monkeypatch):
     # Should keep original watts_actual
     assert reading.watts_actual == 5.0
     assert reading.battery_percent == 85.0
+    assert reading.system_power is None
+
+
+def test_collect_with_smc_prefers_battery_current(monkeypatch):
+    """Test B0AC replaces ioreg amperage, keeping the ioreg value for comparison."""
+    from datetime import datetime
+
+    from powermonitor.collector.iokit.collector import IOKitCollector
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.models import PowerReading
+
+    base_reading = PowerReading(
+        timestamp=datetime.fromtimestamp(1234567890.0),
+        battery_percent=60,
+        watts_actual=-10.0,
+        watts_negotiated=0,
+        voltage=12.0,
+        amperage=-1.0,
+        current_capacity=3600,
+        max_capacity=6000,
+        is_charging=False,
+        external_connected=False,
+        charger_name=None,
+        charger_manufacturer=None,
+    )
+
+    collector = IOKitCollector(verbose=False)
+
+    # Unsigned B0AC while discharging: sign comes from ioreg
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: SMCPowerData(battery_current=1234.0))
+    monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
+
+    reading = collector._collect_with_smc()
+
+    assert reading.amperage == -1.234
+    assert reading.amperage_ioreg == -1.0
+    # Without PDTR, watts are recomputed from the finer current
+    assert reading.watts_actual == 12.0 * -1.234
+
+
+def test_collect_with_smc_detects_adapter_missing_from_ioreg(monkeypatch):
+    """Test SMC input power marks the adapter connected when ioreg has no adapter details."""
+    from dataclasses import replace
+
+    from powermonitor.collector.iokit.collector import IOKitCollector
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.collector.mock import default_mock_reading
+    from powermonitor.models import AdapterSource
+    from powermonitor.models import PowerDirection
+
+    base_reading = replace(
+        default_mock_reading(),
+        watts_negotiated=0,
+        external_connected=False,
+        is_charging=False,
+        charger_name=None,
+        charger_manufacturer=None,
+    )
+    collector = IOKitCollector(verbose=False)
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: SMCPowerData(power_input=42.0))
+    monkeypatch.setattr(collector.fallback_collector, "collect", lambda: replace(base_reading))
+
+    reading = collector._collect_with_smc()
+
+    assert reading.external_connected is True
+    assert reading.adapter_source is AdapterSource.SMC
+    assert reading.direction is PowerDirection.AC_IDLE
+    assert reading.watts_negotiated == 0
+
+    # No input power: still on battery, nothing inferred
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: SMCPowerData(power_input=0.0))
+    reading = collector._collect_with_smc()
+    assert reading.external_connected is False
+    assert reading.adapter_source is None
+
+
+def test_adapter_detected_by_smc():
+    """Test only plausible input power without an ioreg wattage counts as an SMC-detected adapter."""
+    from powermonitor.collector.iokit.collector import adapter_detected_by_smc
+
+    assert adapter_detected_by_smc(42.0, watts_negotiated=0) is True
+    assert adapter_detected_by_smc(42.0, watts_negotiated=96) is False  # ioreg described it
+    assert adapter_detected_by_smc(None, watts_negotiated=0) is False
+    assert adapter_detected_by_smc(0.3, watts_negotiated=0) is False  # Noise
+    assert adapter_detected_by_smc(5000.0, watts_negotiated=0) is False  # Glitch
+    assert adapter_detected_by_smc(float("nan"), watts_negotiated=0) is False
+
+
+def test_signed_battery_current():
+    """Test B0AC sign handling against ioreg amperage and charging state."""
+    from dataclasses import replace
+    from datetime import datetime
+
+    from powermonitor.collector.iokit.collector import signed_battery_current
+    from powermonitor.models import PowerReading
+
+    charging = PowerReading(
+        timestamp=datetime.fromtimestamp(1234567890.0),
+        battery_percent=60,
+        watts_actual=10.0,
+        watts_negotiated=65,
+        voltage=12.0,
+        amperage=0.8,
+        current_capacity=3600,
+        max_capacity=6000,
+        is_charging=True,
+        external_connected=True,
+        charger_name=None,
+        charger_manufacturer=None,
+    )
+    idle_on_battery = replace(charging, amperage=0.0, is_charging=False, external_connected=False)
+
+    assert signed_battery_current(-850.0, charging) == 0.85
+    assert signed_battery_current(850.0, replace(charging, amperage=-0.8)) == -0.85
+    assert signed_battery_current(40.0, idle_on_battery) == -0.04
+    assert signed_battery_current(40.0, replace(charging, amperage=0.0)) == 0.04
 
 
 def test_collect_with_smc_verbose_logging(monkeypatch):
@@ -519,7 +848,7 @@ def test_collect_with_smc_verbose_logging(monkeypatch):
 
     collector = IOKitCollector(verbose=True)
 
-    monkeypatch.setattr(collector, "_read_smc_sensors", lambda: smc_data)
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: smc_data)
     monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
 
     reading = collector._collect_with_smc()
@@ -529,7 +858,7 @@ def test_collect_with_smc_verbose_logging(monkeypatch):
 
 
 def test_read_smc_sensors_with_mock_connection(monkeypatch):
-    """Test _read_smc_sensors reads all sensor keys."""
+    """Test read_smc_sensors reads all sensor keys."""
     from powermonitor.collector.iokit.collector import IOKitCollector
 
     # Track which keys were read
@@ -554,14 +883,16 @@ def test_read_smc_sensors_with_mock_connection(monkeypatch):
                 "PDBR": 3.2,
                 "TB0T": 35.0,
                 "CHCC": 1.0,
+                "B0AC": 1250.0,
             }
             return values.get(key, 0.0)
 
-    # Replace SMCConnection with mock
+    # Replace SMCConnection with mock, on Apple Silicon (which has every key)
     monkeypatch.setattr("powermonitor.collector.iokit.collector.SMCConnection", MockSMCConnection)
+    monkeypatch.setattr("powermonitor.collector.iokit.collector.native_architecture", lambda: "arm64")
 
     collector = IOKitCollector()
-    data = collector._read_smc_sensors()
+    data = collector.read_smc_sensors()
 
     # Check all keys were attempted
     assert "PPBR" in read_keys
@@ -571,6 +902,7 @@ def test_read_smc_sensors_with_mock_connection(monkeypatch):
     assert "PDBR" in read_keys
     assert "TB0T" in read_keys
     assert "CHCC" in read_keys
+    assert "B0AC" in read_keys
 
     # Check values were assigned correctly
     assert data.battery_power == 10.5
@@ -580,10 +912,34 @@ def test_read_smc_sensors_with_mock_connection(monkeypatch):
     assert data.display_power == 3.2
     assert data.battery_temp == 35.0
     assert data.charging_status == 1.0
+    assert data.battery_current == 1250.0
+
+
+def test_read_temperatures_skips_missing_and_implausible_sensors():
+    """Test only sensors this Mac has with plausible values are returned, and the hottest is picked."""
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.collector.iokit.collector import read_temperatures
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.collector.iokit.reader import SmcValue
+
+    smc = MockSmcReader(
+        {
+            "TB0T": SmcValue("sp78", b"\x1e\x80"),  # 30.5
+            "TC0P": SmcValue("sp78", b"\x2d\x00"),  # 45.0
+            "TG0P": SmcValue("sp78", b"\x00\x00"),  # 0: no real sensor behind the key
+            "TW0P": SmcValue("flt ", b"\x43\x48\x00\x00"),  # 200.0: garbage
+        }
+    )
+
+    temperatures = read_temperatures(smc)
+
+    assert temperatures == {"TB0T": 30.5, "TC0P": 45.0}
+    assert SMCPowerData(temperatures=temperatures).hottest == ("TC0P", 45.0)
+    assert SMCPowerData().hottest is None
 
 
 def test_read_smc_sensors_handles_missing_sensors(monkeypatch):
-    """Test _read_smc_sensors handles missing sensors gracefully."""
+    """Test read_smc_sensors handles missing sensors gracefully."""
     from powermonitor.collector.iokit.collector import IOKitCollector
     from powermonitor.collector.iokit.connection import SMCError
 
@@ -604,7 +960,7 @@ def test_read_smc_sensors_handles_missing_sensors(monkeypatch):
     monkeypatch.setattr("powermonitor.collector.iokit.collector.SMCConnection", MockSMCConnection)
 
     collector = IOKitCollector()
-    data = collector._read_smc_sensors()
+    data = collector.read_smc_sensors()
 
     # Only PDTR and TB0T should have values
     assert data.power_input == 18.0
@@ -616,6 +972,85 @@ def test_read_smc_sensors_handles_missing_sensors(monkeypatch):
     assert data.heatpipe_power is None
     assert data.display_power is None
     assert data.charging_status is None
+    assert data.battery_current is None
+
+
+def test_probe_smc_reports_every_key():
+    """Test the probe lists every power and temperature key with its value or read error."""
+    from powermonitor.collector.iokit.collector import SMC_SENSORS
+    from powermonitor.collector.iokit.collector import SMC_TEMPERATURE_SENSORS
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.collector.iokit.reader import SmcValue
+    from powermonitor.probe import probe_smc
+
+    readable = {"PDTR": SmcValue("flt ", b"\x42\x36\x00\x00"), "TB0T": SmcValue("sp78", b"\x1e\x80")}
+
+    result = probe_smc(lambda: MockSmcReader(readable))
+
+    assert result["available"] is True
+    assert list(result["keys"]) == list(SMC_SENSORS)
+    assert list(result["temperature_keys"]) == list(SMC_TEMPERATURE_SENSORS)
+    assert result["keys"]["PDTR"] == {
+        "description": SMC_SENSORS["PDTR"],
+        "readable": True,
+        "value": 45.5,
+        "error": None,
+    }
+    assert result["keys"]["PPBR"]["readable"] is False
+    assert "not available in recording" in result["keys"]["PPBR"]["error"]
+    assert result["temperature_keys"]["TB0T"]["value"] == 30.5
+
+
+def test_sample_burst_reads_only_fast_keys():
+    """Test burst sampling reads just PDTR/PSTR on a fixed tick schedule."""
+    from powermonitor.collector.iokit.burst import sample_burst
+    from powermonitor.collector.iokit.connection import SMCError
+
+    read_keys = []
+    power_inputs = iter([20.0, 65.0, 22.0, 21.0])
+
+    class MockSMCConnection:
+        def __enter__(self):
+            return self
+
+        def __exit__(self, exc_type, exc_val, exc_tb):
+            return False
+
+        def read_key(self, key: str) -> float:
+            read_keys.append(key)
+            if key == "PSTR":
+                raise SMCError("PSTR not available")
+            return next(power_inputs)
+
+    # Fake clock advanced only by sleep(), so the test runs instantly
+    now = [0.0]
+
+    def fake_sleep(seconds: float) -> None:
+        now[0] += seconds
+
+    result = sample_burst(
+        duration=0.4,
+        rate_hz=10.0,
+        connection_factory=MockSMCConnection,
+        clock=lambda: now[0],
+        sleep=fake_sleep,
+    )
+
+    assert set(read_keys) == {"PDTR", "PSTR"}
+    assert len(result.samples) == 4
+    assert result.rate_hz == 10.0
+    assert result.stats("power_input") == (20.0, 65.0, 32.0)
+    assert result.stats("system_power") is None
+
+
+def test_sample_burst_validates_rate():
+    """Test burst sampling rejects rates above the SMC limit."""
+    from powermonitor.collector.iokit.burst import sample_burst
+
+    with pytest.raises(ValueError, match="rate_hz"):
+        sample_burst(duration=1.0, rate_hz=50.0)
+    with pytest.raises(ValueError, match="duration"):
+        sample_burst(duration=0)
 
 
 @pytest.mark.skipif(
@@ -648,3 +1083,235 @@ def test_smc_connection_live():
 
     except Exception as e:
         pytest.skip(f"SMC connection not available: {e}")
+
+
+def test_blend_watts_strategies():
+    """Test each watts_blend strategy with SMC and computed values."""
+    from powermonitor.collector.base import CollectorConfig
+    from powermonitor.collector.base import WattsBlend
+    from powermonitor.collector.iokit.collector import blend_watts
+
+    smc = CollectorConfig(watts_blend=WattsBlend.SMC)
+    ioreg = CollectorConfig(watts_blend=WattsBlend.IOREG)
+    weighted = CollectorConfig(watts_blend=WattsBlend.WEIGHTED, smc_weight=0.75)
+
+    assert blend_watts(20.0, 16.0, smc) == 20.0
+    assert blend_watts(20.0, 16.0, ioreg) == 16.0
+    assert blend_watts(20.0, 16.0, weighted) == 19.0
+    # Without PDTR every strategy uses the computed value
+    for config in (smc, ioreg, weighted):
+        assert blend_watts(None, 16.0, config) == 16.0
+
+
+def test_charge_efficiency():
+    """Test efficiency is the battery's share of adapter input, only while charging and within 0-100%."""
+    from powermonitor.collector.iokit.collector import charge_efficiency
+
+    assert charge_efficiency(-40.0, 50.0, is_charging=True) == 80.0
+    assert charge_efficiency(-40.0, 50.0, is_charging=False) is None
+    assert charge_efficiency(None, 50.0, is_charging=True) is None
+    assert charge_efficiency(-40.0, None, is_charging=True) is None
+    assert charge_efficiency(-60.0, 50.0, is_charging=True) is None  # Over 100%
+    assert charge_efficiency(5.0, 50.0, is_charging=True) is None  # Battery still discharging
+    assert charge_efficiency(-40.0, 0.0, is_charging=True) is None
+    assert charge_efficiency(float("nan"), 50.0, is_charging=True) is None
+
+
+def test_blend_watts_weighted_skips_implausible_values():
+    """Test the weighted blend falls back to SMC when the two sources disagree or one is a glitch."""
+    from powermonitor.collector.base import CollectorConfig
+    from powermonitor.collector.base import WattsBlend
+    from powermonitor.collector.iokit.collector import blend_watts
+
+    weighted = CollectorConfig(watts_blend=WattsBlend.WEIGHTED, smc_weight=0.5)
+
+    assert blend_watts(20.0, -8.0, weighted) == 20.0  # Opposite directions
+    assert blend_watts(20.0, 0.0, weighted) == 20.0  # Computed value missing (0 A)
+    assert blend_watts(20.0, 5000.0, weighted) == 20.0  # Out of range
+    assert blend_watts(20.0, float("nan"), weighted) == 20.0
+
+
+def test_collector_config_validates_smc_weight():
+    """Test smc_weight must be a share between 0 and 1."""
+    from powermonitor.collector.base import CollectorConfig
+
+    with pytest.raises(ValueError, match="smc_weight"):
+        CollectorConfig(smc_weight=1.5)
+
+
+def test_collector_config_validates_smc_timeout():
+    """Test smc_timeout can't be negative."""
+    from powermonitor.collector.base import CollectorConfig
+
+    with pytest.raises(ValueError, match="smc_timeout"):
+        CollectorConfig(smc_timeout=-1.0)
+
+
+def test_smc_read_timeout_falls_back_to_ioreg(monkeypatch):
+    """Test a hung SMC read times out, falls back to ioreg, and doesn't start another reader."""
+    import threading
+    import time
+    from datetime import datetime
+
+    from powermonitor.collector.base import CollectorConfig
+    from powermonitor.collector.iokit.collector import IOKitCollector
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.collector.iokit.structures import SMCTimeoutError
+    from powermonitor.models import PowerReading
+
+    release = threading.Event()
+    opened = []
+
+    def hung_reader():
+        opened.append(True)
+        release.wait(5)
+        return MockSmcReader({})
+
+    base_reading = PowerReading(
+        timestamp=datetime.fromtimestamp(1234567890.0),
+        battery_percent=80,
+        watts_actual=15.5,
+        watts_negotiated=60,
+        voltage=12.0,
+        amperage=1.3,
+        current_capacity=5000,
+        max_capacity=6000,
+        is_charging=True,
+        external_connected=True,
+        charger_name="Test Charger",
+        charger_manufacturer="Test Manufacturer",
+    )
+    collector = IOKitCollector(config=CollectorConfig(smc_timeout=0.05), smc_factory=hung_reader)
+    monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
+
+    try:
+        start = time.monotonic()
+        with pytest.raises(SMCTimeoutError):
+            collector.read_smc_sensors()
+        assert time.monotonic() - start < 1.0
+
+        # The first reader is still blocked: fall back without opening another
+        assert collector.collect() == base_reading
+        assert len(opened) == 1
+    finally:
+        release.set()
+
+
+def test_collect_with_smc_weighted_blend(monkeypatch):
+    """Test IOKitCollector applies the configured blend to PDTR and V × A."""
+    from datetime import datetime
+
+    from powermonitor.collector.base import CollectorConfig
+    from powermonitor.collector.base import WattsBlend
+    from powermonitor.collector.iokit.collector import IOKitCollector
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.models import PowerReading
+
+    base_reading = PowerReading(
+        timestamp=datetime.fromtimestamp(1234567890.0),
+        battery_percent=85,
+        watts_actual=16.0,
+        watts_negotiated=65,
+        voltage=12.5,
+        amperage=1.28,
+        current_capacity=5100,
+        max_capacity=6000,
+        is_charging=True,
+        external_connected=True,
+        charger_name="USB PD",
+        charger_manufacturer="Apple",
+    )
+
+    collector = IOKitCollector(config=CollectorConfig(watts_blend=WattsBlend.WEIGHTED, smc_weight=0.25))
+    monkeypatch.setattr(collector, "read_smc_sensors", lambda: SMCPowerData(power_input=20.0))
+    monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
+
+    reading = collector._collect_with_smc()
+
+    assert reading.watts_actual == pytest.approx(0.25 * 20.0 + 0.75 * 16.0)
+
+
+def test_recording_smc_reader_round_trip(tmp_path):
+    """Test a recording of raw SMC bytes replays to the same decoded sensor data."""
+    from powermonitor.collector.iokit.collector import SMCPowerData
+    from powermonitor.collector.iokit.connection import SMCError
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.collector.iokit.reader import RecordingSmcReader
+    from powermonitor.collector.iokit.reader import SmcValue
+
+    closed = []
+
+    class FakeSMCConnection:
+        def read_raw(self, key: str) -> SmcValue:
+            values = {"PDTR": SmcValue("flt ", b"\x42\x36\x00\x00"), "TB0T": SmcValue("sp78", b"\x1e\x80")}
+            if key not in values:
+                raise SMCError(f"Sensor {key} not available")
+            return values[key]
+
+        def close(self) -> None:
+            closed.append(True)
+
+    with RecordingSmcReader(FakeSMCConnection()) as smc:
+        original = SMCPowerData.read(smc)
+    smc.save(tmp_path / "smc.json")
+
+    assert closed == [True]
+    assert smc.to_dict()["PDTR"] == {"type": "flt ", "bytes": "42360000"}
+    assert smc.to_dict()["PPBR"] is None
+    assert original.power_input == 45.5
+    assert SMCPowerData.read(MockSmcReader.load(tmp_path / "smc.json")) == original
+
+
+def test_mock_smc_reader_rejects_invalid_recordings(tmp_path):
+    """Test malformed recordings fail with ValueError rather than decoding garbage."""
+    from powermonitor.collector.iokit.reader import MockSmcReader
+
+    with pytest.raises(ValueError, match="PDTR"):
+        MockSmcReader.from_dict({"PDTR": {"type": "flt ", "bytes": "not hex"}})
+    with pytest.raises(ValueError, match="PDTR"):
+        MockSmcReader.from_dict({"PDTR": {"type": "flt "}})
+
+    path = tmp_path / "smc.json"
+    path.write_text("[]")
+    with pytest.raises(ValueError, match="JSON object"):
+        MockSmcReader.load(path)
+
+
+def test_iokit_collector_replays_recorded_sensors(monkeypatch):
+    """Test the SMC-to-reading pipeline against a recorded fixture, without IOKit."""
+    from datetime import datetime
+    from pathlib import Path
+
+    from powermonitor.collector.iokit.collector import IOKitCollector
+    from powermonitor.collector.iokit.reader import MockSmcReader
+    from powermonitor.models import PowerReading
+
+    recording = Path(__file__).parent / "fixtures" / "smc_recording.json"
+    base_reading = PowerReading(
+        timestamp=datetime.fromtimestamp(1234567890.0),
+        battery_percent=85,
+        watts_actual=40.0,
+        watts_negotiated=96,
+        voltage=12.5,
+        amperage=3.2,
+        current_capacity=5100,
+        max_capacity=6000,
+        is_charging=True,
+        external_connected=True,
+        charger_name="96W USB-C Power Adapter",
+        charger_manufacturer="Apple Inc.",
+    )
+
+    collector = IOKitCollector(smc_factory=lambda: MockSmcReader.load(recording))
+    monkeypatch.setattr(collector.fallback_collector, "collect", lambda: base_reading)
+
+    reading = collector.collect()
+
+    assert collector.read_smc_sensors().charging_status == 1.0
+    assert reading.watts_actual == 45.5  # PDTR
+    assert reading.system_power == 30.0  # PSTR
+    assert reading.battery_temp == 30.5  # TB0T
+    assert reading.amperage == 3.2  # No B0AC recorded: ioreg current kept
+    assert reading.amperage_ioreg is None
+    assert (reading.max_temp_sensor, reading.max_temp) == ("TB0T", 30.5)  # The only recorded temperature
+    assert reading.charge_efficiency is None  # No PPBR recorded
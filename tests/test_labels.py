@@ -0,0 +1,18 @@
+"""Tests for single-line label sanitizing."""
+
+from powermonitor.labels import sanitize_label
+
+
+def test_sanitize_label_flattens_control_characters():
+    """Test newlines, tabs, and escape sequences become single spaces."""
+    assert sanitize_label("USB-C\nPower\r\nAdapter") == "USB-C Power Adapter"
+    assert sanitize_label("\t96W \x1b[31m Adapter ") == "96W [31m Adapter"
+    assert sanitize_label("  spaced   out  ") == "spaced out"
+
+
+def test_sanitize_label_keeps_printable_text():
+    """Test quotes, commas, brackets, and unicode are left for each format to escape."""
+    name = 'Anker "Nano", 30W [USB-C] 充電器 ⚡'
+    assert sanitize_label(name) == name
+    assert sanitize_label(None) == ""
+    assert sanitize_label("") == ""
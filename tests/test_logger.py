@@ -93,3 +93,18 @@ def test_setup_logger_creates_directory(tmp_path, monkeypatch):
     # Directory should be created
     assert log_dir.exists()
     assert log_dir.is_dir()
+
+
+def test_setup_logger_quiet_keeps_warnings_in_file(tmp_path, monkeypatch, capsys):
+    """Test quiet mode prints only errors to the console but still logs warnings to file."""
+    monkeypatch.setattr(Path, "home", lambda: tmp_path)
+    logger.remove()
+
+    setup_logger(level="INFO", log_to_file=True, enqueue=False, quiet=True)
+    logger.warning("Test quiet warning")
+    logger.error("Test quiet error")
+
+    stderr = capsys.readouterr().err
+    assert "Test quiet warning" not in stderr
+    assert "Test quiet error" in stderr
+    assert "Test quiet warning" in (tmp_path / ".powermonitor" / "powermonitor.log").read_text()
@@ -0,0 +1,65 @@
+"""Tests for Low Power Mode parsing."""
+
+from powermonitor.collector.lowpower import parse_pmset_low_power_mode
+
+CURRENT_SETTINGS_OUTPUT = """\
+System-wide power settings:
+Currently in use:
+ standby              1
+ Sleep On Power Button 1
+ hibernatefile        /var/vm/sleepimage
+ powernap             1
+ networkoversleep     0
+ disksleep            10
+ sleep                1 (sleep prevented by powerd)
+ hibernatemode        3
+ ttyskeepawake        1
+ displaysleep         10
+ tcpkeepalive         1
+ lowpowermode         1
+ womp                 0
+"""
+
+CUSTOM_OUTPUT = """\
+Battery Power:
+ lidwake              1
+ lowpowermode         1
+ standby              1
+ displaysleep         2
+AC Power:
+ lidwake              1
+ lowpowermode         0
+ standby              1
+ displaysleep         10
+"""
+
+
+def test_parse_current_settings():
+    """Test the lowpowermode setting of `pmset -g` decides the state."""
+    assert parse_pmset_low_power_mode(CURRENT_SETTINGS_OUTPUT) is True
+    disabled = CURRENT_SETTINGS_OUTPUT.replace("lowpowermode         1", "lowpowermode         0")
+    assert parse_pmset_low_power_mode(disabled) is False
+
+
+def test_parse_powermode():
+    """Test Macs with High Power Mode report "powermode" (1 is Low Power Mode, 2 High Power Mode)."""
+    assert parse_pmset_low_power_mode(" powermode            1\n") is True
+    assert parse_pmset_low_power_mode(" powermode            2\n") is False
+    assert parse_pmset_low_power_mode(" powermode            0\n") is False
+
+
+def test_parse_custom_picks_current_source():
+    """Test `pmset -g custom` uses the section of the power source in use."""
+    assert parse_pmset_low_power_mode(CUSTOM_OUTPUT, external_connected=False) is True
+    assert parse_pmset_low_power_mode(CUSTOM_OUTPUT, external_connected=True) is False
+
+    # Unknown source: only an answer every source agrees on
+    assert parse_pmset_low_power_mode(CUSTOM_OUTPUT) is None
+    assert parse_pmset_low_power_mode(CUSTOM_OUTPUT.replace("lowpowermode         0", "lowpowermode 1")) is True
+
+
+def test_parse_unrecognized():
+    """Test output without the setting (older macOS) returns None."""
+    assert parse_pmset_low_power_mode("") is None
+    assert parse_pmset_low_power_mode(" standby              1\n displaysleep         10\n") is None
+    assert parse_pmset_low_power_mode("Battery Power:\n standby 1\n", external_connected=False) is None
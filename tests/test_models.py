@@ -1,8 +1,15 @@
 """Tests for PowerReading model."""
 
+from dataclasses import replace
 from datetime import UTC
 from datetime import datetime
+from datetime import timedelta
 
+import pytest
+
+from powermonitor.models import THROTTLE_MAX_PERCENT
+from powermonitor.models import DerivedReading
+from powermonitor.models import PowerDirection
 from powermonitor.models import PowerReading
 
 
@@ -105,3 +112,109 @@ def test_power_reading_full_battery():
     assert reading.battery_percent == 100
     assert reading.current_capacity == reading.max_capacity
     assert reading.watts_actual == 0.0  # Full battery, not charging
+
+
+def test_power_reading_direction_ignores_watts_sign(sample_reading):
+    """Test direction is reconciled from status flags, not the sign of watts_actual."""
+    # Charging flag set but collector reported a small negative flow during a transition
+    charging = replace(sample_reading, is_charging=True, external_connected=True, watts_actual=-0.4)
+    assert charging.direction is PowerDirection.CHARGING
+    assert charging.direction.icon == "⚡"
+
+    # Unplugged but a stale positive reading
+    unplugged = replace(sample_reading, is_charging=True, external_connected=False, watts_actual=3.0)
+    assert unplugged.direction is PowerDirection.DISCHARGING
+    assert unplugged.direction.icon == "🔋"
+
+    # Plugged in at 100%, not charging
+    idle = replace(sample_reading, is_charging=False, external_connected=True, watts_actual=0.0)
+    assert idle.direction is PowerDirection.AC_IDLE
+    assert idle.direction.icon == "🔌"
+
+
+def test_power_reading_direction_plugged_in_but_discharging(sample_reading):
+    """Test a load above the adapter's output is reported as discharging on AC."""
+    draining = replace(sample_reading, is_charging=False, external_connected=True, watts_actual=-12.5)
+    assert draining.direction is PowerDirection.AC_DISCHARGING
+    assert draining.direction.icon == "🔌⚠"
+
+    # Transition noise below the threshold stays idle
+    noisy = replace(draining, watts_actual=-0.4)
+    assert noisy.direction is PowerDirection.AC_IDLE
+
+
+def test_derived_reading(sample_reading):
+    """Test derived values are computed from the wrapped reading."""
+    data = DerivedReading(replace(sample_reading, design_capacity=5103)).to_dict()
+
+    assert data["reading"]["watts_actual"] == 45.5
+    assert data["derived"] == {
+        "direction": "charging",
+        "battery_health_percent": 92.3,  # 4709 / 5103
+        "time_to_full_minutes": 32,  # 1209 mAh at 2.275 A
+        "energy_wh_estimate": 70.0,  # 3500 mAh × 20 V
+        "charging_throttled": False,  # Max charge current unknown
+    }
+
+
+def test_derived_reading_unknown_values(sample_reading):
+    """Test derived values that can't be computed are None."""
+    discharging = replace(sample_reading, is_charging=False, external_connected=False, amperage=-1.5)
+    derived = DerivedReading(discharging).to_dict()["derived"]
+
+    assert derived["direction"] == "discharging"
+    assert derived["battery_health_percent"] is None  # No design capacity
+    assert derived["time_to_full_minutes"] is None
+    assert DerivedReading(replace(sample_reading, current_capacity=0)).energy_wh_estimate is None
+
+
+def test_charging_throttled(sample_reading):
+    """Test charging well below the battery's max charge current counts as throttled, except near full."""
+    throttled = replace(sample_reading, amperage=1.0, max_charge_current=4.5)
+
+    assert throttled.charging_throttled
+    assert not replace(throttled, amperage=3.0).charging_throttled  # Above THROTTLED_RATIO
+    assert not replace(throttled, battery_percent=THROTTLE_MAX_PERCENT).charging_throttled  # Tapering
+    assert not replace(throttled, is_charging=False).charging_throttled
+    assert not replace(throttled, max_charge_current=None).charging_throttled
+
+
+def test_power_reading_diff(sample_reading):
+    """Test diff reports per-field deltas and changed fields."""
+    after = replace(
+        sample_reading,
+        timestamp=sample_reading.timestamp + timedelta(seconds=5),
+        watts_actual=30.0,
+        is_charging=False,
+        label="after",
+    )
+
+    diff = sample_reading.diff(after)
+
+    assert diff.elapsed_seconds == 5.0
+    assert diff["watts_actual"].delta == pytest.approx(30.0 - sample_reading.watts_actual)
+    assert diff["voltage"].delta == 0
+    # Booleans and strings have no numeric delta
+    assert diff["is_charging"].delta is None
+    assert diff["label"].delta is None
+    # Optional field present in only one reading
+    assert (diff["label"].before, diff["label"].after) == (None, "after")
+    assert {f.name for f in diff.changed()} == {"watts_actual", "is_charging", "label"}
+
+
+def test_power_diff_str_side_by_side(sample_reading):
+    """Test the text rendering lists before/after columns and marks changes."""
+    after = replace(sample_reading, battery_percent=sample_reading.battery_percent + 1)
+
+    lines = str(sample_reading.diff(after)).splitlines()
+
+    assert lines[0] == "elapsed: +0.0s"
+    assert lines[1].split() == ["field", "before", "after", "delta"]
+    changed = [line for line in lines if line.startswith("*")]
+    assert len(changed) == 1
+    assert changed[0].split()[1:] == [
+        "battery_percent",
+        str(sample_reading.battery_percent),
+        str(sample_reading.battery_percent + 1),
+        "+1.00",
+    ]
@@ -0,0 +1,63 @@
+"""Tests for MQTT topic and payload building."""
+
+import json
+from dataclasses import replace
+
+import pytest
+
+from powermonitor.config import MqttConfig
+from powermonitor.mqtt import MQTT_SENSORS
+from powermonitor.mqtt import BrokerAddress
+from powermonitor.mqtt import discovery_messages
+from powermonitor.mqtt import node_id
+from powermonitor.mqtt import parse_broker_url
+from powermonitor.mqtt import state_messages
+
+
+def test_parse_broker_url():
+    """Test default ports, TLS detection and rejected URLs."""
+    assert parse_broker_url("mqtt://ha.local") == BrokerAddress("ha.local", 1883, False)
+    assert parse_broker_url("mqtts://ha.local") == BrokerAddress("ha.local", 8883, True)
+    assert parse_broker_url("mqtt://10.0.0.2:1884") == BrokerAddress("10.0.0.2", 1884, False)
+
+    for url in ("http://ha.local", "mqtt://", "mqtt://ha.local:port"):
+        with pytest.raises(ValueError):
+            parse_broker_url(url)
+
+
+def test_node_id():
+    """Test that host names become topic-safe ids."""
+    assert node_id("Narumis-MacBook-Pro.local") == "narumis_macbook_pro"
+    assert node_id("---") == "mac"
+
+
+def test_discovery_messages():
+    """Test one retained config per sensor with stable unique ids and shared availability."""
+    messages = discovery_messages(MqttConfig(topic_prefix="office/mac/"), "mbp")
+
+    assert len(messages) == len(MQTT_SENSORS)
+    topics = dict(messages)
+    watts = json.loads(topics["homeassistant/sensor/mbp/watts/config"])
+    assert watts["unique_id"] == "powermonitor_mbp_watts"
+    assert watts["state_topic"] == "office/mac/watts"
+    assert watts["availability_topic"] == "office/mac/status"
+    assert (watts["device_class"], watts["unit_of_measurement"]) == ("power", "W")
+
+    charging = json.loads(topics["homeassistant/binary_sensor/mbp/charging/config"])
+    assert charging["device_class"] == "battery_charging"
+    assert "unit_of_measurement" not in charging
+    assert charging["device"] == watts["device"]
+
+
+def test_state_messages(sample_reading):
+    """Test payload formatting and that missing values are not published."""
+    states = dict(state_messages(replace(sample_reading, watts_actual=12.345, is_charging=False), "powermonitor"))
+
+    assert states["powermonitor/watts"] == "12.35"
+    assert states["powermonitor/battery"] == "74"
+    assert states["powermonitor/charging"] == "OFF"
+    assert states["powermonitor/plugged_in"] == "ON"
+    assert "powermonitor/battery_temp" not in states
+
+    states = dict(state_messages(replace(sample_reading, battery_temp=31.0), "powermonitor"))
+    assert states["powermonitor/battery_temp"] == "31.00"
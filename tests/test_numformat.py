@@ -0,0 +1,63 @@
+"""Tests for locale-aware number formatting."""
+
+import locale
+
+import pytest
+
+from powermonitor.numformat import CANONICAL
+from powermonitor.numformat import NumberFormat
+from powermonitor.numformat import number_format_for_locale
+
+
+def test_canonical_format():
+    """Test the default format matches plain Python formatting."""
+    assert CANONICAL.format_number(12.345) == "12.3"
+    assert CANONICAL.format_number(-2.5, 2, sign=True) == "-2.50"
+    assert CANONICAL.format_number(2.5, 2, sign=True) == "+2.50"
+    assert CANONICAL.format_number(1234567, 0, grouping=True) == "1,234,567"
+
+
+def test_comma_decimal_format():
+    """Test separators are swapped rather than stacked for comma-decimal locales."""
+    german = NumberFormat(decimal_point=",", thousands_sep=".")
+    assert german.format_number(12.5, sign=True) == "+12,5"
+    assert german.format_number(1234.5, 2, grouping=True) == "1.234,50"
+    assert german.format_number(1234.5, 2) == "1234,50"
+
+
+def test_no_grouping_separator():
+    """Test an empty thousands separator drops grouping entirely."""
+    plain = NumberFormat(decimal_point=".", thousands_sep="")
+    assert plain.format_number(1234567, 0, grouping=True) == "1234567"
+
+
+def test_format_watts_with_reference_voltage():
+    """Test power is shown alone by default and with the equivalent current when asked."""
+    assert CANONICAL.format_watts(15.0) == "15.0W"
+    assert CANONICAL.format_watts(15.0, 5.0) == "15.0W (≈3.0A @ 5V)"
+    assert CANONICAL.format_watts(-12.0, 3.7, sign=True) == "-12.0W (≈3.2A @ 3.7V)"
+    german = NumberFormat(decimal_point=",", thousands_sep=".")
+    assert german.format_watts(15.0, 3.7) == "15,0W (≈4,1A @ 3,7V)"
+
+
+def test_format_watts_deadzone():
+    """Test power inside the deadzone shows as an unsigned zero and larger power is unchanged."""
+    assert CANONICAL.format_watts(0.3, sign=True, deadzone=0.5) == "0.0W"
+    assert CANONICAL.format_watts(-0.3, sign=True, deadzone=0.5) == "0.0W"
+    assert CANONICAL.format_watts(-0.5, sign=True, deadzone=0.5) == "-0.5W"
+    assert CANONICAL.format_watts(0.3, sign=True) == "+0.3W"
+    assert CANONICAL.format_watts(-0.2, 5.0, deadzone=0.5) == "0.0W (≈0.0A @ 5V)"
+
+
+def test_number_format_for_locale_keeps_process_locale():
+    """Test looking up a locale doesn't change the process numeric locale."""
+    before = locale.setlocale(locale.LC_NUMERIC)
+    number_format = number_format_for_locale("C")
+    assert number_format.decimal_point == "."
+    assert locale.setlocale(locale.LC_NUMERIC) == before
+
+
+def test_number_format_for_unknown_locale():
+    """Test an uninstalled locale raises ValueError."""
+    with pytest.raises(ValueError, match="Unknown locale"):
+        number_format_for_locale("xx_NOPE.UTF-8")
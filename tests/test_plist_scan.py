@@ -0,0 +1,42 @@
+"""Tests for the forgiving plist reader."""
+
+import plistlib
+from pathlib import Path
+
+from powermonitor.collector.plist_scan import scan_first_dict
+
+FIXTURES = Path(__file__).parent / "fixtures"
+
+
+def test_scan_matches_plistlib_on_well_formed_output():
+    """Test well-formed ioreg output reads exactly as plistlib reads it."""
+    for name in ("real_mac.txt", "adapter_details_only.txt"):
+        raw = (FIXTURES / name).read_bytes()
+        assert scan_first_dict(raw) == plistlib.loads(raw)[0]
+
+
+def test_scan_drops_only_broken_values():
+    """Test unreadable values are skipped while their neighbors survive."""
+    raw = b"""<plist><array><dict>
+        <key>Name</key><string>Charger & Cable</string>
+        <key>Empty</key><integer></integer>
+        <key>Voltage</key><integer>12500</integer>
+        <key>Nested</key><dict><key>Watts</key><integer>70</integer><key>Bad</key><real>x</real></dict>
+        <key>Flag</key><true/>
+        <key>Unknown</key><widget><integer>1</integer></widget>
+        <key>List</key><array><integer>1</integer><integer>two</integer><string>3</string></array>
+        <key>Amperage</key><integer>-1200"""
+
+    assert scan_first_dict(raw) == {
+        "Name": "Charger & Cable",
+        "Voltage": 12500,
+        "Nested": {"Watts": 70},
+        "Flag": True,
+        "List": [1, "3"],
+    }
+
+
+def test_scan_without_dict():
+    """Test input with no <dict> at all gives None."""
+    assert scan_first_dict(b"<plist><array></array></plist>") is None
+    assert scan_first_dict(b"not xml") is None
@@ -0,0 +1,103 @@
+"""Tests for the minimal pmset collector."""
+
+import subprocess
+from unittest.mock import patch
+
+import pytest
+
+from powermonitor.clock import MockClock
+from powermonitor.collector.base import CollectorConfig
+from powermonitor.collector.pmset import PmsetCollector
+from powermonitor.collector.pmset import parse_pmset_batt
+from powermonitor.models import CollectorUnavailableError
+from powermonitor.models import CommandFailedError
+from powermonitor.models import NoBatteryError
+from powermonitor.models import ParseError
+
+DISCHARGING = (
+    "Now drawing from 'Battery Power'\n"
+    " -InternalBattery-0 (id=6619235)\t85%; discharging; 5:12 remaining present: true\n"
+)
+CHARGING = (
+    "Now drawing from 'AC Power'\n"
+    " -InternalBattery-0 (id=6619235)\t74%; charging; 0:48 remaining present: true\n"
+)
+CHARGED = (
+    "Now drawing from 'AC Power'\n"
+    " -InternalBattery-0 (id=6619235)\t100%; charged; 0:00 remaining present: true\n"
+)
+NOT_CHARGING = (
+    "Now drawing from 'AC Power'\n"
+    " -InternalBattery-0 (id=6619235)\t80%; AC attached; not charging present: true\n"
+)
+
+
+def test_parse_discharging():
+    """Test a Mac running on battery."""
+    reading = parse_pmset_batt(DISCHARGING)
+
+    assert reading.battery_percent == 85
+    assert reading.is_charging is False
+    assert reading.external_connected is False
+    assert reading.fully_charged is False
+    # Fields beyond the minimal level are left empty
+    assert reading.watts_actual == 0.0
+    assert reading.charger_name is None
+
+
+def test_parse_charging():
+    """Test a Mac charging from an adapter."""
+    reading = parse_pmset_batt(CHARGING)
+
+    assert reading.battery_percent == 74
+    assert reading.is_charging is True
+    assert reading.external_connected is True
+
+
+def test_parse_charged_and_not_charging():
+    """Test plugged-in states where no charge is going in."""
+    charged = parse_pmset_batt(CHARGED)
+    assert (charged.battery_percent, charged.is_charging, charged.fully_charged) == (100, False, True)
+
+    held = parse_pmset_batt(NOT_CHARGING)
+    assert (held.battery_percent, held.is_charging, held.external_connected) == (80, False, True)
+
+
+def test_parse_no_battery():
+    """Test a desktop Mac without an internal battery."""
+    with pytest.raises(NoBatteryError):
+        parse_pmset_batt("Now drawing from 'AC Power'\n")
+
+
+def test_parse_unrecognized_battery_line():
+    """Test a battery line in an unknown format is a parse error, not 'no battery'."""
+    with pytest.raises(ParseError):
+        parse_pmset_batt("Now drawing from 'AC Power'\n -InternalBattery-0 (id=1) unknown\n")
+
+
+def test_collect_runs_pmset():
+    """Test the collector parses pmset's output, stamped by the configured clock."""
+    clock = MockClock()
+    completed = subprocess.CompletedProcess(args=[], returncode=0, stdout=CHARGING, stderr="")
+    with patch("powermonitor.collector.pmset.subprocess.run", return_value=completed) as run:
+        reading = PmsetCollector(config=CollectorConfig(clock=clock)).collect()
+
+    assert run.call_args.args[0] == ("pmset", "-g", "batt")
+    assert reading.battery_percent == 74
+    assert reading.timestamp == clock.now()
+
+
+def test_collect_pmset_missing():
+    """Test a missing pmset binary is reported as unavailable rather than failed."""
+    with patch("powermonitor.collector.pmset.subprocess.run", side_effect=FileNotFoundError("pmset")):
+        with pytest.raises(CollectorUnavailableError, match="pmset not found on PATH") as exc_info:
+            PmsetCollector().collect()
+
+    assert exc_info.value.tool == "pmset"
+
+
+def test_collect_pmset_spawn_failure():
+    """Test other spawn errors raise CommandFailedError."""
+    with patch("powermonitor.collector.pmset.subprocess.run", side_effect=PermissionError("denied")):
+        with pytest.raises(CommandFailedError, match="Failed to start pmset"):
+            PmsetCollector().collect()
@@ -0,0 +1,118 @@
+"""Tests for probing the data sources."""
+
+import json
+import subprocess
+from unittest.mock import patch
+
+from powermonitor import probe
+from powermonitor.collector import powermetrics
+from powermonitor.collector.gpu import GpuPowerReader
+from powermonitor.collector.ioreg import IORegCollector
+from powermonitor.collector.pmset import PmsetCollector
+from powermonitor.models import NoBatteryError
+
+BATTERY = {
+    "Voltage": 12000,
+    "Amperage": -800,
+    "CurrentCapacity": 2500,
+    "MaxCapacity": 4000,
+    "IsCharging": False,
+    "ExternalConnected": False,
+}
+
+
+def test_probe_ioreg(monkeypatch):
+    """Test ioreg is reported as present and parsing, with its keys and a sample reading."""
+    collector = IORegCollector()
+    monkeypatch.setattr(collector, "read_battery_dict", lambda: BATTERY)
+
+    result = probe.probe_ioreg(collector)
+
+    assert result["available"] is True
+    assert result["parses"] is True
+    assert result["error"] is None
+    assert result["keys"] == sorted(BATTERY)
+    assert result["sample"]["battery_percent"] == 62
+    assert result["sample"]["voltage"] == 12.0
+    json.dumps(result)
+
+
+def test_probe_ioreg_failures(monkeypatch):
+    """Test a missing battery and an entry that doesn't parse are told apart."""
+    collector = IORegCollector()
+
+    def no_battery():
+        raise NoBatteryError("No battery found")
+
+    monkeypatch.setattr(collector, "read_battery_dict", no_battery)
+    result = probe.probe_ioreg(collector)
+    assert (result["available"], result["parses"], result["error"]) == (False, False, "No battery found")
+
+    monkeypatch.setattr(collector, "read_battery_dict", lambda: {"MaxCapacity": 4000})
+    result = probe.probe_ioreg(collector)
+    assert (result["available"], result["parses"]) == (True, False)
+    assert result["error"] == "Missing required field: Voltage"
+    assert result["keys"] == ["MaxCapacity"]
+    assert result["sample"] is None
+
+
+def test_probe_smc_off_macos(monkeypatch):
+    """Test the SMC is reported unavailable without trying IOKit off macOS."""
+    monkeypatch.setattr(probe.sys, "platform", "linux")
+
+    result = probe.probe_smc()
+
+    assert result == {"available": False, "error": "IOKit requires macOS", "keys": {}, "temperature_keys": {}}
+
+
+def test_probe_pmset():
+    """Test pmset's battery state is sampled, and a missing pmset is reported rather than raised."""
+    completed = subprocess.CompletedProcess(
+        args=[],
+        returncode=0,
+        stdout="Now drawing from 'AC Power'\n -InternalBattery-0 (id=1)\t74%; charging; 0:48 remaining present: true\n",
+        stderr="",
+    )
+    with (
+        patch("powermonitor.collector.pmset.subprocess.run", return_value=completed),
+        patch("powermonitor.probe.read_thermal_state", return_value=None),
+        patch("powermonitor.probe.read_low_power_mode", return_value=False),
+    ):
+        result = probe.probe_pmset(PmsetCollector())
+
+    assert result["available"] is True
+    assert result["sample"]["battery_percent"] == 74
+    assert result["sample"]["is_charging"] is True
+    assert result["sample"]["low_power_mode"] is False
+
+    with patch("powermonitor.collector.pmset.subprocess.run", side_effect=FileNotFoundError("pmset")):
+        result = probe.probe_pmset(PmsetCollector())
+
+    assert result["available"] is False
+    assert "pmset not found on PATH" in result["error"]
+    assert result["sample"] is None
+
+
+def test_probe_powermetrics(monkeypatch):
+    """Test powermetrics runs only when installed and permitted, reporting why it didn't otherwise."""
+
+    def fake_run(command, **kwargs):
+        return subprocess.CompletedProcess(command, 0, stdout="GPU Power: 1234 mW\n", stderr="")
+
+    monkeypatch.setattr(probe.shutil, "which", lambda name: f"/usr/bin/{name}")
+    monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+
+    monkeypatch.setattr(probe.os, "geteuid", lambda: 501)
+    result = probe.probe_powermetrics(GpuPowerReader())
+    assert (result["available"], result["permitted"], result["sample"]) == (True, False, None)
+    assert "needs root" in result["error"]
+
+    monkeypatch.setattr(probe.os, "geteuid", lambda: 0)
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 0)
+    result = probe.probe_powermetrics(GpuPowerReader())
+    assert result == {"available": True, "permitted": True, "error": None, "sample": {"gpu_power": 1.234}}
+
+    monkeypatch.setattr(probe.shutil, "which", lambda name: None)
+    result = probe.probe_powermetrics(GpuPowerReader())
+    assert result["available"] is False
+    assert result["error"] == "powermetrics not found on PATH"
@@ -0,0 +1,74 @@
+"""Tests for NDJSON replay."""
+
+import json
+from dataclasses import replace
+from datetime import timedelta
+
+import pytest
+
+from powermonitor.replay import ReplayCollector
+from powermonitor.replay import ReplayFinishedError
+from powermonitor.replay import read_ndjson
+from powermonitor.replay import replay_schedule
+
+
+def test_read_ndjson_roundtrip(sample_reading, tmp_path):
+    """Test readings written with to_dict() are read back unchanged."""
+    labelled = replace(sample_reading, label="demo", watts_negotiated_stable=67)
+    path = tmp_path / "session.ndjson"
+    path.write_text(json.dumps(labelled.to_dict()) + "\n\n" + json.dumps(sample_reading.to_dict()) + "\n")
+
+    readings = list(read_ndjson(path))
+
+    assert readings == [labelled, sample_reading]
+
+
+def test_read_ndjson_invalid_line(tmp_path):
+    """Test a malformed line raises ValueError naming the line."""
+    path = tmp_path / "broken.ndjson"
+    path.write_text("{not json}\n")
+
+    with pytest.raises(ValueError, match="broken.ndjson:1"):
+        list(read_ndjson(path))
+
+
+def test_replay_schedule_scales_and_orders(sample_reading):
+    """Test delays follow recorded gaps divided by speed, oldest first."""
+    first = sample_reading
+    second = replace(sample_reading, timestamp=first.timestamp + timedelta(seconds=4))
+    third = replace(sample_reading, timestamp=first.timestamp + timedelta(seconds=10))
+
+    schedule = list(replay_schedule([third, first, second], speed=2.0))
+
+    assert [delay for delay, _ in schedule] == [0.0, 2.0, 3.0]
+    assert [reading for _, reading in schedule] == [first, second, third]
+
+
+def test_replay_schedule_caps_long_gaps(sample_reading):
+    """Test gaps between sessions are clamped to max_delay."""
+    later = replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(hours=3))
+
+    schedule = list(replay_schedule([sample_reading, later], max_delay=5.0))
+
+    assert schedule[1][0] == 5.0
+
+
+def test_replay_schedule_rejects_invalid_speed(sample_reading):
+    """Test non-positive speed raises ValueError."""
+    with pytest.raises(ValueError, match="speed must be positive"):
+        list(replay_schedule([sample_reading], speed=0))
+
+
+def test_replay_collector_follows_schedule(sample_reading):
+    """Test readings come out in order with their recorded timestamps, then the collector reports the end."""
+    later = replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=4), watts_actual=-8.0)
+    collector = ReplayCollector(replay_schedule([later, sample_reading], speed=2.0))
+
+    assert collector.next_delay() == 0.0
+    assert collector.collect() == sample_reading
+    assert collector.next_delay() == 2.0
+    assert collector.collect() == later
+
+    assert collector.next_delay() is None
+    with pytest.raises(ReplayFinishedError):
+        collector.collect()
@@ -0,0 +1,109 @@
+"""Tests for HTML reports."""
+
+from dataclasses import replace
+from datetime import UTC
+from datetime import datetime
+from datetime import timedelta
+from pathlib import Path
+
+import pytest
+
+from powermonitor.report import MAX_CHART_POINTS
+from powermonitor.report import Report
+from powermonitor.report import chart_regions
+from powermonitor.report import parse_time_bound
+from powermonitor.report import render_chart_svg
+from powermonitor.report import render_report
+
+NOW = datetime(2025, 12, 28, 12, 0, tzinfo=UTC)
+
+
+def make_readings(sample_reading, count: int) -> list:
+    """Create one reading per minute ending at NOW, with rising power."""
+    return [
+        replace(sample_reading, timestamp=NOW - timedelta(minutes=count - 1 - i), watts_actual=float(i))
+        for i in range(count)
+    ]
+
+
+def test_parse_time_bound():
+    """Test ages count back from now and dates without a zone are local time."""
+    assert parse_time_bound("7d", NOW) == NOW - timedelta(days=7)
+    assert parse_time_bound("90m", NOW) == NOW - timedelta(minutes=90)
+    assert parse_time_bound("2025-12-20T09:30+00:00", NOW) == datetime(2025, 12, 20, 9, 30, tzinfo=UTC)
+
+    local = parse_time_bound("2025-12-20", NOW)
+    assert local.tzinfo is not None
+    assert local == datetime(2025, 12, 20).astimezone()
+
+    with pytest.raises(ValueError, match="Invalid time"):
+        parse_time_bound("last tuesday", NOW)
+
+
+def test_report_build(sample_reading, tmp_path):
+    """Test statistics, chargers, and health are computed over the range."""
+    readings = make_readings(sample_reading, 3)
+    readings[0] = replace(readings[0], max_capacity=4800, design_capacity=5000)
+    readings[1] = replace(readings[1], charger_name=None, charger_manufacturer=None)
+    readings[2] = replace(readings[2], max_capacity=4750, design_capacity=5000)
+
+    report = Report.build(list(reversed(readings)), tmp_path / "missing.db", generated_at=NOW)
+
+    assert report.readings == readings  # Oldest first
+    assert report.stats.count == 3
+    assert report.stats.avg_watts == 1.0
+    assert report.stats.database_size_bytes == 0
+    assert len(report.chargers) == 1
+    assert report.chargers[0].readings == 2
+    assert report.health.change_mah == -50
+    assert report.health.health_percent == pytest.approx(95.0)
+
+    with pytest.raises(ValueError, match="at least one reading"):
+        Report.build([], Path("pm.db"), generated_at=NOW)
+
+
+def test_render_report_is_self_contained(sample_reading, tmp_path):
+    """Test the HTML has every section, inline SVG, escaped text, and no external resources."""
+    readings = [replace(r, charger_name="<b>Cheap</b> Charger") for r in make_readings(sample_reading, 5)]
+    report = Report.build(readings, tmp_path / "pm.db", generated_at=NOW, since=NOW - timedelta(hours=1))
+
+    html = render_report(report)
+
+    assert html.startswith("<!DOCTYPE html>")
+    for heading in ("Power and battery", "Statistics", "Chargers", "Battery health"):
+        assert f"<h2>{heading}</h2>" in html
+    assert "<svg" in html
+    assert "&lt;b&gt;Cheap&lt;/b&gt; Charger" in html
+    assert "<b>Cheap" not in html
+    assert "src=" not in html
+    assert "href=" not in html
+
+
+def test_chart_is_thinned(sample_reading):
+    """Test long ranges are thinned to about MAX_CHART_POINTS points, keeping the last reading."""
+    readings = make_readings(sample_reading, MAX_CHART_POINTS * 3 + 1)
+
+    svg = render_chart_svg(readings)
+
+    power_line = svg.split('<polyline points="')[1].split('"')[0]
+    assert len(power_line.split()) <= MAX_CHART_POINTS + 1
+    assert power_line.split()[-1].startswith("760.0,")  # Last reading at the right edge
+
+
+def test_chart_shades_power_source_regions(sample_reading):
+    """Test the chart background is shaded per charging / on AC / on battery run."""
+    readings = make_readings(sample_reading, 6)
+    readings[2:4] = [replace(r, is_charging=False) for r in readings[2:4]]
+    readings[4:] = [replace(r, is_charging=False, external_connected=False) for r in readings[4:]]
+
+    assert chart_regions(readings) == [("charging", 0, 1), ("ac", 2, 3), ("battery", 4, 5)]
+
+    svg = render_chart_svg(readings)
+
+    assert [line.split('"')[1] for line in svg.splitlines() if line.startswith('<rect class="')] == [
+        "region-charging",
+        "region-ac",
+        "region-battery",
+    ]
+    assert svg.index("region-battery") < svg.index("<polyline")  # Shading sits behind the lines
+    assert chart_regions([]) == []
@@ -0,0 +1,105 @@
+"""Tests for time-of-day sampling schedules."""
+
+import time
+
+import pytest
+
+from powermonitor.schedule import MIN_DELAY
+from powermonitor.schedule import ScheduleWindow
+from powermonitor.schedule import parse_interval
+from powermonitor.schedule import parse_schedule
+
+
+@pytest.fixture
+def new_york(monkeypatch):
+    """Run the test in a time zone with DST."""
+    monkeypatch.setenv("TZ", "America/New_York")
+    time.tzset()
+    yield
+    monkeypatch.undo()
+    time.tzset()
+
+
+def local_time(year, month, day, hour, minute=0, second=0):
+    """Unix time for a local wall-clock time (current TZ)."""
+    return time.mktime((year, month, day, hour, minute, second, 0, 0, -1))
+
+
+def test_parse_interval_units():
+    """Test bare seconds and s/m/h units."""
+    assert parse_interval("30") == 30
+    assert parse_interval("30s") == 30
+    assert parse_interval("5m") == 300
+    assert parse_interval("1.5h") == 5400
+
+    for bad in ("0", "5x", "-1m", ""):
+        with pytest.raises(ValueError):
+            parse_interval(bad)
+
+
+def test_parse_schedule():
+    """Test windows and the * default are parsed in order."""
+    schedule = parse_schedule("09:00-18:00=1m, 22:00-06:00=15m, *=5m", default_interval=1.0)
+
+    assert schedule.windows == (
+        ScheduleWindow(start=9 * 3600, end=18 * 3600, interval=60),
+        ScheduleWindow(start=22 * 3600, end=6 * 3600, interval=900),
+    )
+    assert schedule.default_interval == 300
+
+
+def test_parse_schedule_keeps_default_interval():
+    """Test times outside every window use the given interval when there is no * entry."""
+    schedule = parse_schedule("09:00-18:00=1m", default_interval=2.0)
+
+    assert schedule.interval_at(8 * 3600) == 2.0
+
+
+def test_parse_schedule_rejects_malformed_entries():
+    """Test malformed windows and intervals are reported."""
+    for spec in ("09:00-18:00", "9-18=1m", "09:00-25:00=1m", "09:00-09:00=1m", "09:00-18:00=fast"):
+        with pytest.raises(ValueError):
+            parse_schedule(spec, default_interval=1.0)
+
+
+def test_interval_at_windows():
+    """Test the first matching window wins and windows can wrap midnight."""
+    schedule = parse_schedule("09:00-18:00=1m, 22:00-06:00=15m, *=5m", default_interval=1.0)
+
+    assert schedule.interval_at(9 * 3600) == 60  # Start is inclusive
+    assert schedule.interval_at(18 * 3600) == 300  # End is exclusive
+    assert schedule.interval_at(23 * 3600) == 900
+    assert schedule.interval_at(2 * 3600) == 900
+    assert schedule.interval_at(20 * 3600) == 300
+
+
+def test_next_delay_uses_window_interval(new_york):
+    """Test the delay inside a window is that window's interval."""
+    schedule = parse_schedule("09:00-18:00=1m, *=10m", default_interval=1.0)
+
+    assert schedule.next_delay(local_time(2026, 3, 2, 10, 0)) == 60
+    assert schedule.next_delay(local_time(2026, 3, 2, 20, 0)) == 600
+
+
+def test_next_delay_stops_at_window_start(new_york):
+    """Test a long interval is cut short so the next window starts with a sample."""
+    schedule = parse_schedule("09:00-18:00=1m, *=10m", default_interval=1.0)
+
+    assert schedule.next_delay(local_time(2026, 3, 2, 8, 57)) == 180
+    # Wraps to tomorrow's boundary
+    assert schedule.next_delay(local_time(2026, 3, 2, 23, 55)) == 600
+
+
+def test_next_delay_across_dst_change(new_york):
+    """Test boundaries follow the wall clock when DST starts (01:59 EST -> 03:00 EDT)."""
+    schedule = parse_schedule("03:00-06:00=1m, *=2h", default_interval=1.0)
+
+    # 01:30 EST on 2026-03-08 is 30 real minutes before 03:00 EDT
+    assert schedule.next_delay(local_time(2026, 3, 8, 1, 30)) == 30 * 60
+
+
+def test_next_delay_minimum():
+    """Test the delay never drops below MIN_DELAY."""
+    schedule = parse_schedule("*=0.01s", default_interval=1.0)
+
+    assert schedule.next_delay() == MIN_DELAY
@@ -0,0 +1,117 @@
+"""Tests for charge session detection."""
+
+from dataclasses import replace
+from datetime import timedelta
+
+import pytest
+
+from powermonitor.clock import MockClock
+from powermonitor.collector.mock import MockCollector
+from powermonitor.models import PowerReading
+from powermonitor.sessions import ChargeSessionTracker
+
+
+def make_reading(
+    base: PowerReading,
+    minutes: float,
+    watts: float,
+    percent: int,
+    is_charging: bool = True,
+    external_connected: bool = True,
+    fully_charged: bool = False,
+) -> PowerReading:
+    """Create a reading offset from the base reading's timestamp."""
+    return replace(
+        base,
+        timestamp=base.timestamp + timedelta(minutes=minutes),
+        watts_actual=watts,
+        battery_percent=percent,
+        is_charging=is_charging,
+        external_connected=external_connected,
+        fully_charged=fully_charged,
+    )
+
+
+def test_session_ends_when_unplugged(sample_reading):
+    """Test a session spans charging start to unplug, with energy integrated over time."""
+    tracker = ChargeSessionTracker()
+
+    unplugged = replace(sample_reading, is_charging=False, external_connected=False)
+
+    assert tracker.observe(make_reading(unplugged, 0, -10.0, 40, is_charging=False, external_connected=False)) is None
+    assert tracker.observe(make_reading(sample_reading, 1, 60.0, 40)) is None
+    assert tracker.active
+    assert tracker.observe(make_reading(sample_reading, 31, 60.0, 70)) is None
+    session = tracker.observe(make_reading(unplugged, 61, -8.0, 95, is_charging=False, external_connected=False))
+
+    assert session is not None
+    assert not tracker.active
+    assert session.started_at == sample_reading.timestamp + timedelta(minutes=1)
+    assert session.duration == timedelta(minutes=60)
+    assert (session.start_percent, session.end_percent) == (40, 95)
+    # 30 minutes at 60W, then 30 minutes ramping from 60W to 0W (draining power doesn't count)
+    assert session.total_wh == pytest.approx(30.0 + 15.0)
+
+
+def test_session_ends_when_full(sample_reading):
+    """Test reaching full charge ends the session even while still plugged in."""
+    tracker = ChargeSessionTracker()
+    tracker.observe(make_reading(sample_reading, 0, 20.0, 98))
+
+    session = tracker.observe(make_reading(sample_reading, 10, 0.0, 100, fully_charged=True))
+
+    assert session is not None
+    assert session.end_percent == 100
+    # The full reading doesn't start a new session
+    assert tracker.observe(make_reading(sample_reading, 11, 0.0, 100, fully_charged=True)) is None
+    assert not tracker.active
+
+
+def test_short_sessions_are_ignored(sample_reading):
+    """Test charging flickers shorter than min_seconds don't produce sessions."""
+    tracker = ChargeSessionTracker(min_seconds=60)
+    tracker.observe(make_reading(sample_reading, 0, 30.0, 50))
+
+    assert tracker.observe(make_reading(sample_reading, 0.5, 1.0, 50, is_charging=False)) is None
+    assert not tracker.active
+
+
+def test_finish_closes_open_session(sample_reading):
+    """Test finish() ends a session in progress at the last reading seen."""
+    tracker = ChargeSessionTracker()
+    assert tracker.finish() is None
+
+    tracker.observe(make_reading(sample_reading, 0, 30.0, 50))
+    tracker.observe(make_reading(sample_reading, 20, 30.0, 60))
+    session = tracker.finish()
+
+    assert session is not None
+    assert session.duration == timedelta(minutes=20)
+    assert session.total_wh == pytest.approx(10.0)
+    assert tracker.finish() is None
+
+
+def test_session_from_collector_with_mock_clock(sample_reading):
+    """Test a session built from collected readings has exact times when the clock is mocked."""
+    clock = MockClock(sample_reading.timestamp)
+    unplugged = replace(sample_reading, is_charging=False, external_connected=False, watts_actual=0.0)
+    collector = MockCollector([sample_reading] * 3 + [unplugged], cycle=False, clock=clock)
+    tracker = ChargeSessionTracker()
+
+    sessions = []
+    for _ in range(4):
+        sessions.append(tracker.observe(collector.collect()))
+        clock.advance(600)
+
+    assert sessions[:3] == [None, None, None]
+    session = sessions[3]
+    assert session is not None
+    assert session.duration == timedelta(minutes=30)
+    # 20 minutes at 45.5W, then 10 minutes ramping down to 0W
+    assert session.total_wh == pytest.approx(45.5 / 3 + 45.5 / 12)
+
+
+def test_min_seconds_validation():
+    """Test a negative minimum duration is rejected."""
+    with pytest.raises(ValueError, match="min_seconds"):
+        ChargeSessionTracker(min_seconds=-1)
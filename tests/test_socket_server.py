@@ -0,0 +1,93 @@
+"""Tests for the NDJSON Unix socket server."""
+
+import json
+import socket
+import tempfile
+from pathlib import Path
+
+import pytest
+
+from powermonitor.socket_server import ReadingBroadcaster
+from powermonitor.socket_server import SocketInUseError
+
+
+@pytest.fixture
+def socket_path():
+    """Short socket path (AF_UNIX paths are limited to ~104 bytes, which tmp_path can exceed on macOS)."""
+    with tempfile.TemporaryDirectory(dir="/tmp") as directory:
+        yield Path(directory) / "pm.sock"
+
+
+def connect(path: Path) -> socket.socket:
+    client = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    client.settimeout(2)
+    client.connect(str(path))
+    return client
+
+
+def read_line(client: socket.socket) -> dict:
+    with client.makefile("rb") as stream:
+        return json.loads(stream.readline())
+
+
+def test_broadcast_to_multiple_clients(socket_path, sample_reading):
+    """Test every connected client receives each reading as one JSON line."""
+    with ReadingBroadcaster(socket_path) as server:
+        first = connect(socket_path)
+        second = connect(socket_path)
+        assert server.accept_pending() == 2
+
+        assert server.broadcast(sample_reading) == 2
+        assert read_line(first) == sample_reading.to_dict()
+        assert read_line(second)["battery_percent"] == 74
+
+        first.close()
+        second.close()
+
+
+def test_disconnected_client_is_dropped(socket_path, sample_reading):
+    """Test a client that goes away is removed without affecting the others."""
+    with ReadingBroadcaster(socket_path) as server:
+        leaving = connect(socket_path)
+        staying = connect(socket_path)
+        server.accept_pending()
+        leaving.close()
+
+        # The first send after a disconnect may still succeed; the next one fails
+        server.broadcast(sample_reading)
+        server.broadcast(sample_reading)
+        assert server.client_count == 1
+        assert read_line(staying) == sample_reading.to_dict()
+        staying.close()
+
+
+def test_close_removes_socket_file(socket_path):
+    """Test the socket file exists while serving and is removed on shutdown."""
+    with ReadingBroadcaster(socket_path):
+        assert socket_path.is_socket()
+    assert not socket_path.exists()
+
+
+def test_stale_socket_is_replaced(socket_path):
+    """Test a socket file left by a crashed server doesn't block startup."""
+    stale = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+    stale.bind(str(socket_path))
+    stale.close()  # File remains, nobody listening
+
+    with ReadingBroadcaster(socket_path) as server:
+        client = connect(socket_path)
+        assert server.accept_pending() == 1
+        client.close()
+
+
+def test_refuses_live_socket_and_regular_files(socket_path):
+    """Test a running server and unrelated files are never removed."""
+    with ReadingBroadcaster(socket_path):
+        with pytest.raises(SocketInUseError, match="already serving"):
+            ReadingBroadcaster(socket_path).open()
+        assert socket_path.is_socket()
+
+    socket_path.write_text("not a socket")
+    with pytest.raises(SocketInUseError, match="not a socket"):
+        ReadingBroadcaster(socket_path).open()
+    assert socket_path.read_text() == "not a socket"
@@ -0,0 +1,126 @@
+"""Tests for negotiated wattage stabilization."""
+
+from dataclasses import replace
+from datetime import timedelta
+
+import pytest
+
+from powermonitor.models import PowerReading
+from powermonitor.stabilizer import NegotiatedWattsStabilizer
+
+
+def make_reading(base: PowerReading, seconds: float, watts_negotiated: int, external_connected: bool = True):
+    """Create a reading offset from the base reading's timestamp."""
+    return replace(
+        base,
+        timestamp=base.timestamp + timedelta(seconds=seconds),
+        watts_negotiated=watts_negotiated,
+        external_connected=external_connected,
+        watts_negotiated_stable=None,
+    )
+
+
+def test_non_zero_value_passes_through(sample_reading):
+    """Test non-zero negotiated watts are used as the stable value."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+
+    reading = stabilizer.apply(make_reading(sample_reading, 0, 67))
+
+    assert reading.watts_negotiated == 67
+    assert reading.watts_negotiated_stable == 67
+
+
+def test_zero_within_window_carries_forward(sample_reading):
+    """Test a brief 0W dip while connected keeps the last non-zero value."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+    stabilizer.apply(make_reading(sample_reading, 0, 67))
+
+    reading = stabilizer.apply(make_reading(sample_reading, 5, 0))
+
+    assert reading.watts_negotiated == 0  # Raw value untouched
+    assert reading.watts_negotiated_stable == 67
+
+
+def test_zero_at_window_boundary_carries_forward(sample_reading):
+    """Test the window is inclusive of its end."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+    stabilizer.apply(make_reading(sample_reading, 0, 67))
+
+    reading = stabilizer.apply(make_reading(sample_reading, 10, 0))
+
+    assert reading.watts_negotiated_stable == 67
+
+
+def test_zero_after_window_is_reported(sample_reading):
+    """Test a 0W reading outside the window is not smoothed."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+    stabilizer.apply(make_reading(sample_reading, 0, 67))
+
+    reading = stabilizer.apply(make_reading(sample_reading, 11, 0))
+
+    assert reading.watts_negotiated_stable == 0
+
+
+def test_window_measured_from_last_non_zero(sample_reading):
+    """Test consecutive dips don't extend the window."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+    stabilizer.apply(make_reading(sample_reading, 0, 67))
+
+    assert stabilizer.apply(make_reading(sample_reading, 6, 0)).watts_negotiated_stable == 67
+    assert stabilizer.apply(make_reading(sample_reading, 12, 0)).watts_negotiated_stable == 0
+
+
+def test_unplug_resets_carry_forward(sample_reading):
+    """Test disconnecting external power discards the remembered value."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+    stabilizer.apply(make_reading(sample_reading, 0, 67))
+
+    unplugged = stabilizer.apply(make_reading(sample_reading, 1, 0, external_connected=False))
+    replugged = stabilizer.apply(make_reading(sample_reading, 2, 0))
+
+    assert unplugged.watts_negotiated_stable == 0
+    assert replugged.watts_negotiated_stable == 0
+
+
+def test_new_charger_value_replaces_old(sample_reading):
+    """Test a new non-zero value is carried forward instead of the previous one."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+    stabilizer.apply(make_reading(sample_reading, 0, 67))
+    stabilizer.apply(make_reading(sample_reading, 1, 96))
+
+    reading = stabilizer.apply(make_reading(sample_reading, 2, 0))
+
+    assert reading.watts_negotiated_stable == 96
+
+
+def test_zero_without_history(sample_reading):
+    """Test a 0W reading with no prior non-zero value stays 0."""
+    stabilizer = NegotiatedWattsStabilizer(window_seconds=10)
+
+    reading = stabilizer.apply(make_reading(sample_reading, 0, 0))
+
+    assert reading.watts_negotiated_stable == 0
+
+
+def test_negative_window_rejected():
+    """Test negative window raises ValueError."""
+    with pytest.raises(ValueError, match="window_seconds must be non-negative"):
+        NegotiatedWattsStabilizer(window_seconds=-1)
+
+
+def test_display_watts_negotiated_prefers_stable(sample_reading):
+    """Test display property uses the stable value when available."""
+    reading = replace(sample_reading, watts_negotiated=0, watts_negotiated_stable=67)
+    assert reading.display_watts_negotiated == 67
+
+    raw_only = replace(sample_reading, watts_negotiated=45, watts_negotiated_stable=None)
+    assert raw_only.display_watts_negotiated == 45
+
+
+def test_stabilizer_returns_power_reading(sample_reading):
+    """Test apply() returns the same PowerReading instance."""
+    stabilizer = NegotiatedWattsStabilizer()
+    reading = make_reading(sample_reading, 0, 67)
+
+    assert stabilizer.apply(reading) is reading
+    assert isinstance(reading, PowerReading)
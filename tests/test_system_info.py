@@ -0,0 +1,44 @@
+"""Tests for runtime platform detection."""
+
+import platform
+import sys
+
+from powermonitor import system_info
+
+
+def _fake_sysctl(monkeypatch, values: dict[str, str]) -> None:
+    monkeypatch.setattr(sys, "platform", "darwin")
+    monkeypatch.setattr(system_info, "_sysctl", values.get)
+
+
+def test_rosetta_detection(monkeypatch):
+    """Test that a translated x86_64 process reports the native arm64 hardware."""
+    _fake_sysctl(monkeypatch, {"sysctl.proc_translated": "1", "hw.optional.arm64": "1"})
+    monkeypatch.setattr(platform, "machine", lambda: "x86_64")
+
+    assert system_info.running_under_rosetta() is True
+    assert system_info.native_architecture() == "arm64"
+    assert system_info.describe_architecture() == "x86_64 under Rosetta (native arm64)"
+
+
+def test_native_detection(monkeypatch):
+    """Test native Apple Silicon and Intel Macs (where sysctl.proc_translated doesn't exist)."""
+    _fake_sysctl(monkeypatch, {"sysctl.proc_translated": "0", "hw.optional.arm64": "1"})
+    monkeypatch.setattr(platform, "machine", lambda: "arm64")
+    assert system_info.running_under_rosetta() is False
+    assert system_info.describe_architecture() == "arm64"
+
+    _fake_sysctl(monkeypatch, {})
+    monkeypatch.setattr(platform, "machine", lambda: "x86_64")
+    assert system_info.running_under_rosetta() is False
+    assert system_info.native_architecture() == "x86_64"
+    assert system_info.describe_architecture() == "x86_64"
+
+
+def test_non_macos_is_never_translated(monkeypatch):
+    """Test that sysctl isn't consulted outside macOS."""
+    monkeypatch.setattr(sys, "platform", "linux")
+    monkeypatch.setattr(system_info, "_sysctl", lambda name: "1")
+
+    assert system_info.running_under_rosetta() is False
+    assert system_info.native_architecture() == platform.machine()
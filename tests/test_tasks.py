@@ -0,0 +1,82 @@
+"""Tests for per-process energy via powermetrics."""
+
+import subprocess
+
+from powermonitor.collector import powermetrics
+from powermonitor.collector.tasks import TaskEnergy
+from powermonitor.collector.tasks import TaskEnergyReader
+from powermonitor.collector.tasks import parse_tasks
+
+TASKS_OUTPUT = """Machine model: Mac14,5
+
+*** Sampled system activity (Fri Oct 16 10:00:00 2026 +0900) (1003.52ms elapsed) ***
+
+*** Running tasks ***
+
+Name                            ID    CPU ms/s User%  Deadlines (<2 ms, 2-5 ms) Wakeups (Intr, Pkg idle) Energy Impact
+WindowServer                    152   48.71    61.09  66.82  0.00               218.76  0.00             72.34
+Google Chrome Helper (Renderer) 4711  120.33   88.10  0.00   0.00               45.20   3.10             150.50
+kernel_task                     0     30.12    0.00   0.00   0.00               600.10  120.00           40.00
+ALL_TASKS                       -2    452.90   70.00  100.00 0.00               1500.00 200.00           400.00
+
+**** Battery and backlight usage ****
+"""
+
+
+def test_parse_tasks_sorted_by_energy():
+    """Test task rows are parsed, aggregates skipped, and sorted by energy impact."""
+    tasks = parse_tasks(TASKS_OUTPUT)
+
+    assert tasks == [
+        TaskEnergy("Google Chrome Helper (Renderer)", 4711, 120.33, 150.50),
+        TaskEnergy("WindowServer", 152, 48.71, 72.34),
+        TaskEnergy("kernel_task", 0, 30.12, 40.00),
+    ]
+
+
+def test_parse_tasks_without_energy_column():
+    """Test output without --show-process-energy falls back to sorting by CPU time."""
+    output = (
+        "Name                               ID     CPU ms/s  User%\n"
+        "mds_stores                         301    12.50     40.00\n"
+        "Xcode                              920    95.00     80.00\n"
+    )
+
+    tasks = parse_tasks(output)
+
+    assert [t.name for t in tasks] == ["Xcode", "mds_stores"]
+    assert tasks[0].energy_impact is None
+
+
+def test_parse_tasks_no_table():
+    """Test output without a task table parses to an empty list."""
+    assert parse_tasks("**** GPU usage ****\nGPU Power: 12 mW\n") == []
+
+
+def test_reader_degrades_without_root(monkeypatch):
+    """Test an unprivileged reader returns None without running powermetrics."""
+
+    def fake_run(command, **kwargs):
+        raise AssertionError("powermetrics must not run without root")
+
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 501)
+    monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+    reader = TaskEnergyReader()
+
+    assert reader.read() is None
+    assert reader.available is False
+
+
+def test_reader_reads_tasks(monkeypatch):
+    """Test a successful powermetrics run returns parsed tasks."""
+
+    def fake_run(command, **kwargs):
+        return subprocess.CompletedProcess(command, 0, stdout=TASKS_OUTPUT, stderr="")
+
+    monkeypatch.setattr(powermetrics.os, "geteuid", lambda: 0)
+    monkeypatch.setattr(powermetrics.subprocess, "run", fake_run)
+
+    tasks = TaskEnergyReader().read()
+
+    assert tasks is not None
+    assert tasks[0].pid == 4711
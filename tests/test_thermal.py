@@ -0,0 +1,45 @@
+"""Tests for thermal pressure parsing."""
+
+from powermonitor.collector.thermal import parse_pmset_therm
+from powermonitor.models import ThermalState
+
+NO_WARNINGS_OUTPUT = """\
+Note: No thermal warning level has been recorded
+Note: No performance warning level has been recorded
+Note: No CPU power status has been recorded
+"""
+
+THROTTLED_OUTPUT = """\
+Note: No thermal warning level has been recorded
+Note: No performance warning level has been recorded
+2025-12-28 12:00:00 +0000 CPU Power notify
+\tCPU_Scheduler_Limit \t= 100
+\tCPU_Available_CPUs \t= 8
+\tCPU_Speed_Limit \t= 55
+"""
+
+
+def test_parse_pmset_therm_no_warnings_is_nominal():
+    """Test the 'nothing recorded' output maps to nominal."""
+    assert parse_pmset_therm(NO_WARNINGS_OUTPUT) is ThermalState.NOMINAL
+
+
+def test_parse_pmset_therm_speed_limit():
+    """Test CPU_Speed_Limit takes precedence and maps to a pressure level."""
+    assert parse_pmset_therm(THROTTLED_OUTPUT) is ThermalState.SERIOUS
+    assert parse_pmset_therm("CPU_Speed_Limit = 100") is ThermalState.NOMINAL
+    assert parse_pmset_therm("CPU_Speed_Limit = 80") is ThermalState.FAIR
+    assert parse_pmset_therm("CPU_Speed_Limit = 20") is ThermalState.CRITICAL
+
+
+def test_parse_pmset_therm_explicit_pressure_level():
+    """Test an explicit pressure level line, including pmset's alias names."""
+    assert parse_pmset_therm("Current thermal pressure level: critical") is ThermalState.CRITICAL
+    assert parse_pmset_therm("Thermal pressure level = Heavy") is ThermalState.SERIOUS
+    assert parse_pmset_therm("thermal pressure level: moderate") is ThermalState.FAIR
+
+
+def test_parse_pmset_therm_unrecognized():
+    """Test unrelated output returns None."""
+    assert parse_pmset_therm("") is None
+    assert parse_pmset_therm("pmset: unknown option") is None
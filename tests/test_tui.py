@@ -1,16 +1,40 @@
 """Tests for TUI components."""
 
+import asyncio
+import json
 import sys
+import time
+from dataclasses import replace
 from datetime import UTC
+from datetime import date
 from datetime import datetime
+from datetime import timedelta
+from pathlib import Path
 
 import pytest
 
+from powermonitor.collector.mock import MockCollector
 from powermonitor.config import PowerMonitorConfig
+from powermonitor.config import Thresholds
+from powermonitor.config_loader import load_config
+from powermonitor.database import Database
+from powermonitor.glyphs import ASCII
+from powermonitor.hooks import EventHooks
 from powermonitor.models import PowerReading
+from powermonitor.replay import ReplayCollector
+from powermonitor.replay import replay_schedule
+from powermonitor.tui.app import CHART_MIN_HEIGHT
+from powermonitor.tui.app import MIN_TERMINAL_HEIGHT
+from powermonitor.tui.app import STATS_MIN_HEIGHT
 from powermonitor.tui.app import PowerMonitorApp
+from powermonitor.tui.app import format_title
+from powermonitor.tui.app import panels_for_height
+from powermonitor.tui.widgets import ChartWidget
+from powermonitor.tui.widgets import ExportDialog
 from powermonitor.tui.widgets import LiveDataPanel
 from powermonitor.tui.widgets import StatsPanel
+from powermonitor.tui.widgets import chart_region_changes
+from powermonitor.tui.widgets import chart_series_points
 
 
 @pytest.fixture
@@ -49,6 +73,135 @@ def test_live_data_panel_update(sample_reading):
     assert "Charging" in rendered
 
 
+def test_live_data_panel_reference_voltage(sample_reading):
+    """Test the power line adds the current at the reference voltage only when set."""
+    panel = LiveDataPanel(reference_voltage=5.0)
+    panel.update_reading(sample_reading)
+
+    assert "45.2W (≈9.0A @ 5V) / 67W max" in panel._render_reading()
+
+
+def test_live_data_panel_deadzone(sample_reading):
+    """Test power below the deadzone is shown as zero."""
+    panel = LiveDataPanel(deadzone=0.5)
+    panel.update_reading(replace(sample_reading, watts_actual=-0.3))
+
+    assert "Power: 0.0W / 67W max" in panel._render_reading()
+
+
+def test_live_data_panel_charger_identity_verbose_only(sample_reading):
+    """Test charger serial and firmware are only shown in verbose mode."""
+    reading = replace(sample_reading, charger_serial="C4H1234567890", charger_firmware="01080001")
+
+    panel = LiveDataPanel()
+    panel.update_reading(reading)
+    assert "C4H1234567890" not in panel._render_reading()
+
+    verbose_panel = LiveDataPanel(verbose=True)
+    verbose_panel.update_reading(reading)
+    rendered = verbose_panel._render_reading()
+    assert "C4H1234567890" in rendered
+    assert "01080001" in rendered
+
+
+def test_live_data_panel_battery_identity_verbose_only(sample_reading):
+    """Test battery serial and manufacture date are only shown in verbose mode."""
+    reading = replace(sample_reading, battery_serial="F5DHJ2001E20000G44", battery_manufacture_date=date(2021, 5, 3))
+
+    panel = LiveDataPanel()
+    panel.update_reading(reading)
+    assert "F5DHJ2001E20000G44" not in panel._render_reading()
+
+    verbose_panel = LiveDataPanel(verbose=True)
+    verbose_panel.update_reading(reading)
+    assert "Battery S/N: F5DHJ2001E20000G44  Manufactured: 2021-05-03" in verbose_panel._render_reading()
+
+
+def test_live_data_panel_amperage_comparison_verbose_only(sample_reading):
+    """Test SMC and ioreg currents are compared only in verbose mode."""
+    reading = replace(sample_reading, amperage=3.654, amperage_ioreg=3.6)
+
+    panel = LiveDataPanel()
+    panel.update_reading(reading)
+    assert "ioreg" not in panel._render_reading()
+
+    verbose_panel = LiveDataPanel(verbose=True)
+    verbose_panel.update_reading(reading)
+    assert "Current (SMC / ioreg): 3.654A / 3.600A" in verbose_panel._render_reading()
+
+
+def test_live_data_panel_fully_charged(sample_reading):
+    """Test a fully charged battery is shown distinctly from charging at 100%."""
+    panel = LiveDataPanel()
+
+    panel.update_reading(replace(sample_reading, battery_percent=100, is_charging=False, fully_charged=True))
+    assert "Fully charged (100%)" in panel._render_reading()
+
+    panel.update_reading(replace(sample_reading, battery_percent=100, is_charging=True, fully_charged=False))
+    rendered = panel._render_reading()
+    assert "Fully charged" not in rendered
+    assert "Charging" in rendered
+
+
+def test_live_data_panel_charge_inhibit_reason(sample_reading):
+    """Test the panel explains why a connected charger isn't charging."""
+    panel = LiveDataPanel()
+
+    panel.update_reading(replace(sample_reading, is_charging=False, charge_inhibit_reason="Optimized Battery Charging"))
+    assert "Not charging: Optimized Battery Charging" in panel._render_reading()
+
+    panel.update_reading(replace(sample_reading, is_charging=False))
+    assert "On AC Power (Not Charging)" in panel._render_reading()
+
+
+def test_live_data_panel_ac_discharging(sample_reading):
+    """Test a battery draining while plugged in isn't shown as idle on AC."""
+    panel = LiveDataPanel()
+
+    panel.update_reading(replace(sample_reading, is_charging=False, watts_actual=-15.0))
+    rendered = panel._render_reading()
+    assert "🔌⚠ On AC but discharging (load exceeds adapter)" in rendered
+    assert "Not Charging" not in rendered
+
+
+def test_live_data_panel_uncertified_charger(sample_reading):
+    """Test a charger that looks uncertified is called out."""
+    panel = LiveDataPanel()
+
+    panel.update_reading(replace(sample_reading, charger_certified=False))
+    assert "Possibly uncertified charger" in panel._render_reading()
+
+    for certified in (True, None):
+        panel.update_reading(replace(sample_reading, charger_certified=certified))
+        assert "uncertified" not in panel._render_reading()
+
+
+def test_live_data_panel_escapes_charger_name(sample_reading):
+    """Test markup and newlines in a charger name are shown literally on one line."""
+    from rich.text import Text
+
+    panel = LiveDataPanel()
+    panel.update_reading(replace(sample_reading, charger_name="[bold]Acme[/] 96W\nAdapter", charger_manufacturer="[x"))
+
+    plain = Text.from_markup(panel._render_reading()).plain
+    assert "Charger: [bold]Acme[/] 96W Adapter ([x)" in plain
+
+
+def test_live_data_panel_threshold_warnings(sample_reading):
+    """Test exceeded thresholds are shown as warnings."""
+    panel = LiveDataPanel(thresholds=Thresholds(high_power=40.0, overheat_temp=35.0))
+
+    panel.update_reading(replace(sample_reading, battery_temp=36.2))
+    rendered = panel._render_reading()
+    assert "Temperature: 36.2°C" in rendered
+    assert "High power: 45.2W" in rendered
+    assert "Battery overheating: 36.2°C" in rendered
+
+    default_panel = LiveDataPanel()
+    default_panel.update_reading(sample_reading)
+    assert "⚠" not in default_panel._render_reading()
+
+
 def test_stats_panel_empty():
     """Test StatsPanel with empty statistics."""
     panel = StatsPanel()
@@ -122,3 +275,347 @@ async def test_app_refresh_action():
 
         # Should show notification
         # (actual verification would require mocking collector)
+
+
+async def test_app_collection_with_mock_backend(temp_db):
+    """Test the collection loop stores mock readings in the database off-device."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test():
+        await app._collect_and_update()
+
+        history = app.database.query_history(limit=None)
+        # One reading from the initial refresh on mount, one from the explicit collection
+        assert len(history) == 2
+        assert history[0].watts_actual == 45.5
+        assert history[0].watts_negotiated_stable == 67
+
+        live_panel = app.query_one("#live-data", LiveDataPanel)
+        assert "45.5W" in live_panel._render_reading()
+
+
+async def test_app_collection_scripted_sequence(temp_db, sample_reading):
+    """Test scripted mock readings flow through the watch loop in order."""
+    unplugged = replace(sample_reading, is_charging=False, external_connected=False, watts_actual=-8.0)
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector([sample_reading, unplugged], cycle=False))
+
+    async with app.run_test():
+        await app._collect_and_update()
+
+        history = app.database.query_history(limit=None)
+        assert [r.external_connected for r in reversed(history)] == [True, False]
+
+
+async def test_app_runs_event_hooks(temp_db, sample_reading):
+    """Test collected readings are passed to event hooks."""
+    unplugged = replace(sample_reading, is_charging=False, external_connected=False, watts_actual=-8.0)
+    spawned = []
+    hooks = EventHooks({"unplug": "say unplugged"}, spawn=lambda command, **_: spawned.append(command))
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector([sample_reading, unplugged], cycle=False), hooks=hooks)
+
+    async with app.run_test():
+        await app._collect_and_update()
+
+        assert spawned == ["say unplugged"]
+
+
+async def test_app_live_chart_keeps_bounded_window(temp_db, sample_reading):
+    """Test live chart mode scrolls over the newest chart_limit readings without the database."""
+    readings = [replace(sample_reading, watts_actual=float(watts)) for watts in range(1, 6)]
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db, chart_history_limit=3, live_chart=True)
+    app = PowerMonitorApp(config=config, collector=MockCollector(readings, cycle=False))
+
+    async with app.run_test():
+        # One reading from the initial refresh on mount, four more here
+        for _ in range(4):
+            await app._collect_and_update()
+
+        chart = app.query_one("#chart", ChartWidget)
+        assert [r.watts_actual for r in chart.readings] == [5.0, 4.0, 3.0]
+
+
+async def test_app_store_interval_averages_samples(temp_db):
+    """Test samples within a store interval are kept in memory and stored as one reading on exit."""
+    config = PowerMonitorConfig(collection_interval=1.0, store_interval=3600.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test():
+        await app._collect_and_update()
+        await app._collect_and_update()
+
+        assert app.database.query_history(limit=None) == []
+        assert "45.5W" in app.query_one("#live-data", LiveDataPanel)._render_reading()
+
+    with Database(temp_db) as db:
+        history = db.query_history(limit=None)
+    assert len(history) == 1  # The three samples (including the one on mount), averaged
+    assert history[0].watts_actual == 45.5
+
+
+async def test_app_export_session(temp_db, sample_reading, tmp_path):
+    """Test the export action saves only readings collected since the app started."""
+    with Database(temp_db) as db:
+        db.insert_reading(replace(sample_reading, timestamp=sample_reading.timestamp - timedelta(days=1)))
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test():
+        await app._collect_and_update()
+
+        output = tmp_path / "session.ndjson"
+        app._finish_export(str(output))
+        # One reading from the initial refresh on mount, one from the explicit collection
+        assert [json.loads(line)["watts_actual"] for line in output.read_text().splitlines()] == [45.5, 45.5]
+
+        with pytest.raises(ValueError, match="Cannot detect format"):
+            app.export_session(tmp_path / "session.txt")
+
+
+async def test_app_export_empty_session(temp_db, tmp_path):
+    """Test exporting before anything was collected writes no file."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    assert app.export_session(tmp_path / "session.csv") == 0
+    assert not (tmp_path / "session.csv").exists()
+    app.database.close()
+
+
+async def test_app_replay_exports_recording(temp_db, sample_reading, tmp_path):
+    """Test E in replay mode exports the replayed readings, whose timestamps predate the app starting."""
+    recorded = [replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=i)) for i in range(2)]
+    collector = ReplayCollector(replay_schedule(recorded, speed=100.0))
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=collector)
+    output = tmp_path / "replay.ndjson"
+
+    async with app.run_test() as pilot:
+        await pilot.pause(0.2)
+        await pilot.press("e")
+        await pilot.pause()
+        assert isinstance(app.screen, ExportDialog)
+        app.screen.query_one("#export-path").value = str(output)
+        await pilot.press("enter")
+        await pilot.pause()
+
+    timestamps = [json.loads(line)["timestamp"] for line in output.read_text().splitlines()]
+    assert sorted(timestamps) == [r.timestamp.isoformat() for r in recorded]
+
+
+def test_chart_series_points_axes(sample_reading):
+    """Test selected series become lines, with non-power metrics on the right axis."""
+    readings = [
+        replace(sample_reading, watts_actual=20.0, battery_percent=80, battery_temp=31.0),
+        replace(sample_reading, watts_actual=10.0, battery_percent=79, battery_temp=None),
+    ]  # Newest first
+
+    lines = chart_series_points(readings, ("watts", "battery", "temp"))
+
+    assert [(label, yside) for label, _, _, _, yside in lines] == [
+        ("Power (W)", "left"),
+        ("Battery (%)", "right"),
+        ("Battery Temp (°C)", "right"),
+    ]
+    _, _, x, y, _ = lines[0]
+    assert (x, y) == ([0, 1], [10.0, 20.0])  # Oldest first
+    _, _, x, y, _ = lines[2]
+    assert (x, y) == ([1], [31.0])  # Reading without a temperature is skipped
+
+
+def test_chart_series_points_single_unit(sample_reading):
+    """Test non-power metrics use the left axis when no power series is charted."""
+    lines = chart_series_points([sample_reading], ("battery", "voltage"))
+
+    assert [yside for *_, yside in lines] == ["left", "left"]
+    assert chart_series_points([sample_reading], ("temp",)) == []  # No temperature data at all
+
+
+def test_chart_region_changes(sample_reading):
+    """Test the chart marks where charging stops and the charger is unplugged."""
+    unplugged = replace(sample_reading, is_charging=False, external_connected=False)
+    idle = replace(sample_reading, is_charging=False)
+    readings = [unplugged, unplugged, idle, sample_reading, sample_reading]  # Newest first
+
+    assert chart_region_changes(readings) == [(2, "ac"), (3, "battery")]
+    assert chart_region_changes([sample_reading, sample_reading]) == []
+
+
+def test_panels_for_height():
+    """Test panels collapse in priority order as the terminal shrinks."""
+    assert panels_for_height(50) == {"live-data", "stats", "chart"}
+    assert panels_for_height(STATS_MIN_HEIGHT) == {"live-data", "stats", "chart"}
+    assert panels_for_height(STATS_MIN_HEIGHT - 1) == {"live-data", "chart"}
+    assert panels_for_height(CHART_MIN_HEIGHT - 1) == {"live-data"}
+    assert panels_for_height(MIN_TERMINAL_HEIGHT) == {"live-data"}
+    assert panels_for_height(MIN_TERMINAL_HEIGHT - 1) == set()
+
+
+async def test_app_small_terminal_hides_panels(temp_db):
+    """Test a short terminal hides the statistics panel."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test(size=(80, 24)):
+        assert app.query_one("#live-data").display is True
+        assert app.query_one("#chart").display is True
+        assert app.query_one("#stats").display is False
+        assert app.query_one("#too-small").display is False
+
+
+async def test_app_tiny_terminal_shows_message(temp_db):
+    """Test a terminal below the minimum shows the 'too small' message."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test(size=(80, 8)):
+        assert app.query_one("#too-small").display is True
+        assert app.query_one("#live-data").display is False
+
+
+async def test_app_resize_relayouts(temp_db):
+    """Test growing the terminal re-displays collapsed panels."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test(size=(80, 24)) as pilot:
+        assert app.query_one("#stats").display is False
+
+        await pilot.resize_terminal(100, 50)
+        await pilot.pause()
+
+        assert app.query_one("#stats").display is True
+
+
+async def test_app_pause_stops_collection(temp_db):
+    """Test P stops the collection loop at once, marks the header, and P again resumes."""
+    config = PowerMonitorConfig(collection_interval=0.2, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test() as pilot:
+        await pilot.press("p")
+        assert app.paused
+        assert app.sub_title.startswith("⏸ ")
+        count = len(app.database.query_history(limit=None))
+
+        await pilot.pause(0.6)
+        assert len(app.database.query_history(limit=None)) == count
+
+        await pilot.press("p")
+        assert not app.paused
+        assert not app.sub_title.startswith("⏸")
+        await pilot.pause(0.6)
+        assert len(app.database.query_history(limit=None)) > count
+
+
+async def test_app_tags_readings_with_label(temp_db):
+    """Test readings collected by the TUI carry the session label."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=MockCollector(), label="benchmark")
+
+    async with app.run_test():
+        history = app.database.query_history(limit=None, label="benchmark")
+        assert len(history) == 1
+
+
+async def test_app_replays_recording(temp_db, sample_reading):
+    """Test a ReplayCollector drives the TUI with recorded readings and their labels, then stops collecting."""
+    recorded = [
+        replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=i), watts_actual=40.0 + i)
+        for i in range(3)
+    ]
+    recorded[0].label = "demo"
+    collector = ReplayCollector(replay_schedule(recorded, speed=100.0))
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=collector)
+
+    async with app.run_test() as pilot:
+        await pilot.pause(0.3)
+        assert app.replay is collector
+        assert app._last_reading is not None
+        assert app._last_reading.watts_actual == 42.0
+        history = app.database.query_history(limit=None)
+
+    assert [r.watts_actual for r in history] == [42.0, 41.0, 40.0]
+    assert [r.timestamp for r in reversed(history)] == [r.timestamp for r in recorded]
+    assert history[-1].label == "demo"
+
+
+async def test_app_replay_ignores_refresh(temp_db, sample_reading):
+    """Test R doesn't pull readings out of a replay ahead of their recorded time."""
+    recorded = [replace(sample_reading, timestamp=sample_reading.timestamp + timedelta(seconds=i)) for i in range(2)]
+    collector = ReplayCollector(replay_schedule(recorded))
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=collector)
+
+    async with app.run_test() as pilot:
+        await pilot.press("r")
+        await pilot.pause()
+        assert collector.next_delay() == 1.0
+        assert len(app.database.query_history(limit=None)) == 1
+
+
+def test_format_title_presets(sample_reading):
+    """Test each header preset renders the expected summary."""
+    assert format_title(sample_reading, "watts") == "⚡ 45.2W"
+    assert format_title(sample_reading, "percent") == "⚡ 74%"
+    assert format_title(sample_reading, "watts+percent") == "⚡ 45.2W · 74%"
+
+    on_battery = replace(sample_reading, is_charging=False, external_connected=False, watts_actual=-8.0)
+    assert format_title(on_battery, "watts") == "🔋 8.0W"
+
+    # Conflicting sign: positive flow reported while plugged in but not charging
+    plugged_idle = replace(sample_reading, is_charging=False, watts_actual=1.5)
+    assert format_title(plugged_idle, "percent") == "🔌 74%"
+
+
+def test_format_title_ascii(sample_reading):
+    """Test --ascii swaps the header emoji for ASCII stand-ins."""
+    assert format_title(sample_reading, "watts+percent", ASCII) == "[CHG] 45.2W | 74%"
+
+    on_battery = replace(sample_reading, is_charging=False, external_connected=False, watts_actual=-8.0)
+    assert format_title(on_battery, "watts", ASCII) == "[BAT] 8.0W"
+
+
+async def test_app_cycle_title_format(temp_db, temp_config):
+    """Test the title format key re-renders the header immediately and saves the preset."""
+    config = PowerMonitorConfig(collection_interval=60.0, database_path=temp_db, title_format="watts")
+    app = PowerMonitorApp(config=config, collector=MockCollector())
+
+    async with app.run_test() as pilot:
+        assert app.sub_title == "⚡ 45.5W"
+
+        await pilot.press("t")
+        assert app.title_format == "percent"
+        assert app.sub_title.endswith("%")
+
+    assert load_config().title_format == "percent"
+    assert 'title_format = "percent"' in Path(temp_config).read_text()
+
+
+async def test_app_quit_saves_in_flight_reading(temp_db, sample_reading):
+    """Test quitting waits for a collection in progress to be stored instead of abandoning it."""
+    collecting = asyncio.Event()
+
+    class SlowCollector:
+        calls = 0
+
+        def collect(self) -> PowerReading:
+            self.calls += 1
+            if self.calls > 1:  # The loop's collection (the first is the refresh on mount)
+                loop.call_soon_threadsafe(collecting.set)
+                time.sleep(0.2)
+            return sample_reading
+
+    loop = asyncio.get_running_loop()
+    config = PowerMonitorConfig(collection_interval=0.01, database_path=temp_db)
+    app = PowerMonitorApp(config=config, collector=SlowCollector())
+
+    async with app.run_test():
+        await collecting.wait()
+        await app._stop_collection()
+
+        assert app._collector_task.done()
+        assert len(app.database.query_history(limit=None)) == 2
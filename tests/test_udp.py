@@ -0,0 +1,81 @@
+"""Tests for sending readings over UDP."""
+
+import json
+import socket
+
+import pytest
+
+from powermonitor.udp import ReadingSender
+from powermonitor.udp import datagram
+from powermonitor.udp import parse_collector_address
+
+
+@pytest.fixture
+def receiver():
+    """UDP socket bound to a free loopback port."""
+    sock = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+    sock.settimeout(2)
+    sock.bind(("127.0.0.1", 0))
+    yield sock
+    sock.close()
+
+
+def test_parse_collector_address():
+    """Test host:port and bracketed IPv6 addresses parse, and malformed ones are rejected."""
+    assert parse_collector_address("collector.local:9999") == ("collector.local", 9999)
+    assert parse_collector_address("10.0.0.5:514") == ("10.0.0.5", 514)
+    assert parse_collector_address("[::1]:9999") == ("::1", 9999)
+
+    for text in ("collector.local", ":9999", "::1:9999", "[::1]", "[::1]9999"):
+        with pytest.raises(ValueError, match="Expected host:port"):
+            parse_collector_address(text)
+    with pytest.raises(ValueError, match="Invalid port"):
+        parse_collector_address("collector.local:http")
+    with pytest.raises(ValueError, match="between 1 and 65535"):
+        parse_collector_address("collector.local:70000")
+
+
+def test_datagram_identifies_machine(sample_reading):
+    """Test each datagram is compact JSON with the sender's host name and model before the reading."""
+    payload = datagram(sample_reading, "studio-mbp", "Mac14,5")
+
+    data = json.loads(payload)
+    assert payload == json.dumps(data, separators=(",", ":")).encode()  # No padding after separators
+    assert list(data)[:2] == ["hostname", "model"]
+    assert data == {"hostname": "studio-mbp", "model": "Mac14,5", **sample_reading.to_dict()}
+
+
+def test_sender_delivers_readings(receiver, sample_reading):
+    """Test every sent reading arrives as one datagram."""
+    host, port = receiver.getsockname()
+
+    with ReadingSender(host, port, hostname="studio-mbp", model="Mac14,5") as sender:
+        assert sender.send(sample_reading) is True
+        assert sender.send(sample_reading) is True
+
+    for _ in range(2):
+        data = json.loads(receiver.recv(65535))
+        assert data["hostname"] == "studio-mbp"
+        assert data["model"] == "Mac14,5"
+        assert data["battery_percent"] == 74
+
+
+class UnreachableSocket:
+    """Stands in for a socket whose network is down."""
+
+    def sendto(self, data, address):
+        raise OSError(65, "No route to host")
+
+
+def test_sender_logs_send_errors(receiver, sample_reading):
+    """Test a failed send returns False instead of raising, and later sends still work."""
+    host, port = receiver.getsockname()
+    with ReadingSender(host, port, hostname="studio-mbp") as sender:
+        working = sender._socket
+        sender._socket = UnreachableSocket()
+        assert sender.send(sample_reading) is False
+
+        sender._socket = working
+        assert sender.send(sample_reading) is True
+
+    assert json.loads(receiver.recv(65535))["hostname"] == "studio-mbp"